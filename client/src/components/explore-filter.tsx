@@ -22,6 +22,8 @@ const sortByOptions = [
   'Hardest',
   'Most Reviews',
   'Least Reviews',
+  'Highest Average',
+  'Lowest Average',
 ] as const;
 export type SortByType = (typeof sortByOptions)[number];
 
@@ -4,6 +4,7 @@ import { MemoryRouter } from 'react-router-dom';
 import { afterEach, describe, expect, it, vi } from 'vitest';
 
 import { env } from '../lib/env';
+import { SubscriptionKind } from '../lib/types';
 import { Navbar } from './navbar';
 
 const getNotificationsMock = vi.hoisted(() => vi.fn());
@@ -14,6 +15,7 @@ const sideNavRenderMock = vi.hoisted(() => vi.fn());
 vi.mock('../lib/api', () => ({
   api: {
     getNotifications: getNotificationsMock,
+    streamNotifications: vi.fn(() => () => undefined),
   },
 }));
 
@@ -170,22 +172,31 @@ describe('Navbar', () => {
 
     useAuthMock.mockReturnValue({ id: 'user-1' });
 
-    getNotificationsMock.mockResolvedValue([
-      {
-        review: {
+    getNotificationsMock.mockResolvedValue({
+      notifications: [
+        {
+          _id: '65a0c0ffee0000000000beef',
+          createdAt: '1700000000000',
+          kind: SubscriptionKind.Reviews,
           courseId: 'COMP202',
+          review: {
+            courseId: 'COMP202',
+            userId: 'user-1',
+            timestamp: '1700000000000',
+            content: 'New review',
+            difficulty: 3,
+            instructors: ['Instructor'],
+            likes: 0,
+            rating: 4,
+          },
+          seen: false,
           userId: 'user-1',
-          timestamp: '1700000000000',
-          content: 'New review',
-          difficulty: 3,
-          instructors: ['Instructor'],
-          likes: 0,
-          rating: 4,
+          emailed: false,
         },
-        seen: false,
-        userId: 'user-1',
-      },
-    ]);
+      ],
+      totalCount: 1,
+      unreadCount: 1,
+    });
 
     const user = userEvent.setup();
 
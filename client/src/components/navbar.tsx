@@ -43,8 +43,15 @@ export const Navbar = () => {
 
     api
       .getNotifications()
-      .then((data) => setNotifications(data))
+      .then((data) => setNotifications(data.notifications))
       .catch(() => toast.error('Failed to get notifications.'));
+
+    return api.streamNotifications((notification) =>
+      setNotifications((notifications) => [
+        notification,
+        ...notifications.filter(({ _id }) => _id !== notification._id),
+      ])
+    );
   }, []);
 
   const handleInputChange = (query: string) => {
@@ -4,7 +4,7 @@ import { vi } from 'vitest';
 import type { Mock } from 'vitest';
 
 import { api } from '../lib/api';
-import type { Notification } from '../lib/types';
+import { type Notification, SubscriptionKind } from '../lib/types';
 import { NotificationDropdown } from './notification-dropdown';
 
 const courseReviewMock = vi.hoisted(() =>
@@ -85,6 +85,8 @@ vi.mock('../lib/api', () => ({
   api: {
     updateNotification: vi.fn(),
     deleteNotification: vi.fn(),
+    markAllNotificationsRead: vi.fn(),
+    clearNotifications: vi.fn(),
   },
 }));
 
@@ -101,6 +103,10 @@ describe('NotificationDropdown', () => {
   it('renders notifications with scrollable attachments', async () => {
     const notifications: Notification[] = [
       {
+        _id: '65a0c0ffee0000000000beef',
+        createdAt: '1700000000000',
+        kind: SubscriptionKind.Reviews,
+        courseId: 'COMP202',
         review: {
           courseId: 'COMP202',
           userId: 'user-1',
@@ -113,6 +119,7 @@ describe('NotificationDropdown', () => {
         },
         seen: false,
         userId: 'user-1',
+        emailed: false,
       },
     ];
 
@@ -5,10 +5,27 @@ import { Link } from 'react-router-dom';
 import { toast } from 'sonner';
 
 import { api } from '../lib/api';
-import type { Notification } from '../lib/types';
+import { type Notification, SubscriptionKind } from '../lib/types';
 import { courseIdToUrlParam, spliceCourseCode } from '../lib/utils';
 import { CourseReview, ReviewAttachment } from './course-review';
 
+const notificationSummary = (notification: Notification) => {
+  switch (notification.kind) {
+    case SubscriptionKind.Instructor:
+      return `New review of ${
+        notification.instructor ?? 'an instructor you follow'
+      }`;
+    case SubscriptionKind.Offerings:
+      return `Now offered in ${notification.term ?? 'a new term'}`;
+    case SubscriptionKind.CourseChanges:
+      return 'The schedule changed';
+    case SubscriptionKind.ReviewActivity:
+      return `Your review now has ${notification.review?.likes ?? 0} likes`;
+    default:
+      return 'New review';
+  }
+};
+
 export const NotificationDropdown = ({
   notifications,
   setNotifications,
@@ -54,7 +71,7 @@ export const NotificationDropdown = ({
 
     setNotifications(
       notifications.map((n) => {
-        return seen.has(n.review.courseId) ? { ...n, seen: true } : n;
+        return seen.has(n._id) ? { ...n, seen: true } : n;
       })
     );
 
@@ -65,24 +82,18 @@ export const NotificationDropdown = ({
     if (notification.seen) return;
 
     try {
-      await api.updateNotification(
-        notification.review.courseId,
-        notification.review.userId,
-        true
-      );
-      seen.add(notification.review.courseId);
+      await api.updateNotification(notification._id, true);
+      seen.add(notification._id);
     } catch (err) {
       toast.error('Failed to update notification.');
     }
   };
 
-  const deleteNotification = async (courseId: string) => {
+  const deleteNotification = async (id: string) => {
     try {
-      await api.deleteNotification(courseId);
+      await api.deleteNotification(id);
       setNotifications(
-        notifications.filter(
-          (notification) => notification.review.courseId !== courseId
-        )
+        notifications.filter((notification) => notification._id !== id)
       );
       toast.success('Successfully deleted notification.');
     } catch (err) {
@@ -90,6 +101,25 @@ export const NotificationDropdown = ({
     }
   };
 
+  const markAllRead = async () => {
+    try {
+      await api.markAllNotificationsRead();
+      setNotifications(notifications.map((n) => ({ ...n, seen: true })));
+    } catch (err) {
+      toast.error('Failed to mark notifications as read.');
+    }
+  };
+
+  const clearAll = async () => {
+    try {
+      await api.clearNotifications();
+      setNotifications([]);
+      toast.success('Successfully cleared notifications.');
+    } catch (err) {
+      toast.error('Failed to clear notifications.');
+    }
+  };
+
   return (
     <div className='z-30 text-right'>
       <Menu as='div' className='relative inline-block text-left'>
@@ -125,9 +155,25 @@ export const NotificationDropdown = ({
               >
                 <Menu.Items className='autocomplete absolute -right-8 z-30 mt-2 max-h-[800px] max-w-[325px] origin-top-right divide-y divide-gray-100 overflow-auto rounded-md bg-slate-100 shadow-lg dark:bg-neutral-900 md:max-w-[800px]'>
                   <div className='p-2'>
+                    {notifications.length !== 0 && (
+                      <div className='m-2 flex justify-end gap-x-4 text-sm text-gray-700 dark:text-gray-300'>
+                        <button
+                          onClick={markAllRead}
+                          className='hover:text-gray-900 dark:hover:text-gray-50'
+                        >
+                          Mark all read
+                        </button>
+                        <button
+                          onClick={clearAll}
+                          className='hover:text-gray-900 dark:hover:text-gray-50'
+                        >
+                          Clear all
+                        </button>
+                      </div>
+                    )}
                     {notifications.length !== 0 ? (
                       notifications.map((notification, i) => (
-                        <Menu.Item key={i}>
+                        <Menu.Item key={notification._id}>
                           {() => (
                             <div
                               className='m-2'
@@ -139,11 +185,11 @@ export const NotificationDropdown = ({
                                   <p className='font-semibold text-gray-800 dark:text-gray-200'>
                                     <Link
                                       to={`/course/${courseIdToUrlParam(
-                                        notification.review.courseId
+                                        notification.courseId
                                       )}`}
                                     >
                                       {spliceCourseCode(
-                                        notification.review.courseId,
+                                        notification.courseId,
                                         ' '
                                       )}
                                     </Link>
@@ -154,22 +200,28 @@ export const NotificationDropdown = ({
                                 </div>
                                 <Trash2
                                   onClick={async () =>
-                                    await deleteNotification(
-                                      notification.review.courseId
-                                    )
+                                    await deleteNotification(notification._id)
                                   }
                                   className='ml-auto text-right text-gray-700 underline hover:text-gray-900 dark:text-gray-300 dark:hover:text-gray-50'
                                   size={16}
                                 />
                               </div>
-                              <CourseReview
-                                className='rounded-md'
-                                review={notification.review}
-                                canModify={false}
-                                handleDelete={() => undefined}
-                                openEditReview={() => undefined}
-                                attachment={ReviewAttachment.ScrollButton}
-                              />
+                              {notification.kind !==
+                                SubscriptionKind.Reviews && (
+                                <p className='mb-2 text-sm text-gray-600 dark:text-gray-300'>
+                                  {notificationSummary(notification)}
+                                </p>
+                              )}
+                              {notification.review && (
+                                <CourseReview
+                                  className='rounded-md'
+                                  review={notification.review}
+                                  canModify={false}
+                                  handleDelete={() => undefined}
+                                  openEditReview={() => undefined}
+                                  attachment={ReviewAttachment.ScrollButton}
+                                />
+                              )}
                             </div>
                           )}
                         </Menu.Item>
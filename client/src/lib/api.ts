@@ -1,5 +1,7 @@
 import type { Subscription } from '../lib/types';
 import type { UserResponse } from '../lib/types';
+import type { BulkNotificationsPayload } from '../lib/types';
+import type { GetNotificationsPayload } from '../lib/types';
 import type { Notification } from '../lib/types';
 import { InteractionKind } from '../lib/types';
 import type { GetReviewsPayload } from '../lib/types';
@@ -215,32 +217,60 @@ export const api = {
     });
   },
 
-  async getNotifications(): Promise<Notification[]> {
-    return client.deserialize<Notification[]>('GET', '/notifications');
+  async getNotifications(
+    limit?: number,
+    offset?: number
+  ): Promise<GetNotificationsPayload> {
+    const params = new URLSearchParams();
+
+    if (limit !== undefined) params.set('limit', String(limit));
+    if (offset !== undefined) params.set('offset', String(offset));
+
+    const query = params.toString();
+
+    return client.deserialize<GetNotificationsPayload>(
+      'GET',
+      `/notifications${query ? `?${query}` : ''}`
+    );
   },
 
-  async updateNotification(
-    courseId: string,
-    creatorId: string,
-    seen: boolean
-  ): Promise<Response> {
-    return client.put('/notifications', {
-      headers: { 'Content-Type': 'application/json' },
-      body: JSON.stringify({
-        course_id: courseId,
-        creator_id: creatorId,
-        seen: seen,
-      }),
-    });
+  streamNotifications(
+    onNotification: (notification: Notification) => void
+  ): () => void {
+    const source = new EventSource(`${prefix}/notifications/stream`);
+
+    source.addEventListener('notification', (event: MessageEvent<string>) =>
+      onNotification(JSON.parse(event.data) as Notification)
+    );
+
+    return () => source.close();
   },
 
-  async deleteNotification(courseId: string): Promise<Response> {
-    return client.delete('/notifications', {
+  async updateNotification(id: string, seen: boolean): Promise<Response> {
+    return client.put(`/notifications/${encodeURIComponent(id)}`, {
       headers: { 'Content-Type': 'application/json' },
-      body: JSON.stringify({ course_id: courseId }),
+      body: JSON.stringify({ seen }),
     });
   },
 
+  async deleteNotification(id: string): Promise<Response> {
+    return client.delete(`/notifications/${encodeURIComponent(id)}`);
+  },
+
+  async markAllNotificationsRead(): Promise<BulkNotificationsPayload> {
+    return client.deserialize<BulkNotificationsPayload>(
+      'POST',
+      '/notifications/mark-all-read'
+    );
+  },
+
+  async clearNotifications(): Promise<BulkNotificationsPayload> {
+    return client.deserialize<BulkNotificationsPayload>(
+      'POST',
+      '/notifications/clear-all'
+    );
+  },
+
   async search(query: string): Promise<SearchResults> {
     return client.deserialize<SearchResults>(
       'GET',
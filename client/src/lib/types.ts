@@ -2,6 +2,24 @@
  Generated by typeshare 1.13.3
 */
 
+/** Where a building is, in degrees. */
+export interface Coordinates {
+  lat: number;
+  lng: number;
+}
+
+/** A campus building that courses can meet in. */
+export interface Building {
+  /** The building's code as it appears in schedule locations, e.g. `BURN`. */
+  _id: string;
+  /** The building's full name, e.g. `Burnside Hall`. */
+  name: string;
+  /** Where the building is, if known. */
+  coordinates?: Coordinates;
+  /** The campus the building is on, e.g. `Downtown` or `Macdonald`. */
+  campus?: string;
+}
+
 export interface ChangelogItem {
   number: number;
   summary?: string;
@@ -9,6 +27,45 @@ export interface ChangelogItem {
   mergedAt: string;
 }
 
+/** A course's class average in one term, as a letter grade. */
+export interface CourseAverage {
+  /** The course ID this average is for (e.g. `COMP251`). */
+  courseId: string;
+  /** Term the average was recorded in (e.g. `Fall 2024`). */
+  term: string;
+  /** The class average as a letter grade (e.g. `B+`). */
+  average: string;
+}
+
+/** Summary statistics over a course's grade point averages. */
+export interface GradeTrend {
+  /** Number of terms with a recognized average. */
+  termCount: number;
+  /** Mean grade point average across terms. */
+  mean: number;
+  /** Lowest grade point average in any term. */
+  min: number;
+  /** Highest grade point average in any term. */
+  max: number;
+  /** Grade point average in the most recent term. */
+  latest: number;
+  /**
+   * Least-squares change in grade point average per year, positive when
+   * averages are rising.
+   */
+  slopePerYear: number;
+}
+
+/** A single field that changed between two versions of a course. */
+export interface FieldChange {
+  /** The changed field's name, e.g. `prerequisitesText`. */
+  field: string;
+  /** The field's value in the earlier version. */
+  before: any;
+  /** The field's value in the later version. */
+  after: any;
+}
+
 export interface Instructor {
   name: string;
   nameNgrams?: string;
@@ -34,11 +91,104 @@ export interface Review {
   userId: string;
 }
 
+/** A course an instructor taught in a given term. */
+export interface TeachingAssignment {
+  courseId: string;
+  term: string;
+}
+
+/** An instructor along with everything they've taught. */
+export interface InstructorProfile {
+  /**
+   * A stable identifier derived from the normalized name, e.g.
+   * `mona-elsaadawy`.
+   */
+  _id: string;
+  /** The normalized name, e.g. `Mona Elsaadawy`. */
+  name: string;
+  nameNgrams?: string;
+  /** Every spelling of the name seen in the catalogue or schedule builder. */
+  aliases: string[];
+  /** Every course and term the instructor is known to have taught. */
+  assignments: TeachingAssignment[];
+}
+
+/** Aggregates over a set of reviews. */
+export interface ReviewStats {
+  /** Number of reviews aggregated. */
+  reviewCount: number;
+  /** Average rating, or `0` if there are no reviews. */
+  avgRating: number;
+  /** Average difficulty, or `0` if there are no reviews. */
+  avgDifficulty: number;
+  /** Number of reviews giving each rating, from 1 to 5. */
+  ratingDistribution: number[];
+  /** Number of reviews giving each difficulty, from 1 to 5. */
+  difficultyDistribution: number[];
+}
+
+/** Review aggregates for one course an instructor taught. */
+export interface CourseStats {
+  courseId: string;
+  stats: ReviewStats;
+}
+
+/** Review aggregates for one term an instructor taught a course in. */
+export interface TermStats {
+  courseId: string;
+  term: string;
+  stats: ReviewStats;
+}
+
+/**
+ * Review aggregates for an instructor, overall and broken down by course
+ * and term.
+ */
+export interface InstructorStats {
+  /** Aggregates across every review naming the instructor. */
+  overall: ReviewStats;
+  /**
+   * Aggregates per course, for every course the instructor taught or was
+   * reviewed for.
+   */
+  courses: CourseStats[];
+  /**
+   * Aggregates per course and term the instructor taught, most recent
+   * first.
+   *
+   * Reviews don't record a term, so each review is attributed to the
+   * latest term the instructor taught its course that started before the
+   * review was written.
+   */
+  terms: TermStats[];
+}
+
 export interface GetInstructorPayload {
-  /** Instructor matching the requested name, if found. */
-  instructor?: Instructor;
+  /**
+   * Instructor matching the requested name, including their full teaching
+   * history, if found.
+   */
+  instructor?: InstructorProfile;
   /** Reviews associated with the instructor sorted by newest first. */
   reviews: Review[];
+  /** Review aggregates overall, per course and per term. */
+  stats: InstructorStats;
+}
+
+export interface InstructorComparison {
+  /** The instructor being compared. */
+  instructor: InstructorProfile;
+  /** Aggregates over the instructor's reviews for the course. */
+  stats: ReviewStats;
+  /** Aggregates per term the instructor taught the course, most recent first. */
+  terms: TermStats[];
+}
+
+export interface CompareInstructorsPayload {
+  /** The course ID. */
+  courseId: string;
+  /** Everyone who has taught the course, in name order. */
+  instructors: InstructorComparison[];
 }
 
 export enum InteractionKind {
@@ -81,9 +231,71 @@ export interface GetUserInteractionForCoursePayload {
 }
 
 export interface Notification {
-  review: Review;
+  /** Unique identifier for the notification. */
+  _id: string;
+  /**
+   * When the notification was created, independent of when its review was
+   * written or last edited.
+   */
+  createdAt: string;
+  /** The kind of subscription the notification was sent for. */
+  kind: SubscriptionKind;
+  /** The course the notification is about. */
+  courseId: string;
+  /**
+   * The review the notification is about, for review and review activity
+   * notifications.
+   */
+  review?: Review;
+  /**
+   * The followed instructor the review names, for instructor
+   * notifications.
+   */
+  instructor?: string;
+  /** The newly offered term, for offering notifications. */
+  term?: string;
+  /** What changed, for course change notifications. */
+  changes?: FieldChange[];
   seen: boolean;
   userId: string;
+  /** Whether the notification has been emailed to the user. */
+  emailed: boolean;
+}
+
+export interface GetNotificationsPayload {
+  /** The requested page of notifications, newest first. */
+  notifications: Notification[];
+  /** Number of notifications the user has, across all pages. */
+  totalCount: number;
+  /** Number of notifications the user hasn't seen, across all pages. */
+  unreadCount: number;
+}
+
+export interface BulkNotificationsPayload {
+  /** Number of notifications the action changed. */
+  count: number;
+}
+
+/** How often a user wants notifications emailed to them. */
+export enum EmailFrequency {
+  /** Notifications are only shown on the site. */
+  Never = 'never',
+  /** Each notification is emailed as soon as it's created. */
+  Immediate = 'immediate',
+  /** Unseen notifications are emailed together once a day. */
+  Daily = 'daily',
+  /** Unseen notifications are emailed together once a week. */
+  Weekly = 'weekly',
+}
+
+/** How a user wants to hear about new notifications. */
+export interface NotificationPreferences {
+  userId: string;
+  /** Address to email notifications to. */
+  email?: string;
+  emailFrequency: EmailFrequency;
+  /** When the last digest was emailed, if ever. */
+  lastDigestAt?: string;
 }
 
 export type ReqNode =
@@ -96,18 +308,84 @@ export type ReqNode =
       };
     };
 
+/** How much a natively parsed requirement tree can be trusted. */
+export enum Confidence {
+  /** Parts of the text weren't understood and were skipped. */
+  Low = 'low',
+  /**
+   * The text was understood, but mentions conditions a tree can't express,
+   * like instructor permission or equivalent courses.
+   */
+  Medium = 'medium',
+  /** The whole text was understood and is captured by the tree. */
+  High = 'high',
+}
+
+/**
+ * Who a course is open to, as parsed from its restriction notes.
+ *
+ * Empty lists mean the catalogue doesn't restrict the course along that
+ * dimension.
+ */
+export interface RestrictedTo {
+  /**
+   * Programs whose students may take the course, e.g. `B.Sc. in Computer
+   * Science`.
+   */
+  programs: string[];
+  /** Faculties whose students may take the course, e.g. `Science`. */
+  faculties: string[];
+  /** Years of study (`U1` is 1) in which students may take the course. */
+  years: number[];
+}
+
 export interface Requirements {
   prerequisitesText?: string;
   corequisitesText?: string;
   corequisites: string[];
   prerequisites: string[];
   restrictions?: string;
+  excludedCourses: string[];
+  crossListings: string[];
+  restrictedTo: RestrictedTo;
   logicalPrerequisites?: ReqNode;
   logicalCorequisites?: ReqNode;
+  prerequisitesConfidence?: Confidence;
+  corequisitesConfidence?: Confidence;
+}
+
+/** What a subscription notifies its user about. */
+export enum SubscriptionKind {
+  /** New reviews of a course. */
+  Reviews = 'reviews',
+  /** New reviews naming an instructor, in any course. */
+  Instructor = 'instructor',
+  /**
+   * A course being offered in a term it wasn't before, optionally only a
+   * specific term.
+   */
+  Offerings = 'offerings',
+  /** Changes to a course's schedule when courses are re-seeded. */
+  CourseChanges = 'courseChanges',
+  /** Votes on the user's own reviews. */
+  ReviewActivity = 'reviewActivity',
 }
 
 export interface Subscription {
+  /**
+   * What the subscription is for. Subscriptions saved before there were
+   * kinds are all for course reviews.
+   */
+  kind: SubscriptionKind;
+  /**
+   * The course followed, for course review, offering and course change
+   * subscriptions.
+   */
   courseId: string;
+  /** The instructor followed, for instructor subscriptions. */
+  instructor?: string;
+  /** The only term to notify about, for offering subscriptions. */
+  term?: string;
   userId: string;
 }
 
@@ -123,6 +401,67 @@ export interface UserResponse {
   user?: User;
 }
 
+/** Something that happened which webhooks can be told about. */
+export enum WebhookEventKind {
+  /** A review was written. */
+  ReviewCreated = 'review.created',
+  /** A review was edited. */
+  ReviewUpdated = 'review.updated',
+  /** A review was deleted. */
+  ReviewDeleted = 'review.deleted',
+  /** Seeding changed a course's catalogue entry. */
+  CourseUpdated = 'course.updated',
+}
+
+/** An endpoint that's sent signed events as they happen. */
+export interface Webhook {
+  _id: string;
+  /** Where events are posted. */
+  url: string;
+  /**
+   * Key each payload's HMAC-SHA256 signature is made with, only ever sent
+   * to clients in response to creating the webhook.
+   */
+  secret?: string;
+  /** Events the webhook is sent, or every event if empty. */
+  events: WebhookEventKind[];
+  createdAt: string;
+}
+
+/** Where a webhook delivery is at. */
+export enum WebhookDeliveryStatus {
+  /** Waiting for its first attempt, or a retry. */
+  Pending = 'pending',
+  /** The webhook responded with a success status. */
+  Succeeded = 'succeeded',
+  /** Every attempt failed, or the webhook was deleted. */
+  Failed = 'failed',
+}
+
+/**
+ * One event being sent to one webhook, kept as a log of what was sent and
+ * how it went.
+ */
+export interface WebhookDelivery {
+  _id: string;
+  webhookId: string;
+  eventId: string;
+  event: WebhookEventKind;
+  /** The exact JSON body sent, which is what's signed. */
+  payload: string;
+  status: WebhookDeliveryStatus;
+  /** How many times sending has been tried. */
+  attempts: number;
+  /** When to try sending next, while pending. */
+  nextAttemptAt: string;
+  /** Status code of the last response, if there was one. */
+  responseStatus?: number;
+  /** Why the last attempt failed, if it did. */
+  error?: string;
+  createdAt: string;
+  deliveredAt?: string;
+}
+
 export enum Operator {
   And = 'AND',
   Or = 'OR',
@@ -1,4 +1,9 @@
-import type { Instructor, ReqNode } from '../lib/types';
+import type {
+  Confidence,
+  Instructor,
+  ReqNode,
+  RestrictedTo,
+} from '../lib/types';
 import type { Schedule } from './schedule';
 
 export type Course = {
@@ -19,7 +24,16 @@ export type Course = {
   corequisites: string[];
   logicalPrerequisites?: ReqNode;
   logicalCorequisites?: ReqNode;
+  prerequisitesConfidence?: Confidence;
+  corequisitesConfidence?: Confidence;
   leadingTo: string[];
   restrictions: string;
+  excludedCourses: string[];
+  crossListings: string[];
+  restrictedTo: RestrictedTo;
   schedule: Schedule[];
+  latestAverage?: number;
+  meanAverage?: number;
+  ratingDistribution: number[];
+  difficultyDistribution: number[];
 };
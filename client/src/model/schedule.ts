@@ -2,6 +2,8 @@ export type Block = {
   campus: string;
   display: string;
   location: string;
+  building?: string;
+  room?: string;
   timeblocks: TimeBlock[];
   crn: string;
 };
@@ -51,6 +51,16 @@ const makeSortPayload = (sort: SortByType) => {
         sortType: 'reviewCount',
         reverse: false,
       };
+    case 'Highest Average':
+      return {
+        sortType: 'average',
+        reverse: true,
+      };
+    case 'Lowest Average':
+      return {
+        sortType: 'average',
+        reverse: false,
+      };
   }
 };
 
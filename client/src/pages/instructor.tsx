@@ -11,7 +11,7 @@ import { Layout } from '../components/layout';
 import { ReviewEmptyPrompt } from '../components/review-empty-prompt';
 import { useAuth } from '../hooks/use-auth';
 import { api } from '../lib/api';
-import type { Instructor as InstructorType } from '../lib/types';
+import type { InstructorProfile } from '../lib/types';
 import type { Review } from '../lib/types';
 import { courseIdToUrlParam } from '../lib/utils';
 import { Loading } from './loading';
@@ -24,7 +24,7 @@ export const Instructor = () => {
   const [showAllReviews, setShowAllReviews] = useState(false);
 
   const [instructor, setInstructor] = useState<
-    InstructorType | undefined | null
+    InstructorProfile | undefined | null
   >(undefined);
 
   const user = useAuth();
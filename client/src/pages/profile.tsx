@@ -13,7 +13,7 @@ import { Layout } from '../components/layout';
 import { Spinner } from '../components/spinner';
 import { useAuth } from '../hooks/use-auth';
 import { api } from '../lib/api';
-import type { Subscription } from '../lib/types';
+import { type Subscription, SubscriptionKind } from '../lib/types';
 import type { Review } from '../lib/types';
 import { courseIdToUrlParam } from '../lib/utils';
 import { spliceCourseCode } from '../lib/utils';
@@ -48,7 +48,13 @@ export const Profile = () => {
 
     api
       .getSubscriptions()
-      .then((data) => setUserSubscriptions(data))
+      .then((data) =>
+        setUserSubscriptions(
+          data.filter(
+            (subscription) => subscription.kind === SubscriptionKind.Reviews
+          )
+        )
+      )
       .catch(() =>
         toast.error(
           'An error occurred while fetching your subscriptions, please try again later.'
@@ -4,39 +4,76 @@ use super::*;
 pub struct Db {
   client: Client,
   database: Database,
+  notification_hub: broadcast::Sender<Notification>,
 }
 
 impl Db {
+  const AVERAGE_COLLECTION: &'static str = "averages";
+  const BUILDING_COLLECTION: &'static str = "buildings";
   const COURSE_COLLECTION: &'static str = "courses";
+  const COURSE_VERSION_COLLECTION: &'static str = "course_versions";
+  const EXAM_COLLECTION: &'static str = "exams";
   const INSTRUCTOR_COLLECTION: &'static str = "instructors";
   const INTERACTION_COLLECTION: &'static str = "interactions";
   const NOTIFICATION_COLLECTION: &'static str = "notifications";
+  const NOTIFICATION_PREFERENCES_COLLECTION: &'static str =
+    "notification_preferences";
   const REVIEW_COLLECTION: &'static str = "reviews";
   const SUBSCRIPTION_COLLECTION: &'static str = "subscriptions";
+  const WEBHOOK_COLLECTION: &'static str = "webhooks";
+  const WEBHOOK_DELIVERY_COLLECTION: &'static str = "webhook_deliveries";
+
+  /// How many notifications a slow listener can fall behind by before it
+  /// starts missing them.
+  const NOTIFICATION_HUB_CAPACITY: usize = 256;
+
+  /// The most notifications a single page can hold.
+  pub const NOTIFICATION_PAGE_LIMIT: i64 = 100;
+
+  pub async fn connect(url: &str, db_name: &str) -> Result<Self> {
+    Self::connect_with_handler(url, db_name, None).await
+  }
+
+  /// Connects like [`Db::connect`], reporting every command the client runs
+  /// to `handler`.
+  pub async fn connect_monitored(
+    url: &str,
+    db_name: &str,
+    handler: Arc<dyn CommandEventHandler>,
+  ) -> Result<Self> {
+    Self::connect_with_handler(url, db_name, Some(handler)).await
+  }
 
   #[tracing::instrument(name = "db_connect", skip_all, fields(db_name = %db_name))]
-  pub async fn connect(db_name: &str) -> Result<Self> {
-    let mut client_options =
-      ClientOptions::parse(env::var("MONGODB_URL").unwrap_or_else(|_| {
-        format!("mongodb://localhost:27017/{db_name}?directConnection=true&replicaSet=rs0")
-      }))
-      .await?;
+  async fn connect_with_handler(
+    url: &str,
+    db_name: &str,
+    handler: Option<Arc<dyn CommandEventHandler>>,
+  ) -> Result<Self> {
+    let mut client_options = ClientOptions::parse(url).await?;
 
     client_options.app_name = Some(db_name.to_string());
+    client_options.command_event_handler = handler;
 
     let client = Client::with_options(client_options)?;
 
-    client
-      .database(db_name)
-      .run_command(doc! { "ping": 1 }, None)
-      .await?;
+    let db = Self {
+      database: client.database(db_name),
+      client,
+      notification_hub: broadcast::channel(Self::NOTIFICATION_HUB_CAPACITY).0,
+    };
+
+    db.ping().await?;
 
     info!("Connected to MongoDB");
 
-    Ok(Self {
-      database: client.database(db_name),
-      client,
-    })
+    Ok(db)
+  }
+
+  /// Checks that the database is reachable.
+  pub async fn ping(&self) -> Result {
+    self.database.run_command(doc! { "ping": 1 }, None).await?;
+    Ok(())
   }
 
   pub fn name(&self) -> String {
@@ -59,11 +96,16 @@ impl Db {
       let CourseFilter {
         subjects,
         levels,
+        min_average,
         terms,
         query,
         ..
       } = filter;
 
+      if let Some(min_average) = min_average {
+        document.insert("meanAverage", doc! { "$gte": min_average });
+      }
+
       if let Some(subjects) = subjects {
         document.insert(
           "subject",
@@ -125,6 +167,12 @@ impl Db {
       if let Some(sort_by) = filter.sort_by {
         let reverse = if sort_by.reverse { -1 } else { 1 };
         let field = match sort_by.sort_type {
+          CourseSortType::Average => {
+            if !document.contains_key("meanAverage") {
+              document.insert("meanAverage", doc! { "$ne": null });
+            }
+            "meanAverage"
+          }
           CourseSortType::Rating => {
             document.insert("reviewCount", doc! { "$gt": 0 });
             "avgRating"
@@ -178,7 +226,7 @@ impl Db {
         .try_collect()
         .await?,
       instructors: self
-        .text_search::<Instructor>(Self::INSTRUCTOR_COLLECTION, query, 2)
+        .text_search::<InstructorProfile>(Self::INSTRUCTOR_COLLECTION, query, 2)
         .await?
         .try_collect()
         .await?,
@@ -190,6 +238,21 @@ impl Db {
     self.find_course(doc! { "_id": id }).await
   }
 
+  pub async fn find_courses_by_ids(
+    &self,
+    ids: &[String],
+  ) -> Result<Vec<Course>> {
+    Ok(
+      self
+        .database
+        .collection::<Course>(Self::COURSE_COLLECTION)
+        .find(doc! { "_id": { "$in": ids } }, None)
+        .await?
+        .try_collect()
+        .await?,
+    )
+  }
+
   #[tracing::instrument(name = "db_add_review", skip(self), fields(course_id = %review.course_id, user_id = %review.user_id))]
   pub async fn add_review(&self, review: Review) -> Result<UpdateResult> {
     let mut session = self.client.start_session(None).await?;
@@ -202,23 +265,41 @@ impl Db {
 
     async fn transaction(
       session: &mut ClientSession,
+      database: Database,
       course_coll: Collection<Course>,
       review_coll: Collection<Review>,
       review: Review,
     ) -> mongodb::error::Result<UpdateResult> {
+      let filter = doc! {
+        "courseId": &review.course_id,
+        "userId": &review.user_id
+      };
+
+      let mut stats = Db::stored_review_stats(
+        session,
+        &course_coll,
+        &review_coll,
+        &review.course_id,
+      )
+      .await?;
+
+      if let Some(previous) = review_coll
+        .find_one_with_session(filter.clone(), None, session)
+        .await?
+      {
+        stats.remove(&previous);
+      }
+
       let res = review_coll
         .update_one_with_session(
-          doc! {
-            "courseId": &review.course_id,
-            "userId": review.user_id
-          },
+          filter,
           UpdateModifications::Document(doc! {
             "$set": {
               "content": &review.content,
               "difficulty": review.difficulty,
               "instructors": &review.instructors,
               "rating": review.rating,
-              "timestamp": review.timestamp,
+              "timestamp": review.timestamp.clone(),
               "likes": 0
             },
           }),
@@ -227,61 +308,46 @@ impl Db {
         )
         .await?;
 
-      let course = course_coll
-        .find_one(
-          doc! {
-            "_id": &review.course_id,
-          },
-          None,
-        )
-        .await?
-        .ok_or(mongodb::error::Error::custom(Error::CourseNotFound))?;
-
-      let count = course.review_count as f32;
-
-      let avg_rating =
-        (course.avg_rating * count + (review.rating as f32)) / (count + 1.0);
+      stats.add(&review);
 
-      let avg_difficulty = (course.avg_difficulty * count
-        + (review.difficulty as f32))
-        / (count + 1.0);
+      Db::set_review_stats(session, &course_coll, &review.course_id, stats)
+        .await?;
 
-      course_coll
-        .update_one(
-          doc! {
-            "_id": &review.course_id
+      Db::enqueue_webhook_event(
+        session,
+        &database,
+        WebhookEvent::about_review(
+          if res.upserted_id.is_some() {
+            WebhookEventKind::ReviewCreated
+          } else {
+            WebhookEventKind::ReviewUpdated
           },
-          UpdateModifications::Document(doc! {
-            "$inc": { "reviewCount": 1 },
-            "$set": {
-              "avgRating": avg_rating,
-              "avgDifficulty": avg_difficulty,
-            }
-          }),
-          None,
-        )
-        .await?;
+          review,
+        ),
+      )
+      .await?;
 
       Ok(res)
     }
 
-    let res = session
-      .with_transaction(
-        (),
-        move |session, _| {
-          transaction(
-            session,
-            interaction_coll.clone(),
-            review_coll.clone(),
-            review.clone(),
-          )
-          .boxed()
-        },
-        None,
-      )
-      .await?;
-
-    Ok(res)
+    Ok(
+      session
+        .with_transaction(
+          (),
+          |session, _| {
+            transaction(
+              session,
+              self.database.clone(),
+              interaction_coll.clone(),
+              review_coll.clone(),
+              review.clone(),
+            )
+            .boxed()
+          },
+          None,
+        )
+        .await?,
+    )
   }
 
   pub async fn delete_review(
@@ -299,11 +365,20 @@ impl Db {
 
     async fn transaction(
       session: &mut ClientSession,
+      database: Database,
       course_coll: Collection<Course>,
       review_coll: Collection<Review>,
       course_id: String,
       user_id: String,
     ) -> mongodb::error::Result<Review> {
+      let mut stats = Db::stored_review_stats(
+        session,
+        &course_coll,
+        &review_coll,
+        &course_id,
+      )
+      .await?;
+
       let review = review_coll
         .find_one_and_delete_with_session(
           doc! {
@@ -316,68 +391,183 @@ impl Db {
         .await?
         .ok_or(mongodb::error::Error::custom(Error::ReviewNotFound))?;
 
-      let course = course_coll
-        .find_one(
-          doc! {
-            "_id": &course_id,
-          },
-          None,
-        )
-        .await?
-        .ok_or(mongodb::error::Error::custom(Error::CourseNotFound))?;
+      stats.remove(&review);
 
-      let (avg_rating, avg_difficulty) = if course.review_count == 0 {
-        (0.0, 0.0)
-      } else {
-        let count = course.review_count as f32;
+      Db::set_review_stats(session, &course_coll, &course_id, stats).await?;
 
-        let rating =
-          (course.avg_rating * count - (review.rating as f32)) / (count - 1.0);
+      Db::enqueue_webhook_event(
+        session,
+        &database,
+        WebhookEvent::about_review(
+          WebhookEventKind::ReviewDeleted,
+          review.clone(),
+        ),
+      )
+      .await?;
 
-        let difficulty = (course.avg_difficulty * count
-          - (review.difficulty as f32))
-          / (count - 1.0);
+      Ok(review)
+    }
 
-        (rating, difficulty)
-      };
+    let database = self.database.clone();
 
-      course_coll
-        .update_one(
-          doc! {
-            "_id": &review.course_id
+    Ok(
+      session
+        .with_transaction(
+          (),
+          move |session, _| {
+            transaction(
+              session,
+              database.clone(),
+              interaction_coll.clone(),
+              review_coll.clone(),
+              course_id.to_string(),
+              user_id.to_string(),
+            )
+            .boxed()
           },
-          UpdateModifications::Document(doc! {
-            "$inc": { "reviewCount": -1 },
-            "$set": {
-              "avgRating": avg_rating,
-              "avgDifficulty": avg_difficulty,
-            }
-          }),
           None,
         )
-        .await?;
+        .await?,
+    )
+  }
 
-      Ok(review)
+  /// Review aggregates for `course`, narrowed to reviews naming one of
+  /// `instructors` and to reviews about `term`, without loading review
+  /// content.
+  ///
+  /// Reviews don't record a term, so a review is taken to be about the
+  /// latest term the course was taught (by one of `instructors`, if given)
+  /// that started before it was written.
+  pub async fn course_review_stats(
+    &self,
+    course: &Course,
+    instructors: Option<&[String]>,
+    term: Option<&str>,
+  ) -> Result<ReviewStats> {
+    let mut query = doc! { "courseId": &course.id };
+
+    if let Some(instructors) = instructors {
+      query.insert("instructors", doc! { "$in": instructors });
     }
 
-    let review = session
-      .with_transaction(
-        (),
-        move |session, _| {
-          transaction(
-            session,
-            interaction_coll.clone(),
-            review_coll.clone(),
-            course_id.to_string(),
-            user_id.to_string(),
-          )
-          .boxed()
-        },
-        None,
+    let reviews = self
+      .database
+      .collection::<Review>(Self::REVIEW_COLLECTION)
+      .find(
+        query,
+        FindOptions::builder()
+          .projection(Self::review_score_projection())
+          .build(),
+      )
+      .await?
+      .try_collect::<Vec<_>>()
+      .await?;
+
+    let Some(term) = term else {
+      return Ok(reviews.iter().collect());
+    };
+
+    let terms = course
+      .instructors
+      .iter()
+      .filter(|instructor| {
+        instructors.is_none_or(|names| names.contains(&instructor.name))
+      })
+      .map(|instructor| instructor.term.as_str())
+      .chain(
+        course
+          .terms
+          .iter()
+          .map(String::as_str)
+          .filter(|_| instructors.is_none()),
+      )
+      .collect::<Vec<_>>();
+
+    Ok(
+      reviews
+        .iter()
+        .filter(|review| {
+          review_term(review, terms.iter().copied()) == Some(term)
+        })
+        .collect(),
+    )
+  }
+
+  /// The review aggregates stored on a course.
+  ///
+  /// Courses reviewed before histograms were tracked are missing them, so
+  /// those get rebuilt from their reviews.
+  async fn stored_review_stats(
+    session: &mut ClientSession,
+    course_coll: &Collection<Course>,
+    review_coll: &Collection<Review>,
+    course_id: &str,
+  ) -> mongodb::error::Result<ReviewStats> {
+    let course = course_coll
+      .find_one_with_session(doc! { "_id": course_id }, None, session)
+      .await?
+      .ok_or(mongodb::error::Error::custom(Error::CourseNotFound))?;
+
+    let stats = course.review_stats();
+
+    if stats.rating_distribution.len() == 5
+      && stats.difficulty_distribution.len() == 5
+    {
+      return Ok(stats);
+    }
+
+    let reviews = review_coll
+      .find_with_session(
+        doc! { "courseId": course_id },
+        FindOptions::builder()
+          .projection(Self::review_score_projection())
+          .build(),
+        session,
       )
+      .await?
+      .stream(session)
+      .try_collect::<Vec<_>>()
       .await?;
 
-    Ok(review)
+    Ok(reviews.iter().collect())
+  }
+
+  /// Every review field except its (possibly long) content.
+  fn review_score_projection() -> Document {
+    doc! {
+      "courseId": 1,
+      "userId": 1,
+      "rating": 1,
+      "difficulty": 1,
+      "instructors": 1,
+      "timestamp": 1,
+      "likes": 1,
+      "content": { "$literal": "" },
+    }
+  }
+
+  async fn set_review_stats(
+    session: &mut ClientSession,
+    course_coll: &Collection<Course>,
+    course_id: &str,
+    stats: ReviewStats,
+  ) -> mongodb::error::Result<UpdateResult> {
+    course_coll
+      .update_one_with_session(
+        doc! { "_id": course_id },
+        UpdateModifications::Document(doc! {
+          "$set": {
+            "reviewCount": stats.review_count,
+            "avgRating": stats.avg_rating,
+            "avgDifficulty": stats.avg_difficulty,
+            "ratingDistribution": stats.rating_distribution,
+            "difficultyDistribution": stats.difficulty_distribution,
+          }
+        }),
+        None,
+        session,
+      )
+      .await
   }
 
   pub async fn find_reviews_by_course_id(
@@ -394,6 +584,24 @@ impl Db {
     self.find_reviews(doc! { "userId": user_id }).await
   }
 
+  /// Reviews naming any spelling of `instructor`.
+  pub async fn find_reviews_by_instructor(
+    &self,
+    instructor: &InstructorProfile,
+  ) -> Result<Vec<Review>> {
+    self
+      .find_reviews(doc! {
+        "instructors": {
+          "$in": instructor
+            .aliases
+            .iter()
+            .chain([&instructor.name])
+            .collect::<Vec<_>>()
+        }
+      })
+      .await
+  }
+
   pub async fn find_reviews_by_instructor_name(
     &self,
     instructor_name: &str,
@@ -432,7 +640,7 @@ impl Db {
       interaction_coll: Collection<Interaction>,
       review_coll: Collection<Review>,
       interaction: Interaction,
-    ) -> mongodb::error::Result<()> {
+    ) -> mongodb::error::Result<bool> {
       let old = interaction_coll
         .find_one_and_update_with_session(
           doc! {
@@ -453,7 +661,7 @@ impl Db {
       if let Some(old) = old.clone()
         && old.kind == interaction.kind
       {
-        return Ok(());
+        return Ok(false);
       }
 
       let increment_amount = {
@@ -481,10 +689,12 @@ impl Db {
         )
         .await?;
 
-      Ok(())
+      Ok(true)
     }
 
-    session
+    let voted = interaction.clone();
+
+    let changed = session
       .with_transaction(
         (),
         move |session, _| {
@@ -500,6 +710,10 @@ impl Db {
       )
       .await?;
 
+    if changed {
+      self.notify_review_activity(&voted).await?;
+    }
+
     Ok(())
   }
 
@@ -657,6 +871,7 @@ impl Db {
         .collection::<Subscription>(Self::SUBSCRIPTION_COLLECTION)
         .find_one(
           doc! {
+            "kind": Self::subscription_kind(SubscriptionKind::Reviews),
             "courseId": course_id,
             "userId": user_id,
           },
@@ -709,7 +924,10 @@ impl Db {
         .collection::<Subscription>(Self::SUBSCRIPTION_COLLECTION)
         .delete_one(
           doc! {
+            "kind": Self::subscription_kind(subscription.kind),
             "courseId": subscription.course_id,
+            "instructor": subscription.instructor,
+            "term": subscription.term,
             "userId": subscription.user_id,
           },
           None,
@@ -718,87 +936,575 @@ impl Db {
     )
   }
 
+  /// A user's notifications, newest first.
   pub async fn get_notifications(
     &self,
     user_id: &str,
   ) -> Result<Vec<Notification>> {
+    self.notifications_page(user_id, None, None, false).await
+  }
+
+  /// A page of a user's notifications, newest first, optionally only the
+  /// unseen ones. A `limit` is clamped to between one and
+  /// [`Self::NOTIFICATION_PAGE_LIMIT`].
+  pub async fn notifications_page(
+    &self,
+    user_id: &str,
+    limit: Option<i64>,
+    offset: Option<u64>,
+    unseen_only: bool,
+  ) -> Result<Vec<Notification>> {
+    let mut query = doc! { "userId": user_id };
+
+    if unseen_only {
+      query.insert("seen", false);
+    }
+
     Ok(
       self
         .database
         .collection::<Notification>(Self::NOTIFICATION_COLLECTION)
-        .find(doc! { "userId": user_id }, None)
+        .find(
+          query,
+          FindOptions::builder()
+            .sort(doc! { "createdAt": -1, "_id": -1 })
+            .skip(offset)
+            .limit(
+              limit.map(|limit| limit.clamp(1, Self::NOTIFICATION_PAGE_LIMIT)),
+            )
+            .build(),
+        )
         .await?
         .try_collect::<Vec<Notification>>()
         .await?,
     )
   }
 
+  /// How many notifications a user has in total, and how many are unseen.
+  pub async fn notification_counts(&self, user_id: &str) -> Result<(u64, u64)> {
+    let collection = self
+      .database
+      .collection::<Notification>(Self::NOTIFICATION_COLLECTION);
+
+    Ok((
+      collection
+        .count_documents(doc! { "userId": user_id }, None)
+        .await?,
+      collection
+        .count_documents(doc! { "userId": user_id, "seen": false }, None)
+        .await?,
+    ))
+  }
+
   pub async fn add_notifications(&self, review: Review) -> Result {
-    let course_id = review.course_id.clone();
+    self
+      .insert_notifications(self.notifications_for(&review).await?)
+      .await
+  }
 
-    let subscriptions = self
-      .database
-      .collection::<Subscription>(Self::SUBSCRIPTION_COLLECTION)
-      .find(doc! { "courseId": course_id }, None)
+  /// Builds a notification of `review` for everyone following its course or
+  /// one of its instructors, other than the review's author.
+  ///
+  /// Users following both only get the course notification.
+  pub async fn notifications_for(
+    &self,
+    review: &Review,
+  ) -> Result<Vec<Notification>> {
+    let mut notifications = self
+      .subscribers(
+        SubscriptionKind::Reviews,
+        doc! { "courseId": &review.course_id },
+      )
       .await?
-      .try_collect::<Vec<Subscription>>()
-      .await?;
-
-    let subscriptions = subscriptions
       .into_iter()
-      .filter(|subscription| subscription.user_id != review.user_id)
-      .collect::<Vec<Subscription>>();
-
-    if subscriptions.is_empty() {
-      return Ok(());
-    }
+      .map(|subscription| {
+        Notification::new(&subscription.user_id, review.clone())
+      })
+      .collect::<Vec<Notification>>();
 
-    self
-      .database
-      .collection::<Notification>(Self::NOTIFICATION_COLLECTION)
-      .insert_many(
-        subscriptions
+    if !review.instructors.is_empty() {
+      notifications.extend(
+        self
+          .subscribers(
+            SubscriptionKind::Instructor,
+            doc! { "instructor": { "$in": &review.instructors } },
+          )
+          .await?
           .into_iter()
           .map(|subscription| Notification {
-            review: review.clone(),
-            seen: false,
-            user_id: subscription.user_id,
-          })
-          .collect::<Vec<Notification>>(),
-        None,
-      )
-      .await?;
+            instructor: subscription.instructor,
+            ..Notification::about_review(
+              SubscriptionKind::Instructor,
+              &subscription.user_id,
+              review.clone(),
+            )
+          }),
+      );
+    }
 
-    Ok(())
+    let mut notified = HashSet::new();
+
+    notifications.retain(|notification| {
+      notification.user_id != review.user_id
+        && notified.insert(notification.user_id.clone())
+    });
+
+    Ok(notifications)
   }
 
-  pub async fn delete_notification(
+  /// Subscriptions of `kind` that also match `query`.
+  async fn subscribers(
     &self,
-    user_id: &str,
-    course_id: &str,
-  ) -> Result<DeleteResult> {
+    kind: SubscriptionKind,
+    mut query: Document,
+  ) -> Result<Vec<Subscription>> {
+    query.insert("kind", Self::subscription_kind(kind));
+
     Ok(
       self
         .database
-        .collection::<Notification>(Self::NOTIFICATION_COLLECTION)
-        .delete_one(
-          doc! {
-            "userId": user_id,
-            "review.courseId": course_id,
-          },
-          None,
-        )
+        .collection::<Subscription>(Self::SUBSCRIPTION_COLLECTION)
+        .find(query, None)
+        .await?
+        .try_collect::<Vec<Subscription>>()
         .await?,
     )
   }
 
-  pub async fn delete_notifications(
+  /// Matches subscriptions of `kind`, counting subscriptions saved before
+  /// kinds existed as course review subscriptions.
+  fn subscription_kind(kind: SubscriptionKind) -> Bson {
+    match kind {
+      SubscriptionKind::Reviews => {
+        Bson::Document(doc! { "$in": [kind, Bson::Null] })
+      }
+      _ => kind.into(),
+    }
+  }
+
+  /// Notifies subscribers of a course that it's now offered in `new_terms`,
+  /// or that its schedule changed.
+  ///
+  /// These happen while seeding, so they're only stored in-app, and reach
+  /// email through digests.
+  async fn notify_course_changes(
     &self,
-    creator_id: &str,
     course_id: &str,
-  ) -> Result<DeleteResult> {
-    Ok(
-      self
+    new_terms: &[String],
+    schedule_change: Option<FieldChange>,
+  ) -> Result {
+    let mut notifications = Vec::new();
+
+    if !new_terms.is_empty() {
+      for subscription in self
+        .subscribers(
+          SubscriptionKind::Offerings,
+          doc! { "courseId": course_id },
+        )
+        .await?
+      {
+        notifications.extend(
+          new_terms
+            .iter()
+            .filter(|term| {
+              subscription
+                .term
+                .as_ref()
+                .is_none_or(|wanted| wanted == *term)
+            })
+            .map(|term| Notification {
+              term: Some(term.clone()),
+              ..Notification::about_course(
+                SubscriptionKind::Offerings,
+                &subscription.user_id,
+                course_id,
+              )
+            }),
+        );
+      }
+    }
+
+    if let Some(change) = schedule_change {
+      notifications.extend(
+        self
+          .subscribers(
+            SubscriptionKind::CourseChanges,
+            doc! { "courseId": course_id },
+          )
+          .await?
+          .into_iter()
+          .map(|subscription| Notification {
+            changes: vec![change.clone()],
+            ..Notification::about_course(
+              SubscriptionKind::CourseChanges,
+              &subscription.user_id,
+              course_id,
+            )
+          }),
+      );
+    }
+
+    self.insert_notifications(notifications).await
+  }
+
+  /// Lets the author of the review `interaction` voted on know, if they
+  /// follow activity on their reviews.
+  ///
+  /// Votes on the same review share one unseen notification, which is
+  /// refreshed with the latest like count.
+  async fn notify_review_activity(&self, interaction: &Interaction) -> Result {
+    if interaction.referrer == interaction.user_id {
+      return Ok(());
+    }
+
+    if self
+      .subscribers(
+        SubscriptionKind::ReviewActivity,
+        doc! { "userId": &interaction.user_id },
+      )
+      .await?
+      .is_empty()
+    {
+      return Ok(());
+    }
+
+    let Some(review) = self
+      .find_review(&interaction.course_id, &interaction.user_id)
+      .await?
+    else {
+      return Ok(());
+    };
+
+    let collection = self
+      .database
+      .collection::<Notification>(Self::NOTIFICATION_COLLECTION);
+
+    let refreshed = collection
+      .find_one_and_update(
+        doc! {
+          "userId": &interaction.user_id,
+          "kind": SubscriptionKind::ReviewActivity,
+          "courseId": &interaction.course_id,
+          "seen": false,
+        },
+        UpdateModifications::Document(doc! {
+          "$set": {
+            "review": Into::<Bson>::into(review.clone()),
+            "createdAt": Bson::from(DateTime::from(Utc::now())),
+            "emailed": false,
+          }
+        }),
+        FindOneAndUpdateOptions::builder()
+          .return_document(ReturnDocument::After)
+          .build(),
+      )
+      .await?;
+
+    match refreshed {
+      Some(notification) => self.publish_notifications(&[notification]),
+      None => {
+        self
+          .insert_notifications(vec![Notification::about_review(
+            SubscriptionKind::ReviewActivity,
+            &interaction.user_id,
+            review,
+          )])
+          .await?
+      }
+    }
+
+    Ok(())
+  }
+
+  pub async fn insert_notifications(
+    &self,
+    notifications: Vec<Notification>,
+  ) -> Result {
+    if notifications.is_empty() {
+      return Ok(());
+    }
+
+    self
+      .database
+      .collection::<Bson>(Self::NOTIFICATION_COLLECTION)
+      .insert_many(notifications.iter().cloned().map(Into::<Bson>::into), None)
+      .await?;
+
+    self.publish_notifications(&notifications);
+
+    Ok(())
+  }
+
+  /// A live feed of every user's notifications as they're stored or
+  /// refreshed.
+  pub fn subscribe_to_notifications(
+    &self,
+  ) -> broadcast::Receiver<Notification> {
+    self.notification_hub.subscribe()
+  }
+
+  fn publish_notifications(&self, notifications: &[Notification]) {
+    for notification in notifications {
+      // Sending only fails when nobody is listening, which is fine.
+      let _ = self.notification_hub.send(notification.clone());
+    }
+  }
+
+  /// A user's notifications created at or after `since`, oldest first, for
+  /// catching up after missing some from the live feed before any were
+  /// sent.
+  pub async fn notifications_since(
+    &self,
+    user_id: &str,
+    since: DateTime,
+  ) -> Result<Vec<Notification>> {
+    Ok(
+      self
+        .database
+        .collection::<Notification>(Self::NOTIFICATION_COLLECTION)
+        .find(
+          doc! { "userId": user_id, "createdAt": { "$gte": Bson::from(since) } },
+          FindOptions::builder()
+            .sort(doc! { "createdAt": 1, "_id": 1 })
+            .build(),
+        )
+        .await?
+        .try_collect::<Vec<Notification>>()
+        .await?,
+    )
+  }
+
+  /// A user's notifications created after the one with `last_id`, oldest
+  /// first, for catching up after missing some from the live feed.
+  ///
+  /// If that notification is gone, the time its id was generated is used
+  /// instead.
+  pub async fn notifications_after(
+    &self,
+    user_id: &str,
+    last_id: &str,
+  ) -> Result<Vec<Notification>> {
+    let Bson::ObjectId(last_id) = Notification::bson_id(last_id) else {
+      return Ok(Vec::new());
+    };
+
+    let collection = self
+      .database
+      .collection::<Notification>(Self::NOTIFICATION_COLLECTION);
+
+    let created_at = match collection
+      .find_one(doc! { "_id": last_id, "userId": user_id }, None)
+      .await?
+    {
+      Some(notification) => Bson::from(notification.created_at),
+      None => Bson::DateTime(last_id.timestamp()),
+    };
+
+    Ok(
+      collection
+        .find(
+          doc! {
+            "userId": user_id,
+            "$or": [
+              { "createdAt": { "$gt": &created_at } },
+              { "createdAt": &created_at, "_id": { "$gt": last_id } },
+            ],
+          },
+          FindOptions::builder()
+            .sort(doc! { "createdAt": 1, "_id": 1 })
+            .build(),
+        )
+        .await?
+        .try_collect::<Vec<Notification>>()
+        .await?,
+    )
+  }
+
+  /// Notifications the user hasn't seen on the site or been emailed yet.
+  pub async fn undelivered_notifications(
+    &self,
+    user_id: &str,
+  ) -> Result<Vec<Notification>> {
+    Ok(
+      self
+        .database
+        .collection::<Notification>(Self::NOTIFICATION_COLLECTION)
+        .find(
+          doc! {
+            "userId": user_id,
+            "seen": false,
+            "emailed": { "$ne": true },
+          },
+          None,
+        )
+        .await?
+        .try_collect::<Vec<Notification>>()
+        .await?,
+    )
+  }
+
+  pub async fn mark_notifications_emailed(
+    &self,
+    user_id: &str,
+    notifications: &[Notification],
+  ) -> Result<UpdateResult> {
+    let ids = notifications
+      .iter()
+      .map(|notification| Notification::bson_id(&notification.id))
+      .collect::<Vec<Bson>>();
+
+    Ok(
+      self
+        .database
+        .collection::<Notification>(Self::NOTIFICATION_COLLECTION)
+        .update_many(
+          doc! { "userId": user_id, "_id": { "$in": ids } },
+          UpdateModifications::Document(doc! {
+            "$set": { "emailed": true }
+          }),
+          None,
+        )
+        .await?,
+    )
+  }
+
+  pub async fn notification_preferences(
+    &self,
+    user_id: &str,
+  ) -> Result<Option<NotificationPreferences>> {
+    Ok(
+      self
+        .database
+        .collection::<NotificationPreferences>(
+          Self::NOTIFICATION_PREFERENCES_COLLECTION,
+        )
+        .find_one(doc! { "userId": user_id }, None)
+        .await?,
+    )
+  }
+
+  /// Saves a user's email address and frequency, keeping their digest
+  /// history.
+  pub async fn set_notification_preferences(
+    &self,
+    preferences: NotificationPreferences,
+  ) -> Result<UpdateResult> {
+    Ok(
+      self
+        .database
+        .collection::<NotificationPreferences>(
+          Self::NOTIFICATION_PREFERENCES_COLLECTION,
+        )
+        .update_one(
+          doc! { "userId": &preferences.user_id },
+          UpdateModifications::Document(doc! {
+            "$set": {
+              "email": preferences.email,
+              "emailFrequency": preferences.email_frequency,
+            }
+          }),
+          UpdateOptions::builder().upsert(true).build(),
+        )
+        .await?,
+    )
+  }
+
+  /// Preferences of every user who wants notifications emailed as soon as
+  /// they're created.
+  pub async fn immediate_subscribers(
+    &self,
+  ) -> Result<Vec<NotificationPreferences>> {
+    Ok(
+      self
+        .database
+        .collection::<NotificationPreferences>(
+          Self::NOTIFICATION_PREFERENCES_COLLECTION,
+        )
+        .find(
+          doc! { "emailFrequency": EmailFrequency::Immediate },
+          FindOptions::builder().sort(doc! { "userId": 1 }).build(),
+        )
+        .await?
+        .try_collect::<Vec<NotificationPreferences>>()
+        .await?,
+    )
+  }
+
+  /// Preferences of every user who wants notifications emailed in digests.
+  pub async fn digest_subscribers(
+    &self,
+  ) -> Result<Vec<NotificationPreferences>> {
+    Ok(
+      self
+        .database
+        .collection::<NotificationPreferences>(
+          Self::NOTIFICATION_PREFERENCES_COLLECTION,
+        )
+        .find(
+          doc! {
+            "emailFrequency": {
+              "$in": [EmailFrequency::Daily, EmailFrequency::Weekly]
+            }
+          },
+          FindOptions::builder().sort(doc! { "userId": 1 }).build(),
+        )
+        .await?
+        .try_collect::<Vec<NotificationPreferences>>()
+        .await?,
+    )
+  }
+
+  pub async fn record_digest(
+    &self,
+    user_id: &str,
+    sent_at: DateTime,
+  ) -> Result<UpdateResult> {
+    Ok(
+      self
+        .database
+        .collection::<NotificationPreferences>(
+          Self::NOTIFICATION_PREFERENCES_COLLECTION,
+        )
+        .update_one(
+          doc! { "userId": user_id },
+          UpdateModifications::Document(doc! {
+            "$set": {
+              "lastDigestAt": sent_at.timestamp_millis().to_string()
+            }
+          }),
+          None,
+        )
+        .await?,
+    )
+  }
+
+  pub async fn delete_notification(
+    &self,
+    user_id: &str,
+    course_id: &str,
+  ) -> Result<DeleteResult> {
+    Ok(
+      self
+        .database
+        .collection::<Notification>(Self::NOTIFICATION_COLLECTION)
+        .delete_one(
+          doc! {
+            "userId": user_id,
+            "review.courseId": course_id,
+          },
+          None,
+        )
+        .await?,
+    )
+  }
+
+  pub async fn delete_notifications(
+    &self,
+    creator_id: &str,
+    course_id: &str,
+  ) -> Result<DeleteResult> {
+    Ok(
+      self
         .database
         .collection::<Notification>(Self::NOTIFICATION_COLLECTION)
         .delete_many(
@@ -887,925 +1593,2539 @@ impl Db {
     )
   }
 
-  async fn find_reviews(&self, query: Document) -> Result<Vec<Review>> {
-    Ok(
-      self
-        .database
-        .collection::<Review>(Self::REVIEW_COLLECTION)
-        .find(query, None)
-        .await?
-        .try_collect::<Vec<Review>>()
-        .await?,
-    )
-  }
-
-  async fn find_course(&self, query: Document) -> Result<Option<Course>> {
-    Ok(
-      self
-        .database
-        .collection::<Course>(Self::COURSE_COLLECTION)
-        .find_one(query, None)
-        .await?,
-    )
-  }
+  /// Fills in fields notifications stored before they existed: the creation
+  /// time embedded in their object id, and their review's course.
+  pub(crate) async fn backfill_notifications(&self) -> Result {
+    let collection = self
+      .database
+      .collection::<Notification>(Self::NOTIFICATION_COLLECTION);
 
-  pub(crate) async fn add_course(&self, course: Course) -> Result {
-    match self.find_course(doc! { "_id": &course.id }).await? {
-      Some(found) => {
-        self
-          .update_course(
-            doc! { "_id": &course.id },
-            doc! {
-              "$set": {
-                "code": course.code,
-                "corequisites": course.corequisites,
-                "corequisitesText": course.corequisites_text,
-                "credits": course.credits,
-                "department": course.department,
-                "description": course.description,
-                "faculty": course.faculty,
-                "instructors": course.instructors.combine(found.instructors),
-                "leadingTo": course.leading_to,
-                "logicalCorequisites": course.logical_corequisites,
-                "logicalPrerequisites": course.logical_prerequisites,
-                "prerequisites": course.prerequisites,
-                "prerequisitesText": course.prerequisites_text,
-                "restrictions": course.restrictions,
-                "schedule": course.schedule,
-                "subject": course.subject,
-                "terms": course.terms.combine(found.terms),
-                "title": course.title.clone(),
-                "titleNgrams": course.title.filter_stopwords().ngrams(),
-                "url": course.url,
-              }
-            },
-          )
-          .await?;
-      }
-      None => {
-        self
-          .database
-          .collection::<Course>(Self::COURSE_COLLECTION)
-          .insert_one(
-            Course {
-              id_ngrams: Some(course.id.ngrams()),
-              title_ngrams: Some(course.title.filter_stopwords().ngrams()),
-              ..course
-            },
-            None,
-          )
-          .await?;
-      }
+    for (field, value) in [
+      ("createdAt", doc! { "$toDate": "$_id" }),
+      ("courseId", doc! { "$ifNull": ["$review.courseId", ""] }),
+    ] {
+      collection
+        .update_many(
+          doc! { field: { "$exists": false } },
+          UpdateModifications::Pipeline(vec![doc! {
+            "$set": { field: value }
+          }]),
+          None,
+        )
+        .await?;
     }
 
     Ok(())
   }
 
-  async fn update_course(
+  pub async fn update_notification_by_id(
     &self,
-    query: Document,
-    update: Document,
+    user_id: &str,
+    id: &str,
+    seen: bool,
   ) -> Result<UpdateResult> {
     Ok(
       self
         .database
-        .collection::<Course>(Self::COURSE_COLLECTION)
-        .update_one(query, UpdateModifications::Document(update), None)
+        .collection::<Notification>(Self::NOTIFICATION_COLLECTION)
+        .update_one(
+          doc! { "_id": Notification::bson_id(id), "userId": user_id },
+          UpdateModifications::Document(doc! {
+            "$set": { "seen": seen }
+          }),
+          None,
+        )
         .await?,
     )
   }
 
-  async fn text_search<T>(
+  pub async fn delete_notification_by_id(
     &self,
-    collection: &str,
-    query: &str,
-    limit: i64,
-  ) -> Result<Cursor<T>>
-  where
-    T: Serialize + DeserializeOwned,
-  {
+    user_id: &str,
+    id: &str,
+  ) -> Result<DeleteResult> {
     Ok(
       self
         .database
-        .collection::<T>(collection)
-        .find(
-          doc! {
-            "$text": {
-              "$search": query
-            }
-          },
-          FindOptions::builder()
-            .sort(doc! { "score": { "$meta" : "textScore" }})
-            .limit(limit)
-            .build(),
+        .collection::<Notification>(Self::NOTIFICATION_COLLECTION)
+        .delete_one(
+          doc! { "_id": Notification::bson_id(id), "userId": user_id },
+          None,
         )
         .await?,
     )
   }
 
-  pub(crate) async fn create_index<T>(
+  pub async fn mark_all_notifications_seen(
     &self,
-    collection: &str,
-    keys: Document,
-    weights: Document,
-  ) -> Result<CreateIndexResult>
-  where
-    T: Serialize + DeserializeOwned,
-  {
+    user_id: &str,
+  ) -> Result<UpdateResult> {
     Ok(
       self
         .database
-        .collection::<T>(collection)
-        .create_index(
-          IndexModel::builder()
-            .keys(keys)
-            .options(IndexOptions::builder().weights(weights).build())
-            .build(),
+        .collection::<Notification>(Self::NOTIFICATION_COLLECTION)
+        .update_many(
+          doc! { "userId": user_id, "seen": false },
+          UpdateModifications::Document(doc! {
+            "$set": { "seen": true }
+          }),
           None,
         )
         .await?,
     )
   }
 
-  pub(crate) async fn add_instructor(&self, instructor: Instructor) -> Result {
-    if self
-      .find_instructor_by_name(&instructor.name)
-      .await?
-      .is_none()
-    {
+  pub async fn clear_notifications(
+    &self,
+    user_id: &str,
+  ) -> Result<DeleteResult> {
+    Ok(
       self
         .database
-        .collection::<Instructor>(Self::INSTRUCTOR_COLLECTION)
-        .insert_one(
-          Instructor {
-            name_ngrams: Some(instructor.name.ngrams()),
-            ..instructor
-          },
-          None,
-        )
-        .await?;
-    };
+        .collection::<Notification>(Self::NOTIFICATION_COLLECTION)
+        .delete_many(doc! { "userId": user_id }, None)
+        .await?,
+    )
+  }
+
+  pub async fn add_webhook(&self, webhook: Webhook) -> Result {
+    self
+      .database
+      .collection::<Bson>(Self::WEBHOOK_COLLECTION)
+      .insert_one(Into::<Bson>::into(webhook), None)
+      .await?;
 
     Ok(())
   }
 
-  async fn find_instructor(
-    &self,
-    query: Document,
-  ) -> Result<Option<Instructor>> {
+  /// Every webhook, oldest first.
+  pub async fn webhooks(&self) -> Result<Vec<Webhook>> {
     Ok(
       self
         .database
-        .collection::<Instructor>(Self::INSTRUCTOR_COLLECTION)
-        .find_one(query, None)
+        .collection::<Webhook>(Self::WEBHOOK_COLLECTION)
+        .find(
+          None,
+          FindOptions::builder()
+            .sort(doc! { "createdAt": 1, "_id": 1 })
+            .build(),
+        )
+        .await?
+        .try_collect::<Vec<Webhook>>()
         .await?,
     )
   }
 
-  pub async fn find_instructor_by_name(
-    &self,
-    name: &str,
-  ) -> Result<Option<Instructor>> {
-    self.find_instructor(doc! { "name": name }).await
+  pub async fn webhook(&self, id: &str) -> Result<Option<Webhook>> {
+    Ok(
+      self
+        .database
+        .collection::<Webhook>(Self::WEBHOOK_COLLECTION)
+        .find_one(doc! { "_id": id }, None)
+        .await?,
+    )
   }
 
-  pub async fn reviews(
-    &self,
-    limit: Option<i64>,
-    offset: Option<u64>,
-    filter: Option<ReviewFilter>,
-  ) -> Result<Vec<Review>> {
-    let (mut document, mut sort_document) = (Document::new(), Document::new());
-
-    if let Some(filter) = filter {
-      let ReviewFilter {
-        course_id,
-        instructor_name,
-        sorted,
-        user_id,
-      } = filter;
+  /// Deletes a webhook along with its delivery log.
+  pub async fn delete_webhook(&self, id: &str) -> Result<DeleteResult> {
+    let result = self
+      .database
+      .collection::<Webhook>(Self::WEBHOOK_COLLECTION)
+      .delete_one(doc! { "_id": id }, None)
+      .await?;
 
-      if let Some(course_id) = course_id {
-        document.insert("courseId", course_id);
-      }
+    self
+      .database
+      .collection::<WebhookDelivery>(Self::WEBHOOK_DELIVERY_COLLECTION)
+      .delete_many(doc! { "webhookId": id }, None)
+      .await?;
 
-      if let Some(user_id) = user_id {
-        document.insert("userId", user_id);
-      }
+    Ok(result)
+  }
 
-      if let Some(instructor_name) = instructor_name {
-        document.insert(
-          "instructors",
-          doc! { "instructors": { "$in": vec![instructor_name] } },
-        );
-      }
+  /// Queues a delivery of `event` to every webhook that wants it, as part of
+  /// whatever transaction `session` is in, so that the event is queued if
+  /// and only if the change it's about is made.
+  async fn enqueue_webhook_event(
+    session: &mut ClientSession,
+    database: &Database,
+    event: WebhookEvent,
+  ) -> mongodb::error::Result<()> {
+    let deliveries = database
+      .collection::<Webhook>(Self::WEBHOOK_COLLECTION)
+      .find_with_session(None, None, session)
+      .await?
+      .stream(session)
+      .try_collect::<Vec<_>>()
+      .await?
+      .iter()
+      .filter(|webhook| webhook.wants(event.kind))
+      .map(|webhook| WebhookDelivery::new(webhook, &event))
+      .collect::<std::result::Result<Vec<_>, _>>()
+      .map_err(mongodb::error::Error::custom)?;
 
-      if sorted.unwrap_or(false) {
-        sort_document.insert("timestamp", -1);
-      }
+    if deliveries.is_empty() {
+      return Ok(());
     }
 
+    database
+      .collection::<Bson>(Self::WEBHOOK_DELIVERY_COLLECTION)
+      .insert_many_with_session(
+        deliveries.into_iter().map(Into::<Bson>::into),
+        None,
+        session,
+      )
+      .await?;
+
+    Ok(())
+  }
+
+  /// Pending deliveries whose next attempt is due at `now`, most overdue
+  /// first.
+  pub async fn due_webhook_deliveries(
+    &self,
+    now: DateTime,
+  ) -> Result<Vec<WebhookDelivery>> {
     Ok(
       self
         .database
-        .collection::<Review>(Self::REVIEW_COLLECTION)
+        .collection::<WebhookDelivery>(Self::WEBHOOK_DELIVERY_COLLECTION)
         .find(
-          (!document.is_empty()).then_some(document),
+          doc! {
+            "status": WebhookDeliveryStatus::Pending,
+            "nextAttemptAt": { "$lte": Bson::from(now) },
+          },
           FindOptions::builder()
-            .sort((!sort_document.is_empty()).then_some(sort_document))
-            .skip(offset)
-            .limit(limit)
+            .sort(doc! { "nextAttemptAt": 1, "_id": 1 })
             .build(),
         )
         .await?
-        .try_collect::<Vec<Review>>()
+        .try_collect::<Vec<WebhookDelivery>>()
         .await?,
     )
   }
 
-  pub async fn unique_user_count(&self) -> Result<u64> {
-    // Timestamp of https://github.com/terror/mcgill.courses/pull/500
-    let rmp_scrape_epoch: chrono::DateTime<Utc> =
-      Utc.timestamp_opt(1713472800, 0).unwrap();
-
-    let reviews = self
+  /// Records how an attempt at `delivery` went.
+  pub async fn update_webhook_delivery(
+    &self,
+    delivery: WebhookDelivery,
+  ) -> Result {
+    self
       .database
-      .collection::<Review>(Self::REVIEW_COLLECTION)
-      .distinct(
-        "userId",
-        doc! {
-          "timestamp": { "$gte": rmp_scrape_epoch }
-        },
+      .collection::<Bson>(Self::WEBHOOK_DELIVERY_COLLECTION)
+      .replace_one(
+        doc! { "_id": delivery.id.clone() },
+        Into::<Bson>::into(delivery),
         None,
       )
       .await?;
 
-    Ok(reviews.len().try_into()?)
+    Ok(())
   }
 
-  #[cfg(test)]
-  async fn instructors(&self) -> Result<Vec<Instructor>> {
+  /// A webhook's delivery log, newest first.
+  pub async fn webhook_deliveries(
+    &self,
+    webhook_id: &str,
+    limit: Option<i64>,
+  ) -> Result<Vec<WebhookDelivery>> {
     Ok(
       self
         .database
-        .collection::<Instructor>(Self::INSTRUCTOR_COLLECTION)
-        .find(None, None)
+        .collection::<WebhookDelivery>(Self::WEBHOOK_DELIVERY_COLLECTION)
+        .find(
+          doc! { "webhookId": webhook_id },
+          FindOptions::builder()
+            .sort(doc! { "createdAt": -1, "_id": -1 })
+            .limit(limit)
+            .build(),
+        )
         .await?
-        .try_collect::<Vec<Instructor>>()
+        .try_collect::<Vec<WebhookDelivery>>()
         .await?,
     )
   }
 
-  #[cfg(test)]
-  async fn subscriptions(&self) -> Result<Vec<Subscription>> {
+  pub async fn exams(
+    &self,
+    filter: Option<ExamFilter>,
+  ) -> Result<Vec<FinalExam>> {
+    let mut document = Document::new();
+
+    if let Some(filter) = filter {
+      let ExamFilter {
+        course_id,
+        subject,
+        term,
+      } = filter;
+
+      let subject = subject.map(|subject| {
+        doc! { "$regex": format!("^{}[0-9]", subject), "$options": "i" }
+      });
+
+      match (course_id, subject) {
+        (Some(course_id), Some(subject)) => {
+          document.insert(
+            "$and",
+            vec![doc! { "courseId": course_id }, doc! { "courseId": subject }],
+          );
+        }
+        (Some(course_id), None) => {
+          document.insert("courseId", course_id);
+        }
+        (None, Some(subject)) => {
+          document.insert("courseId", subject);
+        }
+        (None, None) => {}
+      }
+
+      if let Some(term) = term {
+        document.insert("term", term);
+      }
+    }
+
     Ok(
       self
         .database
-        .collection::<Subscription>(Self::SUBSCRIPTION_COLLECTION)
-        .find(None, None)
-        .await?
-        .try_collect::<Vec<Subscription>>()
-        .await?,
+        .collection::<FinalExam>(Self::EXAM_COLLECTION)
+        .find(
+          (!document.is_empty()).then_some(document),
+          FindOptions::builder()
+            .sort(doc! { "startTime": 1, "courseId": 1, "section": 1 })
+            .build(),
+        )
+        .await?
+        .try_collect::<Vec<FinalExam>>()
+        .await?,
     )
   }
 
-  #[cfg(test)]
-  async fn notifications(&self) -> Result<Vec<Notification>> {
+  pub async fn find_exams_by_course_id(
+    &self,
+    course_id: &str,
+  ) -> Result<Vec<FinalExam>> {
+    self
+      .exams(Some(ExamFilter {
+        course_id: Some(course_id.into()),
+        ..Default::default()
+      }))
+      .await
+  }
+
+  pub async fn next_exam(&self, course_id: &str) -> Result<Option<FinalExam>> {
+    let now = campus_time(Utc::now())
+      .format("%Y-%m-%dT%H:%M:%S")
+      .to_string();
+
     Ok(
       self
         .database
-        .collection::<Notification>(Self::NOTIFICATION_COLLECTION)
-        .find(None, None)
+        .collection::<FinalExam>(Self::EXAM_COLLECTION)
+        .find_one(
+          doc! { "courseId": course_id, "startTime": { "$gte": now } },
+          FindOneOptions::builder()
+            .sort(doc! { "startTime": 1 })
+            .build(),
+        )
+        .await?,
+    )
+  }
+
+  pub(crate) async fn set_exams(
+    &self,
+    term: &str,
+    exams: Vec<FinalExam>,
+  ) -> Result {
+    let mut session = self.client.start_session(None).await?;
+
+    let collection =
+      self.database.collection::<FinalExam>(Self::EXAM_COLLECTION);
+
+    async fn transaction(
+      session: &mut ClientSession,
+      collection: Collection<FinalExam>,
+      term: String,
+      exams: Vec<FinalExam>,
+    ) -> mongodb::error::Result<()> {
+      collection
+        .delete_many_with_session(doc! { "term": term }, None, session)
+        .await?;
+
+      if !exams.is_empty() {
+        collection
+          .insert_many_with_session(exams, None, session)
+          .await?;
+      }
+
+      Ok(())
+    }
+
+    session
+      .with_transaction(
+        (),
+        |session, _| {
+          transaction(
+            session,
+            collection.clone(),
+            term.to_string(),
+            exams.clone(),
+          )
+          .boxed()
+        },
+        None,
+      )
+      .await?;
+
+    Ok(())
+  }
+
+  async fn find_reviews(&self, query: Document) -> Result<Vec<Review>> {
+    Ok(
+      self
+        .database
+        .collection::<Review>(Self::REVIEW_COLLECTION)
+        .find(query, None)
         .await?
-        .try_collect::<Vec<Notification>>()
+        .try_collect::<Vec<Review>>()
         .await?,
     )
   }
-}
 
-#[cfg(test)]
-mod tests {
-  use {super::*, pretty_assertions::assert_eq, regex::Regex};
+  async fn find_course(&self, query: Document) -> Result<Option<Course>> {
+    Ok(
+      self
+        .database
+        .collection::<Course>(Self::COURSE_COLLECTION)
+        .find_one(query, None)
+        .await?,
+    )
+  }
 
-  static SEED_DIR: Dir<'_> = include_dir!("crates/db/test-seeds");
+  pub(crate) async fn add_course(&self, course: Course) -> Result {
+    match self.find_course(doc! { "_id": &course.id }).await? {
+      Some(found) => {
+        let new_terms = course
+          .terms
+          .iter()
+          .filter(|term| !found.terms.contains(term))
+          .cloned()
+          .collect::<Vec<String>>();
+
+        let schedule_change = if course.schedule != found.schedule {
+          Some(FieldChange {
+            field: "schedule".into(),
+            before: serde_json::to_value(&found.schedule)?,
+            after: serde_json::to_value(&course.schedule)?,
+          })
+        } else {
+          None
+        };
 
-  fn get_content(name: &str) -> String {
-    SEED_DIR
-      .get_file(name)
-      .unwrap()
-      .contents_utf8()
-      .unwrap()
-      .to_string()
+        let course_id = course.id.clone();
+
+        self
+          .update_course(
+            doc! { "_id": &course.id },
+            doc! {
+              "$set": {
+                "code": course.code,
+                "corequisites": course.corequisites,
+                "corequisitesText": course.corequisites_text,
+                "credits": course.credits,
+                "department": course.department,
+                "description": course.description,
+                "faculty": course.faculty,
+                "instructors": course.instructors.combine(found.instructors.clone()),
+                "leadingTo": course.leading_to,
+                "logicalCorequisites": course.logical_corequisites,
+                "logicalPrerequisites": course.logical_prerequisites,
+                "prerequisitesConfidence": course.prerequisites_confidence,
+                "corequisitesConfidence": course.corequisites_confidence,
+                "prerequisites": course.prerequisites,
+                "prerequisitesText": course.prerequisites_text,
+                "restrictions": course.restrictions,
+                "excludedCourses": course.excluded_courses,
+                "crossListings": course.cross_listings,
+                "restrictedTo": course.restricted_to,
+                "schedule": course.schedule,
+                "subject": course.subject,
+                "terms": course.terms.combine(found.terms.clone()),
+                "title": course.title.clone(),
+                "titleNgrams": course.title.filter_stopwords().ngrams(),
+                "url": course.url,
+              }
+            },
+          )
+          .await?;
+
+        self
+          .notify_course_changes(&course_id, &new_terms, schedule_change)
+          .await?;
+
+        if let Some(updated) =
+          self.find_course(doc! { "_id": &course_id }).await?
+          && updated != found
+        {
+          // Seeding isn't transactional, and the course is already updated,
+          // so a lost event shouldn't stop it.
+          let mut session = self.client.start_session(None).await?;
+
+          if let Err(error) = Self::enqueue_webhook_event(
+            &mut session,
+            &self.database,
+            WebhookEvent::about_course(
+              WebhookEventKind::CourseUpdated,
+              updated,
+            ),
+          )
+          .await
+          {
+            warn!("Failed to queue webhook event for {course_id}: {error}");
+          }
+        }
+      }
+      None => {
+        self
+          .database
+          .collection::<Course>(Self::COURSE_COLLECTION)
+          .insert_one(
+            Course {
+              id_ngrams: Some(course.id.ngrams()),
+              title_ngrams: Some(course.title.filter_stopwords().ngrams()),
+              ..course
+            },
+            None,
+          )
+          .await?;
+      }
+    }
+
+    Ok(())
   }
 
-  struct TestContext {
-    db: Db,
-    db_name: String,
+  /// Records a snapshot of `course` for `catalogue_year` if it differs from
+  /// the snapshot already stored for that year, or from the latest earlier
+  /// one.
+  pub(crate) async fn add_course_version(
+    &self,
+    catalogue_year: &str,
+    course: &Course,
+  ) -> Result {
+    let collection = self
+      .database
+      .collection::<CourseVersion>(Self::COURSE_VERSION_COLLECTION);
+
+    let previous = collection
+      .find_one(
+        doc! {
+          "courseId": &course.id,
+          "catalogueYear": { "$lte": catalogue_year },
+        },
+        FindOneOptions::builder()
+          .sort(doc! { "catalogueYear": -1 })
+          .build(),
+      )
+      .await?;
+
+    let version = CourseVersion::new(catalogue_year, course.clone());
+
+    match previous {
+      Some(previous) if version.changes_from(&previous.course).is_empty() => {
+        Ok(())
+      }
+      _ => {
+        collection
+          .update_one(
+            doc! { "courseId": &course.id, "catalogueYear": catalogue_year },
+            doc! { "$set": { "course": bson::to_bson(&version.course)? } },
+            UpdateOptions::builder().upsert(true).build(),
+          )
+          .await?;
+
+        Ok(())
+      }
+    }
   }
 
-  impl TestContext {
-    async fn new() -> Self {
-      static TEST_DATABASE_NUMBER: AtomicUsize = AtomicUsize::new(0);
+  /// Snapshots of a course, oldest catalogue year first.
+  pub async fn course_versions(
+    &self,
+    course_id: &str,
+  ) -> Result<Vec<CourseVersion>> {
+    Ok(
+      self
+        .database
+        .collection::<CourseVersion>(Self::COURSE_VERSION_COLLECTION)
+        .find(
+          doc! { "courseId": course_id },
+          FindOptions::builder()
+            .sort(doc! { "catalogueYear": 1 })
+            .build(),
+        )
+        .await?
+        .try_collect()
+        .await?,
+    )
+  }
 
-      let test_database_number =
-        TEST_DATABASE_NUMBER.fetch_add(1, Ordering::Relaxed);
+  pub(crate) async fn add_building(
+    &self,
+    building: Building,
+  ) -> Result<UpdateResult> {
+    Ok(
+      self
+        .database
+        .collection::<Building>(Self::BUILDING_COLLECTION)
+        .replace_one(
+          doc! { "_id": &building.code },
+          building,
+          ReplaceOptions::builder().upsert(true).build(),
+        )
+        .await?,
+    )
+  }
 
-      let db_name = format!(
-        "mcgill-courses-test-{}-{}",
-        std::time::SystemTime::now()
-          .duration_since(std::time::SystemTime::UNIX_EPOCH)
-          .unwrap()
-          .as_millis(),
-        test_database_number,
-      );
+  /// Every known building, by code.
+  pub async fn buildings(&self) -> Result<Vec<Building>> {
+    Ok(
+      self
+        .database
+        .collection::<Building>(Self::BUILDING_COLLECTION)
+        .find(None, FindOptions::builder().sort(doc! { "_id": 1 }).build())
+        .await?
+        .try_collect()
+        .await?,
+    )
+  }
+
+  pub async fn find_building(&self, code: &str) -> Result<Option<Building>> {
+    Ok(
+      self
+        .database
+        .collection::<Building>(Self::BUILDING_COLLECTION)
+        .find_one(doc! { "_id": code }, None)
+        .await?,
+    )
+  }
 
-      let db = Db::connect(&db_name).await.unwrap();
+  /// Courses with a block meeting in the building with `code`, optionally
+  /// only in `term`.
+  pub async fn find_courses_by_building(
+    &self,
+    code: &str,
+    term: Option<&str>,
+  ) -> Result<Vec<Course>> {
+    let mut schedule = doc! { "blocks.rooms.building": code };
 
-      TestContext { db, db_name }
+    if let Some(term) = term {
+      schedule.insert("term", term);
     }
+
+    Ok(
+      self
+        .database
+        .collection::<Course>(Self::COURSE_COLLECTION)
+        .find(
+          doc! { "schedule": { "$elemMatch": schedule } },
+          FindOptions::builder().sort(doc! { "_id": 1 }).build(),
+        )
+        .await?
+        .try_collect()
+        .await?,
+    )
   }
 
-  #[tokio::test(flavor = "multi_thread")]
-  async fn on_disk_database_is_persistent() {
-    let TestContext { db, db_name } = TestContext::new().await;
+  pub(crate) async fn add_course_average(
+    &self,
+    average: CourseAverage,
+  ) -> Result<UpdateResult> {
+    Ok(
+      self
+        .database
+        .collection::<CourseAverage>(Self::AVERAGE_COLLECTION)
+        .update_one(
+          doc! { "courseId": &average.course_id, "term": &average.term },
+          doc! { "$set": { "average": &average.average } },
+          UpdateOptions::builder().upsert(true).build(),
+        )
+        .await?,
+    )
+  }
 
-    assert_eq!(db.courses(None, None, None).await.unwrap().len(), 0);
+  /// Stores each course's latest and mean class average in grade points, so
+  /// courses can be filtered and sorted by them.
+  pub(crate) async fn refresh_course_averages(&self) -> Result {
+    let averages = self
+      .database
+      .collection::<CourseAverage>(Self::AVERAGE_COLLECTION)
+      .find(None, None)
+      .await?
+      .try_collect::<Vec<_>>()
+      .await?
+      .into_iter()
+      .into_group_map_by(|average| average.course_id.clone());
 
-    db.add_course(Course::default()).await.unwrap();
+    for (course_id, averages) in averages {
+      let trend = GradeTrend::new(&averages);
 
-    assert_eq!(db.courses(None, None, None).await.unwrap().len(), 1);
+      self
+        .update_course(
+          doc! { "_id": course_id },
+          doc! {
+            "$set": {
+              "latestAverage": trend.as_ref().map(|trend| trend.latest),
+              "meanAverage": trend.map(|trend| trend.mean),
+            }
+          },
+        )
+        .await?;
+    }
+
+    Ok(())
+  }
+
+  /// Recorded class averages for a course, oldest term first.
+  pub async fn course_averages(
+    &self,
+    course_id: &str,
+  ) -> Result<Vec<CourseAverage>> {
+    let mut averages = self
+      .database
+      .collection::<CourseAverage>(Self::AVERAGE_COLLECTION)
+      .find(doc! { "courseId": course_id }, None)
+      .await?
+      .try_collect::<Vec<_>>()
+      .await?;
+
+    CourseAverage::sort_chronologically(&mut averages);
+
+    Ok(averages)
+  }
+
+  async fn update_course(
+    &self,
+    query: Document,
+    update: Document,
+  ) -> Result<UpdateResult> {
+    Ok(
+      self
+        .database
+        .collection::<Course>(Self::COURSE_COLLECTION)
+        .update_one(query, UpdateModifications::Document(update), None)
+        .await?,
+    )
+  }
+
+  async fn text_search<T>(
+    &self,
+    collection: &str,
+    query: &str,
+    limit: i64,
+  ) -> Result<Cursor<T>>
+  where
+    T: Serialize + DeserializeOwned,
+  {
+    Ok(
+      self
+        .database
+        .collection::<T>(collection)
+        .find(
+          doc! {
+            "$text": {
+              "$search": query
+            }
+          },
+          FindOptions::builder()
+            .sort(doc! { "score": { "$meta" : "textScore" }})
+            .limit(limit)
+            .build(),
+        )
+        .await?,
+    )
+  }
+
+  pub(crate) async fn create_index<T>(
+    &self,
+    collection: &str,
+    keys: Document,
+    weights: Document,
+  ) -> Result<CreateIndexResult>
+  where
+    T: Serialize + DeserializeOwned,
+  {
+    self
+      .create_index_with_options::<T>(
+        collection,
+        keys,
+        IndexOptions::builder().weights(weights).build(),
+      )
+      .await
+  }
+
+  pub(crate) async fn create_index_with_options<T>(
+    &self,
+    collection: &str,
+    keys: Document,
+    options: IndexOptions,
+  ) -> Result<CreateIndexResult>
+  where
+    T: Serialize + DeserializeOwned,
+  {
+    Ok(
+      self
+        .database
+        .collection::<T>(collection)
+        .create_index(
+          IndexModel::builder().keys(keys).options(options).build(),
+          None,
+        )
+        .await?,
+    )
+  }
+
+  /// Records that `instructor` taught `course_id`, merging every spelling of
+  /// their name into one profile.
+  pub(crate) async fn add_instructor(
+    &self,
+    course_id: &str,
+    instructor: Instructor,
+  ) -> Result {
+    let name = InstructorProfile::normalize_name(&instructor.name);
+
+    let aliases = [name.clone(), instructor.name.trim().to_string()]
+      .into_iter()
+      .unique()
+      .collect::<Vec<_>>();
+
+    self
+      .database
+      .collection::<InstructorProfile>(Self::INSTRUCTOR_COLLECTION)
+      .update_one(
+        doc! { "_id": InstructorProfile::id_for(&name) },
+        doc! {
+          "$setOnInsert": {
+            "name": &name,
+            "nameNgrams": name.as_str().ngrams(),
+          },
+          "$addToSet": {
+            "aliases": { "$each": aliases },
+            "assignments": TeachingAssignment {
+              course_id: course_id.to_string(),
+              term: instructor.term,
+            },
+          },
+        },
+        UpdateOptions::builder().upsert(true).build(),
+      )
+      .await?;
+
+    Ok(())
+  }
+
+  /// Removes instructors stored before profiles tracked teaching
+  /// assignments, so reseeding can rebuild them.
+  pub(crate) async fn purge_legacy_instructors(&self) -> Result<DeleteResult> {
+    Ok(
+      self
+        .database
+        .collection::<Document>(Self::INSTRUCTOR_COLLECTION)
+        .delete_many(doc! { "assignments": { "$exists": false } }, None)
+        .await?,
+    )
+  }
+
+  async fn find_instructor(
+    &self,
+    query: Document,
+  ) -> Result<Option<InstructorProfile>> {
+    Ok(
+      self
+        .database
+        .collection::<InstructorProfile>(Self::INSTRUCTOR_COLLECTION)
+        .find_one(query, None)
+        .await?,
+    )
+  }
+
+  /// Everyone known to have taught `course_id`, in name order.
+  pub async fn find_instructors_by_course(
+    &self,
+    course_id: &str,
+  ) -> Result<Vec<InstructorProfile>> {
+    Ok(
+      self
+        .database
+        .collection::<InstructorProfile>(Self::INSTRUCTOR_COLLECTION)
+        .find(
+          doc! { "assignments.courseId": course_id },
+          FindOptions::builder().sort(doc! { "name": 1 }).build(),
+        )
+        .await?
+        .try_collect()
+        .await?,
+    )
+  }
+
+  pub async fn find_instructor_by_name(
+    &self,
+    name: &str,
+  ) -> Result<Option<InstructorProfile>> {
+    self
+      .find_instructor(doc! {
+        "$or": [
+          { "_id": InstructorProfile::id_for(name) },
+          { "aliases": name },
+        ]
+      })
+      .await
+  }
+
+  pub async fn reviews(
+    &self,
+    limit: Option<i64>,
+    offset: Option<u64>,
+    filter: Option<ReviewFilter>,
+  ) -> Result<Vec<Review>> {
+    let (mut document, mut sort_document) = (Document::new(), Document::new());
+
+    if let Some(filter) = filter {
+      let ReviewFilter {
+        course_id,
+        instructor_name,
+        sorted,
+        user_id,
+      } = filter;
+
+      if let Some(course_id) = course_id {
+        document.insert("courseId", course_id);
+      }
+
+      if let Some(user_id) = user_id {
+        document.insert("userId", user_id);
+      }
+
+      if let Some(instructor_name) = instructor_name {
+        document.insert(
+          "instructors",
+          doc! { "instructors": { "$in": vec![instructor_name] } },
+        );
+      }
+
+      if sorted.unwrap_or(false) {
+        sort_document.insert("timestamp", -1);
+      }
+    }
+
+    Ok(
+      self
+        .database
+        .collection::<Review>(Self::REVIEW_COLLECTION)
+        .find(
+          (!document.is_empty()).then_some(document),
+          FindOptions::builder()
+            .sort((!sort_document.is_empty()).then_some(sort_document))
+            .skip(offset)
+            .limit(limit)
+            .build(),
+        )
+        .await?
+        .try_collect::<Vec<Review>>()
+        .await?,
+    )
+  }
+
+  pub async fn unique_user_count(&self) -> Result<u64> {
+    // Timestamp of https://github.com/terror/mcgill.courses/pull/500
+    let rmp_scrape_epoch: chrono::DateTime<Utc> =
+      Utc.timestamp_opt(1713472800, 0).unwrap();
+
+    let reviews = self
+      .database
+      .collection::<Review>(Self::REVIEW_COLLECTION)
+      .distinct(
+        "userId",
+        doc! {
+          "timestamp": { "$gte": rmp_scrape_epoch }
+        },
+        None,
+      )
+      .await?;
+
+    Ok(reviews.len().try_into()?)
+  }
+
+  #[cfg(test)]
+  async fn instructors(&self) -> Result<Vec<InstructorProfile>> {
+    Ok(
+      self
+        .database
+        .collection::<InstructorProfile>(Self::INSTRUCTOR_COLLECTION)
+        .find(None, None)
+        .await?
+        .try_collect::<Vec<InstructorProfile>>()
+        .await?,
+    )
+  }
+
+  #[cfg(test)]
+  async fn subscriptions(&self) -> Result<Vec<Subscription>> {
+    Ok(
+      self
+        .database
+        .collection::<Subscription>(Self::SUBSCRIPTION_COLLECTION)
+        .find(None, None)
+        .await?
+        .try_collect::<Vec<Subscription>>()
+        .await?,
+    )
+  }
+
+  #[cfg(test)]
+  async fn notifications(&self) -> Result<Vec<Notification>> {
+    Ok(
+      self
+        .database
+        .collection::<Notification>(Self::NOTIFICATION_COLLECTION)
+        .find(None, None)
+        .await?
+        .try_collect::<Vec<Notification>>()
+        .await?,
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, pretty_assertions::assert_eq, regex::Regex};
+
+  static SEED_DIR: Dir<'_> = include_dir!("crates/db/test-seeds");
+
+  fn get_content(name: &str) -> String {
+    SEED_DIR
+      .get_file(name)
+      .unwrap()
+      .contents_utf8()
+      .unwrap()
+      .to_string()
+  }
+
+  const MONGODB_URL: &str =
+    "mongodb://localhost:27017/?directConnection=true&replicaSet=rs0";
+
+  struct TestContext {
+    db: Db,
+    db_name: String,
+  }
+
+  impl TestContext {
+    async fn new() -> Self {
+      static TEST_DATABASE_NUMBER: AtomicUsize = AtomicUsize::new(0);
+
+      let test_database_number =
+        TEST_DATABASE_NUMBER.fetch_add(1, Ordering::Relaxed);
+
+      let db_name = format!(
+        "mcgill-courses-test-{}-{}",
+        std::time::SystemTime::now()
+          .duration_since(std::time::SystemTime::UNIX_EPOCH)
+          .unwrap()
+          .as_millis(),
+        test_database_number,
+      );
+
+      let db = Db::connect(MONGODB_URL, &db_name).await.unwrap();
+
+      TestContext { db, db_name }
+    }
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn on_disk_database_is_persistent() {
+    let TestContext { db, db_name } = TestContext::new().await;
+
+    assert_eq!(db.courses(None, None, None).await.unwrap().len(), 0);
+
+    db.add_course(Course::default()).await.unwrap();
+
+    assert_eq!(db.courses(None, None, None).await.unwrap().len(), 1);
+
+    drop(db);
+
+    let db = Db::connect(MONGODB_URL, &db_name).await.unwrap();
+
+    assert_eq!(db.courses(None, None, None).await.unwrap().len(), 1);
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn course_seeding_is_accurate() {
+    let TestContext { db, db_name } = TestContext::new().await;
+
+    let tempdir = TempDir::new(&db_name).unwrap();
+
+    let source = tempdir.path().join("courses.json");
+
+    fs::write(&source, get_content("before_update.json")).unwrap();
+
+    db.initialize(InitializeOptions {
+      source,
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(db.courses(None, None, None).await.unwrap().len(), 2);
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn course_seeding_does_not_insert_duplicates() {
+    let TestContext { db, db_name } = TestContext::new().await;
+
+    let tempdir = TempDir::new(&db_name).unwrap();
+
+    let source = tempdir.path().join("courses.json");
+
+    fs::write(
+      &source,
+      serde_json::to_string(
+        &(0..10).map(|_| Course::default()).collect::<Vec<Course>>(),
+      )
+      .unwrap(),
+    )
+    .unwrap();
+
+    db.initialize(InitializeOptions {
+      source,
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(db.courses(None, None, None).await.unwrap().len(), 1);
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn courses_get_updated_when_seeding() {
+    let TestContext { db, db_name } = TestContext::new().await;
+
+    let tempdir = TempDir::new(&db_name).unwrap();
+
+    let source = tempdir.path().join("courses.json");
+
+    fs::write(&source, get_content("before_update.json")).unwrap();
+
+    db.initialize(InitializeOptions {
+      source: source.clone(),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(db.courses(None, None, None).await.unwrap().len(), 2);
+
+    fs::write(&source, get_content("update.json")).unwrap();
+
+    db.initialize(InitializeOptions {
+      source,
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    let courses = db.courses(None, None, None).await.unwrap();
+
+    assert_eq!(courses.len(), 3);
+
+    assert_eq!(
+      courses,
+      serde_json::from_str::<Vec<Course>>(&get_content("after_update.json"))
+        .unwrap()
+        .into_iter()
+        .map(|c| Course {
+          id_ngrams: Some(c.id.ngrams()),
+          title_ngrams: Some(c.title.filter_stopwords().ngrams()),
+          ..c
+        })
+        .collect::<Vec<Course>>()
+    );
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn course_versions_are_recorded_per_catalogue_year() {
+    let TestContext { db, db_name } = TestContext::new().await;
+
+    let tempdir = TempDir::new(&db_name).unwrap();
+
+    fs::write(
+      tempdir.path().join("courses-2022-2023.json"),
+      get_content("before_update.json"),
+    )
+    .unwrap();
+
+    fs::write(
+      tempdir.path().join("courses-2023-2024.json"),
+      get_content("update.json"),
+    )
+    .unwrap();
+
+    for _ in 0..2 {
+      db.initialize(InitializeOptions {
+        source: tempdir.path().into(),
+        ..Default::default()
+      })
+      .await
+      .unwrap();
+    }
+
+    let versions = db.course_versions("COMP251").await.unwrap();
+
+    assert_eq!(
+      versions
+        .iter()
+        .map(|version| version.catalogue_year.as_str())
+        .collect::<Vec<_>>(),
+      vec!["2022-2023", "2023-2024"]
+    );
+
+    assert!(
+      versions[1]
+        .changes_from(&versions[0].course)
+        .iter()
+        .any(|change| change.field == "prerequisites")
+    );
+
+    assert_eq!(db.course_versions("COMP252").await.unwrap().len(), 1);
+    assert!(db.course_versions("COMP999").await.unwrap().is_empty());
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn blocks_are_located_in_buildings() {
+    let TestContext { db, db_name } = TestContext::new().await;
+
+    let tempdir = TempDir::new(&db_name).unwrap();
+
+    let source = tempdir.path().join("courses.json");
+
+    fs::write(&source, get_content("mini.json")).unwrap();
+
+    db.initialize(InitializeOptions {
+      source,
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(
+      db.find_building("ENGTR")
+        .await
+        .unwrap()
+        .map(|building| building.name),
+      Some("Trottier Building".into())
+    );
+
+    let course = db.find_course_by_id("COMP202").await.unwrap().unwrap();
+
+    let block = course
+      .schedule
+      .unwrap()
+      .into_iter()
+      .flat_map(|schedule| schedule.blocks.unwrap_or_default())
+      .find(|block| !block.parse_location().is_empty())
+      .unwrap();
+
+    assert_eq!(
+      block.rooms,
+      vec![
+        Room {
+          building: "ENGTR".into(),
+          room: Some("0100".into()),
+        },
+        Room {
+          building: "STBIO".into(),
+          room: Some("S1/3".into()),
+        },
+      ]
+    );
+
+    assert_eq!(
+      db.find_courses_by_building("ENGTR", Some("Summer 2023"))
+        .await
+        .unwrap()
+        .len(),
+      1
+    );
+
+    assert!(
+      db.find_courses_by_building("ENGTR", Some("Fall 2022"))
+        .await
+        .unwrap()
+        .is_empty()
+    );
+
+    assert_eq!(
+      db.find_courses_by_building("STBIO", None)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|course| course.id)
+        .collect::<Vec<_>>(),
+      vec!["COMP202".to_string()]
+    );
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn course_averages_are_seeded() {
+    let TestContext { db, db_name } = TestContext::new().await;
+
+    let tempdir = TempDir::new(&db_name).unwrap();
+
+    fs::write(
+      tempdir.path().join("courses.json"),
+      get_content("mini.json"),
+    )
+    .unwrap();
+
+    fs::write(
+      tempdir.path().join("averages.json"),
+      get_content("averages.json"),
+    )
+    .unwrap();
+
+    for _ in 0..2 {
+      db.initialize(InitializeOptions {
+        source: tempdir.path().into(),
+        ..Default::default()
+      })
+      .await
+      .unwrap();
+    }
+
+    assert_eq!(
+      db.course_averages("COMP202")
+        .await
+        .unwrap()
+        .iter()
+        .map(|average| (average.term.as_str(), average.average.as_str()))
+        .collect::<Vec<_>>(),
+      vec![
+        ("Fall 2021", "B-"),
+        ("Winter 2022", "B"),
+        ("Fall 2022", "B+")
+      ]
+    );
+
+    assert_eq!(db.course_averages("MATH240").await.unwrap().len(), 1);
+    assert!(db.course_averages("COMP252").await.unwrap().is_empty());
+
+    let course = db.find_course_by_id("COMP202").await.unwrap().unwrap();
+
+    assert_eq!(course.latest_average, Some(3.3));
+    assert!((course.mean_average.unwrap() - 3.0).abs() < 0.001);
+
+    let courses = db
+      .courses(
+        None,
+        None,
+        Some(CourseFilter {
+          sort_by: Some(CourseSort {
+            sort_type: CourseSortType::Average,
+            reverse: true,
+          }),
+          ..Default::default()
+        }),
+      )
+      .await
+      .unwrap();
+
+    assert_eq!(
+      courses
+        .iter()
+        .map(|course| course.id.as_str())
+        .collect::<Vec<_>>(),
+      vec!["COMP202", "MATH240"]
+    );
+
+    let courses = db
+      .courses(
+        None,
+        None,
+        Some(CourseFilter {
+          min_average: Some(2.9),
+          ..Default::default()
+        }),
+      )
+      .await
+      .unwrap();
+
+    assert_eq!(
+      courses
+        .iter()
+        .map(|course| course.id.as_str())
+        .collect::<Vec<_>>(),
+      vec!["COMP202"]
+    );
+  }
+
+  #[test]
+  fn catalogue_year() {
+    assert_eq!(
+      utils::catalogue_year("seed/courses-2025-2026.json"),
+      Some("2025-2026".into())
+    );
+
+    assert_eq!(
+      utils::catalogue_year(
+        "https://www.mcgill.ca/study/2022-2023/courses/comp-251"
+      ),
+      Some("2022-2023".into())
+    );
+
+    assert_eq!(utils::catalogue_year("seed/2025-2027.json"), None);
+    assert_eq!(utils::catalogue_year("courses.json"), None);
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn search_is_accurate() {
+    let TestContext { db, db_name } = TestContext::new().await;
+
+    let tempdir = TempDir::new(&db_name).unwrap();
+
+    let source = tempdir.path().join("courses.json");
+
+    fs::write(&source, get_content("search.json")).unwrap();
+
+    db.initialize(InitializeOptions {
+      source,
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(db.courses(None, None, None).await.unwrap().len(), 123);
+
+    let results = db.search("COMP 202").await.unwrap();
+
+    assert_eq!(results.courses.len(), 4);
+
+    let first = results.courses.first().unwrap();
+
+    assert_eq!(first.subject, "COMP");
+    assert_eq!(first.code, "202");
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn get_course_by_id() {
+    let TestContext { db, db_name } = TestContext::new().await;
+
+    let tempdir = TempDir::new(&db_name).unwrap();
+
+    let source = tempdir.path().join("courses.json");
+
+    fs::write(&source, get_content("search.json")).unwrap();
+
+    db.initialize(InitializeOptions {
+      source,
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    let courses = db.courses(None, None, None).await.unwrap();
+
+    assert_eq!(courses.len(), 123);
+
+    let first = courses.first().unwrap();
+
+    assert_eq!(
+      db.find_course_by_id(&first.id).await.unwrap().unwrap(),
+      *first
+    );
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn search_course_by_id_exact() {
+    let TestContext { db, db_name } = TestContext::new().await;
+
+    let tempdir = TempDir::new(&db_name).unwrap();
+
+    let source = tempdir.path().join("courses.json");
+
+    fs::write(&source, get_content("search.json")).unwrap();
+
+    db.initialize(InitializeOptions {
+      source,
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(db.courses(None, None, None).await.unwrap().len(), 123);
+
+    let results = db.search("COMP202").await.unwrap();
+
+    assert_eq!(results.courses.len(), 1);
+
+    let first = results.courses.first().unwrap();
+
+    assert_eq!(first.subject, "COMP");
+    assert_eq!(first.code, "202");
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn fuzzy_search_course_by_title() {
+    let TestContext { db, db_name } = TestContext::new().await;
+
+    let tempdir = TempDir::new(&db_name).unwrap();
+
+    let source = tempdir.path().join("courses.json");
+
+    fs::write(&source, get_content("search.json")).unwrap();
+
+    db.initialize(InitializeOptions {
+      source,
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(db.courses(None, None, None).await.unwrap().len(), 123);
+
+    let results = db.search("foundations of").await.unwrap();
+
+    assert_eq!(results.courses.len(), 1);
+
+    let first = results.courses.first().unwrap();
+
+    assert_eq!(first.subject, "COMP");
+    assert_eq!(first.code, "202");
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn get_courses_with_limit() {
+    let TestContext { db, db_name } = TestContext::new().await;
+
+    let tempdir = TempDir::new(&db_name).unwrap();
+
+    let source = tempdir.path().join("courses.json");
+
+    fs::write(&source, get_content("search.json")).unwrap();
+
+    db.initialize(InitializeOptions {
+      source,
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(db.courses(Some(10), None, None).await.unwrap().len(), 10);
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn get_courses_with_sort_filter() {
+    let TestContext { db, db_name } = TestContext::new().await;
+
+    let tempdir = TempDir::new(&db_name).unwrap();
+
+    let source = tempdir.path().join("courses.json");
+
+    fs::write(&source, get_content("search.json")).unwrap();
+
+    db.initialize(InitializeOptions {
+      source,
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    db.add_review(Review {
+      course_id: "COMP252".into(),
+      user_id: "1".into(),
+      rating: 5,
+      difficulty: 4,
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    db.add_review(Review {
+      course_id: "COMP252".into(),
+      user_id: "2".into(),
+      rating: 4,
+      difficulty: 3,
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    db.add_review(Review {
+      course_id: "COMP362".into(),
+      user_id: "1".into(),
+      rating: 3,
+      difficulty: 5,
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    db.add_review(Review {
+      course_id: "COMP362".into(),
+      user_id: "2".into(),
+      rating: 4,
+      difficulty: 4,
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    let courses = db
+      .courses(
+        Some(10),
+        None,
+        Some(CourseFilter {
+          sort_by: Some(CourseSort {
+            sort_type: CourseSortType::Rating,
+            reverse: true,
+          }),
+          ..Default::default()
+        }),
+      )
+      .await
+      .unwrap();
+
+    assert!(
+      db.find_course_by_id("COMP252")
+        .await
+        .unwrap()
+        .unwrap()
+        .avg_rating
+        - 4.5
+        < 0.01,
+    );
+
+    assert_eq!(courses[0].id, "COMP252");
+    assert_eq!(courses[1].id, "COMP362");
+
+    let courses = db
+      .courses(
+        Some(10),
+        None,
+        Some(CourseFilter {
+          sort_by: Some(CourseSort {
+            sort_type: CourseSortType::Difficulty,
+            reverse: true,
+          }),
+          ..Default::default()
+        }),
+      )
+      .await
+      .unwrap();
+
+    assert_eq!(courses[0].id, "COMP362");
+    assert_eq!(courses[1].id, "COMP252");
+
+    db.add_review(Review {
+      course_id: "COMP400".into(),
+      user_id: "1".into(),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    db.add_review(Review {
+      course_id: "COMP400".into(),
+      user_id: "2".into(),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    db.add_review(Review {
+      course_id: "COMP400".into(),
+      user_id: "3".into(),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    let courses = db
+      .courses(
+        Some(10),
+        None,
+        Some(CourseFilter {
+          sort_by: Some(CourseSort {
+            sort_type: CourseSortType::ReviewCount,
+            reverse: true,
+          }),
+          ..Default::default()
+        }),
+      )
+      .await
+      .unwrap();
+
+    assert_eq!(courses[0].id, "COMP400");
+    assert_eq!(courses[0].review_count, 3);
+    assert_eq!(courses[1].review_count, 2);
+    assert_eq!(courses[2].review_count, 2);
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn add_reviews() {
+    let TestContext { db, .. } = TestContext::new().await;
+
+    db.add_course(Course {
+      id: "MATH240".into(),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    let reviews = vec![
+      Review {
+        content: "foo".into(),
+        course_id: "MATH240".into(),
+        instructors: vec![String::from("test")],
+        rating: 5,
+        difficulty: 2,
+        user_id: "1".into(),
+        ..Default::default()
+      },
+      Review {
+        content: "foo".into(),
+        course_id: "MATH240".into(),
+        instructors: vec![String::from("test")],
+        rating: 4,
+        difficulty: 3,
+        user_id: "2".into(),
+        ..Default::default()
+      },
+      Review {
+        content: "foo".into(),
+        course_id: "MATH240".into(),
+        instructors: vec![String::from("test")],
+        rating: 3,
+        difficulty: 2,
+        user_id: "3".into(),
+        ..Default::default()
+      },
+    ];
+
+    for review in &reviews {
+      db.add_review(review.clone()).await.unwrap();
+    }
+
+    assert_eq!(db.reviews(None, None, None).await.unwrap().len(), 3);
+    assert_eq!(db.reviews(None, None, None).await.unwrap(), reviews);
+
+    let course = db.find_course_by_id("MATH240").await.unwrap().unwrap();
+    assert!(course.avg_rating - 4.0 < 0.001);
+    assert!(course.avg_difficulty - 2.33 < 0.1);
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn review_histograms_follow_reviews() {
+    let TestContext { db, .. } = TestContext::new().await;
+
+    db.add_course(Course {
+      id: "MATH240".into(),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    let review = |user_id: &str, rating: u32, difficulty: u32| Review {
+      course_id: "MATH240".into(),
+      user_id: user_id.into(),
+      rating,
+      difficulty,
+      ..Default::default()
+    };
+
+    db.add_review(review("1", 5, 2)).await.unwrap();
+    db.add_review(review("2", 3, 4)).await.unwrap();
+    db.add_review(review("2", 4, 4)).await.unwrap();
+
+    let course = db.find_course_by_id("MATH240").await.unwrap().unwrap();
+
+    assert_eq!(course.review_count, 2);
+    assert_eq!(course.rating_distribution, vec![0, 0, 0, 1, 1]);
+    assert_eq!(course.difficulty_distribution, vec![0, 1, 0, 1, 0]);
+    assert_eq!(course.avg_rating, 4.5);
+    assert_eq!(course.avg_difficulty, 3.0);
+
+    db.delete_review("MATH240", "1").await.unwrap();
+
+    let course = db.find_course_by_id("MATH240").await.unwrap().unwrap();
+
+    assert_eq!(course.review_count, 1);
+    assert_eq!(course.rating_distribution, vec![0, 0, 0, 1, 0]);
+    assert_eq!(course.avg_rating, 4.0);
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn review_histograms_are_rebuilt_for_legacy_courses() {
+    let TestContext { db, .. } = TestContext::new().await;
+
+    db.add_course(Course {
+      id: "MATH240".into(),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    db.database
+      .collection::<Review>(Db::REVIEW_COLLECTION)
+      .insert_one(
+        Review {
+          course_id: "MATH240".into(),
+          user_id: "1".into(),
+          rating: 2,
+          difficulty: 5,
+          ..Default::default()
+        },
+        None,
+      )
+      .await
+      .unwrap();
+
+    db.add_review(Review {
+      course_id: "MATH240".into(),
+      user_id: "2".into(),
+      rating: 4,
+      difficulty: 1,
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    let course = db.find_course_by_id("MATH240").await.unwrap().unwrap();
+
+    assert_eq!(course.review_count, 2);
+    assert_eq!(course.rating_distribution, vec![0, 1, 0, 1, 0]);
+    assert_eq!(course.difficulty_distribution, vec![1, 0, 0, 0, 1]);
+    assert_eq!(course.avg_rating, 3.0);
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn course_review_stats_filter_by_instructor_and_term() {
+    let TestContext { db, .. } = TestContext::new().await;
+
+    let course = Course {
+      id: "MATH240".into(),
+      instructors: vec![
+        Instructor {
+          name: "Adrian Roshan Vetta".into(),
+          term: "Fall 2022".into(),
+          ..Default::default()
+        },
+        Instructor {
+          name: "Jeremy Macdonald".into(),
+          term: "Winter 2023".into(),
+          ..Default::default()
+        },
+      ],
+      ..Default::default()
+    };
+
+    db.add_course(course.clone()).await.unwrap();
+
+    for (user_id, instructor, rating, written) in [
+      ("1", "Adrian Roshan Vetta", 5, (2022, 12)),
+      ("2", "Adrian Roshan Vetta", 4, (2023, 3)),
+      ("3", "Jeremy Macdonald", 1, (2023, 3)),
+    ] {
+      db.add_review(Review {
+        content: "a very long review".into(),
+        course_id: "MATH240".into(),
+        user_id: user_id.into(),
+        instructors: vec![instructor.into()],
+        rating,
+        difficulty: 3,
+        timestamp: Utc
+          .with_ymd_and_hms(written.0, written.1, 1, 0, 0, 0)
+          .unwrap()
+          .into(),
+        ..Default::default()
+      })
+      .await
+      .unwrap();
+    }
+
+    let vetta = ["Adrian Roshan Vetta".to_string()];
+
+    let stats = db
+      .course_review_stats(&course, Some(&vetta), None)
+      .await
+      .unwrap();
+
+    assert_eq!(stats.rating_distribution, vec![0, 0, 0, 1, 1]);
+
+    let stats = db
+      .course_review_stats(&course, Some(&vetta), Some("Fall 2022"))
+      .await
+      .unwrap();
+
+    assert_eq!(stats.review_count, 2);
+
+    let stats = db
+      .course_review_stats(&course, None, Some("Winter 2023"))
+      .await
+      .unwrap();
+
+    assert_eq!(stats.rating_distribution, vec![1, 0, 0, 1, 0]);
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn find_courses_by_ids() {
+    let TestContext { db, .. } = TestContext::new().await;
+
+    for id in ["COMP251", "COMP252", "MATH240"] {
+      db.add_course(Course {
+        id: id.into(),
+        ..Default::default()
+      })
+      .await
+      .unwrap();
+    }
 
-    drop(db);
+    let mut courses = db
+      .find_courses_by_ids(&[
+        "COMP252".into(),
+        "MATH240".into(),
+        "MATH999".into(),
+      ])
+      .await
+      .unwrap()
+      .into_iter()
+      .map(|course| course.id)
+      .collect::<Vec<_>>();
 
-    let db = Db::connect(&db_name).await.unwrap();
+    courses.sort();
 
-    assert_eq!(db.courses(None, None, None).await.unwrap().len(), 1);
+    assert_eq!(courses, vec!["COMP252", "MATH240"]);
   }
 
   #[tokio::test(flavor = "multi_thread")]
-  async fn course_seeding_is_accurate() {
-    let TestContext { db, db_name } = TestContext::new().await;
-
-    let tempdir = TempDir::new(&db_name).unwrap();
-
-    let source = tempdir.path().join("courses.json");
-
-    fs::write(&source, get_content("before_update.json")).unwrap();
+  async fn find_reviews_by_course_id() {
+    let TestContext { db, .. } = TestContext::new().await;
 
-    db.initialize(InitializeOptions {
-      source,
+    db.add_course(Course {
+      id: "MATH240".into(),
       ..Default::default()
     })
     .await
     .unwrap();
 
-    assert_eq!(db.courses(None, None, None).await.unwrap().len(), 2);
-  }
+    db.add_course(Course {
+      id: "MATH340".into(),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
 
-  #[tokio::test(flavor = "multi_thread")]
-  async fn course_seeding_does_not_insert_duplicates() {
-    let TestContext { db, db_name } = TestContext::new().await;
+    let reviews = vec![
+      Review {
+        content: "foo".into(),
+        user_id: "1".into(),
+        instructors: vec![String::from("test")],
+        rating: 5,
+        course_id: "MATH240".into(),
+        ..Default::default()
+      },
+      Review {
+        content: "foo".into(),
+        user_id: "2".into(),
+        instructors: vec![String::from("test")],
+        rating: 5,
+        course_id: "MATH240".into(),
+        ..Default::default()
+      },
+      Review {
+        content: "foo".into(),
+        user_id: "3".into(),
+        instructors: vec![String::from("test")],
+        rating: 5,
+        course_id: "MATH340".into(),
+        ..Default::default()
+      },
+    ];
 
-    let tempdir = TempDir::new(&db_name).unwrap();
+    for review in &reviews {
+      db.add_review(review.clone()).await.unwrap();
+    }
 
-    let source = tempdir.path().join("courses.json");
+    assert_eq!(db.reviews(None, None, None).await.unwrap().len(), 3);
+    assert_eq!(db.reviews(None, None, None).await.unwrap(), reviews);
 
-    fs::write(
-      &source,
-      serde_json::to_string(
-        &(0..10).map(|_| Course::default()).collect::<Vec<Course>>(),
-      )
-      .unwrap(),
+    assert_eq!(
+      db.find_reviews_by_course_id("MATH240").await.unwrap(),
+      vec![
+        Review {
+          content: "foo".into(),
+          user_id: "1".into(),
+          instructors: vec![String::from("test")],
+          rating: 5,
+          course_id: "MATH240".into(),
+          ..Default::default()
+        },
+        Review {
+          content: "foo".into(),
+          course_id: "MATH240".into(),
+          instructors: vec![String::from("test")],
+          rating: 5,
+          user_id: "2".into(),
+          ..Default::default()
+        }
+      ]
     )
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn find_reviews_by_user_id() {
+    let TestContext { db, .. } = TestContext::new().await;
+
+    db.add_course(Course {
+      id: "MATH240".into(),
+      ..Default::default()
+    })
+    .await
     .unwrap();
 
-    db.initialize(InitializeOptions {
-      source,
+    db.add_course(Course {
+      id: "MATH340".into(),
       ..Default::default()
     })
     .await
     .unwrap();
 
-    assert_eq!(db.courses(None, None, None).await.unwrap().len(), 1);
-  }
+    let reviews = vec![
+      Review {
+        content: "foo".into(),
+        user_id: "1".into(),
+        course_id: "MATH240".into(),
+        ..Default::default()
+      },
+      Review {
+        content: "foo".into(),
+        user_id: "2".into(),
+        course_id: "MATH240".into(),
+        ..Default::default()
+      },
+      Review {
+        content: "foo".into(),
+        user_id: "3".into(),
+        course_id: "MATH340".into(),
+        ..Default::default()
+      },
+    ];
 
-  #[tokio::test(flavor = "multi_thread")]
-  async fn courses_get_updated_when_seeding() {
-    let TestContext { db, db_name } = TestContext::new().await;
+    for review in &reviews {
+      db.add_review(review.clone()).await.unwrap();
+    }
 
-    let tempdir = TempDir::new(&db_name).unwrap();
+    assert_eq!(db.reviews(None, None, None).await.unwrap().len(), 3);
+    assert_eq!(db.reviews(None, None, None).await.unwrap(), reviews);
 
-    let source = tempdir.path().join("courses.json");
+    assert_eq!(
+      db.find_reviews_by_user_id("2").await.unwrap(),
+      vec![Review {
+        content: "foo".into(),
+        user_id: "2".into(),
+        course_id: "MATH240".into(),
+        ..Default::default()
+      },]
+    )
+  }
 
-    fs::write(&source, get_content("before_update.json")).unwrap();
+  #[tokio::test(flavor = "multi_thread")]
+  async fn find_reviews_by_user_instructor_name() {
+    let TestContext { db, .. } = TestContext::new().await;
 
-    db.initialize(InitializeOptions {
-      source: source.clone(),
+    db.add_course(Course {
+      id: "MATH240".into(),
       ..Default::default()
     })
     .await
     .unwrap();
 
-    assert_eq!(db.courses(None, None, None).await.unwrap().len(), 2);
-
-    fs::write(&source, get_content("update.json")).unwrap();
-
-    db.initialize(InitializeOptions {
-      source,
+    db.add_course(Course {
+      id: "MATH340".into(),
       ..Default::default()
     })
     .await
     .unwrap();
 
-    let courses = db.courses(None, None, None).await.unwrap();
+    let reviews = vec![
+      Review {
+        content: "foo".into(),
+        user_id: "1".into(),
+        course_id: "MATH240".into(),
+        instructors: vec![
+          String::from("test"),
+          String::from("foo"),
+          String::from("bar"),
+        ],
+        ..Default::default()
+      },
+      Review {
+        content: "foo".into(),
+        user_id: "2".into(),
+        course_id: "MATH240".into(),
+        instructors: vec![String::from("test"), String::from("foo")],
+        ..Default::default()
+      },
+      Review {
+        content: "foo".into(),
+        user_id: "3".into(),
+        course_id: "MATH340".into(),
+        instructors: vec![String::from("foo"), String::from("bar")],
+        ..Default::default()
+      },
+    ];
 
-    assert_eq!(courses.len(), 3);
+    for review in &reviews {
+      db.add_review(review.clone()).await.unwrap();
+    }
+
+    assert_eq!(db.reviews(None, None, None).await.unwrap().len(), 3);
+    assert_eq!(db.reviews(None, None, None).await.unwrap(), reviews);
 
     assert_eq!(
-      courses,
-      serde_json::from_str::<Vec<Course>>(&get_content("after_update.json"))
-        .unwrap()
-        .into_iter()
-        .map(|c| Course {
-          id_ngrams: Some(c.id.ngrams()),
-          title_ngrams: Some(c.title.filter_stopwords().ngrams()),
-          ..c
-        })
-        .collect::<Vec<Course>>()
-    );
+      db.find_reviews_by_instructor_name("test").await.unwrap(),
+      vec![
+        Review {
+          content: "foo".into(),
+          user_id: "1".into(),
+          course_id: "MATH240".into(),
+          instructors: vec![
+            String::from("test"),
+            String::from("foo"),
+            String::from("bar"),
+          ],
+          ..Default::default()
+        },
+        Review {
+          content: "foo".into(),
+          user_id: "2".into(),
+          course_id: "MATH240".into(),
+          instructors: vec![String::from("test"), String::from("foo")],
+          ..Default::default()
+        },
+      ]
+    )
   }
 
   #[tokio::test(flavor = "multi_thread")]
-  async fn search_is_accurate() {
-    let TestContext { db, db_name } = TestContext::new().await;
-
-    let tempdir = TempDir::new(&db_name).unwrap();
-
-    let source = tempdir.path().join("courses.json");
-
-    fs::write(&source, get_content("search.json")).unwrap();
+  async fn dont_add_multiple_reviews_per_user() {
+    let TestContext { db, .. } = TestContext::new().await;
 
-    db.initialize(InitializeOptions {
-      source,
+    db.add_course(Course {
+      id: "MATH240".into(),
       ..Default::default()
     })
     .await
     .unwrap();
 
-    assert_eq!(db.courses(None, None, None).await.unwrap().len(), 123);
-
-    let results = db.search("COMP 202").await.unwrap();
-
-    assert_eq!(results.courses.len(), 4);
+    let review = Review {
+      user_id: "1".into(),
+      course_id: "MATH240".into(),
+      ..Default::default()
+    };
 
-    let first = results.courses.first().unwrap();
+    for _ in 0..10 {
+      db.add_review(review.clone()).await.unwrap();
+    }
 
-    assert_eq!(first.subject, "COMP");
-    assert_eq!(first.code, "202");
+    assert_eq!(db.reviews(None, None, None).await.unwrap().len(), 1);
   }
 
   #[tokio::test(flavor = "multi_thread")]
-  async fn get_course_by_id() {
-    let TestContext { db, db_name } = TestContext::new().await;
-
-    let tempdir = TempDir::new(&db_name).unwrap();
-
-    let source = tempdir.path().join("courses.json");
-
-    fs::write(&source, get_content("search.json")).unwrap();
+  async fn update_review() {
+    let TestContext { db, .. } = TestContext::new().await;
 
-    db.initialize(InitializeOptions {
-      source,
+    db.add_course(Course {
+      id: "MATH240".into(),
       ..Default::default()
     })
     .await
     .unwrap();
 
-    let courses = db.courses(None, None, None).await.unwrap();
-
-    assert_eq!(courses.len(), 123);
+    db.add_review(Review {
+      content: "foo".into(),
+      course_id: "MATH240".into(),
+      instructors: vec![String::from("bar")],
+      rating: 5,
+      difficulty: 5,
+      user_id: "1".into(),
+      timestamp: DateTime::from(Utc::now()),
+      ..Review::default()
+    })
+    .await
+    .unwrap();
 
-    let first = courses.first().unwrap();
+    let timestamp = DateTime::from(Utc::now());
 
     assert_eq!(
-      db.find_course_by_id(&first.id).await.unwrap().unwrap(),
-      *first
+      db.add_review(Review {
+        content: "bar".into(),
+        course_id: "MATH240".into(),
+        instructors: vec![String::from("foo")],
+        rating: 4,
+        difficulty: 4,
+        user_id: "1".into(),
+        timestamp: timestamp.clone(),
+        ..Review::default()
+      })
+      .await
+      .unwrap()
+      .modified_count,
+      1
     );
-  }
 
-  #[tokio::test(flavor = "multi_thread")]
-  async fn search_course_by_id_exact() {
-    let TestContext { db, db_name } = TestContext::new().await;
+    assert_eq!(
+      db.add_review(Review {
+        content: "bar".into(),
+        course_id: "MATH240".into(),
+        instructors: vec![String::from("foo")],
+        rating: 4,
+        difficulty: 4,
+        user_id: "2".into(),
+        ..Default::default()
+      })
+      .await
+      .unwrap()
+      .modified_count,
+      0
+    );
 
-    let tempdir = TempDir::new(&db_name).unwrap();
+    let review = db.find_review("MATH240", "1").await.unwrap().unwrap();
 
-    let source = tempdir.path().join("courses.json");
+    assert_eq!(review.content, "bar");
+    assert_eq!(review.instructors, vec![String::from("foo")]);
+    assert_eq!(review.rating, 4);
+    assert_eq!(review.timestamp, timestamp);
+  }
 
-    fs::write(&source, get_content("search.json")).unwrap();
+  #[tokio::test(flavor = "multi_thread")]
+  async fn delete_review() {
+    let TestContext { db, .. } = TestContext::new().await;
 
-    db.initialize(InitializeOptions {
-      source,
+    db.add_course(Course {
+      id: "MATH240".into(),
       ..Default::default()
     })
     .await
     .unwrap();
 
-    assert_eq!(db.courses(None, None, None).await.unwrap().len(), 123);
-
-    let results = db.search("COMP202").await.unwrap();
+    db.add_review(Review {
+      content: "foo".into(),
+      course_id: "MATH240".into(),
+      user_id: "1".into(),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
 
-    assert_eq!(results.courses.len(), 1);
+    assert!(db.delete_review("MATH240", "2").await.is_err());
 
-    let first = results.courses.first().unwrap();
+    assert!(db.delete_review("MATH240", "1").await.is_ok());
 
-    assert_eq!(first.subject, "COMP");
-    assert_eq!(first.code, "202");
+    assert_eq!(db.find_review("MATH240", "1").await.unwrap(), None);
   }
 
   #[tokio::test(flavor = "multi_thread")]
-  async fn fuzzy_search_course_by_title() {
-    let TestContext { db, db_name } = TestContext::new().await;
-
-    let tempdir = TempDir::new(&db_name).unwrap();
-
-    let source = tempdir.path().join("courses.json");
-
-    fs::write(&source, get_content("search.json")).unwrap();
+  async fn delete_review_then_add_again() {
+    let TestContext { db, .. } = TestContext::new().await;
 
-    db.initialize(InitializeOptions {
-      source,
+    db.add_course(Course {
+      id: "MATH240".into(),
       ..Default::default()
     })
     .await
     .unwrap();
 
-    assert_eq!(db.courses(None, None, None).await.unwrap().len(), 123);
-
-    let results = db.search("foundations of").await.unwrap();
-
-    assert_eq!(results.courses.len(), 1);
+    db.add_review(Review {
+      content: "foo".into(),
+      course_id: "MATH240".into(),
+      user_id: "1".into(),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
 
-    let first = results.courses.first().unwrap();
+    assert!(db.delete_review("MATH240", "1").await.is_ok());
 
-    assert_eq!(first.subject, "COMP");
-    assert_eq!(first.code, "202");
+    assert!(
+      db.add_review(Review {
+        content: "foo".into(),
+        course_id: "MATH240".into(),
+        user_id: "1".into(),
+        ..Default::default()
+      })
+      .await
+      .is_ok()
+    );
   }
 
   #[tokio::test(flavor = "multi_thread")]
-  async fn get_courses_with_limit() {
+  async fn filter_courses_by_subject() {
     let TestContext { db, db_name } = TestContext::new().await;
 
     let tempdir = TempDir::new(&db_name).unwrap();
 
     let source = tempdir.path().join("courses.json");
 
-    fs::write(&source, get_content("search.json")).unwrap();
+    fs::write(&source, get_content("mix.json")).unwrap();
 
     db.initialize(InitializeOptions {
       source,
+
       ..Default::default()
     })
     .await
     .unwrap();
 
-    assert_eq!(db.courses(Some(10), None, None).await.unwrap().len(), 10);
+    let total = db.courses(None, None, None).await.unwrap();
+
+    assert_eq!(total.len(), 314);
+
+    let filtered = db
+      .courses(
+        None,
+        None,
+        Some(CourseFilter {
+          subjects: Some(vec!["MATH".into()]),
+          ..Default::default()
+        }),
+      )
+      .await
+      .unwrap();
+
+    assert!(filtered.len() < total.len());
+
+    for course in filtered {
+      assert_eq!(course.subject, "MATH");
+    }
   }
 
   #[tokio::test(flavor = "multi_thread")]
-  async fn get_courses_with_sort_filter() {
+  async fn filter_courses_by_level() {
     let TestContext { db, db_name } = TestContext::new().await;
 
     let tempdir = TempDir::new(&db_name).unwrap();
 
     let source = tempdir.path().join("courses.json");
 
-    fs::write(&source, get_content("search.json")).unwrap();
+    fs::write(&source, get_content("mix.json")).unwrap();
 
     db.initialize(InitializeOptions {
       source,
-      ..Default::default()
-    })
-    .await
-    .unwrap();
-
-    db.add_review(Review {
-      course_id: "COMP252".into(),
-      user_id: "1".into(),
-      rating: 5,
-      difficulty: 4,
-      ..Default::default()
-    })
-    .await
-    .unwrap();
 
-    db.add_review(Review {
-      course_id: "COMP252".into(),
-      user_id: "2".into(),
-      rating: 4,
-      difficulty: 3,
       ..Default::default()
     })
     .await
     .unwrap();
 
-    db.add_review(Review {
-      course_id: "COMP362".into(),
-      user_id: "1".into(),
-      rating: 3,
-      difficulty: 5,
-      ..Default::default()
-    })
-    .await
-    .unwrap();
+    let total = db.courses(None, None, None).await.unwrap();
 
-    db.add_review(Review {
-      course_id: "COMP362".into(),
-      user_id: "2".into(),
-      rating: 4,
-      difficulty: 4,
-      ..Default::default()
-    })
-    .await
-    .unwrap();
+    assert_eq!(total.len(), 314);
 
-    let courses = db
+    let filtered = db
       .courses(
-        Some(10),
+        None,
         None,
         Some(CourseFilter {
-          sort_by: Some(CourseSort {
-            sort_type: CourseSortType::Rating,
-            reverse: true,
-          }),
+          levels: Some(vec!["100".into()]),
           ..Default::default()
         }),
       )
       .await
       .unwrap();
 
-    assert!(
-      db.find_course_by_id("COMP252")
-        .await
-        .unwrap()
-        .unwrap()
-        .avg_rating
-        - 4.5
-        < 0.01,
-    );
-
-    assert_eq!(courses[0].id, "COMP252");
-    assert_eq!(courses[1].id, "COMP362");
+    assert!(filtered.len() < total.len());
 
-    let courses = db
-      .courses(
-        Some(10),
-        None,
-        Some(CourseFilter {
-          sort_by: Some(CourseSort {
-            sort_type: CourseSortType::Difficulty,
-            reverse: true,
-          }),
-          ..Default::default()
-        }),
-      )
-      .await
-      .unwrap();
+    for course in filtered {
+      assert!(course.code.starts_with('1'));
+    }
+  }
 
-    assert_eq!(courses[0].id, "COMP362");
-    assert_eq!(courses[1].id, "COMP252");
+  #[tokio::test(flavor = "multi_thread")]
+  async fn filter_courses_by_term() {
+    let TestContext { db, db_name } = TestContext::new().await;
 
-    db.add_review(Review {
-      course_id: "COMP400".into(),
-      user_id: "1".into(),
-      ..Default::default()
-    })
-    .await
-    .unwrap();
+    let tempdir = TempDir::new(&db_name).unwrap();
 
-    db.add_review(Review {
-      course_id: "COMP400".into(),
-      user_id: "2".into(),
-      ..Default::default()
-    })
-    .await
-    .unwrap();
+    let source = tempdir.path().join("courses.json");
+
+    fs::write(&source, get_content("mix.json")).unwrap();
+
+    db.initialize(InitializeOptions {
+      source,
 
-    db.add_review(Review {
-      course_id: "COMP400".into(),
-      user_id: "3".into(),
       ..Default::default()
     })
     .await
     .unwrap();
 
-    let courses = db
+    let total = db.courses(None, None, None).await.unwrap();
+
+    assert_eq!(total.len(), 314);
+
+    let filtered = db
       .courses(
-        Some(10),
+        None,
         None,
         Some(CourseFilter {
-          sort_by: Some(CourseSort {
-            sort_type: CourseSortType::ReviewCount,
-            reverse: true,
-          }),
+          terms: Some(vec!["Winter".into()]),
           ..Default::default()
         }),
       )
       .await
       .unwrap();
 
-    assert_eq!(courses[0].id, "COMP400");
-    assert_eq!(courses[0].review_count, 3);
-    assert_eq!(courses[1].review_count, 2);
-    assert_eq!(courses[2].review_count, 2);
+    assert!(filtered.len() < total.len());
+
+    for course in filtered {
+      assert!(
+        course
+          .terms
+          .iter()
+          .any(|term| term.starts_with(&"Winter".to_string()))
+      );
+    }
   }
 
   #[tokio::test(flavor = "multi_thread")]
-  async fn add_reviews() {
+  async fn add_instructors() {
     let TestContext { db, .. } = TestContext::new().await;
 
-    db.add_course(Course {
-      id: "MATH240".into(),
-      ..Default::default()
-    })
-    .await
-    .unwrap();
-
-    let reviews = vec![
-      Review {
-        content: "foo".into(),
-        course_id: "MATH240".into(),
-        instructors: vec![String::from("test")],
-        rating: 5,
-        difficulty: 2,
-        user_id: "1".into(),
+    let instructors = vec![
+      Instructor {
+        name: "foo".into(),
+        term: "Summer 2023".into(),
         ..Default::default()
       },
-      Review {
-        content: "foo".into(),
-        course_id: "MATH240".into(),
-        instructors: vec![String::from("test")],
-        rating: 4,
-        difficulty: 3,
-        user_id: "2".into(),
+      Instructor {
+        name: "bar".into(),
+        term: "Summer 2023".into(),
         ..Default::default()
       },
-      Review {
-        content: "foo".into(),
-        course_id: "MATH240".into(),
-        instructors: vec![String::from("test")],
-        rating: 3,
-        difficulty: 2,
-        user_id: "3".into(),
+      Instructor {
+        name: "bar".into(),
+        term: "Winter 2023".into(),
         ..Default::default()
       },
     ];
 
-    for review in &reviews {
-      db.add_review(review.clone()).await.unwrap();
+    for instructor in instructors {
+      db.add_instructor("COMP202", instructor).await.unwrap();
     }
 
-    assert_eq!(db.reviews(None, None, None).await.unwrap().len(), 3);
-    assert_eq!(db.reviews(None, None, None).await.unwrap(), reviews);
-
-    let course = db.find_course_by_id("MATH240").await.unwrap().unwrap();
-    assert!(course.avg_rating - 4.0 < 0.001);
-    assert!(course.avg_difficulty - 2.33 < 0.1);
+    assert_eq!(db.instructors().await.unwrap().len(), 2);
   }
 
   #[tokio::test(flavor = "multi_thread")]
-  async fn find_reviews_by_course_id() {
+  async fn instructor_profiles_track_assignments_and_aliases() {
     let TestContext { db, .. } = TestContext::new().await;
 
-    db.add_course(Course {
-      id: "MATH240".into(),
-      ..Default::default()
-    })
-    .await
-    .unwrap();
-
-    db.add_course(Course {
-      id: "MATH340".into(),
-      ..Default::default()
-    })
-    .await
-    .unwrap();
+    for (course_id, name, term) in [
+      ("COMP251", "Mona Elsaadawy", "Fall 2024"),
+      ("COMP251", "Elsaadawy, Mona", "Fall 2025"),
+      ("COMP250", "Mona  Elsaadawy", "Winter 2025"),
+      ("COMP251", "Mona Elsaadawy", "Fall 2024"),
+    ] {
+      db.add_instructor(
+        course_id,
+        Instructor {
+          name: name.into(),
+          term: term.into(),
+          ..Default::default()
+        },
+      )
+      .await
+      .unwrap();
+    }
 
-    let reviews = vec![
-      Review {
-        content: "foo".into(),
-        user_id: "1".into(),
-        instructors: vec![String::from("test")],
-        rating: 5,
-        course_id: "MATH240".into(),
-        ..Default::default()
-      },
-      Review {
-        content: "foo".into(),
-        user_id: "2".into(),
-        instructors: vec![String::from("test")],
-        rating: 5,
-        course_id: "MATH240".into(),
-        ..Default::default()
-      },
-      Review {
-        content: "foo".into(),
-        user_id: "3".into(),
-        instructors: vec![String::from("test")],
-        rating: 5,
-        course_id: "MATH340".into(),
-        ..Default::default()
-      },
-    ];
+    let instructors = db.instructors().await.unwrap();
 
-    for review in &reviews {
-      db.add_review(review.clone()).await.unwrap();
-    }
+    assert_eq!(instructors.len(), 1);
 
-    assert_eq!(db.reviews(None, None, None).await.unwrap().len(), 3);
-    assert_eq!(db.reviews(None, None, None).await.unwrap(), reviews);
+    let instructor = &instructors[0];
 
+    assert_eq!(instructor.id, "mona-elsaadawy");
+    assert_eq!(instructor.name, "Mona Elsaadawy");
     assert_eq!(
-      db.find_reviews_by_course_id("MATH240").await.unwrap(),
+      instructor.aliases,
+      vec!["Mona Elsaadawy", "Elsaadawy, Mona", "Mona  Elsaadawy"]
+    );
+    assert_eq!(
+      instructor.assignments,
       vec![
-        Review {
-          content: "foo".into(),
-          user_id: "1".into(),
-          instructors: vec![String::from("test")],
-          rating: 5,
-          course_id: "MATH240".into(),
-          ..Default::default()
+        TeachingAssignment {
+          course_id: "COMP251".into(),
+          term: "Fall 2024".into(),
+        },
+        TeachingAssignment {
+          course_id: "COMP251".into(),
+          term: "Fall 2025".into(),
+        },
+        TeachingAssignment {
+          course_id: "COMP250".into(),
+          term: "Winter 2025".into(),
         },
-        Review {
-          content: "foo".into(),
-          course_id: "MATH240".into(),
-          instructors: vec![String::from("test")],
-          rating: 5,
-          user_id: "2".into(),
-          ..Default::default()
-        }
       ]
-    )
+    );
+
+    assert_eq!(
+      db.find_instructor_by_name("Elsaadawy, Mona")
+        .await
+        .unwrap()
+        .map(|instructor| instructor.id),
+      Some("mona-elsaadawy".into())
+    );
+
+    assert_eq!(
+      db.find_instructors_by_course("COMP250")
+        .await
+        .unwrap()
+        .len(),
+      1
+    );
+
+    assert!(
+      db.find_instructors_by_course("COMP202")
+        .await
+        .unwrap()
+        .is_empty()
+    );
   }
 
   #[tokio::test(flavor = "multi_thread")]
-  async fn find_reviews_by_user_id() {
-    let TestContext { db, .. } = TestContext::new().await;
+  async fn search_instructor_by_name_exact() {
+    let TestContext { db, db_name } = TestContext::new().await;
 
-    db.add_course(Course {
-      id: "MATH240".into(),
-      ..Default::default()
-    })
-    .await
-    .unwrap();
+    let tempdir = TempDir::new(&db_name).unwrap();
 
-    db.add_course(Course {
-      id: "MATH340".into(),
+    let source = tempdir.path().join("courses.json");
+
+    fs::write(&source, get_content("search.json")).unwrap();
+
+    db.initialize(InitializeOptions {
+      source,
       ..Default::default()
     })
     .await
     .unwrap();
 
-    let reviews = vec![
-      Review {
-        content: "foo".into(),
-        user_id: "1".into(),
-        course_id: "MATH240".into(),
-        ..Default::default()
-      },
-      Review {
-        content: "foo".into(),
-        user_id: "2".into(),
-        course_id: "MATH240".into(),
-        ..Default::default()
-      },
-      Review {
-        content: "foo".into(),
-        user_id: "3".into(),
-        course_id: "MATH340".into(),
-        ..Default::default()
-      },
-    ];
+    assert_eq!(db.courses(None, None, None).await.unwrap().len(), 123);
 
-    for review in &reviews {
-      db.add_review(review.clone()).await.unwrap();
-    }
+    let results = db.search("Giulia Alberini").await.unwrap();
 
-    assert_eq!(db.reviews(None, None, None).await.unwrap().len(), 3);
-    assert_eq!(db.reviews(None, None, None).await.unwrap(), reviews);
+    assert_eq!(results.instructors.len(), 1);
 
-    assert_eq!(
-      db.find_reviews_by_user_id("2").await.unwrap(),
-      vec![Review {
-        content: "foo".into(),
-        user_id: "2".into(),
-        course_id: "MATH240".into(),
-        ..Default::default()
-      },]
-    )
+    assert_eq!(results.instructors.first().unwrap().name, "Giulia Alberini");
   }
 
   #[tokio::test(flavor = "multi_thread")]
-  async fn find_reviews_by_user_instructor_name() {
+  async fn review_interaction_flow() {
     let TestContext { db, .. } = TestContext::new().await;
 
     db.add_course(Course {
@@ -1815,109 +4135,173 @@ mod tests {
     .await
     .unwrap();
 
-    db.add_course(Course {
-      id: "MATH340".into(),
+    let review = Review {
+      content: "foo".into(),
+      course_id: "MATH240".into(),
+      user_id: "1".into(),
       ..Default::default()
+    };
+
+    db.add_review(review.clone()).await.unwrap();
+
+    assert_eq!(db.reviews(None, None, None).await.unwrap().len(), 1);
+
+    db.add_interaction(Interaction {
+      kind: InteractionKind::Like,
+      course_id: review.course_id.clone(),
+      user_id: review.user_id.clone(),
+      referrer: "10".into(),
     })
     .await
     .unwrap();
 
-    let reviews = vec![
-      Review {
-        content: "foo".into(),
-        user_id: "1".into(),
-        course_id: "MATH240".into(),
-        instructors: vec![
-          String::from("test"),
-          String::from("foo"),
-          String::from("bar"),
-        ],
-        ..Default::default()
-      },
-      Review {
-        content: "foo".into(),
-        user_id: "2".into(),
-        course_id: "MATH240".into(),
-        instructors: vec![String::from("test"), String::from("foo")],
-        ..Default::default()
-      },
-      Review {
-        content: "foo".into(),
-        user_id: "3".into(),
-        course_id: "MATH340".into(),
-        instructors: vec![String::from("foo"), String::from("bar")],
-        ..Default::default()
-      },
-    ];
+    let interactions = db
+      .interactions_for_review(&review.course_id, &review.user_id)
+      .await
+      .unwrap();
 
-    for review in &reviews {
-      db.add_review(review.clone()).await.unwrap();
-    }
+    assert_eq!(interactions.len(), 1);
+    assert_eq!(interactions.first().unwrap().kind, InteractionKind::Like);
 
-    assert_eq!(db.reviews(None, None, None).await.unwrap().len(), 3);
-    assert_eq!(db.reviews(None, None, None).await.unwrap(), reviews);
+    let review = db
+      .find_review(&review.course_id, &review.user_id)
+      .await
+      .unwrap()
+      .unwrap();
+    assert_eq!(review.likes, 1);
+
+    db.add_interaction(Interaction {
+      kind: InteractionKind::Dislike,
+      course_id: review.course_id.clone(),
+      user_id: review.user_id.clone(),
+      referrer: "10".into(),
+    })
+    .await
+    .unwrap();
+
+    let interactions = db
+      .interactions_for_review(&review.course_id, &review.user_id)
+      .await
+      .unwrap();
+
+    assert_eq!(interactions.len(), 1);
+    assert_eq!(interactions.first().unwrap().kind, InteractionKind::Dislike);
+
+    let review = db
+      .find_review(&review.course_id, &review.user_id)
+      .await
+      .unwrap()
+      .unwrap();
+    assert_eq!(review.likes, -1);
+
+    db.delete_interaction(&review.course_id, &review.user_id, "10")
+      .await
+      .unwrap();
 
     assert_eq!(
-      db.find_reviews_by_instructor_name("test").await.unwrap(),
-      vec![
-        Review {
-          content: "foo".into(),
-          user_id: "1".into(),
-          course_id: "MATH240".into(),
-          instructors: vec![
-            String::from("test"),
-            String::from("foo"),
-            String::from("bar"),
-          ],
-          ..Default::default()
-        },
-        Review {
-          content: "foo".into(),
-          user_id: "2".into(),
-          course_id: "MATH240".into(),
-          instructors: vec![String::from("test"), String::from("foo")],
-          ..Default::default()
-        },
-      ]
-    )
+      db.interactions_for_review(&review.course_id, &review.user_id)
+        .await
+        .unwrap()
+        .len(),
+      0
+    );
+
+    let review = db
+      .find_review(&review.course_id, &review.user_id)
+      .await
+      .unwrap()
+      .unwrap();
+    assert_eq!(review.likes, 0);
   }
 
   #[tokio::test(flavor = "multi_thread")]
-  async fn dont_add_multiple_reviews_per_user() {
+  async fn subscription_flow() {
     let TestContext { db, .. } = TestContext::new().await;
 
-    db.add_course(Course {
-      id: "MATH240".into(),
+    let subscription = Subscription {
+      course_id: "MATH240".into(),
+      user_id: "1".into(),
+      ..Default::default()
+    };
+
+    db.add_subscription(subscription.clone()).await.unwrap();
+
+    assert_eq!(db.subscriptions().await.unwrap().len(), 1);
+
+    db.delete_subscription(Subscription {
+      course_id: subscription.course_id,
+      user_id: subscription.user_id,
       ..Default::default()
     })
     .await
     .unwrap();
 
+    assert_eq!(db.subscriptions().await.unwrap().len(), 0);
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn notify_many_subscribers() {
+    let TestContext { db, .. } = TestContext::new().await;
+
     let review = Review {
+      content: "foo".into(),
+      course_id: "MATH240".into(),
+      instructors: vec![String::from("bar")],
+      rating: 5,
+      difficulty: 5,
+      user_id: "3".into(),
+      timestamp: DateTime::from(Utc::now()),
+      ..Review::default()
+    };
+
+    let subscription = Subscription {
+      course_id: "MATH240".into(),
       user_id: "1".into(),
+      ..Default::default()
+    };
+
+    db.add_subscription(subscription.clone()).await.unwrap();
+
+    let subscription = Subscription {
       course_id: "MATH240".into(),
+      user_id: "2".into(),
       ..Default::default()
     };
 
-    for _ in 0..10 {
-      db.add_review(review.clone()).await.unwrap();
-    }
+    db.add_subscription(subscription.clone()).await.unwrap();
 
-    assert_eq!(db.reviews(None, None, None).await.unwrap().len(), 1);
+    assert_eq!(db.subscriptions().await.unwrap().len(), 2);
+
+    db.add_notifications(review).await.unwrap();
+
+    assert_eq!(db.notifications().await.unwrap().len(), 2);
   }
 
   #[tokio::test(flavor = "multi_thread")]
-  async fn update_review() {
+  async fn notify_empty_subscribers() {
     let TestContext { db, .. } = TestContext::new().await;
 
-    db.add_course(Course {
-      id: "MATH240".into(),
-      ..Default::default()
-    })
-    .await
-    .unwrap();
+    let review = Review {
+      content: "foo".into(),
+      course_id: "MATH240".into(),
+      instructors: vec![String::from("bar")],
+      rating: 5,
+      difficulty: 5,
+      user_id: "1".into(),
+      timestamp: DateTime::from(Utc::now()),
+      ..Review::default()
+    };
 
-    db.add_review(Review {
+    db.add_notifications(review).await.unwrap();
+
+    assert_eq!(db.notifications().await.unwrap().len(), 0);
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn dont_notify_review_creator() {
+    let TestContext { db, .. } = TestContext::new().await;
+
+    let review = Review {
       content: "foo".into(),
       course_id: "MATH240".into(),
       instructors: vec![String::from("bar")],
@@ -1926,561 +4310,658 @@ mod tests {
       user_id: "1".into(),
       timestamp: DateTime::from(Utc::now()),
       ..Review::default()
-    })
-    .await
-    .unwrap();
+    };
 
-    let timestamp = DateTime::from(Utc::now());
+    let subscription = Subscription {
+      course_id: "MATH240".into(),
+      user_id: "1".into(),
+      ..Default::default()
+    };
 
-    assert_eq!(
-      db.add_review(Review {
-        content: "bar".into(),
-        course_id: "MATH240".into(),
-        instructors: vec![String::from("foo")],
-        rating: 4,
-        difficulty: 4,
-        user_id: "1".into(),
-        timestamp: timestamp.clone(),
-        ..Review::default()
-      })
-      .await
-      .unwrap()
-      .modified_count,
-      1
-    );
+    db.add_subscription(subscription.clone()).await.unwrap();
 
-    assert_eq!(
-      db.add_review(Review {
-        content: "bar".into(),
-        course_id: "MATH240".into(),
-        instructors: vec![String::from("foo")],
-        rating: 4,
-        difficulty: 4,
-        user_id: "2".into(),
-        ..Default::default()
-      })
-      .await
-      .unwrap()
-      .modified_count,
-      0
-    );
+    let subscription = Subscription {
+      course_id: "MATH240".into(),
+      user_id: "2".into(),
+      ..Default::default()
+    };
+
+    db.add_subscription(subscription.clone()).await.unwrap();
 
-    let review = db.find_review("MATH240", "1").await.unwrap().unwrap();
+    assert_eq!(db.subscriptions().await.unwrap().len(), 2);
 
-    assert_eq!(review.content, "bar");
-    assert_eq!(review.instructors, vec![String::from("foo")]);
-    assert_eq!(review.rating, 4);
-    assert_eq!(review.timestamp, timestamp);
+    db.add_notifications(review).await.unwrap();
+
+    assert_eq!(db.notifications().await.unwrap().len(), 1);
   }
 
   #[tokio::test(flavor = "multi_thread")]
-  async fn delete_review() {
+  async fn delete_subscription() {
     let TestContext { db, .. } = TestContext::new().await;
 
-    db.add_course(Course {
-      id: "MATH240".into(),
-      ..Default::default()
-    })
-    .await
-    .unwrap();
-
-    db.add_review(Review {
-      content: "foo".into(),
+    let subscription = Subscription {
       course_id: "MATH240".into(),
       user_id: "1".into(),
       ..Default::default()
-    })
-    .await
-    .unwrap();
+    };
 
-    assert!(db.delete_review("MATH240", "2").await.is_err());
+    db.add_subscription(subscription.clone()).await.unwrap();
 
-    assert!(db.delete_review("MATH240", "1").await.is_ok());
+    assert_eq!(db.subscriptions().await.unwrap().len(), 1);
 
-    assert_eq!(db.find_review("MATH240", "1").await.unwrap(), None);
+    db.delete_subscription(subscription.clone()).await.unwrap();
+
+    assert_eq!(db.subscriptions().await.unwrap().len(), 0);
   }
 
   #[tokio::test(flavor = "multi_thread")]
-  async fn delete_review_then_add_again() {
+  async fn delete_notification() {
     let TestContext { db, .. } = TestContext::new().await;
 
-    db.add_course(Course {
-      id: "MATH240".into(),
-      ..Default::default()
-    })
-    .await
-    .unwrap();
-
-    db.add_review(Review {
+    let review = Review {
       content: "foo".into(),
+      course_id: "MATH240".into(),
+      instructors: vec![String::from("bar")],
+      rating: 5,
+      difficulty: 5,
+      user_id: "3".into(),
+      timestamp: DateTime::from(Utc::now()),
+      ..Review::default()
+    };
+
+    let subscription = Subscription {
       course_id: "MATH240".into(),
       user_id: "1".into(),
       ..Default::default()
-    })
-    .await
-    .unwrap();
-
-    assert!(db.delete_review("MATH240", "1").await.is_ok());
+    };
 
-    assert!(
-      db.add_review(Review {
-        content: "foo".into(),
-        course_id: "MATH240".into(),
-        user_id: "1".into(),
-        ..Default::default()
-      })
-      .await
-      .is_ok()
-    );
-  }
+    db.add_subscription(subscription.clone()).await.unwrap();
 
-  #[tokio::test(flavor = "multi_thread")]
-  async fn filter_courses_by_subject() {
-    let TestContext { db, db_name } = TestContext::new().await;
+    let subscription = Subscription {
+      course_id: "MATH240".into(),
+      user_id: "2".into(),
+      ..Default::default()
+    };
 
-    let tempdir = TempDir::new(&db_name).unwrap();
+    db.add_subscription(subscription.clone()).await.unwrap();
 
-    let source = tempdir.path().join("courses.json");
+    assert_eq!(db.subscriptions().await.unwrap().len(), 2);
 
-    fs::write(&source, get_content("mix.json")).unwrap();
+    db.add_notifications(review.clone()).await.unwrap();
 
-    db.initialize(InitializeOptions {
-      source,
+    assert_eq!(db.notifications().await.unwrap().len(), 2);
 
-      ..Default::default()
-    })
-    .await
-    .unwrap();
+    db.delete_notification("1", &review.course_id)
+      .await
+      .unwrap();
 
-    let total = db.courses(None, None, None).await.unwrap();
+    assert_eq!(db.get_notifications("1").await.unwrap().len(), 0);
+    assert_eq!(db.notifications().await.unwrap().len(), 1);
+  }
 
-    assert_eq!(total.len(), 314);
+  #[tokio::test(flavor = "multi_thread")]
+  async fn typed_subscriptions_notify_from_their_code_paths() {
+    let TestContext { db, .. } = TestContext::new().await;
 
-    let filtered = db
-      .courses(
+    for (user_id, kind, course_id, instructor, term) in [
+      ("1", SubscriptionKind::Reviews, "COMP202", None, None),
+      (
+        "1",
+        SubscriptionKind::Instructor,
+        "",
+        Some("Adrian Vetta"),
         None,
+      ),
+      (
+        "2",
+        SubscriptionKind::Instructor,
+        "",
+        Some("Adrian Vetta"),
         None,
-        Some(CourseFilter {
-          subjects: Some(vec!["MATH".into()]),
-          ..Default::default()
-        }),
-      )
+      ),
+      (
+        "3",
+        SubscriptionKind::Offerings,
+        "COMP202",
+        None,
+        Some("Summer 2026"),
+      ),
+      ("4", SubscriptionKind::Offerings, "COMP202", None, None),
+      ("5", SubscriptionKind::CourseChanges, "COMP202", None, None),
+      ("6", SubscriptionKind::ReviewActivity, "", None, None),
+    ] {
+      db.add_subscription(Subscription {
+        kind,
+        course_id: course_id.into(),
+        instructor: instructor.map(str::to_string),
+        term: term.map(str::to_string),
+        user_id: user_id.into(),
+      })
       .await
       .unwrap();
-
-    assert!(filtered.len() < total.len());
-
-    for course in filtered {
-      assert_eq!(course.subject, "MATH");
     }
-  }
-
-  #[tokio::test(flavor = "multi_thread")]
-  async fn filter_courses_by_level() {
-    let TestContext { db, db_name } = TestContext::new().await;
-
-    let tempdir = TempDir::new(&db_name).unwrap();
 
-    let source = tempdir.path().join("courses.json");
+    let kinds = async |user_id: &str| {
+      db.get_notifications(user_id)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|notification| (notification.kind, notification.term))
+        .collect::<Vec<_>>()
+    };
 
-    fs::write(&source, get_content("mix.json")).unwrap();
+    let course = Course {
+      id: "COMP202".into(),
+      terms: vec!["Fall 2025".into()],
+      ..Default::default()
+    };
 
-    db.initialize(InitializeOptions {
-      source,
+    db.add_course(course.clone()).await.unwrap();
 
-      ..Default::default()
+    db.add_course(Course {
+      terms: vec!["Fall 2025".into(), "Winter 2026".into()],
+      schedule: Some(vec![Schedule {
+        term: Some("Winter 2026".into()),
+        blocks: None,
+      }]),
+      ..course
     })
     .await
     .unwrap();
 
-    let total = db.courses(None, None, None).await.unwrap();
+    assert_eq!(kinds("3").await, Vec::new());
 
-    assert_eq!(total.len(), 314);
+    assert_eq!(
+      kinds("4").await,
+      vec![(SubscriptionKind::Offerings, Some("Winter 2026".into()))]
+    );
 
-    let filtered = db
-      .courses(
-        None,
-        None,
-        Some(CourseFilter {
-          levels: Some(vec!["100".into()]),
-          ..Default::default()
-        }),
-      )
+    let changes = db.get_notifications("5").await.unwrap();
+
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].kind, SubscriptionKind::CourseChanges);
+    assert_eq!(changes[0].changes[0].field, "schedule");
+
+    let review = Review {
+      course_id: "COMP202".into(),
+      instructors: vec!["Adrian Vetta".into()],
+      rating: 5,
+      difficulty: 3,
+      user_id: "6".into(),
+      timestamp: DateTime::from(Utc::now()),
+      ..Review::default()
+    };
+
+    db.add_review(review.clone()).await.unwrap();
+    db.add_notifications(review).await.unwrap();
+
+    assert_eq!(kinds("1").await, vec![(SubscriptionKind::Reviews, None)]);
+    assert_eq!(kinds("2").await, vec![(SubscriptionKind::Instructor, None)]);
+    assert_eq!(kinds("6").await, Vec::new());
+
+    for (kind, referrer) in [
+      (InteractionKind::Like, "7"),
+      (InteractionKind::Like, "8"),
+      (InteractionKind::Like, "8"),
+      (InteractionKind::Like, "6"),
+    ] {
+      db.add_interaction(Interaction {
+        kind,
+        user_id: "6".into(),
+        course_id: "COMP202".into(),
+        referrer: referrer.into(),
+      })
       .await
       .unwrap();
+    }
 
-    assert!(filtered.len() < total.len());
+    let activity = db.get_notifications("6").await.unwrap();
 
-    for course in filtered {
-      assert!(course.code.starts_with('1'));
-    }
+    assert_eq!(activity.len(), 1);
+    assert_eq!(activity[0].kind, SubscriptionKind::ReviewActivity);
+    assert_eq!(activity[0].review.as_ref().unwrap().likes, 2);
   }
 
   #[tokio::test(flavor = "multi_thread")]
-  async fn filter_courses_by_term() {
-    let TestContext { db, db_name } = TestContext::new().await;
+  async fn notification_preferences_track_digest_state() {
+    let TestContext { db, .. } = TestContext::new().await;
 
-    let tempdir = TempDir::new(&db_name).unwrap();
+    assert_eq!(db.notification_preferences("1").await.unwrap(), None);
+
+    for (user_id, email_frequency) in [
+      ("1", EmailFrequency::Daily),
+      ("2", EmailFrequency::Immediate),
+      ("3", EmailFrequency::Weekly),
+    ] {
+      db.set_notification_preferences(NotificationPreferences {
+        user_id: user_id.into(),
+        email: Some(format!("{user_id}@mail.mcgill.ca")),
+        email_frequency,
+        last_digest_at: None,
+      })
+      .await
+      .unwrap();
+    }
 
-    let source = tempdir.path().join("courses.json");
+    assert_eq!(
+      db.digest_subscribers()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|preferences| preferences.user_id)
+        .collect::<Vec<_>>(),
+      vec!["1", "3"]
+    );
 
-    fs::write(&source, get_content("mix.json")).unwrap();
+    let sent_at = DateTime::from_millis(1_700_000_000_000);
 
-    db.initialize(InitializeOptions {
-      source,
+    db.record_digest("1", sent_at.clone()).await.unwrap();
 
-      ..Default::default()
+    db.set_notification_preferences(NotificationPreferences {
+      user_id: "1".into(),
+      email: Some("other@mail.mcgill.ca".into()),
+      email_frequency: EmailFrequency::Weekly,
+      last_digest_at: None,
     })
     .await
     .unwrap();
 
-    let total = db.courses(None, None, None).await.unwrap();
-
-    assert_eq!(total.len(), 314);
+    assert_eq!(
+      db.notification_preferences("1").await.unwrap(),
+      Some(NotificationPreferences {
+        user_id: "1".into(),
+        email: Some("other@mail.mcgill.ca".into()),
+        email_frequency: EmailFrequency::Weekly,
+        last_digest_at: Some(sent_at),
+      })
+    );
 
-    let filtered = db
-      .courses(
-        None,
-        None,
-        Some(CourseFilter {
-          terms: Some(vec!["Winter".into()]),
-          ..Default::default()
-        }),
-      )
+    for course_id in ["COMP202", "MATH240"] {
+      db.add_subscription(Subscription {
+        course_id: course_id.into(),
+        user_id: "1".into(),
+        ..Default::default()
+      })
       .await
       .unwrap();
 
-    assert!(filtered.len() < total.len());
-
-    for course in filtered {
-      assert!(
-        course
-          .terms
-          .iter()
-          .any(|term| term.starts_with(&"Winter".to_string()))
-      );
-    }
-  }
-
-  #[tokio::test(flavor = "multi_thread")]
-  async fn add_instructors() {
-    let TestContext { db, .. } = TestContext::new().await;
-
-    let instructors = vec![
-      Instructor {
-        name: "foo".into(),
-        term: "Summer 2023".into(),
-        ..Default::default()
-      },
-      Instructor {
-        name: "bar".into(),
-        term: "Summer 2023".into(),
-        ..Default::default()
-      },
-      Instructor {
-        name: "bar".into(),
-        term: "Winter 2023".into(),
-        ..Default::default()
-      },
-    ];
-
-    for instructor in instructors {
-      db.add_instructor(instructor).await.unwrap();
-    }
-
-    assert_eq!(db.instructors().await.unwrap().len(), 2);
-  }
-
-  #[tokio::test(flavor = "multi_thread")]
-  async fn search_instructor_by_name_exact() {
-    let TestContext { db, db_name } = TestContext::new().await;
-
-    let tempdir = TempDir::new(&db_name).unwrap();
+      db.add_notifications(Review {
+        course_id: course_id.into(),
+        user_id: "2".into(),
+        timestamp: DateTime::from(Utc::now()),
+        ..Review::default()
+      })
+      .await
+      .unwrap();
+    }
 
-    let source = tempdir.path().join("courses.json");
+    let undelivered = db.undelivered_notifications("1").await.unwrap();
 
-    fs::write(&source, get_content("search.json")).unwrap();
+    assert_eq!(undelivered.len(), 2);
 
-    db.initialize(InitializeOptions {
-      source,
-      ..Default::default()
-    })
+    db.mark_notifications_emailed(
+      "1",
+      &undelivered
+        .into_iter()
+        .filter(|notification| notification.course_id == "COMP202")
+        .collect::<Vec<_>>(),
+    )
     .await
     .unwrap();
 
-    assert_eq!(db.courses(None, None, None).await.unwrap().len(), 123);
-
-    let results = db.search("Giulia Alberini").await.unwrap();
+    db.update_notification("1", "MATH240", "2", true)
+      .await
+      .unwrap();
 
-    assert_eq!(results.instructors.len(), 1);
+    assert_eq!(db.undelivered_notifications("1").await.unwrap(), Vec::new());
 
-    assert_eq!(results.instructors.first().unwrap().name, "Giulia Alberini");
+    assert!(
+      db.get_notifications("1")
+        .await
+        .unwrap()
+        .iter()
+        .any(|notification| notification.emailed)
+    );
   }
 
   #[tokio::test(flavor = "multi_thread")]
-  async fn review_interaction_flow() {
+  async fn notifications_are_paged_and_managed_by_id() {
     let TestContext { db, .. } = TestContext::new().await;
 
-    db.add_course(Course {
-      id: "MATH240".into(),
-      ..Default::default()
-    })
+    db.insert_notifications(
+      ["COMP202", "COMP250", "MATH240"]
+        .into_iter()
+        .enumerate()
+        .map(|(i, course_id)| Notification {
+          created_at: DateTime::from_millis(i as i64 * 1000),
+          ..Notification::new(
+            "1",
+            Review {
+              course_id: course_id.into(),
+              user_id: "2".into(),
+              ..Review::default()
+            },
+          )
+        })
+        .collect(),
+    )
     .await
     .unwrap();
 
-    let review = Review {
-      content: "foo".into(),
-      course_id: "MATH240".into(),
-      user_id: "1".into(),
-      ..Default::default()
+    let course_ids = |notifications: Vec<Notification>| {
+      notifications
+        .into_iter()
+        .map(|notification| notification.course_id)
+        .collect::<Vec<_>>()
     };
 
-    db.add_review(review.clone()).await.unwrap();
-
-    assert_eq!(db.reviews(None, None, None).await.unwrap().len(), 1);
-
-    db.add_interaction(Interaction {
-      kind: InteractionKind::Like,
-      course_id: review.course_id.clone(),
-      user_id: review.user_id.clone(),
-      referrer: "10".into(),
-    })
-    .await
-    .unwrap();
+    let notifications = db.get_notifications("1").await.unwrap();
 
-    let interactions = db
-      .interactions_for_review(&review.course_id, &review.user_id)
-      .await
-      .unwrap();
+    assert_eq!(
+      course_ids(notifications.clone()),
+      vec!["MATH240", "COMP250", "COMP202"]
+    );
 
-    assert_eq!(interactions.len(), 1);
-    assert_eq!(interactions.first().unwrap().kind, InteractionKind::Like);
+    assert_eq!(
+      course_ids(
+        db.notifications_page("1", Some(1), Some(1), false)
+          .await
+          .unwrap()
+      ),
+      vec!["COMP250"]
+    );
 
-    let review = db
-      .find_review(&review.course_id, &review.user_id)
-      .await
-      .unwrap()
-      .unwrap();
-    assert_eq!(review.likes, 1);
+    assert_eq!(
+      course_ids(
+        db.notifications_page("1", Some(0), None, false)
+          .await
+          .unwrap()
+      ),
+      vec!["MATH240"]
+    );
 
-    db.add_interaction(Interaction {
-      kind: InteractionKind::Dislike,
-      course_id: review.course_id.clone(),
-      user_id: review.user_id.clone(),
-      referrer: "10".into(),
-    })
-    .await
-    .unwrap();
+    assert_eq!(
+      db.notifications_page("1", Some(i64::MAX), None, false)
+        .await
+        .unwrap()
+        .len(),
+      3
+    );
 
-    let interactions = db
-      .interactions_for_review(&review.course_id, &review.user_id)
+    let result = db
+      .update_notification_by_id("1", &notifications[0].id, true)
       .await
       .unwrap();
 
-    assert_eq!(interactions.len(), 1);
-    assert_eq!(interactions.first().unwrap().kind, InteractionKind::Dislike);
-
-    let review = db
-      .find_review(&review.course_id, &review.user_id)
-      .await
-      .unwrap()
-      .unwrap();
-    assert_eq!(review.likes, -1);
+    assert_eq!(result.matched_count, 1);
 
-    db.delete_interaction(&review.course_id, &review.user_id, "10")
-      .await
-      .unwrap();
+    assert_eq!(
+      db.update_notification_by_id("2", &notifications[1].id, true)
+        .await
+        .unwrap()
+        .matched_count,
+      0
+    );
 
     assert_eq!(
-      db.interactions_for_review(&review.course_id, &review.user_id)
+      db.update_notification_by_id("1", "not-an-id", true)
         .await
         .unwrap()
-        .len(),
+        .matched_count,
       0
     );
 
-    let review = db
-      .find_review(&review.course_id, &review.user_id)
-      .await
-      .unwrap()
-      .unwrap();
-    assert_eq!(review.likes, 0);
-  }
+    assert_eq!(db.notification_counts("1").await.unwrap(), (3, 2));
 
-  #[tokio::test(flavor = "multi_thread")]
-  async fn subscription_flow() {
-    let TestContext { db, .. } = TestContext::new().await;
+    assert_eq!(
+      course_ids(db.notifications_page("1", None, None, true).await.unwrap()),
+      vec!["COMP250", "COMP202"]
+    );
 
-    let subscription = Subscription {
-      course_id: "MATH240".into(),
-      user_id: "1".into(),
-    };
+    assert_eq!(
+      db.delete_notification_by_id("1", &notifications[2].id)
+        .await
+        .unwrap()
+        .deleted_count,
+      1
+    );
 
-    db.add_subscription(subscription.clone()).await.unwrap();
+    assert_eq!(
+      db.mark_all_notifications_seen("1")
+        .await
+        .unwrap()
+        .modified_count,
+      1
+    );
 
-    assert_eq!(db.subscriptions().await.unwrap().len(), 1);
+    assert_eq!(db.notification_counts("1").await.unwrap(), (2, 0));
 
-    db.delete_subscription(Subscription {
-      course_id: subscription.course_id,
-      user_id: subscription.user_id,
-    })
-    .await
-    .unwrap();
+    assert_eq!(db.clear_notifications("1").await.unwrap().deleted_count, 2);
 
-    assert_eq!(db.subscriptions().await.unwrap().len(), 0);
+    assert_eq!(db.notification_counts("1").await.unwrap(), (0, 0));
   }
 
   #[tokio::test(flavor = "multi_thread")]
-  async fn notify_many_subscribers() {
+  async fn notifications_are_published_and_replayed() {
     let TestContext { db, .. } = TestContext::new().await;
 
-    let review = Review {
-      content: "foo".into(),
-      course_id: "MATH240".into(),
-      instructors: vec![String::from("bar")],
-      rating: 5,
-      difficulty: 5,
-      user_id: "3".into(),
-      timestamp: DateTime::from(Utc::now()),
-      ..Review::default()
-    };
+    let mut receiver = db.subscribe_to_notifications();
 
-    let subscription = Subscription {
-      course_id: "MATH240".into(),
-      user_id: "1".into(),
-    };
+    let notifications = ["COMP202", "COMP250", "MATH240"]
+      .into_iter()
+      .enumerate()
+      .map(|(i, course_id)| Notification {
+        created_at: DateTime::from_millis(i as i64 * 1000),
+        ..Notification::about_course(SubscriptionKind::Reviews, "1", course_id)
+      })
+      .collect::<Vec<_>>();
 
-    db.add_subscription(subscription.clone()).await.unwrap();
+    db.insert_notifications(notifications.clone())
+      .await
+      .unwrap();
 
-    let subscription = Subscription {
-      course_id: "MATH240".into(),
-      user_id: "2".into(),
+    for notification in &notifications {
+      assert_eq!(receiver.recv().await.unwrap(), *notification);
+    }
+
+    let replayed = |notifications: Vec<Notification>| {
+      notifications
+        .into_iter()
+        .map(|notification| notification.course_id)
+        .collect::<Vec<_>>()
     };
 
-    db.add_subscription(subscription.clone()).await.unwrap();
+    assert_eq!(
+      replayed(
+        db.notifications_after("1", &notifications[0].id)
+          .await
+          .unwrap()
+      ),
+      vec!["COMP250", "MATH240"]
+    );
 
-    assert_eq!(db.subscriptions().await.unwrap().len(), 2);
+    assert_eq!(
+      replayed(
+        db.notifications_after("2", &notifications[0].id)
+          .await
+          .unwrap()
+      ),
+      Vec::<String>::new()
+    );
 
-    db.add_notifications(review).await.unwrap();
+    assert_eq!(
+      replayed(db.notifications_after("1", "not-an-id").await.unwrap()),
+      Vec::<String>::new()
+    );
 
-    assert_eq!(db.notifications().await.unwrap().len(), 2);
+    assert_eq!(
+      replayed(
+        db.notifications_since("1", DateTime::from_millis(1000))
+          .await
+          .unwrap()
+      ),
+      vec!["COMP250", "MATH240"]
+    );
+
+    db.delete_notification_by_id("1", &notifications[1].id)
+      .await
+      .unwrap();
+
+    assert_eq!(
+      replayed(
+        db.notifications_after("1", &notifications[1].id)
+          .await
+          .unwrap()
+      ),
+      Vec::<String>::new()
+    );
   }
 
   #[tokio::test(flavor = "multi_thread")]
-  async fn notify_empty_subscribers() {
+  async fn webhook_events_are_queued_for_interested_webhooks() {
     let TestContext { db, .. } = TestContext::new().await;
 
-    let review = Review {
-      content: "foo".into(),
-      course_id: "MATH240".into(),
-      instructors: vec![String::from("bar")],
-      rating: 5,
-      difficulty: 5,
-      user_id: "1".into(),
-      timestamp: DateTime::from(Utc::now()),
-      ..Review::default()
-    };
+    let everything = Webhook::new("https://a.example", "a", Vec::new());
 
-    db.add_notifications(review).await.unwrap();
+    let deletions = Webhook::new(
+      "https://b.example",
+      "b",
+      vec![WebhookEventKind::ReviewDeleted],
+    );
 
-    assert_eq!(db.notifications().await.unwrap().len(), 0);
-  }
+    db.add_webhook(everything.clone()).await.unwrap();
+    db.add_webhook(deletions.clone()).await.unwrap();
 
-  #[tokio::test(flavor = "multi_thread")]
-  async fn dont_notify_review_creator() {
-    let TestContext { db, .. } = TestContext::new().await;
+    assert_eq!(
+      db.webhooks().await.unwrap(),
+      vec![everything.clone(), deletions.clone()]
+    );
 
-    let review = Review {
-      content: "foo".into(),
-      course_id: "MATH240".into(),
-      instructors: vec![String::from("bar")],
-      rating: 5,
-      difficulty: 5,
-      user_id: "1".into(),
-      timestamp: DateTime::from(Utc::now()),
-      ..Review::default()
+    let course = Course {
+      id: "COMP202".into(),
+      title: "Foundations of Programming".into(),
+      ..Default::default()
     };
 
-    let subscription = Subscription {
-      course_id: "MATH240".into(),
-      user_id: "1".into(),
-    };
+    db.add_course(course.clone()).await.unwrap();
+    db.add_course(course.clone()).await.unwrap();
 
-    db.add_subscription(subscription.clone()).await.unwrap();
+    db.add_course(Course {
+      title: "Foundations of Computing".into(),
+      ..course
+    })
+    .await
+    .unwrap();
 
-    let subscription = Subscription {
-      course_id: "MATH240".into(),
-      user_id: "2".into(),
+    let review = Review {
+      course_id: "COMP202".into(),
+      user_id: "1".into(),
+      ..Default::default()
     };
 
-    db.add_subscription(subscription.clone()).await.unwrap();
-
-    assert_eq!(db.subscriptions().await.unwrap().len(), 2);
-
-    db.add_notifications(review).await.unwrap();
+    db.add_review(review.clone()).await.unwrap();
 
-    assert_eq!(db.notifications().await.unwrap().len(), 1);
-  }
+    db.add_review(Review {
+      content: "Edited".into(),
+      ..review
+    })
+    .await
+    .unwrap();
 
-  #[tokio::test(flavor = "multi_thread")]
-  async fn delete_subscription() {
-    let TestContext { db, .. } = TestContext::new().await;
+    db.delete_review("COMP202", "1").await.unwrap();
 
-    let subscription = Subscription {
-      course_id: "MATH240".into(),
-      user_id: "1".into(),
+    let events = |deliveries: Vec<WebhookDelivery>| {
+      deliveries
+        .into_iter()
+        .map(|delivery| delivery.event)
+        .collect::<Vec<_>>()
     };
 
-    db.add_subscription(subscription.clone()).await.unwrap();
+    assert_eq!(
+      events(db.webhook_deliveries(&everything.id, None).await.unwrap()),
+      vec![
+        WebhookEventKind::ReviewDeleted,
+        WebhookEventKind::ReviewUpdated,
+        WebhookEventKind::ReviewCreated,
+        WebhookEventKind::CourseUpdated,
+      ]
+    );
+
+    let deliveries = db.webhook_deliveries(&deletions.id, None).await.unwrap();
 
-    assert_eq!(db.subscriptions().await.unwrap().len(), 1);
+    assert_eq!(
+      events(deliveries.clone()),
+      vec![WebhookEventKind::ReviewDeleted]
+    );
 
-    db.delete_subscription(subscription.clone()).await.unwrap();
+    let payload =
+      serde_json::from_str::<serde_json::Value>(&deliveries[0].payload)
+        .unwrap();
 
-    assert_eq!(db.subscriptions().await.unwrap().len(), 0);
-  }
+    assert_eq!(payload["type"], "review.deleted");
+    assert_eq!(payload["review"]["content"], "Edited");
 
-  #[tokio::test(flavor = "multi_thread")]
-  async fn delete_notification() {
-    let TestContext { db, .. } = TestContext::new().await;
+    let course_update = db
+      .webhook_deliveries(&everything.id, None)
+      .await
+      .unwrap()
+      .pop()
+      .unwrap();
 
-    let review = Review {
-      content: "foo".into(),
-      course_id: "MATH240".into(),
-      instructors: vec![String::from("bar")],
-      rating: 5,
-      difficulty: 5,
-      user_id: "3".into(),
-      timestamp: DateTime::from(Utc::now()),
-      ..Review::default()
-    };
+    let payload =
+      serde_json::from_str::<serde_json::Value>(&course_update.payload)
+        .unwrap();
 
-    let subscription = Subscription {
-      course_id: "MATH240".into(),
-      user_id: "1".into(),
-    };
+    assert_eq!(payload["course"]["title"], "Foundations of Computing");
 
-    db.add_subscription(subscription.clone()).await.unwrap();
+    let due = db.due_webhook_deliveries(Utc::now().into()).await.unwrap();
 
-    let subscription = Subscription {
-      course_id: "MATH240".into(),
-      user_id: "2".into(),
-    };
+    assert_eq!(due.len(), 5);
 
-    db.add_subscription(subscription.clone()).await.unwrap();
+    assert_eq!(
+      db.due_webhook_deliveries(DateTime::from_millis(0))
+        .await
+        .unwrap(),
+      Vec::new()
+    );
 
-    assert_eq!(db.subscriptions().await.unwrap().len(), 2);
+    db.update_webhook_delivery(WebhookDelivery {
+      status: WebhookDeliveryStatus::Succeeded,
+      attempts: 1,
+      response_status: Some(200),
+      ..deliveries[0].clone()
+    })
+    .await
+    .unwrap();
 
-    db.add_notifications(review.clone()).await.unwrap();
+    assert_eq!(
+      db.due_webhook_deliveries(Utc::now().into())
+        .await
+        .unwrap()
+        .len(),
+      4
+    );
 
-    assert_eq!(db.notifications().await.unwrap().len(), 2);
+    assert_eq!(
+      db.webhook_deliveries(&deletions.id, None).await.unwrap()[0]
+        .response_status,
+      Some(200)
+    );
 
-    db.delete_notification("1", &review.course_id)
-      .await
-      .unwrap();
+    assert_eq!(
+      db.delete_webhook(&deletions.id)
+        .await
+        .unwrap()
+        .deleted_count,
+      1
+    );
 
-    assert_eq!(db.get_notifications("1").await.unwrap().len(), 0);
-    assert_eq!(db.notifications().await.unwrap().len(), 1);
+    assert_eq!(db.webhook(&deletions.id).await.unwrap(), None);
+
+    assert_eq!(
+      db.webhook_deliveries(&deletions.id, None).await.unwrap(),
+      Vec::new()
+    );
   }
 
   #[tokio::test(flavor = "multi_thread")]
@@ -2625,4 +5106,322 @@ mod tests {
 
     assert_eq!(db.course_count().await.unwrap(), 1);
   }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn exam_seeding_is_accurate() {
+    let TestContext { db, db_name } = TestContext::new().await;
+
+    let tempdir = TempDir::new(&db_name).unwrap();
+
+    let source = tempdir.path().join("exams.json");
+
+    fs::write(&source, get_content("exams.json")).unwrap();
+
+    db.initialize(InitializeOptions {
+      source,
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    let exams = db.exams(None).await.unwrap();
+
+    assert_eq!(exams.len(), 4);
+
+    assert_eq!(
+      exams
+        .iter()
+        .map(|exam| (exam.course_id.as_str(), exam.start_time.as_str()))
+        .collect::<Vec<_>>(),
+      vec![
+        ("COMP202", "2000-12-09T18:30:00"),
+        ("COMP202", "2099-12-10T14:00:00"),
+        ("MATH240", "2099-12-12T09:00:00"),
+        ("COMP202", "2099-12-15T09:00:00"),
+      ]
+    );
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn seeding_reports_progress() {
+    let TestContext { db, db_name } = TestContext::new().await;
+
+    let tempdir = TempDir::new(&db_name).unwrap();
+
+    fs::write(tempdir.path().join("exams.json"), get_content("exams.json"))
+      .unwrap();
+
+    fs::write(tempdir.path().join("mini.json"), get_content("mini.json"))
+      .unwrap();
+
+    let progress = Arc::new(model::SeedProgress::default());
+
+    db.initialize(InitializeOptions {
+      source: tempdir.path().to_path_buf(),
+      progress: progress.clone(),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(progress.total(), 2);
+    assert_eq!(progress.completed(), 2);
+    assert_eq!(
+      progress.seeded(),
+      vec![
+        tempdir.path().join("exams.json"),
+        tempdir.path().join("mini.json")
+      ]
+    );
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn up_to_date_seed_files_are_skipped() {
+    let TestContext { db, db_name } = TestContext::new().await;
+
+    let tempdir = TempDir::new(&db_name).unwrap();
+
+    fs::write(tempdir.path().join("exams.json"), get_content("exams.json"))
+      .unwrap();
+
+    fs::write(tempdir.path().join("mini.json"), get_content("mini.json"))
+      .unwrap();
+
+    let progress = Arc::new(model::SeedProgress::default());
+
+    db.initialize(InitializeOptions {
+      source: tempdir.path().to_path_buf(),
+      progress: progress.clone(),
+      up_to_date: HashSet::from([tempdir.path().join("exams.json")]),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(progress.seeded(), vec![tempdir.path().join("mini.json")]);
+    assert!(db.exams(None).await.unwrap().is_empty());
+    assert_eq!(db.courses(None, None, None).await.unwrap().len(), 3);
+
+    let progress = Arc::new(model::SeedProgress::default());
+
+    db.initialize(InitializeOptions {
+      source: tempdir.path().to_path_buf(),
+      progress: progress.clone(),
+      up_to_date: HashSet::from([
+        tempdir.path().join("exams.json"),
+        tempdir.path().join("mini.json"),
+      ]),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    assert!(progress.seeded().is_empty());
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn cancelled_seeding_stops_between_seed_files() {
+    let TestContext { db, db_name } = TestContext::new().await;
+
+    let tempdir = TempDir::new(&db_name).unwrap();
+
+    let source = tempdir.path().join("exams.json");
+
+    fs::write(&source, get_content("exams.json")).unwrap();
+
+    let progress = Arc::new(model::SeedProgress::default());
+
+    progress.cancel();
+
+    assert!(matches!(
+      db.initialize(InitializeOptions {
+        source,
+        progress: progress.clone(),
+        ..Default::default()
+      })
+      .await,
+      Err(Error::SeedingCancelled)
+    ));
+
+    assert_eq!(progress.completed(), 0);
+    assert!(db.exams(None).await.unwrap().is_empty());
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn exam_seeding_replaces_term() {
+    let TestContext { db, .. } = TestContext::new().await;
+
+    db.set_exams(
+      "Fall 2099",
+      vec![
+        FinalExam {
+          course_id: "COMP202".into(),
+          term: "Fall 2099".into(),
+          ..Default::default()
+        },
+        FinalExam {
+          course_id: "MATH240".into(),
+          term: "Fall 2099".into(),
+          ..Default::default()
+        },
+      ],
+    )
+    .await
+    .unwrap();
+
+    db.set_exams(
+      "Fall 2099",
+      vec![FinalExam {
+        course_id: "MATH240".into(),
+        term: "Fall 2099".into(),
+        ..Default::default()
+      }],
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+      db.exams(None).await.unwrap(),
+      vec![FinalExam {
+        course_id: "MATH240".into(),
+        term: "Fall 2099".into(),
+        ..Default::default()
+      }]
+    );
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn filter_exams() {
+    let TestContext { db, db_name } = TestContext::new().await;
+
+    let tempdir = TempDir::new(&db_name).unwrap();
+
+    let source = tempdir.path().join("exams.json");
+
+    fs::write(&source, get_content("exams.json")).unwrap();
+
+    db.initialize(InitializeOptions {
+      source,
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(
+      db.exams(Some(ExamFilter {
+        term: Some("Fall 2099".into()),
+        subject: Some("COMP".into()),
+        ..Default::default()
+      }))
+      .await
+      .unwrap()
+      .len(),
+      2
+    );
+
+    assert_eq!(
+      db.exams(Some(ExamFilter {
+        subject: Some("math".into()),
+        ..Default::default()
+      }))
+      .await
+      .unwrap()
+      .len(),
+      1
+    );
+
+    assert_eq!(
+      db.find_exams_by_course_id("COMP202").await.unwrap().len(),
+      3
+    );
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn next_exam_skips_past_exams() {
+    let TestContext { db, db_name } = TestContext::new().await;
+
+    let tempdir = TempDir::new(&db_name).unwrap();
+
+    let source = tempdir.path().join("exams.json");
+
+    fs::write(&source, get_content("exams.json")).unwrap();
+
+    db.initialize(InitializeOptions {
+      source,
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    let next = db.next_exam("COMP202").await.unwrap().unwrap();
+
+    assert_eq!(next.section, "001");
+    assert_eq!(next.start_time, "2099-12-10T14:00:00");
+
+    assert_eq!(db.next_exam("COMP250").await.unwrap(), None);
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn exam_filters_combine_course_and_subject() {
+    let TestContext { db, db_name } = TestContext::new().await;
+
+    let tempdir = TempDir::new(&db_name).unwrap();
+
+    let source = tempdir.path().join("exams.json");
+
+    fs::write(&source, get_content("exams.json")).unwrap();
+
+    db.initialize(InitializeOptions {
+      source,
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    let exams = |course_id: &str, subject: &str| {
+      db.exams(Some(ExamFilter {
+        course_id: Some(course_id.into()),
+        subject: Some(subject.into()),
+        ..Default::default()
+      }))
+    };
+
+    assert_eq!(exams("COMP202", "COMP").await.unwrap().len(), 3);
+    assert!(exams("COMP202", "MATH").await.unwrap().is_empty());
+  }
+
+  #[test]
+  fn buildings_come_from_client_assets() {
+    let buildings = Initializer::buildings().unwrap();
+
+    assert_eq!(buildings.len(), 69);
+
+    let campus = |code: &str| {
+      buildings
+        .iter()
+        .find(|building| building.code == code)
+        .unwrap()
+        .campus
+        .clone()
+    };
+
+    assert_eq!(campus("BURN"), Some("Downtown".into()));
+    assert_eq!(campus("AGTECH"), Some("Macdonald".into()));
+    assert_eq!(campus("BARTON"), Some("Macdonald".into()));
+    assert_eq!(campus("FIELD"), None);
+  }
+
+  #[test]
+  fn campus_time_follows_daylight_saving() {
+    let campus = |utc: &str| {
+      campus_time(utc.parse::<chrono::DateTime<Utc>>().unwrap()).to_string()
+    };
+
+    assert_eq!(campus("2099-12-11T03:30:00Z"), "2099-12-10 22:30:00");
+    assert_eq!(campus("2025-07-01T03:30:00Z"), "2025-06-30 23:30:00");
+    assert_eq!(campus("2025-03-09T06:59:00Z"), "2025-03-09 01:59:00");
+    assert_eq!(campus("2025-03-09T07:00:00Z"), "2025-03-09 03:00:00");
+    assert_eq!(campus("2025-11-02T05:59:00Z"), "2025-11-02 01:59:00");
+    assert_eq!(campus("2025-11-02T06:00:00Z"), "2025-11-02 01:00:00");
+  }
 }
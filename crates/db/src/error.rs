@@ -18,6 +18,8 @@ pub enum Error {
   MongoDB(#[from] mongodb::error::Error),
   #[error("review not found")]
   ReviewNotFound,
+  #[error("seeding was cancelled")]
+  SeedingCancelled,
   #[error("integer conversion error: {0}")]
   TryFromInt(#[from] TryFromIntError),
 }
@@ -7,13 +7,70 @@ pub(crate) struct Initializer {
 }
 
 impl Initializer {
+  const AVERAGE_COLLECTION: &'static str = "averages";
   const COURSE_COLLECTION: &'static str = "courses";
+  const COURSE_VERSION_COLLECTION: &'static str = "course_versions";
+  const EXAM_COLLECTION: &'static str = "exams";
   const INSTRUCTOR_COLLECTION: &'static str = "instructors";
+  const NOTIFICATION_COLLECTION: &'static str = "notifications";
+  const NOTIFICATION_PREFERENCES_COLLECTION: &'static str =
+    "notification_preferences";
+  const WEBHOOK_DELIVERY_COLLECTION: &'static str = "webhook_deliveries";
+
+  /// How long seen notifications are kept before MongoDB expires them.
+  const SEEN_NOTIFICATION_TTL: Duration =
+    Duration::from_secs(90 * 24 * 60 * 60);
+
+  /// Building names by code, shared with the client since they rarely change.
+  const BUILDING_CODES: &'static str =
+    include_str!("../../../client/src/assets/building-codes.json");
+
+  /// Building coordinates by code, or null where they aren't known.
+  const BUILDING_COORDINATES: &'static str =
+    include_str!("../../../client/src/assets/building-coordinates.json");
+
+  /// Buildings on the Macdonald campus. Every other building with known
+  /// coordinates is downtown.
+  const MACDONALD_BUILDINGS: &'static [&'static str] = &[
+    "AGTECH", "BARTON", "CENTEN", "FARM", "PARA", "RAYMND", "STHALL",
+  ];
 
   pub(crate) fn new(db: Db, options: InitializeOptions) -> Self {
     Self { db, options }
   }
 
+  /// Campus buildings, from the client's building names and coordinates.
+  pub(crate) fn buildings() -> Result<Vec<Building>> {
+    let names =
+      serde_json::from_str::<BTreeMap<String, String>>(Self::BUILDING_CODES)?;
+
+    let mut coordinates = serde_json::from_str::<
+      HashMap<String, Option<Coordinates>>,
+    >(Self::BUILDING_COORDINATES)?;
+
+    Ok(
+      names
+        .into_iter()
+        .map(|(code, name)| {
+          let coordinates = coordinates.remove(&code).flatten();
+
+          let campus = if Self::MACDONALD_BUILDINGS.contains(&code.as_str()) {
+            Some("Macdonald".into())
+          } else {
+            coordinates.as_ref().map(|_| "Downtown".into())
+          };
+
+          Building {
+            code,
+            name,
+            coordinates,
+            campus,
+          }
+        })
+        .collect(),
+    )
+  }
+
   pub(crate) async fn run(&self) -> Result {
     self.index().await?;
     self.seed().await?;
@@ -65,22 +122,151 @@ impl Initializer {
       )
       .await?;
 
+    info!("Building course average sort index...");
+
+    self
+      .db
+      .create_index_with_options::<Course>(
+        Self::COURSE_COLLECTION,
+        doc! { "meanAverage": -1 },
+        IndexOptions::default(),
+      )
+      .await?;
+
+    info!("Building course version index...");
+
+    self
+      .db
+      .create_index_with_options::<CourseVersion>(
+        Self::COURSE_VERSION_COLLECTION,
+        doc! { "courseId": 1, "catalogueYear": 1 },
+        IndexOptions::builder().unique(true).build(),
+      )
+      .await?;
+
+    info!("Building course average index...");
+
+    self
+      .db
+      .create_index_with_options::<CourseAverage>(
+        Self::AVERAGE_COLLECTION,
+        doc! { "courseId": 1, "term": 1 },
+        IndexOptions::builder().unique(true).build(),
+      )
+      .await?;
+
     info!("Building instructor index...");
 
     self
       .db
-      .create_index::<Instructor>(
+      .create_index::<InstructorProfile>(
         Self::INSTRUCTOR_COLLECTION,
         doc! { "name": "text", "nameNgrams": "text" },
         doc! { "name": 10, "nameNgrams": 4 },
       )
       .await?;
 
+    info!("Building exam index...");
+
+    self
+      .db
+      .create_index_with_options::<FinalExam>(
+        Self::EXAM_COLLECTION,
+        doc! { "courseId": 1, "term": 1 },
+        IndexOptions::default(),
+      )
+      .await?;
+
+    info!("Building notification indices...");
+
+    self.db.backfill_notifications().await?;
+
+    self
+      .db
+      .create_index_with_options::<Notification>(
+        Self::NOTIFICATION_COLLECTION,
+        doc! { "userId": 1, "createdAt": -1 },
+        IndexOptions::default(),
+      )
+      .await?;
+
+    self
+      .db
+      .create_index_with_options::<Notification>(
+        Self::NOTIFICATION_COLLECTION,
+        doc! { "createdAt": 1 },
+        IndexOptions::builder()
+          .name(String::from("seen_notification_ttl"))
+          .expire_after(Self::SEEN_NOTIFICATION_TTL)
+          .partial_filter_expression(doc! { "seen": true })
+          .build(),
+      )
+      .await?;
+
+    info!("Building notification preferences index...");
+
+    self
+      .db
+      .create_index_with_options::<NotificationPreferences>(
+        Self::NOTIFICATION_PREFERENCES_COLLECTION,
+        doc! { "userId": 1 },
+        IndexOptions::builder().unique(true).build(),
+      )
+      .await?;
+
+    info!("Building webhook delivery indices...");
+
+    self
+      .db
+      .create_index_with_options::<WebhookDelivery>(
+        Self::WEBHOOK_DELIVERY_COLLECTION,
+        doc! { "status": 1, "nextAttemptAt": 1 },
+        IndexOptions::default(),
+      )
+      .await?;
+
+    self
+      .db
+      .create_index_with_options::<WebhookDelivery>(
+        Self::WEBHOOK_DELIVERY_COLLECTION,
+        doc! { "webhookId": 1, "createdAt": -1 },
+        IndexOptions::default(),
+      )
+      .await?;
+
     info!("All indices complete.");
 
     Ok(())
   }
 
+  /// Attaches the buildings and rooms each block meets in, leaving out
+  /// buildings we don't know about.
+  fn locate(course: Course, buildings: &HashSet<String>) -> Course {
+    Course {
+      schedule: course.schedule.map(|schedules| {
+        schedules
+          .into_iter()
+          .map(|mut schedule| {
+            for block in schedule.blocks.iter_mut().flatten() {
+              block.rooms = block
+                .parse_location()
+                .into_iter()
+                .filter(|(code, _)| buildings.contains(*code))
+                .map(|(code, room)| Room {
+                  building: code.into(),
+                  room: room.map(String::from),
+                })
+                .collect();
+            }
+
+            schedule
+          })
+          .collect()
+      }),
+      ..course
+    }
+  }
+
   async fn populate<
     Item: Clone + Send,
     Fut: Future<Output = Result> + Send + 'static,
@@ -132,24 +318,106 @@ impl Initializer {
       }
     }
 
+    // Later course files take precedence over earlier ones, so every course
+    // file after one that changed is seeded again too.
+    let mut courses_changed = false;
+
+    seeds.retain(|seed| match seed {
+      Seed::Courses(_) if self.options.skip_courses => false,
+      Seed::Reviews(_) if self.options.skip_reviews => false,
+      Seed::Courses((path, _)) => {
+        courses_changed |= !self.options.up_to_date.contains(path);
+        courses_changed
+      }
+      seed => !self.options.up_to_date.contains(seed.path()),
+    });
+
+    if seeds.is_empty() {
+      info!("Every seed file is up to date.");
+      return Ok(());
+    }
+
+    if !self.options.skip_courses {
+      self.db.purge_legacy_instructors().await?;
+    }
+
+    info!("Seeding buildings...");
+
+    let buildings = Self::buildings()?;
+
+    let codes = buildings
+      .iter()
+      .map(|building| building.code.clone())
+      .collect::<HashSet<_>>();
+
+    for building in buildings {
+      self.db.add_building(building).await?;
+    }
+
+    self.options.progress.start(seeds.len());
+
     for seed in seeds {
+      let path = seed.path().clone();
+
+      if self.options.progress.is_cancelled() {
+        warn!(
+          "Seeding cancelled after {} of {} seed files",
+          self.options.progress.completed(),
+          self.options.progress.total()
+        );
+
+        return Err(Error::SeedingCancelled);
+      }
+
       match seed {
-        Seed::Courses((path, courses)) if !self.options.skip_courses => {
+        Seed::Courses((path, courses)) => {
           info!("Seeding courses from {}...", path.display());
 
-          let runner = |db: Db, item: Course| async move {
-            db.add_course(item.clone()).await?;
+          let catalogue_year = catalogue_year(&path.to_string_lossy());
+
+          let runner = |db: Db, item: Course| {
+            let catalogue_year = catalogue_year
+              .clone()
+              .or_else(|| utils::catalogue_year(&item.url));
+
+            let item = Self::locate(item, &codes);
 
-            for instructor in item.instructors {
-              db.add_instructor(instructor).await?;
+            async move {
+              db.add_course(item.clone()).await?;
+
+              if let Some(catalogue_year) = catalogue_year {
+                db.add_course_version(&catalogue_year, &item).await?;
+              }
+
+              for instructor in item.instructors {
+                db.add_instructor(&item.id, instructor).await?;
+              }
+
+              Ok(())
             }
+          };
+
+          self.populate(courses, runner).await?;
+        }
+        Seed::Averages((path, averages)) => {
+          info!("Seeding course averages from {}...", path.display());
 
+          let runner = |db: Db, item: CourseAverage| async move {
+            db.add_course_average(item).await?;
             Ok(())
           };
 
-          self.populate(courses, runner).await?;
+          self.populate(averages, runner).await?;
+        }
+        Seed::Exams((path, schedules)) => {
+          info!("Seeding exams from {}...", path.display());
+
+          for schedule in schedules {
+            let term = schedule.term.clone();
+            self.db.set_exams(&term, schedule.final_exams()).await?;
+          }
         }
-        Seed::Reviews((path, reviews)) if !self.options.skip_reviews => {
+        Seed::Reviews((path, reviews)) => {
           info!("Seeding reviews from {}...", path.display());
 
           let runner = |db: Db, item: Review| async move {
@@ -165,10 +433,13 @@ impl Initializer {
             path.display()
           );
         }
-        _ => continue,
       }
+
+      self.options.progress.advance(path);
     }
 
+    self.db.refresh_course_averages().await?;
+
     info!("Seeding complete.");
 
     Ok(())
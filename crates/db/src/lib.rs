@@ -1,6 +1,7 @@
 use {
   bson::Bson,
-  chrono::{Datelike, TimeZone, Utc},
+  chrono::{Datelike, NaiveDateTime, TimeZone, Utc},
+  chrono_tz::America::Montreal,
   combine::Combine,
   futures::Future,
   futures::FutureExt,
@@ -8,21 +9,36 @@ use {
   itertools::Itertools,
   lazy_static::lazy_static,
   model::{
-    Course, CourseFilter, CourseSortType, InitializeOptions, Instructor,
-    Interaction, InteractionKind, Notification, Review, ReviewFilter,
-    SearchResults, Subscription,
+    Building, Coordinates, Course, CourseAverage, CourseFilter, CourseSortType,
+    CourseVersion, DateTime, EmailFrequency, ExamFilter, ExamSchedule,
+    FieldChange, FinalExam, GradeTrend, InitializeOptions, Instructor,
+    InstructorProfile, Interaction, InteractionKind, Notification,
+    NotificationPreferences, Review, ReviewFilter, ReviewStats, Room,
+    SearchResults, Subscription, SubscriptionKind, TeachingAssignment, Webhook,
+    WebhookDelivery, WebhookDeliveryStatus, WebhookEvent, WebhookEventKind,
+    review_term,
   },
   mongodb::{
     Client, Cursor, Database, IndexModel,
     bson::{Document, doc},
     options::UpdateModifications,
-    options::{ClientOptions, FindOptions, IndexOptions, UpdateOptions},
+    options::{
+      ClientOptions, FindOneOptions, FindOptions, IndexOptions, ReplaceOptions,
+      ReturnDocument, UpdateOptions,
+    },
     results::{CreateIndexResult, DeleteResult, InsertOneResult, UpdateResult},
   },
   mongodb::{ClientSession, Collection, options::FindOneAndUpdateOptions},
   serde::{Serialize, de::DeserializeOwned},
-  std::{collections::HashSet, env, fs, num::TryFromIntError, path::PathBuf},
-  tokio::task::JoinError,
+  std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fs,
+    num::TryFromIntError,
+    path::PathBuf,
+    sync::Arc,
+    time::Duration,
+  },
+  tokio::{sync::broadcast, task::JoinError},
   tracing::{info, warn},
   {initializer::Initializer, seed::Seed, str_ext::StrExt, utils::*},
 };
@@ -30,7 +46,7 @@ use {
 #[cfg(test)]
 use {
   include_dir::{Dir, include_dir},
-  model::{CourseSort, DateTime},
+  model::{CourseSort, Schedule},
   std::sync::atomic::{AtomicUsize, Ordering},
   tempdir::TempDir,
 };
@@ -44,4 +60,9 @@ mod seed;
 mod str_ext;
 mod utils;
 
-pub use crate::{db::Db, error::Error};
+pub use {
+  crate::{db::Db, error::Error},
+  mongodb::event::command::{
+    CommandEventHandler, CommandFailedEvent, CommandSucceededEvent,
+  },
+};
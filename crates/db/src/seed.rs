@@ -1,20 +1,56 @@
-use super::*;
+use {super::*, serde::Deserialize, std::collections::BTreeMap};
+
+/// A single term's average, in the format produced by the
+/// `course-average-fetcher` tool (keyed by course ID).
+#[derive(Deserialize)]
+struct TermAverage {
+  term: String,
+  average: String,
+}
 
 #[derive(Debug, Clone)]
 pub(crate) enum Seed {
+  Averages((PathBuf, Vec<CourseAverage>)),
   Courses((PathBuf, Vec<Course>)),
+  Exams((PathBuf, Vec<ExamSchedule>)),
   Reviews((PathBuf, Vec<Review>)),
   Unknown(PathBuf),
 }
 
 impl Seed {
+  pub(crate) fn path(&self) -> &PathBuf {
+    match self {
+      Self::Averages((path, _))
+      | Self::Courses((path, _))
+      | Self::Exams((path, _))
+      | Self::Reviews((path, _))
+      | Self::Unknown(path) => path,
+    }
+  }
+
   pub(crate) fn from_content(path: PathBuf, content: String) -> Self {
     match (
       serde_json::from_str::<Vec<Course>>(&content).ok(),
       serde_json::from_str::<Vec<Review>>(&content).ok(),
+      serde_json::from_str::<Vec<ExamSchedule>>(&content).ok(),
+      serde_json::from_str::<BTreeMap<String, Vec<TermAverage>>>(&content).ok(),
     ) {
-      (Some(courses), _) => Self::Courses((path, courses)),
-      (_, Some(reviews)) => Self::Reviews((path, reviews)),
+      (Some(courses), _, _, _) => Self::Courses((path, courses)),
+      (_, Some(reviews), _, _) => Self::Reviews((path, reviews)),
+      (_, _, Some(schedules), _) => Self::Exams((path, schedules)),
+      (_, _, _, Some(averages)) => Self::Averages((
+        path,
+        averages
+          .into_iter()
+          .flat_map(|(course_id, averages)| {
+            averages.into_iter().map(move |average| CourseAverage {
+              course_id: course_id.clone(),
+              term: average.term,
+              average: average.average,
+            })
+          })
+          .collect(),
+      )),
       _ => Self::Unknown(path),
     }
   }
@@ -1,5 +1,28 @@
 use super::*;
 
+/// Finds a catalogue year like `2025-2026` in a seed path or course URL.
+pub(crate) fn catalogue_year(s: &str) -> Option<String> {
+  let bytes = s.as_bytes();
+
+  (0..bytes.len().saturating_sub(8)).find_map(|start| {
+    let candidate = s.get(start..start + 9)?;
+
+    let (first, second) = candidate.split_once('-')?;
+
+    let (first, second) = (
+      first.parse::<u32>().ok().filter(|_| first.len() == 4)?,
+      second.parse::<u32>().ok().filter(|_| second.len() == 4)?,
+    );
+
+    (second == first + 1).then(|| candidate.to_string())
+  })
+}
+
+/// `now` on the clock in Montreal, where exam times are given.
+pub(crate) fn campus_time(now: chrono::DateTime<Utc>) -> NaiveDateTime {
+  now.with_timezone(&Montreal).naive_local()
+}
+
 pub(crate) fn current_terms() -> Vec<String> {
   let now = Utc::now().date_naive();
 
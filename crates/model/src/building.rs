@@ -0,0 +1,38 @@
+use super::*;
+
+/// Where a building is, in degrees.
+#[derive(
+  Clone, Debug, Default, Deserialize, PartialEq, Serialize, ToSchema,
+)]
+#[typeshare]
+pub struct Coordinates {
+  pub lat: f64,
+  pub lng: f64,
+}
+
+impl Into<Bson> for Coordinates {
+  fn into(self) -> Bson {
+    Bson::Document(doc! {
+      "lat": self.lat,
+      "lng": self.lng,
+    })
+  }
+}
+
+/// A campus building that courses can meet in.
+#[derive(
+  Clone, Debug, Default, Deserialize, PartialEq, Serialize, ToSchema,
+)]
+#[serde(rename_all = "camelCase")]
+#[typeshare]
+pub struct Building {
+  /// The building's code as it appears in schedule locations, e.g. `BURN`.
+  #[serde(rename = "_id", alias = "code")]
+  pub code: String,
+  /// The building's full name, e.g. `Burnside Hall`.
+  pub name: String,
+  /// Where the building is, if known.
+  pub coordinates: Option<Coordinates>,
+  /// The campus the building is on, e.g. `Downtown` or `Macdonald`.
+  pub campus: Option<String>,
+}
@@ -27,7 +27,21 @@ pub struct Course {
   pub leading_to: Vec<String>,
   pub logical_prerequisites: Option<ReqNode>,
   pub logical_corequisites: Option<ReqNode>,
+  /// Confidence in a natively parsed `logical_prerequisites` tree, or `None`
+  /// if the tree came from elsewhere.
+  pub prerequisites_confidence: Option<Confidence>,
+  /// Confidence in a natively parsed `logical_corequisites` tree, or `None`
+  /// if the tree came from elsewhere.
+  pub corequisites_confidence: Option<Confidence>,
   pub restrictions: Option<String>,
+  /// Courses that can't be taken alongside or after this one.
+  #[serde(default)]
+  pub excluded_courses: Vec<String>,
+  /// Courses offered under another code with the same content.
+  #[serde(default)]
+  pub cross_listings: Vec<String>,
+  #[serde(default)]
+  pub restricted_to: RestrictedTo,
   pub schedule: Option<Vec<Schedule>>,
   #[derivative(PartialEq = "ignore")]
   #[derivative(Hash = "ignore")]
@@ -41,6 +55,26 @@ pub struct Course {
   #[derivative(Hash = "ignore")]
   #[serde(default = "zero")]
   pub review_count: i32,
+  /// The class average in the most recent term with one, in grade points.
+  #[derivative(PartialEq = "ignore")]
+  #[derivative(Hash = "ignore")]
+  #[serde(default)]
+  pub latest_average: Option<f32>,
+  /// The mean class average across every recorded term, in grade points.
+  #[derivative(PartialEq = "ignore")]
+  #[derivative(Hash = "ignore")]
+  #[serde(default)]
+  pub mean_average: Option<f32>,
+  /// Number of reviews giving each rating, from 1 to 5.
+  #[derivative(PartialEq = "ignore")]
+  #[derivative(Hash = "ignore")]
+  #[serde(default)]
+  pub rating_distribution: Vec<u32>,
+  /// Number of reviews giving each difficulty, from 1 to 5.
+  #[derivative(PartialEq = "ignore")]
+  #[derivative(Hash = "ignore")]
+  #[serde(default)]
+  pub difficulty_distribution: Vec<u32>,
 }
 
 const fn zero() -> i32 {
@@ -64,16 +98,59 @@ impl PartialOrd for Course {
 }
 
 impl Course {
+  /// `id` as stored on courses, e.g. `COMP250` for `comp 250`, since listed
+  /// prerequisites and user input don't always match that format.
+  pub fn normalize_id(id: &str) -> String {
+    id.split_whitespace().collect::<String>().to_uppercase()
+  }
+
+  /// The review aggregates stored on the course.
+  pub fn review_stats(&self) -> ReviewStats {
+    ReviewStats {
+      review_count: u32::try_from(self.review_count).unwrap_or_default(),
+      avg_rating: self.avg_rating,
+      avg_difficulty: self.avg_difficulty,
+      rating_distribution: self.rating_distribution.clone(),
+      difficulty_distribution: self.difficulty_distribution.clone(),
+    }
+  }
+
+  /// Merges a freshly scraped `other` into this course.
+  ///
+  /// Requirement trees that didn't come from the native parser (i.e. have no
+  /// confidence) are kept as long as the text they were parsed from is
+  /// unchanged.
   pub fn merge(self, other: Course) -> Course {
+    let (logical_prerequisites, prerequisites_confidence) = Self::merge_tree(
+      (self.logical_prerequisites, self.prerequisites_confidence),
+      (other.logical_prerequisites, other.prerequisites_confidence),
+      self.prerequisites_text == other.prerequisites_text,
+    );
+
+    let (logical_corequisites, corequisites_confidence) = Self::merge_tree(
+      (self.logical_corequisites, self.corequisites_confidence),
+      (other.logical_corequisites, other.corequisites_confidence),
+      self.corequisites_text == other.corequisites_text,
+    );
+
     Course {
-      logical_prerequisites: other
-        .logical_prerequisites
-        .or(self.logical_prerequisites),
-      logical_corequisites: other
-        .logical_corequisites
-        .or(self.logical_corequisites),
+      logical_prerequisites,
+      prerequisites_confidence,
+      logical_corequisites,
+      corequisites_confidence,
       schedule: Some(other.schedule.combine_opt(self.schedule)),
       ..other
     }
   }
+
+  fn merge_tree(
+    existing: (Option<ReqNode>, Option<Confidence>),
+    scraped: (Option<ReqNode>, Option<Confidence>),
+    unchanged: bool,
+  ) -> (Option<ReqNode>, Option<Confidence>) {
+    match existing {
+      (Some(tree), None) if unchanged => (Some(tree), None),
+      _ => scraped,
+    }
+  }
 }
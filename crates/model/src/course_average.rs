@@ -0,0 +1,185 @@
+use {super::*, crate::instructor_stats::term_start};
+
+/// A course's class average in one term, as a letter grade.
+#[derive(
+  Clone, Debug, Default, Deserialize, PartialEq, Serialize, ToSchema,
+)]
+#[serde(rename_all = "camelCase")]
+#[typeshare]
+pub struct CourseAverage {
+  /// The course ID this average is for (e.g. `COMP251`).
+  pub course_id: String,
+  /// Term the average was recorded in (e.g. `Fall 2024`).
+  pub term: String,
+  /// The class average as a letter grade (e.g. `B+`).
+  pub average: String,
+}
+
+impl Into<Bson> for CourseAverage {
+  fn into(self) -> Bson {
+    Bson::Document(doc! {
+      "courseId": self.course_id,
+      "term": self.term,
+      "average": self.average,
+    })
+  }
+}
+
+impl CourseAverage {
+  /// The average on McGill's 4.0 grade point scale, or `None` if the letter
+  /// grade isn't recognized.
+  pub fn gpa(&self) -> Option<f32> {
+    Some(match self.average.trim() {
+      "A" => 4.0,
+      "A-" => 3.7,
+      "B+" => 3.3,
+      "B" => 3.0,
+      "B-" => 2.7,
+      "C+" => 2.3,
+      "C" => 2.0,
+      "D" => 1.0,
+      "F" => 0.0,
+      _ => return None,
+    })
+  }
+
+  /// When the term started, as a fractional year, for ordering and trends.
+  fn when(&self) -> Option<f32> {
+    term_start(&self.term)
+      .map(|(year, month)| year as f32 + (month - 1) as f32 / 12.0)
+  }
+
+  /// Sorts averages oldest term first, with unrecognized terms last.
+  pub fn sort_chronologically(averages: &mut [CourseAverage]) {
+    averages.sort_by(|a, b| match (a.when(), b.when()) {
+      (Some(a), Some(b)) => a.total_cmp(&b),
+      (Some(_), None) => Ordering::Less,
+      (None, Some(_)) => Ordering::Greater,
+      (None, None) => a.term.cmp(&b.term),
+    });
+  }
+}
+
+/// Summary statistics over a course's grade point averages.
+#[derive(
+  Clone, Debug, Default, Deserialize, PartialEq, Serialize, ToSchema,
+)]
+#[serde(rename_all = "camelCase")]
+#[typeshare]
+pub struct GradeTrend {
+  /// Number of terms with a recognized average.
+  pub term_count: u32,
+  /// Mean grade point average across terms.
+  pub mean: f32,
+  /// Lowest grade point average in any term.
+  pub min: f32,
+  /// Highest grade point average in any term.
+  pub max: f32,
+  /// Grade point average in the most recent term.
+  pub latest: f32,
+  /// Least-squares change in grade point average per year, positive when
+  /// averages are rising.
+  pub slope_per_year: f32,
+}
+
+impl GradeTrend {
+  /// Computes the trend over `averages`, or `None` if none of them have a
+  /// recognized grade and term.
+  pub fn new(averages: &[CourseAverage]) -> Option<Self> {
+    let mut points = averages
+      .iter()
+      .filter_map(|average| Some((average.when()?, average.gpa()?)))
+      .collect::<Vec<_>>();
+
+    points.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let (_, latest) = *points.last()?;
+
+    let n = points.len() as f32;
+
+    let (mean_x, mean) = (
+      points.iter().map(|(x, _)| x).sum::<f32>() / n,
+      points.iter().map(|(_, y)| y).sum::<f32>() / n,
+    );
+
+    let variance = points
+      .iter()
+      .map(|(x, _)| (x - mean_x).powi(2))
+      .sum::<f32>();
+
+    let slope_per_year = if variance == 0.0 {
+      0.0
+    } else {
+      points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean))
+        .sum::<f32>()
+        / variance
+    };
+
+    Some(Self {
+      term_count: u32::try_from(points.len()).unwrap_or(u32::MAX),
+      mean,
+      min: points.iter().map(|(_, y)| *y).fold(f32::INFINITY, f32::min),
+      max: points
+        .iter()
+        .map(|(_, y)| *y)
+        .fold(f32::NEG_INFINITY, f32::max),
+      latest,
+      slope_per_year,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn average(term: &str, average: &str) -> CourseAverage {
+    CourseAverage {
+      course_id: "COMP202".into(),
+      term: term.into(),
+      average: average.into(),
+    }
+  }
+
+  #[test]
+  fn averages_sort_by_term_start() {
+    let mut averages = vec![
+      average("Fall 2022", "B"),
+      average("Someday", "B"),
+      average("Winter 2022", "B"),
+      average("Summer 2022", "B"),
+    ];
+
+    CourseAverage::sort_chronologically(&mut averages);
+
+    assert_eq!(
+      averages
+        .iter()
+        .map(|average| average.term.as_str())
+        .collect::<Vec<_>>(),
+      vec!["Winter 2022", "Summer 2022", "Fall 2022", "Someday"]
+    );
+  }
+
+  #[test]
+  fn trend_summarizes_grade_points() {
+    let trend = GradeTrend::new(&[
+      average("Fall 2021", "B-"),
+      average("Fall 2022", "B"),
+      average("Fall 2023", "B+"),
+      average("Winter 2024", "Pass"),
+    ])
+    .unwrap();
+
+    assert_eq!(trend.term_count, 3);
+    assert_eq!(trend.min, 2.7);
+    assert_eq!(trend.max, 3.3);
+    assert_eq!(trend.latest, 3.3);
+    assert!((trend.mean - 3.0).abs() < 0.001);
+    assert!((trend.slope_per_year - 0.3).abs() < 0.001);
+
+    assert_eq!(GradeTrend::new(&[average("Fall 2023", "Pass")]), None);
+  }
+}
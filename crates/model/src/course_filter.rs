@@ -3,6 +3,7 @@ use super::*;
 #[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub enum CourseSortType {
+  Average,
   Difficulty,
   Rating,
   ReviewCount,
@@ -19,6 +20,9 @@ pub struct CourseSort {
 #[serde(rename_all = "camelCase")]
 pub struct CourseFilter {
   pub levels: Option<Vec<String>>,
+  /// Only include courses whose mean class average, in grade points, is at
+  /// least this (e.g. `3.7` for an A-).
+  pub min_average: Option<f32>,
   pub query: Option<String>,
   pub subjects: Option<Vec<String>>,
   pub terms: Option<Vec<String>>,
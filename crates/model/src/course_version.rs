@@ -0,0 +1,115 @@
+use super::*;
+
+/// A snapshot of a course as it appeared in one catalogue year.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CourseVersion {
+  /// The course this is a snapshot of.
+  pub course_id: String,
+  /// The catalogue year this snapshot was taken from, e.g. `2025-2026`.
+  pub catalogue_year: String,
+  /// The course as it appeared in the catalogue year.
+  pub course: Course,
+}
+
+/// A single field that changed between two versions of a course.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[typeshare]
+pub struct FieldChange {
+  /// The changed field's name, e.g. `prerequisitesText`.
+  pub field: String,
+  /// The field's value in the earlier version.
+  #[typeshare(serialized_as = "any")]
+  pub before: Value,
+  /// The field's value in the later version.
+  #[typeshare(serialized_as = "any")]
+  pub after: Value,
+}
+
+impl CourseVersion {
+  /// Catalogue fields worth tracking across years.
+  ///
+  /// Terms, instructors and schedules change every year by design, so
+  /// they'd only drown out the interesting changes.
+  pub const TRACKED_FIELDS: &'static [&'static str] = &[
+    "title",
+    "credits",
+    "description",
+    "department",
+    "faculty",
+    "prerequisitesText",
+    "corequisitesText",
+    "prerequisites",
+    "corequisites",
+    "restrictions",
+    "excludedCourses",
+    "crossListings",
+  ];
+
+  pub fn new(catalogue_year: &str, course: Course) -> Self {
+    Self {
+      course_id: course.id.clone(),
+      catalogue_year: catalogue_year.to_string(),
+      course,
+    }
+  }
+
+  /// The tracked fields that differ between `previous` and this version.
+  pub fn changes_from(&self, previous: &Course) -> Vec<FieldChange> {
+    let (before, after) = (
+      serde_json::to_value(previous).unwrap_or_default(),
+      serde_json::to_value(&self.course).unwrap_or_default(),
+    );
+
+    Self::TRACKED_FIELDS
+      .iter()
+      .filter(|field| before[**field] != after[**field])
+      .map(|field| FieldChange {
+        field: field.to_string(),
+        before: before[*field].clone(),
+        after: after[*field].clone(),
+      })
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, serde_json::json};
+
+  #[test]
+  fn changes_only_include_tracked_fields() {
+    let previous = Course {
+      id: "COMP251".into(),
+      credits: "3".into(),
+      prerequisites_text: Some("Prerequisite: COMP 250".into()),
+      terms: vec!["Fall 2024".into()],
+      ..Default::default()
+    };
+
+    let version = CourseVersion::new(
+      "2025-2026",
+      Course {
+        credits: "4".into(),
+        terms: vec!["Fall 2025".into()],
+        ..previous.clone()
+      },
+    );
+
+    assert_eq!(
+      version.changes_from(&previous),
+      vec![FieldChange {
+        field: "credits".into(),
+        before: json!("3"),
+        after: json!("4"),
+      }]
+    );
+
+    assert!(
+      CourseVersion::new("2024-2025", previous.clone())
+        .changes_from(&previous)
+        .is_empty()
+    );
+  }
+}
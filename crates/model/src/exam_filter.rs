@@ -0,0 +1,9 @@
+use super::*;
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExamFilter {
+  pub course_id: Option<String>,
+  pub subject: Option<String>,
+  pub term: Option<String>,
+}
@@ -0,0 +1,141 @@
+use super::*;
+
+#[derive(
+  Clone, Debug, Default, Deserialize, PartialEq, Serialize, ToSchema,
+)]
+#[serde(rename_all = "camelCase")]
+#[typeshare]
+pub struct FinalExam {
+  /// The course ID this exam is for (e.g. `COMP251`).
+  pub course_id: String,
+  /// Course section the exam is scheduled for.
+  pub section: String,
+  /// Term the exam is held in (e.g. `Fall 2025`).
+  pub term: String,
+  /// Delivery format of the exam (e.g. `IN-PERSON`, `ONLINE`).
+  pub format: String,
+  /// Kind of exam (e.g. `FORMAL EXAM`).
+  pub exam_type: String,
+  /// Where the exam takes place, if known.
+  pub location: Option<String>,
+  /// Local start time in `YYYY-MM-DDTHH:MM:SS` format.
+  pub start_time: String,
+  /// Local end time in `YYYY-MM-DDTHH:MM:SS` format.
+  pub end_time: String,
+  /// URL of the schedule this exam was parsed from.
+  pub url: String,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ExamDetails {
+  pub format: String,
+  #[serde(rename = "type")]
+  pub exam_type: String,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub location: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ExamScheduleEntry {
+  pub id: String,
+  pub section: String,
+  pub exam: ExamDetails,
+  pub start_time: String,
+  pub end_time: String,
+}
+
+/// A single term's exam schedule, in the format produced by the
+/// `final-exam-schedule-parser` tool.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ExamSchedule {
+  pub term: String,
+  pub url: String,
+  pub exams: Vec<ExamScheduleEntry>,
+}
+
+impl ExamSchedule {
+  pub fn final_exams(self) -> Vec<FinalExam> {
+    self
+      .exams
+      .into_iter()
+      .map(|entry| FinalExam {
+        course_id: entry.id,
+        section: entry.section,
+        term: self.term.clone(),
+        format: entry.exam.format,
+        exam_type: entry.exam.exam_type,
+        location: entry.exam.location,
+        start_time: entry.start_time,
+        end_time: entry.end_time,
+        url: self.url.clone(),
+      })
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, serde_json::json};
+
+  #[test]
+  fn exam_schedule_into_final_exams() {
+    let schedule = serde_json::from_value::<ExamSchedule>(json!({
+      "term": "Fall 2025",
+      "url": "https://www.mcgill.ca/exams/files/exams/december_2025.pdf",
+      "exams": [
+        {
+          "id": "COMP251",
+          "section": "001",
+          "exam": {
+            "format": "IN-PERSON",
+            "type": "FORMAL EXAM",
+            "location": "D.T. CAMPUS"
+          },
+          "start_time": "2025-12-09T18:30:00",
+          "end_time": "2025-12-09T21:30:00"
+        },
+        {
+          "id": "COMP250",
+          "section": "001",
+          "exam": {
+            "format": "ONLINE",
+            "type": "TAKE HOME"
+          },
+          "start_time": "2025-12-10T09:00:00",
+          "end_time": "2025-12-10T12:00:00"
+        }
+      ]
+    }))
+    .unwrap();
+
+    assert_eq!(
+      schedule.final_exams(),
+      vec![
+        FinalExam {
+          course_id: "COMP251".into(),
+          section: "001".into(),
+          term: "Fall 2025".into(),
+          format: "IN-PERSON".into(),
+          exam_type: "FORMAL EXAM".into(),
+          location: Some("D.T. CAMPUS".into()),
+          start_time: "2025-12-09T18:30:00".into(),
+          end_time: "2025-12-09T21:30:00".into(),
+          url: "https://www.mcgill.ca/exams/files/exams/december_2025.pdf"
+            .into(),
+        },
+        FinalExam {
+          course_id: "COMP250".into(),
+          section: "001".into(),
+          term: "Fall 2025".into(),
+          format: "ONLINE".into(),
+          exam_type: "TAKE HOME".into(),
+          location: None,
+          start_time: "2025-12-10T09:00:00".into(),
+          end_time: "2025-12-10T12:00:00".into(),
+          url: "https://www.mcgill.ca/exams/files/exams/december_2025.pdf"
+            .into(),
+        },
+      ]
+    );
+  }
+}
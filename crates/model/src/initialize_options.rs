@@ -4,7 +4,10 @@ use super::*;
 pub struct InitializeOptions {
   pub latest_courses: bool,
   pub multithreaded: bool,
+  pub progress: Arc<SeedProgress>,
   pub skip_courses: bool,
   pub skip_reviews: bool,
   pub source: PathBuf,
+  /// Seed files that were already seeded as they are, which are left out.
+  pub up_to_date: HashSet<PathBuf>,
 }
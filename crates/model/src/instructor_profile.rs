@@ -0,0 +1,121 @@
+use super::*;
+
+/// A course an instructor taught in a given term.
+#[derive(
+  Clone,
+  Debug,
+  Default,
+  Deserialize,
+  Eq,
+  Hash,
+  Ord,
+  PartialEq,
+  PartialOrd,
+  Serialize,
+  ToSchema,
+)]
+#[serde(rename_all = "camelCase")]
+#[typeshare]
+pub struct TeachingAssignment {
+  pub course_id: String,
+  pub term: String,
+}
+
+impl Into<Bson> for TeachingAssignment {
+  fn into(self) -> Bson {
+    Bson::Document(doc! {
+      "courseId": self.course_id,
+      "term": self.term,
+    })
+  }
+}
+
+/// An instructor along with everything they've taught.
+#[derive(
+  Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize, ToSchema,
+)]
+#[serde(rename_all = "camelCase")]
+#[typeshare]
+pub struct InstructorProfile {
+  /// A stable identifier derived from the normalized name, e.g.
+  /// `mona-elsaadawy`.
+  #[serde(rename = "_id")]
+  pub id: String,
+  /// The normalized name, e.g. `Mona Elsaadawy`.
+  pub name: String,
+  pub name_ngrams: Option<String>,
+  /// Every spelling of the name seen in the catalogue or schedule builder.
+  pub aliases: Vec<String>,
+  /// Every course and term the instructor is known to have taught.
+  pub assignments: Vec<TeachingAssignment>,
+}
+
+impl InstructorProfile {
+  /// Normalizes a name to `First Last`, accepting the `Last, First` form used
+  /// by the catalogue and VSB and collapsing stray whitespace.
+  pub fn normalize_name(name: &str) -> String {
+    let name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    match name.split_once(", ") {
+      Some((last, first)) if !first.contains(',') => format!("{first} {last}"),
+      _ => name,
+    }
+  }
+
+  /// Whether `name` is one of the instructor's spellings.
+  pub fn is_known_as(&self, name: &str) -> bool {
+    self.name == name || self.aliases.iter().any(|alias| alias == name)
+  }
+
+  /// The stable identifier for a name, shared by all of its spellings.
+  pub fn id_for(name: &str) -> String {
+    Self::normalize_name(name)
+      .to_lowercase()
+      .split(|c: char| !c.is_alphanumeric())
+      .filter(|part| !part.is_empty())
+      .collect::<Vec<_>>()
+      .join("-")
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn spellings_share_an_id() {
+    for name in [
+      "Mona Elsaadawy",
+      "Elsaadawy, Mona",
+      "  mona   ELSAADAWY ",
+      "Mona Elsaadawy.",
+    ] {
+      assert_eq!(InstructorProfile::id_for(name), "mona-elsaadawy");
+    }
+
+    assert_eq!(
+      InstructorProfile::normalize_name("Roshan Vetta, Adrian"),
+      "Adrian Roshan Vetta"
+    );
+
+    assert_eq!(
+      InstructorProfile::normalize_name("  Becerra,   David "),
+      "David Becerra"
+    );
+
+    assert_eq!(
+      InstructorProfile::normalize_name("Giulia  Alberini"),
+      "Giulia Alberini"
+    );
+
+    assert_eq!(
+      InstructorProfile::normalize_name("Smith, John, Jr."),
+      "Smith, John, Jr."
+    );
+
+    assert_eq!(
+      InstructorProfile::id_for("O'Connor, Patrick"),
+      "patrick-o-connor"
+    );
+  }
+}
@@ -0,0 +1,381 @@
+use super::*;
+
+/// Aggregates over a set of reviews.
+#[derive(
+  Clone, Debug, Default, Deserialize, PartialEq, Serialize, ToSchema,
+)]
+#[serde(rename_all = "camelCase")]
+#[typeshare]
+pub struct ReviewStats {
+  /// Number of reviews aggregated.
+  pub review_count: u32,
+  /// Average rating, or `0` if there are no reviews.
+  pub avg_rating: f32,
+  /// Average difficulty, or `0` if there are no reviews.
+  pub avg_difficulty: f32,
+  /// Number of reviews giving each rating, from 1 to 5.
+  pub rating_distribution: Vec<u32>,
+  /// Number of reviews giving each difficulty, from 1 to 5.
+  pub difficulty_distribution: Vec<u32>,
+}
+
+impl ReviewStats {
+  /// Counts `review` towards the aggregates.
+  pub fn add(&mut self, review: &Review) {
+    self.review_count += 1;
+    self.adjust(review, |count| *count += 1);
+  }
+
+  /// Stops counting `review` towards the aggregates.
+  pub fn remove(&mut self, review: &Review) {
+    self.review_count = self.review_count.saturating_sub(1);
+    self.adjust(review, |count| *count = count.saturating_sub(1));
+  }
+
+  fn adjust(&mut self, review: &Review, f: impl Fn(&mut u32)) {
+    for (distribution, value) in [
+      (&mut self.rating_distribution, review.rating),
+      (&mut self.difficulty_distribution, review.difficulty),
+    ] {
+      distribution.resize(5, 0);
+
+      if let Some(count) = (value as usize)
+        .checked_sub(1)
+        .and_then(|index| distribution.get_mut(index))
+      {
+        f(count);
+      }
+    }
+
+    self.avg_rating = Self::average(&self.rating_distribution);
+    self.avg_difficulty = Self::average(&self.difficulty_distribution);
+  }
+
+  fn average(distribution: &[u32]) -> f32 {
+    let total = distribution.iter().sum::<u32>();
+
+    if total == 0 {
+      return 0.0;
+    }
+
+    distribution
+      .iter()
+      .zip(1..)
+      .map(|(count, value)| count * value)
+      .sum::<u32>() as f32
+      / total as f32
+  }
+}
+
+impl<'a> FromIterator<&'a Review> for ReviewStats {
+  fn from_iter<I: IntoIterator<Item = &'a Review>>(iter: I) -> Self {
+    let mut stats = Self {
+      rating_distribution: vec![0; 5],
+      difficulty_distribution: vec![0; 5],
+      ..Default::default()
+    };
+
+    for review in iter {
+      stats.add(review);
+    }
+
+    stats
+  }
+}
+
+/// Review aggregates for one course an instructor taught.
+#[derive(
+  Clone, Debug, Default, Deserialize, PartialEq, Serialize, ToSchema,
+)]
+#[serde(rename_all = "camelCase")]
+#[typeshare]
+pub struct CourseStats {
+  pub course_id: String,
+  pub stats: ReviewStats,
+}
+
+/// Review aggregates for one term an instructor taught a course in.
+#[derive(
+  Clone, Debug, Default, Deserialize, PartialEq, Serialize, ToSchema,
+)]
+#[serde(rename_all = "camelCase")]
+#[typeshare]
+pub struct TermStats {
+  pub course_id: String,
+  pub term: String,
+  pub stats: ReviewStats,
+}
+
+/// Review aggregates for an instructor, overall and broken down by course
+/// and term.
+#[derive(
+  Clone, Debug, Default, Deserialize, PartialEq, Serialize, ToSchema,
+)]
+#[serde(rename_all = "camelCase")]
+#[typeshare]
+pub struct InstructorStats {
+  /// Aggregates across every review naming the instructor.
+  pub overall: ReviewStats,
+  /// Aggregates per course, for every course the instructor taught or was
+  /// reviewed for.
+  pub courses: Vec<CourseStats>,
+  /// Aggregates per course and term the instructor taught, most recent
+  /// first.
+  ///
+  /// Reviews don't record a term, so each review is attributed to the
+  /// latest term the instructor taught its course that started before the
+  /// review was written.
+  pub terms: Vec<TermStats>,
+}
+
+impl InstructorStats {
+  /// Aggregates `reviews`, which should all name `instructor`.
+  pub fn new(instructor: &InstructorProfile, reviews: &[Review]) -> Self {
+    let mut course_ids = instructor
+      .assignments
+      .iter()
+      .map(|assignment| assignment.course_id.as_str())
+      .chain(reviews.iter().map(|review| review.course_id.as_str()))
+      .collect::<Vec<_>>();
+
+    course_ids.sort();
+    course_ids.dedup();
+
+    let courses = course_ids
+      .into_iter()
+      .map(|course_id| CourseStats {
+        course_id: course_id.to_string(),
+        stats: reviews
+          .iter()
+          .filter(|review| review.course_id == course_id)
+          .collect(),
+      })
+      .collect();
+
+    let mut assignments = instructor
+      .assignments
+      .iter()
+      .filter_map(|assignment| {
+        Some((term_start(&assignment.term)?, assignment))
+      })
+      .collect::<Vec<_>>();
+
+    assignments.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+    let attributed = reviews
+      .iter()
+      .filter_map(|review| {
+        let written = month_of(&review.timestamp);
+
+        assignments
+          .iter()
+          .find(|(start, assignment)| {
+            assignment.course_id == review.course_id && *start <= written
+          })
+          .map(|(_, assignment)| (*assignment, review))
+      })
+      .collect::<Vec<_>>();
+
+    let terms = assignments
+      .iter()
+      .map(|(_, assignment)| TermStats {
+        course_id: assignment.course_id.clone(),
+        term: assignment.term.clone(),
+        stats: attributed
+          .iter()
+          .filter(|(term, _)| term == assignment)
+          .map(|(_, review)| *review)
+          .collect(),
+      })
+      .collect();
+
+    Self {
+      overall: reviews.iter().collect(),
+      courses,
+      terms,
+    }
+  }
+}
+
+/// The latest of `terms` that started before `review` was written, which is
+/// the term the review is most likely about.
+pub fn review_term<'a>(
+  review: &Review,
+  terms: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+  let written = month_of(&review.timestamp);
+
+  terms
+    .into_iter()
+    .filter_map(|term| Some((term_start(term)?, term)))
+    .filter(|(start, _)| *start <= written)
+    .max_by_key(|(start, _)| *start)
+    .map(|(_, term)| term)
+}
+
+fn month_of(timestamp: &DateTime) -> (i32, u32) {
+  let timestamp = timestamp.0.to_chrono();
+  (timestamp.year(), timestamp.month())
+}
+
+/// The year and month a term like `Fall 2024` starts in.
+pub(crate) fn term_start(term: &str) -> Option<(i32, u32)> {
+  let (season, year) = term.split_once(' ')?;
+
+  let month = match season {
+    "Winter" => 1,
+    "Summer" => 5,
+    "Fall" => 9,
+    _ => return None,
+  };
+
+  Some((year.parse().ok()?, month))
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, chrono::TimeZone};
+
+  fn review(course_id: &str, rating: u32, written: (i32, u32)) -> Review {
+    Review {
+      course_id: course_id.into(),
+      rating,
+      difficulty: 3,
+      timestamp: Utc
+        .with_ymd_and_hms(written.0, written.1, 15, 0, 0, 0)
+        .unwrap()
+        .into(),
+      ..Default::default()
+    }
+  }
+
+  #[test]
+  fn review_stats_count_distributions() {
+    let reviews = [
+      review("COMP202", 5, (2023, 1)),
+      review("COMP202", 4, (2023, 1)),
+      review("COMP202", 5, (2023, 1)),
+    ];
+
+    assert_eq!(
+      reviews.iter().collect::<ReviewStats>(),
+      ReviewStats {
+        review_count: 3,
+        avg_rating: 14.0 / 3.0,
+        avg_difficulty: 3.0,
+        rating_distribution: vec![0, 0, 0, 1, 2],
+        difficulty_distribution: vec![0, 0, 3, 0, 0],
+      }
+    );
+
+    assert_eq!(
+      [].iter().collect::<ReviewStats>(),
+      ReviewStats {
+        rating_distribution: vec![0; 5],
+        difficulty_distribution: vec![0; 5],
+        ..Default::default()
+      }
+    );
+  }
+
+  #[test]
+  fn review_stats_can_be_maintained_incrementally() {
+    let (first, second) = (
+      review("COMP202", 5, (2023, 1)),
+      review("COMP202", 2, (2023, 1)),
+    );
+
+    let mut stats = ReviewStats::default();
+
+    stats.add(&first);
+    stats.add(&second);
+
+    assert_eq!(stats, [&first, &second].into_iter().collect());
+
+    stats.remove(&first);
+
+    assert_eq!(stats, [&second].into_iter().collect());
+    assert_eq!(stats.avg_rating, 2.0);
+
+    stats.remove(&second);
+
+    assert_eq!(stats, [].iter().collect());
+  }
+
+  #[test]
+  fn review_term_picks_latest_started_term() {
+    let terms = ["Fall 2022", "Winter 2023", "Fall 2023", "Summer 2023"];
+
+    assert_eq!(
+      review_term(&review("COMP202", 5, (2023, 4)), terms),
+      Some("Winter 2023")
+    );
+
+    assert_eq!(
+      review_term(&review("COMP202", 5, (2023, 6)), terms),
+      Some("Summer 2023")
+    );
+
+    assert_eq!(review_term(&review("COMP202", 5, (2022, 8)), terms), None);
+  }
+
+  #[test]
+  fn reviews_are_attributed_to_the_latest_term_taught() {
+    let instructor = InstructorProfile {
+      assignments: vec![
+        TeachingAssignment {
+          course_id: "COMP202".into(),
+          term: "Fall 2022".into(),
+        },
+        TeachingAssignment {
+          course_id: "COMP202".into(),
+          term: "Fall 2023".into(),
+        },
+        TeachingAssignment {
+          course_id: "COMP250".into(),
+          term: "Winter 2023".into(),
+        },
+      ],
+      ..Default::default()
+    };
+
+    let stats = InstructorStats::new(
+      &instructor,
+      &[
+        review("COMP202", 5, (2022, 12)),
+        review("COMP202", 3, (2023, 8)),
+        review("COMP202", 1, (2023, 12)),
+        review("COMP250", 4, (2023, 4)),
+        review("COMP250", 2, (2022, 10)),
+      ],
+    );
+
+    assert_eq!(stats.overall.review_count, 5);
+
+    assert_eq!(
+      stats
+        .courses
+        .iter()
+        .map(|course| (course.course_id.as_str(), course.stats.review_count))
+        .collect::<Vec<_>>(),
+      vec![("COMP202", 3), ("COMP250", 2)]
+    );
+
+    assert_eq!(
+      stats
+        .terms
+        .iter()
+        .map(|term| (
+          term.course_id.as_str(),
+          term.term.as_str(),
+          term.stats.rating_distribution.clone()
+        ))
+        .collect::<Vec<_>>(),
+      vec![
+        ("COMP202", "Fall 2023", vec![1, 0, 0, 0, 0]),
+        ("COMP250", "Winter 2023", vec![0, 0, 0, 1, 0]),
+        ("COMP202", "Fall 2022", vec![0, 0, 1, 0, 1]),
+      ]
+    );
+  }
+}
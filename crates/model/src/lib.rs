@@ -1,6 +1,6 @@
 use {
   bson::{Bson, DateTime as BsonDateTime, doc},
-  chrono::{DateTime as ChronoDateTime, Utc},
+  chrono::{DateTime as ChronoDateTime, Datelike, Utc},
   combine::Combine,
   derivative::Derivative,
   serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error},
@@ -8,8 +8,13 @@ use {
   std::{
     borrow::Cow,
     cmp::Ordering,
+    collections::HashSet,
     fmt::{self, Display, Formatter},
     path::PathBuf,
+    sync::{
+      Arc, Mutex,
+      atomic::{self, AtomicBool, AtomicUsize},
+    },
   },
   typeshare::typeshare,
   utoipa::{
@@ -21,34 +26,61 @@ use {
   },
 };
 
+mod building;
 mod course;
+mod course_average;
 mod course_filter;
 mod course_page;
+mod course_version;
 mod datetime;
+mod exam_filter;
+mod final_exam;
 mod initialize_options;
 mod instructor;
+mod instructor_profile;
+mod instructor_stats;
 mod interaction;
 mod notification;
+mod notification_preferences;
 mod requirements;
+mod restricted_to;
 mod review;
 mod review_filter;
 mod schedule;
 mod search_results;
+mod seed_progress;
 mod subscription;
+mod webhook;
 
 pub use crate::{
+  building::{Building, Coordinates},
   course::Course,
+  course_average::{CourseAverage, GradeTrend},
   course_filter::{CourseFilter, CourseSort, CourseSortType},
   course_page::CoursePage,
+  course_version::{CourseVersion, FieldChange},
   datetime::DateTime,
+  exam_filter::ExamFilter,
+  final_exam::{ExamDetails, ExamSchedule, ExamScheduleEntry, FinalExam},
   initialize_options::InitializeOptions,
   instructor::Instructor,
+  instructor_profile::{InstructorProfile, TeachingAssignment},
+  instructor_stats::{
+    CourseStats, InstructorStats, ReviewStats, TermStats, review_term,
+  },
   interaction::{Interaction, InteractionKind},
   notification::Notification,
-  requirements::{Operator, ReqNode, Requirement, Requirements},
+  notification_preferences::{EmailFrequency, NotificationPreferences},
+  requirements::{Confidence, Operator, ReqNode, Requirement, Requirements},
+  restricted_to::RestrictedTo,
   review::Review,
   review_filter::ReviewFilter,
-  schedule::{Block, Schedule, TimeBlock},
+  schedule::{Block, Room, Schedule, TimeBlock},
   search_results::SearchResults,
-  subscription::Subscription,
+  seed_progress::SeedProgress,
+  subscription::{Subscription, SubscriptionKind},
+  webhook::{
+    Webhook, WebhookDelivery, WebhookDeliveryStatus, WebhookEvent,
+    WebhookEventKind,
+  },
 };
@@ -1,10 +1,273 @@
-use super::*;
+use {super::*, bson::oid::ObjectId};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[typeshare]
 pub struct Notification {
-  pub review: Review,
+  /// Unique identifier for the notification.
+  #[serde(rename = "_id", deserialize_with = "deserialize_id")]
+  pub id: String,
+  /// When the notification was created, independent of when its review was
+  /// written or last edited.
+  #[serde(default = "unknown_creation_time")]
+  #[typeshare(serialized_as = "String")]
+  pub created_at: DateTime,
+  /// The kind of subscription the notification was sent for.
+  #[serde(default)]
+  pub kind: SubscriptionKind,
+  /// The course the notification is about.
+  #[serde(default)]
+  pub course_id: String,
+  /// The review the notification is about, for review and review activity
+  /// notifications.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub review: Option<Review>,
+  /// The followed instructor the review names, for instructor
+  /// notifications.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub instructor: Option<String>,
+  /// The newly offered term, for offering notifications.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub term: Option<String>,
+  /// What changed, for course change notifications.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub changes: Vec<FieldChange>,
   pub seen: bool,
   pub user_id: String,
+  /// Whether the notification has been emailed to the user.
+  #[serde(default)]
+  pub emailed: bool,
+}
+
+impl Into<Bson> for Notification {
+  fn into(self) -> Bson {
+    Bson::Document(doc! {
+      "_id": Self::bson_id(&self.id),
+      "createdAt": Bson::from(self.created_at),
+      "kind": self.kind,
+      "courseId": self.course_id,
+      "review": self.review.map(Into::<Bson>::into),
+      "instructor": self.instructor,
+      "term": self.term,
+      "changes": bson::to_bson(&self.changes).unwrap_or(Bson::Array(Vec::new())),
+      "seen": self.seen,
+      "userId": self.user_id,
+      "emailed": self.emailed,
+    })
+  }
+}
+
+impl Notification {
+  /// A fresh, unseen notification of a new review for `user_id`, who follows
+  /// the review's course.
+  pub fn new(user_id: &str, review: Review) -> Self {
+    Self::about_review(SubscriptionKind::Reviews, user_id, review)
+  }
+
+  /// A fresh, unseen notification of `kind` about `review`.
+  pub fn about_review(
+    kind: SubscriptionKind,
+    user_id: &str,
+    review: Review,
+  ) -> Self {
+    Self {
+      review: Some(review.clone()),
+      ..Self::about_course(kind, user_id, &review.course_id)
+    }
+  }
+
+  /// A fresh, unseen notification of `kind` about `course_id`.
+  pub fn about_course(
+    kind: SubscriptionKind,
+    user_id: &str,
+    course_id: &str,
+  ) -> Self {
+    Self {
+      id: ObjectId::new().to_hex(),
+      created_at: Utc::now().into(),
+      kind,
+      course_id: course_id.into(),
+      review: None,
+      instructor: None,
+      term: None,
+      changes: Vec::new(),
+      seen: false,
+      user_id: user_id.into(),
+      emailed: false,
+    }
+  }
+
+  /// The `_id` a notification with `id` is stored under.
+  ///
+  /// Ids are stored as object ids, so anything that doesn't parse as one
+  /// can't match a notification.
+  pub fn bson_id(id: &str) -> Bson {
+    ObjectId::parse_str(id)
+      .map(Bson::ObjectId)
+      .unwrap_or_else(|_| Bson::String(id.into()))
+  }
+
+  /// A one line description of the notification, e.g. for email subjects.
+  pub fn summary(&self) -> String {
+    match self.kind {
+      SubscriptionKind::Reviews => format!("New review of {}", self.course_id),
+      SubscriptionKind::Instructor => format!(
+        "New review of {} in {}",
+        self
+          .instructor
+          .as_deref()
+          .unwrap_or("an instructor you follow"),
+        self.course_id
+      ),
+      SubscriptionKind::Offerings => format!(
+        "{} is offered in {}",
+        self.course_id,
+        self.term.as_deref().unwrap_or("a new term")
+      ),
+      SubscriptionKind::CourseChanges => {
+        format!("The schedule for {} changed", self.course_id)
+      }
+      SubscriptionKind::ReviewActivity => format!(
+        "Your review of {} now has {} likes",
+        self.course_id,
+        self.review.as_ref().map_or(0, |review| review.likes)
+      ),
+    }
+  }
+}
+
+/// Notifications written before `createdAt` existed are backfilled from their
+/// id when the database is initialized, and treated as ancient until then.
+fn unknown_creation_time() -> DateTime {
+  DateTime::from_millis(0)
+}
+
+/// Accepts the `_id` as a plain string, or as an object id in the
+/// `{"$oid": "..."}` form MongoDB hands back.
+fn deserialize_id<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  match Value::deserialize(deserializer)? {
+    Value::String(id) => Ok(id),
+    Value::Object(map) => map
+      .get("$oid")
+      .and_then(Value::as_str)
+      .map(str::to_string)
+      .ok_or_else(|| D::Error::custom("missing '$oid' field in id object")),
+    _ => Err(D::Error::custom(
+      "expected either an id string or object id",
+    )),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn ids_round_trip_through_bson() {
+    let notification = Notification {
+      changes: vec![FieldChange {
+        field: "schedule".into(),
+        before: Value::Null,
+        after: serde_json::json!([{ "term": "Fall 2025" }]),
+      }],
+      ..Notification::about_course(
+        SubscriptionKind::CourseChanges,
+        "1",
+        "COMP202",
+      )
+    };
+
+    let Bson::Document(document) = notification.clone().into() else {
+      panic!("expected a document");
+    };
+
+    assert!(matches!(document.get("_id"), Some(Bson::ObjectId(_))));
+    assert!(matches!(document.get("createdAt"), Some(Bson::DateTime(_))));
+
+    let mut bytes = Vec::new();
+
+    document.to_writer(&mut bytes).unwrap();
+
+    assert_eq!(
+      bson::from_slice::<Notification>(&bytes).unwrap(),
+      notification
+    );
+  }
+
+  #[test]
+  fn legacy_notifications_deserialize() {
+    let notification =
+      serde_json::from_value::<Notification>(serde_json::json!({
+        "_id": { "$oid": "65a0c0ffee0000000000beef" },
+        "review": serde_json::to_value(Review::default()).unwrap(),
+        "seen": true,
+        "userId": "1",
+      }))
+      .unwrap();
+
+    assert_eq!(notification.id, "65a0c0ffee0000000000beef");
+    assert_eq!(notification.created_at, DateTime::from_millis(0));
+    assert_eq!(notification.kind, SubscriptionKind::Reviews);
+    assert!(notification.review.is_some());
+    assert!(!notification.emailed);
+  }
+
+  #[test]
+  fn summaries_describe_each_kind() {
+    let review = Review {
+      course_id: "COMP202".into(),
+      likes: 3,
+      ..Default::default()
+    };
+
+    assert_eq!(
+      Notification::new("1", review.clone()).summary(),
+      "New review of COMP202"
+    );
+
+    assert_eq!(
+      Notification {
+        instructor: Some("Adrian Vetta".into()),
+        ..Notification::about_review(
+          SubscriptionKind::Instructor,
+          "1",
+          review.clone()
+        )
+      }
+      .summary(),
+      "New review of Adrian Vetta in COMP202"
+    );
+
+    assert_eq!(
+      Notification {
+        term: Some("Fall 2025".into()),
+        ..Notification::about_course(
+          SubscriptionKind::Offerings,
+          "1",
+          "COMP202"
+        )
+      }
+      .summary(),
+      "COMP202 is offered in Fall 2025"
+    );
+
+    assert_eq!(
+      Notification::about_review(SubscriptionKind::ReviewActivity, "1", review)
+        .summary(),
+      "Your review of COMP202 now has 3 likes"
+    );
+  }
+
+  #[test]
+  fn unparseable_ids_match_nothing() {
+    assert_eq!(Notification::bson_id("nope"), Bson::String("nope".into()));
+
+    assert!(matches!(
+      Notification::bson_id("65a0c0ffee0000000000beef"),
+      Bson::ObjectId(_)
+    ));
+  }
 }
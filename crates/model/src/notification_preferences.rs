@@ -0,0 +1,106 @@
+use super::*;
+
+/// How often a user wants notifications emailed to them.
+#[derive(
+  Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize, ToSchema,
+)]
+#[serde(rename_all = "camelCase")]
+#[typeshare]
+pub enum EmailFrequency {
+  /// Notifications are only shown on the site.
+  #[default]
+  Never,
+  /// Each notification is emailed as soon as it's created.
+  Immediate,
+  /// Unseen notifications are emailed together once a day.
+  Daily,
+  /// Unseen notifications are emailed together once a week.
+  Weekly,
+}
+
+impl EmailFrequency {
+  /// How long to wait between digests, if this is a digest frequency.
+  pub fn digest_period(self) -> Option<chrono::Duration> {
+    match self {
+      Self::Daily => Some(chrono::Duration::days(1)),
+      Self::Weekly => Some(chrono::Duration::weeks(1)),
+      Self::Never | Self::Immediate => None,
+    }
+  }
+}
+
+impl Into<Bson> for EmailFrequency {
+  fn into(self) -> Bson {
+    Bson::String(
+      match self {
+        Self::Never => "never",
+        Self::Immediate => "immediate",
+        Self::Daily => "daily",
+        Self::Weekly => "weekly",
+      }
+      .into(),
+    )
+  }
+}
+
+/// How a user wants to hear about new notifications.
+#[derive(
+  Clone, Debug, Default, Deserialize, PartialEq, Serialize, ToSchema,
+)]
+#[serde(rename_all = "camelCase")]
+#[typeshare]
+pub struct NotificationPreferences {
+  pub user_id: String,
+  /// Address to email notifications to.
+  pub email: Option<String>,
+  #[serde(default)]
+  pub email_frequency: EmailFrequency,
+  /// When the last digest was emailed, if ever.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  #[typeshare(serialized_as = "Option<String>")]
+  pub last_digest_at: Option<DateTime>,
+}
+
+impl NotificationPreferences {
+  /// Whether a digest should be emailed at `now`.
+  pub fn digest_due(&self, now: ChronoDateTime<Utc>) -> bool {
+    match (self.email_frequency.digest_period(), &self.last_digest_at) {
+      (None, _) => false,
+      (Some(_), None) => true,
+      (Some(period), Some(last)) => last.0.to_chrono() + period <= now,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, chrono::TimeZone};
+
+  #[test]
+  fn digests_are_due_once_per_period() {
+    let now = Utc.with_ymd_and_hms(2025, 1, 8, 12, 0, 0).unwrap();
+
+    let preferences = |email_frequency, last_digest_at: Option<(u32, u32)>| {
+      NotificationPreferences {
+        email_frequency,
+        last_digest_at: last_digest_at.map(|(day, hour)| {
+          Utc
+            .with_ymd_and_hms(2025, 1, day, hour, 0, 0)
+            .unwrap()
+            .into()
+        }),
+        ..Default::default()
+      }
+    };
+
+    assert!(preferences(EmailFrequency::Daily, None).digest_due(now));
+    assert!(preferences(EmailFrequency::Daily, Some((7, 12))).digest_due(now));
+    assert!(!preferences(EmailFrequency::Daily, Some((7, 13))).digest_due(now));
+    assert!(preferences(EmailFrequency::Weekly, Some((1, 12))).digest_due(now));
+    assert!(
+      !preferences(EmailFrequency::Weekly, Some((2, 12))).digest_due(now)
+    );
+    assert!(!preferences(EmailFrequency::Immediate, None).digest_due(now));
+    assert!(!preferences(EmailFrequency::Never, None).digest_due(now));
+  }
+}
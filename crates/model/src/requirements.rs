@@ -143,12 +143,73 @@ impl Into<Bson> for ReqNode {
   }
 }
 
+impl ReqNode {
+  /// Whether having taken `courses` satisfies this requirement.
+  pub fn is_satisfied_by(&self, courses: &[String]) -> bool {
+    match self {
+      Self::Course(course) => {
+        course.is_empty()
+          || courses.iter().any(|taken| {
+            Course::normalize_id(taken) == Course::normalize_id(course)
+          })
+      }
+      Self::Group { operator, groups } => match operator {
+        Operator::And => {
+          groups.iter().all(|group| group.is_satisfied_by(courses))
+        }
+        Operator::Or => {
+          groups.iter().any(|group| group.is_satisfied_by(courses))
+        }
+      },
+    }
+  }
+}
+
 impl Default for ReqNode {
   fn default() -> Self {
     Self::Course("".to_string())
   }
 }
 
+/// How much a natively parsed requirement tree can be trusted.
+#[derive(
+  Clone,
+  Copy,
+  Debug,
+  Deserialize,
+  Eq,
+  Hash,
+  Ord,
+  PartialEq,
+  PartialOrd,
+  Serialize,
+  ToSchema,
+)]
+#[serde(rename_all = "camelCase")]
+#[typeshare]
+pub enum Confidence {
+  /// Parts of the text weren't understood and were skipped.
+  Low,
+  /// The text was understood, but mentions conditions a tree can't express,
+  /// like instructor permission or equivalent courses.
+  Medium,
+  /// The whole text was understood and is captured by the tree.
+  High,
+}
+
+impl Into<Bson> for Confidence {
+  fn into(self) -> Bson {
+    Bson::String(
+      match self {
+        Self::Low => "low",
+        Self::Medium => "medium",
+        Self::High => "high",
+      }
+      .to_string(),
+    )
+  }
+}
+
 #[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 #[typeshare]
@@ -158,8 +219,13 @@ pub struct Requirements {
   pub corequisites: Vec<String>,
   pub prerequisites: Vec<String>,
   pub restrictions: Option<String>,
+  pub excluded_courses: Vec<String>,
+  pub cross_listings: Vec<String>,
+  pub restricted_to: RestrictedTo,
   pub logical_prerequisites: Option<ReqNode>,
   pub logical_corequisites: Option<ReqNode>,
+  pub prerequisites_confidence: Option<Confidence>,
+  pub corequisites_confidence: Option<Confidence>,
 }
 
 impl Requirements {
@@ -183,6 +249,20 @@ impl Requirements {
     self.restrictions = Some(restrictions);
   }
 
+  pub fn add_excluded_courses(&mut self, courses: Vec<String>) {
+    extend_unique(&mut self.excluded_courses, courses);
+  }
+
+  pub fn add_cross_listings(&mut self, courses: Vec<String>) {
+    extend_unique(&mut self.cross_listings, courses);
+  }
+
+  pub fn add_restricted_to(&mut self, restricted_to: RestrictedTo) {
+    extend_unique(&mut self.restricted_to.programs, restricted_to.programs);
+    extend_unique(&mut self.restricted_to.faculties, restricted_to.faculties);
+    extend_unique(&mut self.restricted_to.years, restricted_to.years);
+  }
+
   pub fn set_logical_prerequisites(
     &mut self,
     logical_prerequisites: Option<ReqNode>,
@@ -196,4 +276,26 @@ impl Requirements {
   ) {
     self.logical_corequisites = logical_corequisites;
   }
+
+  pub fn set_prerequisites_confidence(
+    &mut self,
+    prerequisites_confidence: Option<Confidence>,
+  ) {
+    self.prerequisites_confidence = prerequisites_confidence;
+  }
+
+  pub fn set_corequisites_confidence(
+    &mut self,
+    corequisites_confidence: Option<Confidence>,
+  ) {
+    self.corequisites_confidence = corequisites_confidence;
+  }
+}
+
+fn extend_unique<T: PartialEq>(items: &mut Vec<T>, other: Vec<T>) {
+  for item in other {
+    if !items.contains(&item) {
+      items.push(item);
+    }
+  }
 }
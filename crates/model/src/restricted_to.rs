@@ -0,0 +1,38 @@
+use super::*;
+
+/// Who a course is open to, as parsed from its restriction notes.
+///
+/// Empty lists mean the catalogue doesn't restrict the course along that
+/// dimension.
+#[derive(
+  Clone, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize, ToSchema,
+)]
+#[serde(rename_all = "camelCase")]
+#[typeshare]
+pub struct RestrictedTo {
+  /// Programs whose students may take the course, e.g. `B.Sc. in Computer
+  /// Science`.
+  pub programs: Vec<String>,
+  /// Faculties whose students may take the course, e.g. `Science`.
+  pub faculties: Vec<String>,
+  /// Years of study (`U1` is 1) in which students may take the course.
+  pub years: Vec<u32>,
+}
+
+impl RestrictedTo {
+  pub fn is_empty(&self) -> bool {
+    self.programs.is_empty()
+      && self.faculties.is_empty()
+      && self.years.is_empty()
+  }
+}
+
+impl Into<Bson> for RestrictedTo {
+  fn into(self) -> Bson {
+    Bson::Document(doc! {
+      "programs": self.programs,
+      "faculties": self.faculties,
+      "years": self.years,
+    })
+  }
+}
@@ -17,24 +17,85 @@ pub struct Block {
   pub campus: Option<String>,
   pub display: Option<String>,
   pub location: Option<String>,
+  /// Known buildings and rooms the block meets in, parsed from `location`.
+  #[serde(default)]
+  pub rooms: Vec<Room>,
   pub timeblocks: Option<Vec<TimeBlock>>,
   pub crn: Option<String>,
   #[serde(skip)]
   pub instructors: Vec<String>,
 }
 
+impl Block {
+  /// The building code and room of each of the block's locations, e.g.
+  /// `BURN` and `1B45` then `LEA` and `132` for `BURN 1B45; LEA 132`.
+  /// Locations that don't start with a building code are left out.
+  pub fn parse_location(&self) -> Vec<(&str, Option<&str>)> {
+    self
+      .location
+      .as_deref()
+      .unwrap_or_default()
+      .split(';')
+      .filter_map(|location| {
+        let location = location.trim();
+
+        let (code, room) = match location.split_once(char::is_whitespace) {
+          Some((code, room)) => (code, Some(room.trim())),
+          None => (location, None),
+        };
+
+        code
+          .chars()
+          .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+          .then_some((code, room.filter(|room| !room.is_empty())))
+          .filter(|(code, _)| !code.is_empty())
+      })
+      .collect()
+  }
+}
+
 impl Into<Bson> for Block {
   fn into(self) -> bson::Bson {
     Bson::Document(doc! {
       "campus": self.campus,
       "display": self.display,
       "location": self.location,
+      "rooms": self.rooms,
       "timeblocks": self.timeblocks,
       "crn": self.crn,
     })
   }
 }
 
+#[derive(
+  Clone,
+  Debug,
+  Default,
+  Deserialize,
+  Eq,
+  Hash,
+  Ord,
+  PartialEq,
+  PartialOrd,
+  Serialize,
+  ToSchema,
+)]
+pub struct Room {
+  /// Code of the building, e.g. `BURN`.
+  pub building: String,
+  /// The room in the building, e.g. `1B45`, if given.
+  pub room: Option<String>,
+}
+
+impl Into<Bson> for Room {
+  fn into(self) -> bson::Bson {
+    Bson::Document(doc! {
+      "building": self.building,
+      "room": self.room,
+    })
+  }
+}
+
 #[derive(
   Clone,
   Debug,
@@ -90,3 +151,36 @@ impl Into<Bson> for Schedule {
     })
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parse_location() {
+    let block = |location: &str| Block {
+      location: Some(location.into()),
+      ..Default::default()
+    };
+
+    assert_eq!(
+      block("BURN 1B45").parse_location(),
+      vec![("BURN", Some("1B45"))]
+    );
+
+    assert_eq!(
+      block("ENGTR 0100; STBIO S1/3").parse_location(),
+      vec![("ENGTR", Some("0100")), ("STBIO", Some("S1/3"))]
+    );
+
+    assert_eq!(
+      block("D.T. CAMPUS; LEA 132").parse_location(),
+      vec![("LEA", Some("132"))]
+    );
+
+    assert_eq!(block("NOROOM").parse_location(), vec![("NOROOM", None)]);
+    assert!(block("D.T. CAMPUS").parse_location().is_empty());
+    assert!(block("").parse_location().is_empty());
+    assert!(Block::default().parse_location().is_empty());
+  }
+}
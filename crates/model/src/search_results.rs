@@ -5,5 +5,5 @@ pub struct SearchResults {
   /// Courses that match the search query ordered by relevance.
   pub courses: Vec<Course>,
   /// Instructors that match the search query ordered by relevance.
-  pub instructors: Vec<Instructor>,
+  pub instructors: Vec<InstructorProfile>,
 }
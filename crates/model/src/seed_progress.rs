@@ -0,0 +1,46 @@
+use super::*;
+
+/// Which seed files have been processed, shared with whoever started
+/// seeding so they can report on it or stop it.
+#[derive(Debug, Default)]
+pub struct SeedProgress {
+  cancelled: AtomicBool,
+  completed: AtomicUsize,
+  seeded: Mutex<Vec<PathBuf>>,
+  total: AtomicUsize,
+}
+
+impl SeedProgress {
+  /// Asks seeding to stop once the seed file it's on is done.
+  pub fn cancel(&self) {
+    self.cancelled.store(true, atomic::Ordering::Relaxed);
+  }
+
+  pub fn is_cancelled(&self) -> bool {
+    self.cancelled.load(atomic::Ordering::Relaxed)
+  }
+
+  pub fn start(&self, total: usize) {
+    self.total.store(total, atomic::Ordering::Relaxed);
+    self.completed.store(0, atomic::Ordering::Relaxed);
+    self.seeded.lock().unwrap().clear();
+  }
+
+  pub fn advance(&self, seeded: PathBuf) {
+    self.seeded.lock().unwrap().push(seeded);
+    self.completed.fetch_add(1, atomic::Ordering::Relaxed);
+  }
+
+  pub fn completed(&self) -> usize {
+    self.completed.load(atomic::Ordering::Relaxed)
+  }
+
+  /// Seed files processed so far, in order.
+  pub fn seeded(&self) -> Vec<PathBuf> {
+    self.seeded.lock().unwrap().clone()
+  }
+
+  pub fn total(&self) -> usize {
+    self.total.load(atomic::Ordering::Relaxed)
+  }
+}
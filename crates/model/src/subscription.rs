@@ -1,9 +1,130 @@
 use super::*;
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+/// What a subscription notifies its user about.
+#[derive(
+  Clone,
+  Copy,
+  Debug,
+  Default,
+  Deserialize,
+  Eq,
+  Hash,
+  PartialEq,
+  Serialize,
+  ToSchema,
+)]
+#[serde(rename_all = "camelCase")]
+#[typeshare]
+pub enum SubscriptionKind {
+  /// New reviews of a course.
+  #[default]
+  Reviews,
+  /// New reviews naming an instructor, in any course.
+  Instructor,
+  /// A course being offered in a term it wasn't before, optionally only a
+  /// specific term.
+  Offerings,
+  /// Changes to a course's schedule when courses are re-seeded.
+  CourseChanges,
+  /// Votes on the user's own reviews.
+  ReviewActivity,
+}
+
+impl Display for SubscriptionKind {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Reviews => write!(f, "reviews"),
+      Self::Instructor => write!(f, "instructor"),
+      Self::Offerings => write!(f, "offerings"),
+      Self::CourseChanges => write!(f, "courseChanges"),
+      Self::ReviewActivity => write!(f, "reviewActivity"),
+    }
+  }
+}
+
+impl Into<Bson> for SubscriptionKind {
+  fn into(self) -> Bson {
+    Bson::String(self.to_string())
+  }
+}
+
+#[derive(
+  Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema,
+)]
 #[serde(rename_all = "camelCase")]
 #[typeshare]
 pub struct Subscription {
+  /// What the subscription is for. Subscriptions saved before there were
+  /// kinds are all for course reviews.
+  #[serde(default)]
+  pub kind: SubscriptionKind,
+  /// The course followed, for course review, offering and course change
+  /// subscriptions.
+  #[serde(default)]
   pub course_id: String,
+  /// The instructor followed, for instructor subscriptions.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub instructor: Option<String>,
+  /// The only term to notify about, for offering subscriptions.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub term: Option<String>,
   pub user_id: String,
 }
+
+impl Subscription {
+  /// Whether the subscription names everything its kind needs to follow.
+  pub fn is_complete(&self) -> bool {
+    match self.kind {
+      SubscriptionKind::Reviews
+      | SubscriptionKind::Offerings
+      | SubscriptionKind::CourseChanges => !self.course_id.is_empty(),
+      SubscriptionKind::Instructor => self
+        .instructor
+        .as_deref()
+        .is_some_and(|instructor| !instructor.trim().is_empty()),
+      SubscriptionKind::ReviewActivity => true,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn legacy_subscriptions_follow_course_reviews() {
+    let subscription = serde_json::from_value::<Subscription>(
+      serde_json::json!({ "courseId": "COMP202", "userId": "1" }),
+    )
+    .unwrap();
+
+    assert_eq!(subscription.kind, SubscriptionKind::Reviews);
+    assert!(subscription.is_complete());
+  }
+
+  #[test]
+  fn subscriptions_need_a_target_for_their_kind() {
+    let subscription =
+      |kind, course_id: &str, instructor: Option<&str>| Subscription {
+        kind,
+        course_id: course_id.into(),
+        instructor: instructor.map(str::to_string),
+        ..Default::default()
+      };
+
+    assert!(!subscription(SubscriptionKind::Reviews, "", None).is_complete());
+    assert!(
+      subscription(SubscriptionKind::Offerings, "COMP202", None).is_complete()
+    );
+    assert!(
+      !subscription(SubscriptionKind::Instructor, "", Some(" ")).is_complete()
+    );
+    assert!(
+      subscription(SubscriptionKind::Instructor, "", Some("Adrian Vetta"))
+        .is_complete()
+    );
+    assert!(
+      subscription(SubscriptionKind::ReviewActivity, "", None).is_complete()
+    );
+  }
+}
@@ -0,0 +1,320 @@
+use {super::*, bson::oid::ObjectId};
+
+/// Something that happened which webhooks can be told about.
+#[derive(
+  Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize, ToSchema,
+)]
+#[typeshare]
+pub enum WebhookEventKind {
+  /// A review was written.
+  #[serde(rename = "review.created")]
+  ReviewCreated,
+  /// A review was edited.
+  #[serde(rename = "review.updated")]
+  ReviewUpdated,
+  /// A review was deleted.
+  #[serde(rename = "review.deleted")]
+  ReviewDeleted,
+  /// Seeding changed a course's catalogue entry.
+  #[serde(rename = "course.updated")]
+  CourseUpdated,
+}
+
+impl Display for WebhookEventKind {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::ReviewCreated => write!(f, "review.created"),
+      Self::ReviewUpdated => write!(f, "review.updated"),
+      Self::ReviewDeleted => write!(f, "review.deleted"),
+      Self::CourseUpdated => write!(f, "course.updated"),
+    }
+  }
+}
+
+impl Into<Bson> for WebhookEventKind {
+  fn into(self) -> Bson {
+    Bson::String(self.to_string())
+  }
+}
+
+/// An endpoint that's sent signed events as they happen.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[typeshare]
+pub struct Webhook {
+  #[serde(rename = "_id")]
+  pub id: String,
+  /// Where events are posted.
+  pub url: String,
+  /// Key each payload's HMAC-SHA256 signature is made with, only ever sent
+  /// to clients in response to creating the webhook.
+  #[serde(default, skip_serializing_if = "String::is_empty")]
+  #[typeshare(serialized_as = "Option<String>")]
+  pub secret: String,
+  /// Events the webhook is sent, or every event if empty.
+  #[serde(default)]
+  pub events: Vec<WebhookEventKind>,
+  #[typeshare(serialized_as = "String")]
+  pub created_at: DateTime,
+}
+
+impl Into<Bson> for Webhook {
+  fn into(self) -> Bson {
+    Bson::Document(doc! {
+      "_id": self.id,
+      "url": self.url,
+      "secret": self.secret,
+      "events": self.events,
+      "createdAt": Bson::from(self.created_at),
+    })
+  }
+}
+
+impl Webhook {
+  pub fn new(url: &str, secret: &str, events: Vec<WebhookEventKind>) -> Self {
+    Self {
+      id: ObjectId::new().to_hex(),
+      url: url.into(),
+      secret: secret.into(),
+      events,
+      created_at: Utc::now().into(),
+    }
+  }
+
+  /// The webhook with its secret left out, for listing it.
+  pub fn without_secret(self) -> Self {
+    Self {
+      secret: String::new(),
+      ..self
+    }
+  }
+
+  /// Whether the webhook should be sent events of `kind`.
+  pub fn wants(&self, kind: WebhookEventKind) -> bool {
+    self.events.is_empty() || self.events.contains(&kind)
+  }
+}
+
+/// The JSON body posted to webhooks.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookEvent {
+  /// Unique identifier for the event, shared by every webhook sent it.
+  pub id: String,
+  #[serde(rename = "type")]
+  pub kind: WebhookEventKind,
+  pub created_at: DateTime,
+  /// The review, for review events. Deleted reviews are as they were just
+  /// before being deleted.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub review: Option<Review>,
+  /// The course as it is now, for course events.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub course: Option<Course>,
+}
+
+impl WebhookEvent {
+  fn new(kind: WebhookEventKind) -> Self {
+    Self {
+      id: ObjectId::new().to_hex(),
+      kind,
+      created_at: Utc::now().into(),
+      review: None,
+      course: None,
+    }
+  }
+
+  pub fn about_review(kind: WebhookEventKind, review: Review) -> Self {
+    Self {
+      review: Some(review),
+      ..Self::new(kind)
+    }
+  }
+
+  pub fn about_course(kind: WebhookEventKind, course: Course) -> Self {
+    Self {
+      course: Some(course),
+      ..Self::new(kind)
+    }
+  }
+}
+
+/// Where a webhook delivery is at.
+#[derive(
+  Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize, ToSchema,
+)]
+#[serde(rename_all = "camelCase")]
+#[typeshare]
+pub enum WebhookDeliveryStatus {
+  /// Waiting for its first attempt, or a retry.
+  #[default]
+  Pending,
+  /// The webhook responded with a success status.
+  Succeeded,
+  /// Every attempt failed, or the webhook was deleted.
+  Failed,
+}
+
+impl Into<Bson> for WebhookDeliveryStatus {
+  fn into(self) -> Bson {
+    Bson::String(
+      match self {
+        Self::Pending => "pending",
+        Self::Succeeded => "succeeded",
+        Self::Failed => "failed",
+      }
+      .into(),
+    )
+  }
+}
+
+/// One event being sent to one webhook, kept as a log of what was sent and
+/// how it went.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[typeshare]
+pub struct WebhookDelivery {
+  #[serde(rename = "_id")]
+  pub id: String,
+  pub webhook_id: String,
+  pub event_id: String,
+  pub event: WebhookEventKind,
+  /// The exact JSON body sent, which is what's signed.
+  pub payload: String,
+  pub status: WebhookDeliveryStatus,
+  /// How many times sending has been tried.
+  pub attempts: u32,
+  /// When to try sending next, while pending.
+  #[typeshare(serialized_as = "String")]
+  pub next_attempt_at: DateTime,
+  /// Status code of the last response, if there was one.
+  #[serde(default)]
+  pub response_status: Option<u16>,
+  /// Why the last attempt failed, if it did.
+  #[serde(default)]
+  pub error: Option<String>,
+  #[typeshare(serialized_as = "String")]
+  pub created_at: DateTime,
+  #[serde(default)]
+  #[typeshare(serialized_as = "Option<String>")]
+  pub delivered_at: Option<DateTime>,
+}
+
+impl Into<Bson> for WebhookDelivery {
+  fn into(self) -> Bson {
+    Bson::Document(doc! {
+      "_id": self.id,
+      "webhookId": self.webhook_id,
+      "eventId": self.event_id,
+      "event": self.event,
+      "payload": self.payload,
+      "status": self.status,
+      "attempts": self.attempts,
+      "nextAttemptAt": Bson::from(self.next_attempt_at),
+      "responseStatus": self.response_status.map(i32::from),
+      "error": self.error,
+      "createdAt": Bson::from(self.created_at),
+      "deliveredAt": self.delivered_at.map(Bson::from),
+    })
+  }
+}
+
+impl WebhookDelivery {
+  /// A delivery of `event` to `webhook`, due right away.
+  pub fn new(
+    webhook: &Webhook,
+    event: &WebhookEvent,
+  ) -> Result<Self, serde_json::Error> {
+    Ok(Self {
+      id: ObjectId::new().to_hex(),
+      webhook_id: webhook.id.clone(),
+      event_id: event.id.clone(),
+      event: event.kind,
+      payload: serde_json::to_string(event)?,
+      status: WebhookDeliveryStatus::Pending,
+      attempts: 0,
+      next_attempt_at: event.created_at.clone(),
+      response_status: None,
+      error: None,
+      created_at: event.created_at.clone(),
+      delivered_at: None,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn event_kinds_use_dotted_names() {
+    assert_eq!(
+      serde_json::to_string(&WebhookEventKind::ReviewCreated).unwrap(),
+      "\"review.created\""
+    );
+
+    assert_eq!(
+      Into::<Bson>::into(WebhookEventKind::CourseUpdated),
+      Bson::String("course.updated".into())
+    );
+  }
+
+  #[test]
+  fn webhooks_without_events_want_everything() {
+    let webhook = Webhook::new("https://example.com", "secret", Vec::new());
+
+    assert!(webhook.wants(WebhookEventKind::ReviewDeleted));
+
+    let webhook = Webhook {
+      events: vec![WebhookEventKind::ReviewCreated],
+      ..webhook
+    };
+
+    assert!(webhook.wants(WebhookEventKind::ReviewCreated));
+    assert!(!webhook.wants(WebhookEventKind::CourseUpdated));
+  }
+
+  #[test]
+  fn deliveries_carry_the_serialized_event() {
+    let webhook = Webhook::new("https://example.com", "secret", Vec::new());
+
+    let event = WebhookEvent::about_review(
+      WebhookEventKind::ReviewCreated,
+      Review {
+        course_id: "COMP202".into(),
+        ..Review::default()
+      },
+    );
+
+    let delivery = WebhookDelivery::new(&webhook, &event).unwrap();
+
+    assert_eq!(delivery.webhook_id, webhook.id);
+    assert_eq!(delivery.event_id, event.id);
+    assert_eq!(delivery.status, WebhookDeliveryStatus::Pending);
+
+    let payload =
+      serde_json::from_str::<serde_json::Value>(&delivery.payload).unwrap();
+
+    assert_eq!(payload["type"], "review.created");
+    assert_eq!(payload["review"]["courseId"], "COMP202");
+    assert!(payload.get("course").is_none());
+
+    let Bson::Document(document) = delivery.clone().into() else {
+      panic!("expected a document");
+    };
+
+    assert!(matches!(
+      document.get("nextAttemptAt"),
+      Some(Bson::DateTime(_))
+    ));
+
+    let mut bytes = Vec::new();
+
+    document.to_writer(&mut bytes).unwrap();
+
+    assert_eq!(
+      bson::from_slice::<WebhookDelivery>(&bytes).unwrap(),
+      delivery
+    );
+  }
+}
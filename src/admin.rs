@@ -0,0 +1,50 @@
+use super::*;
+
+/// Email addresses of the users allowed to use admin endpoints.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Admins(Arc<HashSet<String>>);
+
+impl Admins {
+  pub(crate) fn new<I: IntoIterator<Item = S>, S: AsRef<str>>(
+    emails: I,
+  ) -> Self {
+    Self(Arc::new(
+      emails
+        .into_iter()
+        .map(|email| email.as_ref().trim().to_lowercase())
+        .filter(|email| !email.is_empty())
+        .collect(),
+    ))
+  }
+
+  fn contains(&self, user: &User) -> bool {
+    self.0.contains(&user.mail().to_lowercase())
+  }
+}
+
+/// A signed in user who is also an admin.
+pub(crate) struct Admin(#[allow(unused)] pub(crate) User);
+
+impl<S> FromRequestParts<S> for Admin
+where
+  Admins: FromRef<S>,
+  MongodbSessionStore: FromRef<S>,
+  S: Send + Sync,
+{
+  type Rejection = Response;
+
+  async fn from_request_parts(
+    parts: &mut Parts,
+    state: &S,
+  ) -> Result<Self, Self::Rejection> {
+    let user = <User as FromRequestParts<S>>::from_request_parts(parts, state)
+      .await
+      .map_err(IntoResponse::into_response)?;
+
+    if !Admins::from_ref(state).contains(&user) {
+      return Err(StatusCode::FORBIDDEN.into_response());
+    }
+
+    Ok(Self(user))
+  }
+}
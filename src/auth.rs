@@ -140,7 +140,7 @@ pub(crate) async fn login_authorized(
 
   let mut session = Session::new();
 
-  session.expire_in(Duration::from_secs(60 * 60 * 24 * 7));
+  session.expire_in(state.session_lifetime);
 
   debug!("Inserting user data into session...");
 
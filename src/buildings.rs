@@ -0,0 +1,125 @@
+use super::*;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub(crate) struct GetBuildingCoursesParams {
+  /// Term to filter courses by (e.g. `Fall 2025`).
+  pub(crate) term: Option<String>,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BuildingCourse {
+  /// The course ID.
+  pub(crate) course_id: String,
+  /// The course title.
+  pub(crate) title: String,
+  /// Term the course meets in the building.
+  pub(crate) term: String,
+  /// Rooms in the building the course meets in, if known.
+  pub(crate) rooms: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GetBuildingCoursesPayload {
+  /// The building.
+  pub(crate) building: Building,
+  /// Courses meeting in the building, by course ID and then term.
+  pub(crate) courses: Vec<BuildingCourse>,
+}
+
+#[utoipa::path(
+  get,
+  path = "/buildings",
+  tag = "buildings",
+  description = "Get every known campus building.",
+  responses(
+    (status = StatusCode::OK, description = "Campus buildings sorted by code.", body = [Building]),
+    (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error.", body = String)
+  )
+)]
+pub(crate) async fn get_buildings(
+  AppState(db): AppState<Arc<Db>>,
+) -> Result<impl IntoResponse> {
+  Ok((StatusCode::OK, Json(db.buildings().await?)))
+}
+
+#[utoipa::path(
+  get,
+  path = "/buildings/{code}/courses",
+  tag = "buildings",
+  description = "Get the courses that meet in a building.",
+  params(
+    ("code" = String, Path, description = "Building code, e.g. `BURN`."),
+    ("term" = Option<String>, Query, description = "Term to filter courses by."),
+  ),
+  responses(
+    (status = StatusCode::OK, description = "Courses meeting in the building.", body = GetBuildingCoursesPayload),
+    (status = StatusCode::NOT_FOUND, description = "Building not found."),
+    (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error.", body = String)
+  )
+)]
+pub(crate) async fn get_building_courses(
+  Path(code): Path<String>,
+  Query(params): Query<GetBuildingCoursesParams>,
+  AppState(db): AppState<Arc<Db>>,
+) -> Result<impl IntoResponse> {
+  let code = code.to_uppercase();
+
+  let Some(building) = db.find_building(&code).await? else {
+    return Ok((StatusCode::NOT_FOUND, Json(None)));
+  };
+
+  let courses = db
+    .find_courses_by_building(&code, params.term.as_deref())
+    .await?
+    .into_iter()
+    .flat_map(|course| {
+      course
+        .schedule
+        .clone()
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|schedule| {
+          let term = schedule.term?;
+
+          if params.term.as_ref().is_some_and(|filter| *filter != term) {
+            return None;
+          }
+
+          let located = schedule
+            .blocks
+            .unwrap_or_default()
+            .into_iter()
+            .flat_map(|block| block.rooms)
+            .filter(|room| room.building == code)
+            .collect::<Vec<_>>();
+
+          if located.is_empty() {
+            return None;
+          }
+
+          let mut rooms = located
+            .into_iter()
+            .filter_map(|room| room.room)
+            .collect::<Vec<_>>();
+
+          rooms.sort();
+          rooms.dedup();
+
+          Some(BuildingCourse {
+            course_id: course.id.clone(),
+            title: course.title.clone(),
+            term,
+            rooms,
+          })
+        })
+        .collect::<Vec<_>>()
+    })
+    .collect();
+
+  Ok((
+    StatusCode::OK,
+    Json(Some(GetBuildingCoursesPayload { building, courses })),
+  ))
+}
@@ -0,0 +1,843 @@
+use {
+  super::*,
+  lettre::message::Mailbox,
+  serde::{Deserializer, de},
+  std::path::Path,
+};
+
+/// Which deployment the server is running as.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Environment {
+  #[default]
+  Development,
+  Production,
+}
+
+impl Display for Environment {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "{}",
+      match self {
+        Self::Development => "development",
+        Self::Production => "production",
+      }
+    )
+  }
+}
+
+/// Where the server keeps objects, like the hashes of seeded files.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum StorageBackend {
+  /// Files under a local directory.
+  Local,
+  /// Memory, so nothing survives a restart.
+  Memory,
+  /// An S3 bucket, or one in an S3-compatible store.
+  S3,
+}
+
+/// A value that's kept out of logs and debug output.
+#[derive(Clone, Default, Deserialize, PartialEq)]
+#[serde(transparent)]
+pub(crate) struct Secret(pub(crate) String);
+
+impl Debug for Secret {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(f, "<redacted>")
+  }
+}
+
+impl From<String> for Secret {
+  fn from(value: String) -> Self {
+    Self(value)
+  }
+}
+
+/// Parses durations like `500ms`, `30s`, `15m`, `12h` or `7d`.
+pub(crate) fn parse_duration(s: &str) -> Result<Duration, anyhow::Error> {
+  let invalid = || anyhow!("Invalid duration `{s}`, expected e.g. `30s`");
+
+  let s = s.trim();
+
+  let split = s.find(|c: char| !c.is_ascii_digit()).ok_or_else(invalid)?;
+
+  let (amount, unit) = s.split_at(split);
+
+  let amount = amount.parse::<u64>().map_err(|_| invalid())?;
+
+  let seconds = |multiplier: u64| {
+    amount
+      .checked_mul(multiplier)
+      .map(Duration::from_secs)
+      .ok_or_else(invalid)
+  };
+
+  match unit {
+    "ms" => Ok(Duration::from_millis(amount)),
+    "s" => seconds(1),
+    "m" => seconds(60),
+    "h" => seconds(60 * 60),
+    "d" => seconds(60 * 60 * 24),
+    _ => Err(invalid()),
+  }
+}
+
+fn deserialize_duration<'de, D: Deserializer<'de>>(
+  deserializer: D,
+) -> Result<Option<Duration>, D::Error> {
+  parse_duration(&String::deserialize(deserializer)?)
+    .map(Some)
+    .map_err(de::Error::custom)
+}
+
+fn deserialize_from_str<'de, D, T>(
+  deserializer: D,
+) -> Result<Option<T>, D::Error>
+where
+  D: Deserializer<'de>,
+  T: FromStr,
+  T::Err: Display,
+{
+  String::deserialize(deserializer)?
+    .parse()
+    .map(Some)
+    .map_err(de::Error::custom)
+}
+
+/// Settings as given on the command line, in the environment or in a TOML
+/// config file, before defaults are filled in and they're validated.
+#[derive(Args, Clone, Debug, Default, Deserialize)]
+#[clap(about = None, long_about = None)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct Settings {
+  #[clap(
+    long,
+    env = "ADMIN_EMAILS",
+    value_delimiter = ',',
+    help = "Emails of the users allowed to use admin endpoints"
+  )]
+  pub(crate) admin_emails: Option<Vec<String>>,
+  #[clap(long, env = "ASSET_DIR", help = "Directory to serve assets from")]
+  pub(crate) asset_dir: Option<PathBuf>,
+  #[clap(long, env = "DB_NAME", help = "Database name [default: admin]")]
+  pub(crate) db_name: Option<String>,
+  #[clap(
+    long = "env",
+    env = "ENV",
+    value_enum,
+    help = "Environment to run as [default: development]"
+  )]
+  #[serde(rename = "env")]
+  pub(crate) environment: Option<Environment>,
+  #[clap(flatten)]
+  pub(crate) microsoft: MicrosoftSettings,
+  #[clap(long, env = "MONGODB_URL", help = "MongoDB connection string")]
+  pub(crate) mongodb_url: Option<String>,
+  #[clap(long, env = "PORT", help = "Port to listen on [default: 8000]")]
+  pub(crate) port: Option<u16>,
+  #[clap(flatten)]
+  pub(crate) rate_limits: RateLimitSettings,
+  #[clap(
+    long,
+    env = "SESSION_LIFETIME",
+    value_parser = parse_duration,
+    help = "How long users stay signed in [default: 7d]"
+  )]
+  #[serde(deserialize_with = "deserialize_duration")]
+  pub(crate) session_lifetime: Option<Duration>,
+  #[clap(flatten)]
+  pub(crate) smtp: SmtpSettings,
+  #[clap(flatten)]
+  pub(crate) storage: StorageSettings,
+}
+
+#[derive(Args, Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct MicrosoftSettings {
+  #[clap(
+    long = "ms-client-id",
+    env = "MS_CLIENT_ID",
+    help = "OAuth client ID"
+  )]
+  pub(crate) client_id: Option<String>,
+  #[clap(
+    long = "ms-client-secret",
+    env = "MS_CLIENT_SECRET",
+    hide_env_values = true,
+    help = "OAuth client secret"
+  )]
+  pub(crate) client_secret: Option<Secret>,
+  #[clap(
+    long = "ms-redirect-uri",
+    env = "MS_REDIRECT_URI",
+    help = "OAuth redirect URI"
+  )]
+  pub(crate) redirect_uri: Option<String>,
+}
+
+#[derive(Args, Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct RateLimitSettings {
+  #[clap(
+    long = "rate-limit-auth",
+    value_name = "POLICY",
+    env = "RATE_LIMIT_AUTH",
+    help = "Rate limit for signing in and out, e.g. `20/1m`"
+  )]
+  #[serde(deserialize_with = "deserialize_from_str")]
+  pub(crate) auth: Option<RateLimitPolicy>,
+  #[clap(
+    long = "rate-limit-interactions",
+    value_name = "POLICY",
+    env = "RATE_LIMIT_INTERACTIONS",
+    help = "Rate limit for liking and disliking reviews"
+  )]
+  #[serde(deserialize_with = "deserialize_from_str")]
+  pub(crate) interactions: Option<RateLimitPolicy>,
+  #[clap(
+    long = "rate-limit-reads",
+    value_name = "POLICY",
+    env = "RATE_LIMIT_READS",
+    help = "Rate limit for every other route"
+  )]
+  #[serde(deserialize_with = "deserialize_from_str")]
+  pub(crate) reads: Option<RateLimitPolicy>,
+  #[clap(
+    long = "rate-limit-review-writes",
+    value_name = "POLICY",
+    env = "RATE_LIMIT_REVIEW_WRITES",
+    help = "Rate limit for writing, editing and deleting reviews"
+  )]
+  #[serde(deserialize_with = "deserialize_from_str")]
+  pub(crate) review_writes: Option<RateLimitPolicy>,
+  #[clap(
+    long = "rate-limit-search",
+    value_name = "POLICY",
+    env = "RATE_LIMIT_SEARCH",
+    help = "Rate limit for search"
+  )]
+  #[serde(deserialize_with = "deserialize_from_str")]
+  pub(crate) search: Option<RateLimitPolicy>,
+  #[clap(
+    long,
+    value_name = "IP",
+    env = "TRUSTED_PROXIES",
+    value_delimiter = ',',
+    help = "Reverse proxies whose `X-Forwarded-For` entries are trusted"
+  )]
+  pub(crate) trusted_proxies: Option<Vec<IpAddr>>,
+}
+
+#[derive(Args, Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct SmtpSettings {
+  #[clap(
+    long = "smtp-from",
+    env = "SMTP_FROM",
+    help = "Address to email notifications from"
+  )]
+  pub(crate) from: Option<String>,
+  #[clap(
+    long = "smtp-host",
+    env = "SMTP_HOST",
+    help = "SMTP relay to email notifications through, if any"
+  )]
+  pub(crate) host: Option<String>,
+  #[clap(
+    long = "smtp-insecure",
+    env = "SMTP_INSECURE",
+    help = "Skip TLS, only meant for local mail catchers"
+  )]
+  pub(crate) insecure: Option<bool>,
+  #[clap(
+    long = "smtp-password",
+    env = "SMTP_PASSWORD",
+    hide_env_values = true,
+    help = "SMTP password"
+  )]
+  pub(crate) password: Option<Secret>,
+  #[clap(
+    id = "smtp_port",
+    long = "smtp-port",
+    value_name = "PORT",
+    env = "SMTP_PORT",
+    help = "SMTP port"
+  )]
+  pub(crate) port: Option<u16>,
+  #[clap(
+    long = "smtp-username",
+    env = "SMTP_USERNAME",
+    help = "SMTP username"
+  )]
+  pub(crate) username: Option<String>,
+}
+
+#[derive(Args, Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct StorageSettings {
+  #[clap(
+    long = "storage",
+    env = "STORAGE",
+    value_enum,
+    help = "Where to store seed file hashes [default: s3 in production, \
+            local otherwise]"
+  )]
+  pub(crate) backend: Option<StorageBackend>,
+  #[clap(
+    long = "s3-bucket",
+    env = "S3_BUCKET",
+    help = "S3 bucket to store objects in [default: mcgill.courses]"
+  )]
+  pub(crate) bucket: Option<String>,
+  #[clap(
+    long = "storage-dir",
+    env = "STORAGE_DIR",
+    help = "Directory to store objects in locally [default: .storage]"
+  )]
+  pub(crate) dir: Option<PathBuf>,
+  #[clap(
+    long = "s3-endpoint",
+    env = "S3_ENDPOINT",
+    help = "Endpoint of an S3-compatible store, like MinIO"
+  )]
+  pub(crate) endpoint: Option<String>,
+  #[clap(
+    long = "s3-region",
+    env = "S3_REGION",
+    help = "S3 region [default: us-east-1]"
+  )]
+  pub(crate) region: Option<String>,
+}
+
+impl Settings {
+  /// Reads settings from a TOML file.
+  pub(crate) fn read(path: &Path) -> Result<Self> {
+    let contents = fs::read_to_string(path).map_err(|error| {
+      anyhow!("Failed to read config file `{}`: {error}", path.display())
+    })?;
+
+    toml::from_str(&contents).map_err(|error| {
+      anyhow!("Invalid config file `{}`: {error}", path.display()).into()
+    })
+  }
+
+  /// These settings, falling back to `other` for any that aren't set.
+  pub(crate) fn or(self, other: Self) -> Self {
+    Self {
+      admin_emails: self.admin_emails.or(other.admin_emails),
+      asset_dir: self.asset_dir.or(other.asset_dir),
+      db_name: self.db_name.or(other.db_name),
+      environment: self.environment.or(other.environment),
+      microsoft: MicrosoftSettings {
+        client_id: self.microsoft.client_id.or(other.microsoft.client_id),
+        client_secret: self
+          .microsoft
+          .client_secret
+          .or(other.microsoft.client_secret),
+        redirect_uri: self
+          .microsoft
+          .redirect_uri
+          .or(other.microsoft.redirect_uri),
+      },
+      mongodb_url: self.mongodb_url.or(other.mongodb_url),
+      port: self.port.or(other.port),
+      rate_limits: RateLimitSettings {
+        auth: self.rate_limits.auth.or(other.rate_limits.auth),
+        interactions: self
+          .rate_limits
+          .interactions
+          .or(other.rate_limits.interactions),
+        reads: self.rate_limits.reads.or(other.rate_limits.reads),
+        review_writes: self
+          .rate_limits
+          .review_writes
+          .or(other.rate_limits.review_writes),
+        search: self.rate_limits.search.or(other.rate_limits.search),
+        trusted_proxies: self
+          .rate_limits
+          .trusted_proxies
+          .or(other.rate_limits.trusted_proxies),
+      },
+      session_lifetime: self.session_lifetime.or(other.session_lifetime),
+      smtp: SmtpSettings {
+        from: self.smtp.from.or(other.smtp.from),
+        host: self.smtp.host.or(other.smtp.host),
+        insecure: self.smtp.insecure.or(other.smtp.insecure),
+        password: self.smtp.password.or(other.smtp.password),
+        port: self.smtp.port.or(other.smtp.port),
+        username: self.smtp.username.or(other.smtp.username),
+      },
+      storage: StorageSettings {
+        backend: self.storage.backend.or(other.storage.backend),
+        bucket: self.storage.bucket.or(other.storage.bucket),
+        dir: self.storage.dir.or(other.storage.dir),
+        endpoint: self.storage.endpoint.or(other.storage.endpoint),
+        region: self.storage.region.or(other.storage.region),
+      },
+    }
+  }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct MicrosoftConfig {
+  pub(crate) client_id: String,
+  pub(crate) client_secret: Secret,
+  pub(crate) redirect_url: RedirectUrl,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct SmtpConfig {
+  pub(crate) credentials: Option<(String, Secret)>,
+  pub(crate) from: Mailbox,
+  pub(crate) host: String,
+  pub(crate) insecure: bool,
+  pub(crate) port: Option<u16>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum StorageConfig {
+  Local(PathBuf),
+  Memory,
+  S3 { bucket: String, region: Region },
+}
+
+impl StorageConfig {
+  /// The object store this describes.
+  pub(crate) fn store(&self) -> Box<dyn Object> {
+    match self {
+      Self::Local(dir) => Box::new(LocalStore { dir: dir.clone() }),
+      Self::Memory => Box::new(MemoryStore::default()),
+      Self::S3 { bucket, region } => Box::new(S3Store {
+        bucket: bucket.clone(),
+        client: S3Client::new(region.clone()),
+      }),
+    }
+  }
+}
+
+/// Validated server configuration, with defaults filled in.
+#[derive(Clone, Debug)]
+pub(crate) struct Config {
+  pub(crate) admins: Admins,
+  pub(crate) asset_dir: Option<PathBuf>,
+  pub(crate) db_name: String,
+  pub(crate) environment: Environment,
+  pub(crate) microsoft: MicrosoftConfig,
+  pub(crate) mongodb_url: String,
+  pub(crate) port: u16,
+  pub(crate) rate_limits: RateLimits,
+  pub(crate) session_lifetime: Duration,
+  /// Where to email notifications through, if anywhere.
+  pub(crate) smtp: Option<SmtpConfig>,
+  pub(crate) storage: StorageConfig,
+}
+
+impl Config {
+  const DEFAULT_MONGODB_URL: &'static str =
+    "mongodb://localhost:27017/?directConnection=true&replicaSet=rs0";
+
+  /// Command line and environment `settings`, falling back to those in the
+  /// TOML file at `path`.
+  pub(crate) fn load(settings: Settings, path: Option<&Path>) -> Result<Self> {
+    let file = match path {
+      Some(path) => Settings::read(path)?,
+      None => Settings::default(),
+    };
+
+    settings.or(file).try_into()
+  }
+}
+
+/// Errors for a required setting that wasn't given anywhere.
+fn required<T>(
+  value: Option<T>,
+  description: &str,
+  flag: &str,
+  env: &str,
+  key: &str,
+) -> Result<T> {
+  value.ok_or_else(|| {
+    anyhow!(
+      "Missing {description}, set it with `--{flag}`, `{env}` or `{key}` in \
+       the config file"
+    )
+    .into()
+  })
+}
+
+impl TryFrom<Settings> for Config {
+  type Error = Error;
+
+  fn try_from(settings: Settings) -> Result<Self> {
+    let Settings {
+      admin_emails,
+      asset_dir,
+      db_name,
+      environment,
+      microsoft,
+      mongodb_url,
+      port,
+      rate_limits,
+      session_lifetime,
+      smtp,
+      storage,
+    } = settings;
+
+    let redirect_uri = required(
+      microsoft.redirect_uri,
+      "Microsoft OAuth redirect URI",
+      "ms-redirect-uri",
+      "MS_REDIRECT_URI",
+      "microsoft.redirect_uri",
+    )?;
+
+    let microsoft = MicrosoftConfig {
+      client_id: required(
+        microsoft.client_id,
+        "Microsoft OAuth client ID",
+        "ms-client-id",
+        "MS_CLIENT_ID",
+        "microsoft.client_id",
+      )?,
+      client_secret: required(
+        microsoft.client_secret,
+        "Microsoft OAuth client secret",
+        "ms-client-secret",
+        "MS_CLIENT_SECRET",
+        "microsoft.client_secret",
+      )?,
+      redirect_url: RedirectUrl::new(redirect_uri.clone()).map_err(
+        |error| {
+          anyhow!(
+            "Invalid Microsoft OAuth redirect URI `{redirect_uri}`: {error}"
+          )
+        },
+      )?,
+    };
+
+    let mongodb_url =
+      mongodb_url.unwrap_or_else(|| Self::DEFAULT_MONGODB_URL.into());
+
+    if !mongodb_url.starts_with("mongodb://")
+      && !mongodb_url.starts_with("mongodb+srv://")
+    {
+      return Err(anyhow!(
+        "Invalid MongoDB URL, expected a `mongodb://` or `mongodb+srv://` connection string"
+      )
+      .into());
+    }
+
+    let db_name = db_name.unwrap_or_else(|| "admin".into());
+
+    if db_name.is_empty() {
+      return Err(anyhow!("Database name can't be empty").into());
+    }
+
+    let session_lifetime =
+      session_lifetime.unwrap_or(Duration::from_secs(60 * 60 * 24 * 7));
+
+    if session_lifetime.is_zero() {
+      return Err(anyhow!("Session lifetime can't be zero").into());
+    }
+
+    let defaults = RateLimits::default();
+
+    let rate_limits = RateLimits {
+      auth: rate_limits.auth.unwrap_or(defaults.auth),
+      interactions: rate_limits.interactions.unwrap_or(defaults.interactions),
+      reads: rate_limits.reads.unwrap_or(defaults.reads),
+      review_writes: rate_limits
+        .review_writes
+        .unwrap_or(defaults.review_writes),
+      search: rate_limits.search.unwrap_or(defaults.search),
+      trusted_proxies: rate_limits.trusted_proxies.unwrap_or_default(),
+    };
+
+    let smtp = match smtp.host {
+      Some(host) => {
+        let from = smtp
+          .from
+          .unwrap_or_else(|| "mcgill.courses <noreply@mcgill.courses>".into());
+
+        Some(SmtpConfig {
+          credentials: match (smtp.username, smtp.password) {
+            (Some(username), Some(password)) => Some((username, password)),
+            (None, None) => None,
+            _ => {
+              return Err(
+                anyhow!("SMTP username and password must be set together")
+                  .into(),
+              );
+            }
+          },
+          from: from.parse().map_err(|error| {
+            anyhow!("Invalid SMTP from address `{from}`: {error}")
+          })?,
+          host,
+          insecure: smtp.insecure.unwrap_or_default(),
+          port: smtp.port,
+        })
+      }
+      None => None,
+    };
+
+    let environment = environment.unwrap_or_default();
+
+    let storage = match storage.backend.unwrap_or(match environment {
+      Environment::Development => StorageBackend::Local,
+      Environment::Production => StorageBackend::S3,
+    }) {
+      StorageBackend::Local => StorageConfig::Local(
+        storage.dir.unwrap_or_else(|| PathBuf::from(".storage")),
+      ),
+      StorageBackend::Memory => StorageConfig::Memory,
+      StorageBackend::S3 => {
+        let bucket = storage.bucket.unwrap_or_else(|| "mcgill.courses".into());
+
+        if bucket.is_empty() {
+          return Err(anyhow!("S3 bucket name can't be empty").into());
+        }
+
+        let name = storage.region.unwrap_or_else(|| "us-east-1".into());
+
+        let region = match storage.endpoint {
+          Some(endpoint) => {
+            Url::parse(&endpoint).map_err(|error| {
+              anyhow!("Invalid S3 endpoint `{endpoint}`: {error}")
+            })?;
+
+            Region::Custom { name, endpoint }
+          }
+          None => name
+            .parse()
+            .map_err(|error| anyhow!("Invalid S3 region `{name}`: {error}"))?,
+        };
+
+        StorageConfig::S3 { bucket, region }
+      }
+    };
+
+    Ok(Self {
+      admins: Admins::new(admin_emails.unwrap_or_default()),
+      asset_dir,
+      db_name,
+      environment,
+      microsoft,
+      mongodb_url,
+      port: port.unwrap_or(8000),
+      rate_limits,
+      session_lifetime,
+      smtp,
+      storage,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn settings() -> Settings {
+    Settings {
+      microsoft: MicrosoftSettings {
+        client_id: Some("id".into()),
+        client_secret: Some(Secret("secret".into())),
+        redirect_uri: Some("http://localhost:8000/api/auth/authorized".into()),
+      },
+      ..Default::default()
+    }
+  }
+
+  #[test]
+  fn durations_parse() {
+    assert_eq!(parse_duration("500ms").unwrap(), Duration::from_millis(500));
+    assert_eq!(parse_duration("15m").unwrap(), Duration::from_secs(900));
+    assert_eq!(
+      parse_duration(" 7d ").unwrap(),
+      Duration::from_secs(604_800)
+    );
+
+    for invalid in ["", "7", "d", "7w", "-1s", "99999999999999999999d"] {
+      assert!(parse_duration(invalid).is_err(), "{invalid}");
+    }
+  }
+
+  #[test]
+  fn defaults_are_filled_in() {
+    let config = Config::try_from(settings()).unwrap();
+
+    assert_eq!(config.db_name, "admin");
+    assert_eq!(config.environment, Environment::Development);
+    assert_eq!(config.mongodb_url, Config::DEFAULT_MONGODB_URL);
+    assert_eq!(config.port, 8000);
+    assert_eq!(config.rate_limits, RateLimits::default());
+    assert_eq!(config.session_lifetime, Duration::from_secs(604_800));
+    assert!(config.smtp.is_none());
+    assert_eq!(config.storage, StorageConfig::Local(".storage".into()));
+
+    let config = Config::try_from(Settings {
+      environment: Some(Environment::Production),
+      ..settings()
+    })
+    .unwrap();
+
+    assert_eq!(
+      config.storage,
+      StorageConfig::S3 {
+        bucket: "mcgill.courses".into(),
+        region: Region::UsEast1,
+      }
+    );
+  }
+
+  #[test]
+  fn s3_compatible_stores_are_configured_by_endpoint() {
+    let config = Config::try_from(
+      Settings {
+        storage: StorageSettings {
+          endpoint: Some("http://localhost:9000".into()),
+          ..Default::default()
+        },
+        ..settings()
+      }
+      .or(
+        toml::from_str::<Settings>(
+          r#"
+            [storage]
+            backend = "s3"
+            bucket = "seeds"
+          "#,
+        )
+        .unwrap(),
+      ),
+    )
+    .unwrap();
+
+    assert_eq!(
+      config.storage,
+      StorageConfig::S3 {
+        bucket: "seeds".into(),
+        region: Region::Custom {
+          name: "us-east-1".into(),
+          endpoint: "http://localhost:9000".into(),
+        },
+      }
+    );
+  }
+
+  #[test]
+  fn settings_fall_back_to_the_config_file() {
+    let file = toml::from_str::<Settings>(
+      r#"
+        admin_emails = ["admin@mail.mcgill.ca"]
+        db_name = "file"
+        env = "production"
+        port = 9000
+        session_lifetime = "1d"
+
+        [microsoft]
+        client_id = "file"
+
+        [rate_limits]
+        search = "5/1s"
+        trusted_proxies = ["10.0.0.1"]
+
+        [smtp]
+        host = "smtp.example.com"
+        username = "user"
+        password = "pass"
+      "#,
+    )
+    .unwrap();
+
+    let config = Config::try_from(
+      Settings {
+        port: Some(8080),
+        ..settings()
+      }
+      .or(file),
+    )
+    .unwrap();
+
+    assert_eq!(config.db_name, "file");
+    assert_eq!(config.environment, Environment::Production);
+    assert_eq!(config.microsoft.client_id, "id");
+    assert_eq!(config.port, 8080);
+    assert_eq!(config.session_lifetime, Duration::from_secs(86_400));
+    assert_eq!(config.rate_limits.search, "5/1s".parse().unwrap());
+    assert_eq!(config.rate_limits.reads, RateLimits::default().reads);
+    assert_eq!(
+      config.rate_limits.trusted_proxies,
+      vec![IpAddr::from([10, 0, 0, 1])]
+    );
+
+    let smtp = config.smtp.unwrap();
+
+    assert_eq!(smtp.host, "smtp.example.com");
+    assert_eq!(
+      smtp.credentials,
+      Some(("user".into(), Secret("pass".into())))
+    );
+  }
+
+  #[test]
+  fn invalid_settings_are_rejected() {
+    assert!(toml::from_str::<Settings>("unknown = 1").is_err());
+    assert!(toml::from_str::<Settings>("env = \"staging\"").is_err());
+    assert!(
+      toml::from_str::<Settings>("[rate_limits]\nreads = \"fast\"").is_err()
+    );
+
+    let error =
+      |settings: Settings| Config::try_from(settings).unwrap_err().to_string();
+
+    assert_eq!(
+      error(Settings::default()),
+      "Missing Microsoft OAuth redirect URI, set it with `--ms-redirect-uri`, \
+       `MS_REDIRECT_URI` or `microsoft.redirect_uri` in the config file"
+    );
+
+    assert!(
+      error(Settings {
+        mongodb_url: Some("localhost:27017".into()),
+        ..settings()
+      })
+      .starts_with("Invalid MongoDB URL")
+    );
+
+    assert_eq!(
+      error(Settings {
+        smtp: SmtpSettings {
+          host: Some("smtp.example.com".into()),
+          username: Some("user".into()),
+          ..Default::default()
+        },
+        ..settings()
+      }),
+      "SMTP username and password must be set together"
+    );
+
+    assert!(
+      error(Settings {
+        session_lifetime: Some(Duration::ZERO),
+        ..settings()
+      })
+      .starts_with("Session lifetime")
+    );
+
+    assert!(
+      error(Settings {
+        storage: StorageSettings {
+          backend: Some(StorageBackend::S3),
+          region: Some("mars-1".into()),
+          ..Default::default()
+        },
+        ..settings()
+      })
+      .starts_with("Invalid S3 region `mars-1`")
+    );
+  }
+}
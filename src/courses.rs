@@ -59,8 +59,16 @@ pub(crate) async fn get_courses(
 
 #[derive(Debug, Deserialize, ToSchema)]
 pub(crate) struct GetCourseByIdParams {
+  /// Whether to include the course's historical class averages.
+  with_averages: Option<bool>,
+  /// Whether to include the course's next upcoming final exam.
+  with_next_exam: Option<bool>,
   /// Whether to include reviews in the response.
   with_reviews: Option<bool>,
+  /// Instructor to compute filtered review statistics for.
+  instructor: Option<String>,
+  /// Term to compute filtered review statistics for (e.g. `Fall 2025`).
+  term: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -70,6 +78,84 @@ pub(crate) struct GetCourseByIdPayload {
   pub(crate) course: Course,
   /// Reviews for the course (sorted by timestamp, newest first).
   pub(crate) reviews: Vec<Review>,
+  /// Historical class averages, oldest term first (if requested).
+  #[serde(default)]
+  pub(crate) averages: Vec<CourseAverage>,
+  /// The course's next upcoming final exam (if requested and scheduled).
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub(crate) next_exam: Option<FinalExam>,
+  /// Cross-listed and mutually exclusive courses.
+  #[serde(default)]
+  pub(crate) equivalent_courses: Vec<EquivalentCourse>,
+  /// Review statistics narrowed to the requested instructor and/or term (if
+  /// either was requested). Unfiltered statistics are on the course itself.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub(crate) filtered_stats: Option<ReviewStats>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum Equivalence {
+  /// The same course offered under another code.
+  CrossListed,
+  /// A course that can't be taken for credit alongside this one.
+  MutuallyExclusive,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct EquivalentCourse {
+  /// The equivalent course's ID.
+  pub(crate) id: String,
+  /// The equivalent course's title (if it's in the catalogue).
+  pub(crate) title: Option<String>,
+  /// How the equivalent course relates to this one.
+  pub(crate) equivalence: Equivalence,
+}
+
+async fn equivalent_courses(
+  db: &Db,
+  course: &Course,
+) -> Result<Vec<EquivalentCourse>> {
+  let related = course
+    .cross_listings
+    .iter()
+    .map(|id| (id, Equivalence::CrossListed))
+    .chain(
+      course
+        .excluded_courses
+        .iter()
+        .filter(|id| !course.cross_listings.contains(id))
+        .map(|id| (id, Equivalence::MutuallyExclusive)),
+    )
+    .collect::<Vec<_>>();
+
+  if related.is_empty() {
+    return Ok(Vec::new());
+  }
+
+  let found = db
+    .find_courses_by_ids(
+      &related
+        .iter()
+        .map(|(id, _)| (*id).clone())
+        .collect::<Vec<_>>(),
+    )
+    .await?;
+
+  Ok(
+    related
+      .into_iter()
+      .map(|(id, equivalence)| EquivalentCourse {
+        id: id.clone(),
+        title: found
+          .iter()
+          .find(|course| course.id == *id)
+          .map(|course| course.title.clone()),
+        equivalence,
+      })
+      .collect(),
+  )
 }
 
 #[utoipa::path(
@@ -78,7 +164,11 @@ pub(crate) struct GetCourseByIdPayload {
   description = "Get information about a specific course by its ID.",
   params(
     ("id" = String, Path, description = "Course ID to get course information for."),
+    ("with_averages" = Option<bool>, Query, description = "Whether to include the course's historical class averages."),
+    ("with_next_exam" = Option<bool>, Query, description = "Whether to include the course's next upcoming final exam."),
     ("with_reviews" = Option<bool>, Query, description = "Whether to include reviews in the response."),
+    ("instructor" = Option<String>, Query, description = "Instructor to compute filtered review statistics for."),
+    ("term" = Option<String>, Query, description = "Term to compute filtered review statistics for."),
   ),
   responses(
     (status = StatusCode::OK, description = "Information about a specific course.", body = GetCourseByIdPayload),
@@ -93,31 +183,278 @@ pub(crate) async fn get_course_by_id(
 ) -> Result<impl IntoResponse> {
   Ok(match state.db.find_course_by_id(&id).await? {
     Some(course) => {
-      let mut reviews = params
-        .with_reviews
-        .unwrap_or(false)
-        .then_some(state.db.find_reviews_by_course_id(&id).await?);
-
-      if let Some(ref mut reviews) = reviews {
-        reviews.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
-
-        return Ok((
-          StatusCode::OK,
-          Json(Some(GetCourseByIdPayload {
-            course,
-            reviews: reviews.to_vec(),
-          })),
-        ));
-      }
+      let mut reviews = if params.with_reviews.unwrap_or(false) {
+        state.db.find_reviews_by_course_id(&id).await?
+      } else {
+        Vec::new()
+      };
+
+      reviews.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+
+      let averages = if params.with_averages.unwrap_or(false) {
+        state.db.course_averages(&id).await?
+      } else {
+        Vec::new()
+      };
+
+      let next_exam = if params.with_next_exam.unwrap_or(false) {
+        state.db.next_exam(&id).await?
+      } else {
+        None
+      };
+
+      let equivalent_courses = equivalent_courses(&state.db, &course).await?;
+
+      let instructors = match &params.instructor {
+        Some(name) => {
+          Some(match state.db.find_instructor_by_name(name).await? {
+            Some(instructor) => instructor
+              .aliases
+              .into_iter()
+              .chain([instructor.name])
+              .collect(),
+            None => vec![name.clone()],
+          })
+        }
+        None => None,
+      };
+
+      let filtered_stats = if instructors.is_some() || params.term.is_some() {
+        Some(
+          state
+            .db
+            .course_review_stats(
+              &course,
+              instructors.as_deref(),
+              params.term.as_deref(),
+            )
+            .await?,
+        )
+      } else {
+        None
+      };
 
       (
         StatusCode::OK,
         Json(Some(GetCourseByIdPayload {
           course,
-          reviews: vec![],
+          reviews,
+          averages,
+          next_exam,
+          equivalent_courses,
+          filtered_stats,
         })),
       )
     }
     None => (StatusCode::NOT_FOUND, Json(None)),
   })
 }
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub(crate) struct GetCourseEligibilityParams {
+  /// Comma-separated IDs of courses the student has taken or is taking.
+  taken: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GetCourseEligibilityPayload {
+  /// Whether the student can take the course for credit, if known.
+  pub(crate) eligible: Option<bool>,
+  /// Whether the student's courses satisfy the course's prerequisites, if
+  /// known. Without a parsed requirement tree, listed prerequisites can't
+  /// tell which are alternatives, so this is left out.
+  pub(crate) prerequisites_met: Option<bool>,
+  /// Taken courses that exclude the student from this one.
+  pub(crate) exclusions: Vec<String>,
+  /// Programs, faculties and years the course is restricted to.
+  pub(crate) restricted_to: RestrictedTo,
+}
+
+#[utoipa::path(
+  get,
+  path = "/courses/{id}/eligibility",
+  description = "Check whether a student who has taken the given courses can take a course.",
+  params(
+    ("id" = String, Path, description = "Course ID to check eligibility for."),
+    ("taken" = Option<String>, Query, description = "Comma-separated IDs of courses the student has taken or is taking."),
+  ),
+  responses(
+    (status = StatusCode::OK, description = "Eligibility for a specific course.", body = GetCourseEligibilityPayload),
+    (status = StatusCode::NOT_FOUND, description = "Course not found."),
+    (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error.", body = String)
+  )
+)]
+pub(crate) async fn get_course_eligibility(
+  Path(id): Path<String>,
+  Query(params): Query<GetCourseEligibilityParams>,
+  AppState(db): AppState<Arc<Db>>,
+) -> Result<impl IntoResponse> {
+  let Some(course) = db.find_course_by_id(&id).await? else {
+    return Ok((StatusCode::NOT_FOUND, Json(None)));
+  };
+
+  let taken = params
+    .taken
+    .unwrap_or_default()
+    .split(',')
+    .map(Course::normalize_id)
+    .filter(|id| !id.is_empty())
+    .collect::<Vec<_>>();
+
+  // The flat list of prerequisites mixes in alternatives, so without a
+  // parsed requirement tree there's no telling whether they're met.
+  let prerequisites_met = match &course.logical_prerequisites {
+    Some(prerequisites) => Some(prerequisites.is_satisfied_by(&taken)),
+    None if course.prerequisites.is_empty() => Some(true),
+    None => None,
+  };
+
+  let exclusions = course
+    .excluded_courses
+    .iter()
+    .filter(|excluded| taken.contains(&Course::normalize_id(excluded)))
+    .cloned()
+    .collect::<Vec<_>>();
+
+  Ok((
+    StatusCode::OK,
+    Json(Some(GetCourseEligibilityPayload {
+      eligible: if exclusions.is_empty() {
+        prerequisites_met
+      } else {
+        Some(false)
+      },
+      prerequisites_met,
+      exclusions,
+      restricted_to: course.restricted_to,
+    })),
+  ))
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CourseHistoryEntry {
+  /// The catalogue year, e.g. `2025-2026`.
+  pub(crate) catalogue_year: String,
+  /// Fields that changed since the previous recorded catalogue year (empty
+  /// for the earliest one).
+  pub(crate) changes: Vec<FieldChange>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GetCourseHistoryPayload {
+  /// The course ID.
+  pub(crate) course_id: String,
+  /// Recorded catalogue years, oldest first.
+  pub(crate) history: Vec<CourseHistoryEntry>,
+}
+
+#[utoipa::path(
+  get,
+  path = "/courses/{id}/history",
+  description = "Get how a course changed across catalogue years.",
+  params(
+    ("id" = String, Path, description = "Course ID to get the history for."),
+  ),
+  responses(
+    (status = StatusCode::OK, description = "Field-level changes between catalogue years.", body = GetCourseHistoryPayload),
+    (status = StatusCode::NOT_FOUND, description = "Course not found."),
+    (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error.", body = String)
+  )
+)]
+pub(crate) async fn get_course_history(
+  Path(id): Path<String>,
+  AppState(db): AppState<Arc<Db>>,
+) -> Result<impl IntoResponse> {
+  if db.find_course_by_id(&id).await?.is_none() {
+    return Ok((StatusCode::NOT_FOUND, Json(None)));
+  }
+
+  let versions = db.course_versions(&id).await?;
+
+  let history = versions
+    .iter()
+    .enumerate()
+    .map(|(index, version)| CourseHistoryEntry {
+      catalogue_year: version.catalogue_year.clone(),
+      changes: index
+        .checked_sub(1)
+        .map(|previous| version.changes_from(&versions[previous].course))
+        .unwrap_or_default(),
+    })
+    .collect();
+
+  Ok((
+    StatusCode::OK,
+    Json(Some(GetCourseHistoryPayload {
+      course_id: id,
+      history,
+    })),
+  ))
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TermGradeAverage {
+  /// The term, e.g. `Fall 2024`.
+  pub(crate) term: String,
+  /// The class average as a letter grade, e.g. `B+`.
+  pub(crate) average: String,
+  /// The class average in grade points, if the letter grade is recognized.
+  pub(crate) gpa: Option<f32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GetCourseAveragesPayload {
+  /// The course ID.
+  pub(crate) course_id: String,
+  /// Recorded class averages, oldest term first.
+  pub(crate) averages: Vec<TermGradeAverage>,
+  /// Statistics over the averages' grade points, if any are recognized.
+  pub(crate) trend: Option<GradeTrend>,
+}
+
+#[utoipa::path(
+  get,
+  path = "/courses/{id}/averages",
+  description = "Get a course's historical class averages in grade points, along with trend statistics.",
+  params(
+    ("id" = String, Path, description = "Course ID to get averages for."),
+  ),
+  responses(
+    (status = StatusCode::OK, description = "Class averages and their trend.", body = GetCourseAveragesPayload),
+    (status = StatusCode::NOT_FOUND, description = "Course not found."),
+    (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error.", body = String)
+  )
+)]
+pub(crate) async fn get_course_averages(
+  Path(id): Path<String>,
+  AppState(db): AppState<Arc<Db>>,
+) -> Result<impl IntoResponse> {
+  if db.find_course_by_id(&id).await?.is_none() {
+    return Ok((StatusCode::NOT_FOUND, Json(None)));
+  }
+
+  let averages = db.course_averages(&id).await?;
+
+  let trend = GradeTrend::new(&averages);
+
+  Ok((
+    StatusCode::OK,
+    Json(Some(GetCourseAveragesPayload {
+      course_id: id,
+      averages: averages
+        .into_iter()
+        .map(|average| TermGradeAverage {
+          gpa: average.gpa(),
+          term: average.term,
+          average: average.average,
+        })
+        .collect(),
+      trend,
+    })),
+  ))
+}
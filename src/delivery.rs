@@ -0,0 +1,376 @@
+use {
+  super::*,
+  lettre::{
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+    message::{Mailbox, header::ContentType},
+    transport::smtp::authentication::Credentials,
+  },
+  tokio::sync::Notify,
+};
+
+/// When a channel is asked to deliver notifications.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Delivery {
+  /// Shortly after a review is written, in the background.
+  Immediate,
+  /// Batched up periodically, for users who asked for digests.
+  Digest,
+}
+
+/// A way of getting notifications in front of users, beyond showing them on
+/// the site.
+#[async_trait]
+pub(crate) trait Channel: Send + Sync {
+  fn name(&self) -> &'static str;
+
+  /// Whether a user with `preferences` should hear from this channel for
+  /// `delivery`.
+  fn wants(
+    &self,
+    preferences: &NotificationPreferences,
+    delivery: Delivery,
+  ) -> bool;
+
+  async fn deliver(
+    &self,
+    preferences: &NotificationPreferences,
+    notifications: &[Notification],
+  ) -> Result;
+}
+
+/// Emails notifications over SMTP, marking them as emailed once sent.
+pub(crate) struct EmailChannel {
+  db: Arc<Db>,
+  from: Mailbox,
+  mailer: AsyncSmtpTransport<Tokio1Executor>,
+}
+
+impl EmailChannel {
+  pub(crate) fn new(
+    db: Arc<Db>,
+    mailer: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+  ) -> Self {
+    Self { db, from, mailer }
+  }
+
+  /// Sends through the relay in `config`, skipping TLS if it's marked
+  /// insecure, which is only meant for local mail catchers.
+  pub(crate) fn from_config(db: Arc<Db>, config: &SmtpConfig) -> Result<Self> {
+    let mut builder = if config.insecure {
+      AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.host)
+    } else {
+      AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host)?
+    };
+
+    if let Some(port) = config.port {
+      builder = builder.port(port);
+    }
+
+    if let Some((username, password)) = &config.credentials {
+      builder = builder
+        .credentials(Credentials::new(username.clone(), password.0.clone()));
+    }
+
+    Ok(Self::new(db, builder.build(), config.from.clone()))
+  }
+
+  fn message(
+    &self,
+    to: Mailbox,
+    notifications: &[Notification],
+  ) -> Result<Message> {
+    let subject = match notifications {
+      [notification] => notification.summary(),
+      _ => format!("{} new notifications", notifications.len()),
+    };
+
+    let body = notifications
+      .iter()
+      .map(|notification| {
+        let mut section = notification.summary();
+
+        if let Some(review) = &notification.review {
+          section.push_str(&format!(
+            " (rating {}/5, difficulty {}/5)\n\n{}",
+            review.rating, review.difficulty, review.content,
+          ));
+        }
+
+        format!(
+          "{section}\n\nhttps://mcgill.courses/course/{}",
+          notification.course_id.to_lowercase()
+        )
+      })
+      .collect::<Vec<_>>()
+      .join("\n\n---\n\n");
+
+    Ok(
+      Message::builder()
+        .from(self.from.clone())
+        .to(to)
+        .subject(subject)
+        .header(ContentType::TEXT_PLAIN)
+        .body(body)?,
+    )
+  }
+}
+
+#[async_trait]
+impl Channel for EmailChannel {
+  fn name(&self) -> &'static str {
+    "email"
+  }
+
+  fn wants(
+    &self,
+    preferences: &NotificationPreferences,
+    delivery: Delivery,
+  ) -> bool {
+    preferences.email.is_some()
+      && match delivery {
+        Delivery::Immediate => {
+          preferences.email_frequency == EmailFrequency::Immediate
+        }
+        Delivery::Digest => {
+          preferences.email_frequency.digest_period().is_some()
+        }
+      }
+  }
+
+  async fn deliver(
+    &self,
+    preferences: &NotificationPreferences,
+    notifications: &[Notification],
+  ) -> Result {
+    let Some(email) = &preferences.email else {
+      return Ok(());
+    };
+
+    self
+      .mailer
+      .send(self.message(email.parse()?, notifications)?)
+      .await?;
+
+    self
+      .db
+      .mark_notifications_emailed(&preferences.user_id, notifications)
+      .await?;
+
+    Ok(())
+  }
+}
+
+/// Stores notifications for users to see on the site, then fans them out to
+/// every channel a user wants them on.
+pub(crate) struct Dispatcher {
+  channels: Vec<Box<dyn Channel>>,
+  db: Arc<Db>,
+  /// Woken when notifications are stored, so they're delivered promptly.
+  pending: Notify,
+}
+
+impl Debug for Dispatcher {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    f.debug_struct("Dispatcher")
+      .field(
+        "channels",
+        &self
+          .channels
+          .iter()
+          .map(|channel| channel.name())
+          .collect::<Vec<_>>(),
+      )
+      .finish_non_exhaustive()
+  }
+}
+
+impl Dispatcher {
+  /// How often to retry deliveries when no new notifications come in.
+  pub(crate) const DELIVERY_INTERVAL: Duration = Duration::from_secs(60);
+
+  /// How often to check for digests that are due.
+  pub(crate) const DIGEST_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+  pub(crate) fn new(db: Arc<Db>, channels: Vec<Box<dyn Channel>>) -> Self {
+    Self {
+      channels,
+      db,
+      pending: Notify::new(),
+    }
+  }
+
+  /// Email if SMTP is configured.
+  pub(crate) fn from_config(db: Arc<Db>, config: &Config) -> Result<Self> {
+    let mut channels: Vec<Box<dyn Channel>> = Vec::new();
+
+    match &config.smtp {
+      Some(smtp) => {
+        channels.push(Box::new(EmailChannel::from_config(db.clone(), smtp)?))
+      }
+      None => info!("SMTP isn't configured, notifications won't be emailed"),
+    }
+
+    Ok(Self::new(db, channels))
+  }
+
+  /// Notifies every subscriber to `review` on the site, leaving other
+  /// channels to [`Self::run_deliveries`].
+  pub(crate) async fn dispatch(&self, review: &Review) -> Result {
+    self
+      .db
+      .insert_notifications(self.db.notifications_for(review).await?)
+      .await?;
+
+    self.pending.notify_one();
+
+    Ok(())
+  }
+
+  /// Sends unseen, undelivered notifications to every user who wants them
+  /// right away, returning how many users were sent any.
+  ///
+  /// Channels are tried in order, and a failing channel is logged rather
+  /// than stopping the rest. Whatever wasn't delivered is tried again next
+  /// time.
+  pub(crate) async fn deliver_pending(&self) -> Result<usize> {
+    let mut sent = 0;
+
+    for preferences in self.db.immediate_subscribers().await? {
+      let channels = self
+        .channels
+        .iter()
+        .filter(|channel| channel.wants(&preferences, Delivery::Immediate))
+        .collect::<Vec<_>>();
+
+      if channels.is_empty() {
+        continue;
+      }
+
+      let notifications = self
+        .db
+        .undelivered_notifications(&preferences.user_id)
+        .await?;
+
+      if notifications.is_empty() {
+        continue;
+      }
+
+      let mut delivered = true;
+
+      for channel in channels {
+        if let Err(error) = channel.deliver(&preferences, &notifications).await
+        {
+          error!(
+            "Failed to deliver notifications over {}: {error}",
+            channel.name()
+          );
+
+          delivered = false;
+        }
+      }
+
+      if delivered {
+        sent += 1;
+      }
+    }
+
+    Ok(sent)
+  }
+
+  /// Delivers pending notifications whenever new ones are stored, and every
+  /// [`Self::DELIVERY_INTERVAL`] to retry failures, until `shutdown`.
+  pub(crate) async fn run_deliveries(
+    self: Arc<Self>,
+    shutdown: CancellationToken,
+  ) {
+    let mut interval = tokio::time::interval(Self::DELIVERY_INTERVAL);
+
+    loop {
+      tokio::select! {
+        _ = interval.tick() => {}
+        () = self.pending.notified() => {}
+        () = shutdown.cancelled() => return,
+      }
+
+      if let Err(error) = self.deliver_pending().await {
+        error!("Failed to deliver notifications: {error}");
+      }
+    }
+  }
+
+  /// Sends a digest of unseen, unemailed notifications to every user whose
+  /// digest is due at `now`, returning how many digests were sent.
+  pub(crate) async fn send_digests(&self, now: DateTime<Utc>) -> Result<usize> {
+    let mut sent = 0;
+
+    for preferences in self.db.digest_subscribers().await? {
+      if !preferences.digest_due(now) {
+        continue;
+      }
+
+      let channels = self
+        .channels
+        .iter()
+        .filter(|channel| channel.wants(&preferences, Delivery::Digest))
+        .collect::<Vec<_>>();
+
+      if channels.is_empty() {
+        continue;
+      }
+
+      let notifications = self
+        .db
+        .undelivered_notifications(&preferences.user_id)
+        .await?;
+
+      let mut delivered = true;
+
+      if !notifications.is_empty() {
+        for channel in channels {
+          if let Err(error) =
+            channel.deliver(&preferences, &notifications).await
+          {
+            error!("Failed to deliver digest over {}: {error}", channel.name());
+
+            delivered = false;
+          }
+        }
+
+        if delivered {
+          sent += 1;
+        }
+      }
+
+      if delivered {
+        self
+          .db
+          .record_digest(&preferences.user_id, now.into())
+          .await?;
+      }
+    }
+
+    Ok(sent)
+  }
+
+  /// Sends due digests every [`Self::DIGEST_INTERVAL`], until `shutdown`.
+  pub(crate) async fn run_digests(
+    self: Arc<Self>,
+    shutdown: CancellationToken,
+  ) {
+    let mut interval = tokio::time::interval(Self::DIGEST_INTERVAL);
+
+    loop {
+      tokio::select! {
+        _ = interval.tick() => {}
+        () = shutdown.cancelled() => return,
+      }
+
+      match self.send_digests(Utc::now()).await {
+        Ok(0) => {}
+        Ok(sent) => info!("Sent {sent} notification digests"),
+        Err(error) => error!("Failed to send notification digests: {error}"),
+      }
+    }
+  }
+}
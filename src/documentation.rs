@@ -43,16 +43,31 @@ impl Modify for MicrosoftOAuthSecurity {
     auth::login_authorized,
     auth::logout,
     auth::microsoft_auth,
+    buildings::get_building_courses,
+    buildings::get_buildings,
+    courses::get_course_averages,
     courses::get_course_by_id,
+    courses::get_course_eligibility,
+    courses::get_course_history,
     courses::get_courses,
+    exams::get_course_exams,
+    exams::get_exams,
+    instructors::compare_instructors,
     instructors::get_instructor,
     interactions::add_interaction,
     interactions::delete_interaction,
     interactions::get_interaction_kind,
     interactions::get_user_interactions_for_course,
+    notifications::clear_all_notifications,
     notifications::delete_notification,
+    notifications::delete_notification_by_id,
+    notifications::get_notification_preferences,
     notifications::get_notifications,
+    notifications::mark_all_notifications_read,
+    notifications::stream_notifications,
     notifications::update_notification,
+    notifications::update_notification_by_id,
+    notifications::update_notification_preferences,
     reviews::add_review,
     reviews::delete_review,
     reviews::get_review,
@@ -62,22 +77,45 @@ impl Modify for MicrosoftOAuthSecurity {
     subscriptions::add_subscription,
     subscriptions::delete_subscription,
     subscriptions::get_subscription,
-    user::get_user
+    user::get_user,
+    webhooks::add_webhook,
+    webhooks::delete_webhook,
+    webhooks::get_webhook_deliveries,
+    webhooks::get_webhooks
   ),
   components(
     schemas(
+      buildings::BuildingCourse,
+      buildings::GetBuildingCoursesParams,
+      buildings::GetBuildingCoursesPayload,
+      courses::GetCourseAveragesPayload,
       courses::GetCourseByIdParams,
+      courses::CourseHistoryEntry,
+      courses::Equivalence,
+      courses::EquivalentCourse,
       courses::GetCourseByIdPayload,
+      courses::GetCourseEligibilityParams,
+      courses::GetCourseEligibilityPayload,
+      courses::GetCourseHistoryPayload,
       courses::GetCoursesParams,
       courses::GetCoursesPayload,
+      courses::TermGradeAverage,
+      exams::GetExamsParams,
+      instructors::CompareInstructorsPayload,
       instructors::GetInstructorPayload,
+      instructors::InstructorComparison,
       interactions::AddInteractionBody,
       interactions::DeleteInteractionBody,
       interactions::GetInteractionKindParams,
       interactions::GetInteractionKindPayload,
       interactions::GetUserInteractionForCoursePayload,
+      notifications::BulkNotificationsPayload,
       notifications::DeleteNotificationBody,
+      notifications::GetNotificationsParams,
+      notifications::GetNotificationsPayload,
       notifications::UpdateNotificationBody,
+      notifications::UpdateNotificationByIdBody,
+      notifications::UpdateNotificationPreferencesBody,
       reviews::AddOrUpdateReviewBody,
       reviews::DeleteReviewBody,
       reviews::GetReviewsParams,
@@ -88,11 +126,16 @@ impl Modify for MicrosoftOAuthSecurity {
       subscriptions::SubscriptionResponse,
       user::User,
       user::UserResponse,
+      webhooks::AddWebhookBody,
+      webhooks::GetWebhookDeliveriesParams,
     )
   ),
   tags(
+    (name = "admin", description = "All admin only endpoints."),
     (name = "auth", description = "All authentication related endpoints."),
+    (name = "buildings", description = "All campus building related endpoints."),
     (name = "courses", description = "All course related endpoints."),
+    (name = "exams", description = "All final exam related endpoints."),
     (name = "instructors", description = "All instructor related endpoints."),
     (name = "interactions", description = "All interaction related endpoints."),
     (name = "notifications", description = "All notification related endpoints."),
@@ -0,0 +1,85 @@
+use super::*;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub(crate) struct GetExamsParams {
+  /// Term to filter exams by (e.g. `Fall 2025`).
+  pub(crate) term: Option<String>,
+  /// Subject to filter exams by (e.g. `COMP`).
+  pub(crate) subject: Option<String>,
+}
+
+impl GetExamsParams {
+  /// Subjects are three or four letter codes, and anything else could be
+  /// read as a pattern when matched against course codes.
+  fn valid_subject(&self) -> bool {
+    self.subject.as_ref().is_none_or(|subject| {
+      (3..=4).contains(&subject.len())
+        && subject.chars().all(|c| c.is_ascii_alphabetic())
+    })
+  }
+}
+
+impl From<GetExamsParams> for ExamFilter {
+  fn from(params: GetExamsParams) -> Self {
+    ExamFilter {
+      subject: params.subject,
+      term: params.term,
+      ..Default::default()
+    }
+  }
+}
+
+#[utoipa::path(
+  get,
+  path = "/exams",
+  tag = "exams",
+  description = "Get final exams with optional filtering by term and subject.",
+  params(
+    ("term" = Option<String>, Query, description = "Term to filter exams by."),
+    ("subject" = Option<String>, Query, description = "Subject to filter exams by."),
+  ),
+  responses(
+    (status = StatusCode::OK, description = "Final exams sorted by start time.", body = [FinalExam]),
+    (status = StatusCode::BAD_REQUEST, description = "The subject isn't a three or four letter code.", body = String),
+    (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error.", body = String)
+  )
+)]
+pub(crate) async fn get_exams(
+  Query(params): Query<GetExamsParams>,
+  AppState(db): AppState<Arc<Db>>,
+) -> Result<Response> {
+  if !params.valid_subject() {
+    return Ok(
+      (
+        StatusCode::BAD_REQUEST,
+        "The subject must be a three or four letter code",
+      )
+        .into_response(),
+    );
+  }
+
+  Ok(
+    (StatusCode::OK, Json(db.exams(Some(params.into())).await?))
+      .into_response(),
+  )
+}
+
+#[utoipa::path(
+  get,
+  path = "/courses/{id}/exams",
+  tag = "exams",
+  description = "Get all final exams scheduled for a course.",
+  params(
+    ("id" = String, Path, description = "Course ID to get final exams for.")
+  ),
+  responses(
+    (status = StatusCode::OK, description = "Final exams for the course sorted by start time.", body = [FinalExam]),
+    (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error.", body = String)
+  )
+)]
+pub(crate) async fn get_course_exams(
+  Path(id): Path<String>,
+  AppState(db): AppState<Arc<Db>>,
+) -> Result<impl IntoResponse> {
+  Ok((StatusCode::OK, Json(db.find_exams_by_course_id(&id).await?)))
+}
@@ -0,0 +1,189 @@
+use super::*;
+
+/// Where seeding the database is at.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum SeedingState {
+  /// Seeding was stopped by the server shutting down.
+  Cancelled,
+  /// Seeding finished, or there was nothing to seed.
+  Complete,
+  /// Seeding stopped with an error.
+  Failed(String),
+  /// Seeding is under way.
+  Running,
+}
+
+impl SeedingState {
+  pub(crate) const NAMES: [&'static str; 4] =
+    ["cancelled", "complete", "failed", "running"];
+
+  pub(crate) fn name(&self) -> &'static str {
+    match self {
+      Self::Cancelled => "cancelled",
+      Self::Complete => "complete",
+      Self::Failed(_) => "failed",
+      Self::Running => "running",
+    }
+  }
+}
+
+/// Tracks the seeding task spawned on startup, if any.
+#[derive(Clone, Debug)]
+pub(crate) struct Seeding {
+  progress: Arc<SeedProgress>,
+  state: Arc<Mutex<SeedingState>>,
+}
+
+impl Default for Seeding {
+  fn default() -> Self {
+    Self {
+      progress: Arc::default(),
+      state: Arc::new(Mutex::new(SeedingState::Complete)),
+    }
+  }
+}
+
+impl Seeding {
+  fn set(&self, state: SeedingState) {
+    *self.state.lock().unwrap() = state;
+  }
+
+  pub(crate) fn start(&self) {
+    self.set(SeedingState::Running);
+  }
+
+  pub(crate) fn cancel(&self) {
+    self.set(SeedingState::Cancelled);
+  }
+
+  pub(crate) fn complete(&self) {
+    self.set(SeedingState::Complete);
+  }
+
+  pub(crate) fn fail(&self, error: String) {
+    self.set(SeedingState::Failed(error));
+  }
+
+  /// Progress to hand to the seeding task.
+  pub(crate) fn progress(&self) -> Arc<SeedProgress> {
+    self.progress.clone()
+  }
+
+  pub(crate) fn status(&self) -> (SeedingState, Arc<SeedProgress>) {
+    (self.state.lock().unwrap().clone(), self.progress.clone())
+  }
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Check {
+  pub(crate) ok: bool,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub(crate) detail: Option<String>,
+}
+
+impl Check {
+  fn from_result<T, E: Display>(result: Result<T, E>) -> Self {
+    match result {
+      Ok(_) => Self {
+        ok: true,
+        detail: None,
+      },
+      Err(error) => Self {
+        ok: false,
+        detail: Some(error.to_string()),
+      },
+    }
+  }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ReadinessPayload {
+  pub(crate) ready: bool,
+  pub(crate) mongo: Check,
+  pub(crate) seeding: Check,
+  pub(crate) session_store: Check,
+  /// Set once the server has started shutting down, while it drains.
+  pub(crate) shutting_down: bool,
+  pub(crate) tasks: BTreeMap<String, TaskStatus>,
+}
+
+/// Succeeds whenever the server is up to answer.
+pub(crate) async fn healthz() -> impl IntoResponse {
+  "ok"
+}
+
+/// Succeeds once MongoDB and the session store are reachable and seeding
+/// has completed, until the server starts shutting down, answering with 503
+/// and what failed otherwise.
+pub(crate) async fn readyz(
+  AppState(db): AppState<Arc<Db>>,
+  AppState(seeding): AppState<Seeding>,
+  AppState(session_store): AppState<MongodbSessionStore>,
+  AppState(supervisor): AppState<Supervisor>,
+) -> impl IntoResponse {
+  let mongo = Check::from_result(db.ping().await);
+
+  let session_store =
+    Check::from_result(match Session::new().into_cookie_value() {
+      Some(cookie) => session_store.load_session(cookie).await,
+      None => Err(anyhow!("Failed to create a session cookie")),
+    });
+
+  let (state, progress) = seeding.status();
+
+  let seeding = match state {
+    SeedingState::Cancelled => Check {
+      ok: false,
+      detail: Some("Seeding was cancelled".into()),
+    },
+    SeedingState::Complete => Check {
+      ok: true,
+      detail: None,
+    },
+    SeedingState::Failed(error) => Check {
+      ok: false,
+      detail: Some(format!("Seeding failed: {error}")),
+    },
+    SeedingState::Running => Check {
+      ok: false,
+      detail: Some(format!(
+        "Seeding is running, {} of {} seed files done",
+        progress.completed(),
+        progress.total()
+      )),
+    },
+  };
+
+  let shutting_down = supervisor.shutting_down();
+
+  let ready = mongo.ok && seeding.ok && session_store.ok && !shutting_down;
+
+  (
+    if ready {
+      StatusCode::OK
+    } else {
+      StatusCode::SERVICE_UNAVAILABLE
+    },
+    Json(ReadinessPayload {
+      ready,
+      mongo,
+      seeding,
+      session_store,
+      shutting_down,
+      tasks: supervisor.statuses(),
+    }),
+  )
+}
+
+/// Every metric, in the Prometheus text format.
+pub(crate) async fn metrics(
+  AppState(metrics): AppState<Arc<Metrics>>,
+  AppState(seeding): AppState<Seeding>,
+) -> Result<impl IntoResponse> {
+  Ok((
+    [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+    metrics.render(&seeding)?,
+  ))
+}
@@ -1,20 +1,24 @@
 use super::*;
 
 #[derive(Debug, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
 #[typeshare]
 pub(crate) struct GetInstructorPayload {
-  /// Instructor matching the requested name, if found.
-  pub(crate) instructor: Option<Instructor>,
+  /// Instructor matching the requested name, including their full teaching
+  /// history, if found.
+  pub(crate) instructor: Option<InstructorProfile>,
   /// Reviews associated with the instructor sorted by newest first.
   pub(crate) reviews: Vec<Review>,
+  /// Review aggregates overall, per course and per term.
+  pub(crate) stats: InstructorStats,
 }
 
 #[utoipa::path(
   get,
   path = "/instructors/{name}",
-  description = "Get information about an instructor along with their reviews.",
+  description = "Get information about an instructor along with their teaching history and reviews.",
   params(
-    ("name" = String, Path, description = "Instructor name to retrieve information for.")
+    ("name" = String, Path, description = "Instructor name (or any known spelling of it) to retrieve information for.")
   ),
   responses(
     (status = StatusCode::OK, description = "Instructor information with associated reviews.", body = GetInstructorPayload),
@@ -29,15 +33,105 @@ pub(crate) async fn get_instructor(
 
   let instructor = db.find_instructor_by_name(&name).await?;
 
+  let mut reviews = match &instructor {
+    Some(instructor) => db.find_reviews_by_instructor(instructor).await?,
+    None => Vec::new(),
+  };
+
+  reviews.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+
+  let stats = instructor
+    .as_ref()
+    .map(|instructor| InstructorStats::new(instructor, &reviews))
+    .unwrap_or_default();
+
   Ok(Json(GetInstructorPayload {
-    instructor: instructor.clone(),
-    reviews: match instructor {
-      Some(ins) => {
-        let mut reviews = db.find_reviews_by_instructor_name(&ins.name).await?;
-        reviews.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
-        reviews
-      }
-      None => vec![],
-    },
+    instructor,
+    reviews,
+    stats,
   }))
 }
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[typeshare]
+pub(crate) struct InstructorComparison {
+  /// The instructor being compared.
+  pub(crate) instructor: InstructorProfile,
+  /// Aggregates over the instructor's reviews for the course.
+  pub(crate) stats: ReviewStats,
+  /// Aggregates per term the instructor taught the course, most recent first.
+  pub(crate) terms: Vec<TermStats>,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[typeshare]
+pub(crate) struct CompareInstructorsPayload {
+  /// The course ID.
+  pub(crate) course_id: String,
+  /// Everyone who has taught the course, in name order.
+  pub(crate) instructors: Vec<InstructorComparison>,
+}
+
+#[utoipa::path(
+  get,
+  path = "/courses/{id}/instructors/compare",
+  description = "Compare review statistics for everyone who has taught a course.",
+  params(
+    ("id" = String, Path, description = "Course ID to compare instructors for."),
+  ),
+  responses(
+    (status = StatusCode::OK, description = "Review statistics for each instructor of the course.", body = CompareInstructorsPayload),
+    (status = StatusCode::NOT_FOUND, description = "Course not found."),
+    (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error.", body = String)
+  )
+)]
+pub(crate) async fn compare_instructors(
+  Path(id): Path<String>,
+  AppState(db): AppState<Arc<Db>>,
+) -> Result<impl IntoResponse> {
+  if db.find_course_by_id(&id).await?.is_none() {
+    return Ok((StatusCode::NOT_FOUND, Json(None)));
+  }
+
+  let reviews = db.find_reviews_by_course_id(&id).await?;
+
+  let instructors = db
+    .find_instructors_by_course(&id)
+    .await?
+    .into_iter()
+    .map(|instructor| {
+      let reviews = reviews
+        .iter()
+        .filter(|review| {
+          review
+            .instructors
+            .iter()
+            .any(|name| instructor.is_known_as(name))
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+
+      let stats = InstructorStats::new(&instructor, &reviews);
+
+      InstructorComparison {
+        stats: stats.overall,
+        terms: stats
+          .terms
+          .into_iter()
+          .filter(|term| term.course_id == id)
+          .collect(),
+        instructor,
+      }
+    })
+    .collect();
+
+  Ok((
+    StatusCode::OK,
+    Json(Some(CompareInstructorsPayload {
+      course_id: id,
+      instructors,
+    })),
+  ))
+}
@@ -1,14 +1,27 @@
 use {
   crate::{
+    admin::{Admin, Admins},
     assets::Assets,
     auth::{AuthRedirect, COOKIE_NAME},
+    config::{
+      Config, Environment, MicrosoftConfig, Settings, SmtpConfig,
+      parse_duration,
+    },
+    delivery::Dispatcher,
     documentation::Documentation,
     error::Error,
     hash::Hash,
-    object::Object,
+    health::{Seeding, SeedingState},
+    metrics::Metrics,
+    notification_stream::NotificationStream,
+    object::{LocalStore, MemoryStore, Object, S3Store},
+    rate_limit::{RateLimitPolicy, RateLimiter, RateLimits, RouteGroup},
+    seed_hashes::SeedHashes,
     server::Server,
     state::State,
+    supervisor::{Supervisor, TaskStatus},
     user::User,
+    webhook_sender::WebhookSender,
   },
   anyhow::anyhow,
   async_mongodb_session::MongodbSessionStore,
@@ -17,63 +30,82 @@ use {
     Json, RequestPartsExt,
     body::Body,
     extract::{
-      FromRef, FromRequestParts, OptionalFromRequestParts, Path, Query,
-      State as AppState,
+      ConnectInfo, FromRef, FromRequestParts, OptionalFromRequestParts, Path,
+      Query, State as AppState,
+    },
+    middleware::{self, Next},
+    response::{
+      IntoResponse, Redirect, Response,
+      sse::{Event, KeepAlive, Sse},
     },
-    response::{IntoResponse, Redirect, Response},
-    routing::{Router, get, post},
+    routing::{Router, delete, get, post, put},
   },
   axum_extra::{
-    TypedHeader, headers::Cookie, typed_header::TypedHeaderRejectionReason,
+    TypedHeader,
+    headers::{Cookie, HeaderMapExt},
+    typed_header::TypedHeaderRejectionReason,
   },
   base64::{Engine, engine::general_purpose::STANDARD},
   chrono::prelude::*,
-  clap::Parser,
+  clap::{Args, Parser, ValueEnum},
   db::Db,
   dotenv::dotenv,
-  futures::TryStreamExt,
+  futures::{
+    FutureExt, Stream, StreamExt, TryStreamExt, future::join_all, stream,
+  },
   http::{
     HeaderMap, Request, StatusCode, header, header::SET_COOKIE, request::Parts,
   },
   model::{
-    Course, CourseFilter, InitializeOptions, Instructor, Interaction,
-    InteractionKind, Notification, Review, ReviewFilter, SearchResults,
-    Subscription,
+    Building, Course, CourseAverage, CourseFilter, EmailFrequency, ExamFilter,
+    FieldChange, FinalExam, GradeTrend, InitializeOptions, InstructorProfile,
+    InstructorStats, Interaction, InteractionKind, Notification,
+    NotificationPreferences, RestrictedTo, Review, ReviewFilter, ReviewStats,
+    SearchResults, SeedProgress, Subscription, SubscriptionKind, TermStats,
+    Webhook, WebhookDelivery, WebhookDeliveryStatus, WebhookEventKind,
   },
   oauth2::{
     AuthType, AuthUrl, ClientId, ClientSecret, CsrfToken, RedirectUrl, Scope,
     TokenUrl, basic::BasicClient,
   },
+  rand::distr::{Alphanumeric, SampleString},
   rusoto_core::Region,
-  rusoto_s3::S3Client,
-  rusoto_s3::{GetObjectRequest, PutObjectOutput, PutObjectRequest, S3},
+  rusoto_s3::{GetObjectRequest, PutObjectRequest, S3, S3Client},
   serde::{Deserialize, Serialize},
   sha2::{Digest, Sha256},
   std::{
+    any::Any,
     backtrace::BacktraceStatus,
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
     convert::Infallible,
-    env,
-    fmt::{self, Display, Formatter},
+    fmt::{self, Debug, Display, Formatter},
     fs,
     fs::File,
-    io::Read,
-    net::SocketAddr,
+    io::{self, Read},
+    mem,
+    net::{IpAddr, SocketAddr},
+    num::NonZeroU32,
+    panic::AssertUnwindSafe,
     path::PathBuf,
     process,
-    sync::Arc,
-    thread,
+    str::FromStr,
+    sync::{Arc, Mutex},
     time::Duration,
   },
-  tokio::net::TcpListener,
-  tower::ServiceBuilder,
-  tower_governor::{GovernorLayer, governor::GovernorConfigBuilder},
+  tokio::{
+    net::TcpListener,
+    sync::broadcast::{self, error::RecvError},
+    task::JoinHandle,
+    time::{Instant, Interval, MissedTickBehavior},
+  },
+  tokio_util::sync::CancellationToken,
   tower_http::{
     cors::CorsLayer,
     services::{ServeDir, ServeFile},
     trace::TraceLayer,
   },
   tracing::Span,
-  tracing::{debug, error, info, trace},
+  tracing::{debug, error, info, trace, warn},
   tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt},
   typeshare::typeshare,
   url::Url,
@@ -88,28 +120,40 @@ use {
   walkdir::WalkDir,
 };
 
+mod admin;
 mod assets;
 mod auth;
+mod buildings;
+mod config;
 mod courses;
+mod delivery;
 mod documentation;
 mod error;
+mod exams;
 mod hash;
+mod health;
 mod instructors;
 mod interactions;
+mod metrics;
+mod notification_stream;
 mod notifications;
 mod object;
 mod options;
+mod rate_limit;
 mod reviews;
 mod search;
+mod seed_hashes;
 mod server;
 mod state;
 mod subscriptions;
+mod supervisor;
 mod user;
+mod webhook_sender;
+mod webhooks;
 
 type Result<T = (), E = error::Error> = std::result::Result<T, E>;
 
-#[tokio::main]
-async fn main() {
+fn initialize_tracing(environment: Environment) {
   let env_filter = tracing_subscriber::EnvFilter::try_from_default_env()
     .unwrap_or_else(|_| "info,tower_http=debug,hyper=debug".into());
 
@@ -119,7 +163,7 @@ async fn main() {
     .with_file(true)
     .with_line_number(true);
 
-  if env::var("ENV").unwrap_or_default() == "production" {
+  if environment == Environment::Production {
     tracing_subscriber::registry()
       .with(env_filter)
       .with(fmt_layer.json())
@@ -130,10 +174,23 @@ async fn main() {
       .with(fmt_layer.pretty())
       .init();
   }
+}
 
+async fn run() -> Result {
   dotenv().ok();
 
-  if let Err(error) = Server::parse().run().await {
+  let server = Server::parse();
+
+  let config = server.config()?;
+
+  initialize_tracing(config.environment);
+
+  server.run(config).await
+}
+
+#[tokio::main]
+async fn main() {
+  if let Err(error) = run().await {
     eprintln!("error: {error}");
 
     for (i, error) in error.0.chain().skip(1).enumerate() {
@@ -0,0 +1,198 @@
+use {
+  super::*,
+  axum::extract::MatchedPath,
+  db::{CommandEventHandler, CommandFailedEvent, CommandSucceededEvent},
+  prometheus::{
+    HistogramOpts, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec, Opts,
+    Registry, TextEncoder,
+  },
+};
+
+/// Prometheus metrics for the whole server, rendered by `/metrics`.
+#[derive(Debug)]
+pub(crate) struct Metrics {
+  db_commands: HistogramVec,
+  http_requests: HistogramVec,
+  rate_limit_rejections: IntCounterVec,
+  registry: Registry,
+  seed_files_completed: IntGauge,
+  seed_files_total: IntGauge,
+  seeding_state: IntGaugeVec,
+}
+
+impl Metrics {
+  pub(crate) fn new() -> Result<Self> {
+    let registry = Registry::new();
+
+    let db_commands = HistogramVec::new(
+      HistogramOpts::new(
+        "db_command_duration_seconds",
+        "How long MongoDB commands took, by command and outcome.",
+      )
+      .buckets(vec![
+        0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5,
+      ]),
+      &["command", "outcome"],
+    )?;
+
+    let http_requests = HistogramVec::new(
+      HistogramOpts::new(
+        "http_request_duration_seconds",
+        "How long HTTP requests took, by method, matched route and status.",
+      ),
+      &["method", "route", "status"],
+    )?;
+
+    let rate_limit_rejections = IntCounterVec::new(
+      Opts::new(
+        "rate_limit_rejections_total",
+        "Requests rejected for exceeding a rate limit, by route group.",
+      ),
+      &["group"],
+    )?;
+
+    let seed_files_completed = IntGauge::new(
+      "seed_files_completed",
+      "Seed files processed by the current or last seeding run.",
+    )?;
+
+    let seed_files_total = IntGauge::new(
+      "seed_files_total",
+      "Seed files in the current or last seeding run.",
+    )?;
+
+    let seeding_state = IntGaugeVec::new(
+      Opts::new("seeding_state", "Whether seeding is in each state."),
+      &["state"],
+    )?;
+
+    registry.register(Box::new(db_commands.clone()))?;
+    registry.register(Box::new(http_requests.clone()))?;
+    registry.register(Box::new(rate_limit_rejections.clone()))?;
+    registry.register(Box::new(seed_files_completed.clone()))?;
+    registry.register(Box::new(seed_files_total.clone()))?;
+    registry.register(Box::new(seeding_state.clone()))?;
+
+    Ok(Self {
+      db_commands,
+      http_requests,
+      rate_limit_rejections,
+      registry,
+      seed_files_completed,
+      seed_files_total,
+      seeding_state,
+    })
+  }
+
+  fn db_command(&self, command: &str, outcome: &str, duration: Duration) {
+    self
+      .db_commands
+      .with_label_values(&[command, outcome])
+      .observe(duration.as_secs_f64());
+  }
+
+  pub(crate) fn rate_limit_rejected(&self, group: RouteGroup) {
+    self
+      .rate_limit_rejections
+      .with_label_values(&[group.to_string()])
+      .inc();
+  }
+
+  /// Every metric in the Prometheus text format, with seeding gauges as of
+  /// now.
+  pub(crate) fn render(&self, seeding: &Seeding) -> Result<String> {
+    let (state, progress) = seeding.status();
+
+    for name in SeedingState::NAMES {
+      self
+        .seeding_state
+        .with_label_values(&[name])
+        .set(i64::from(state.name() == name));
+    }
+
+    self
+      .seed_files_completed
+      .set(progress.completed().try_into().unwrap_or(i64::MAX));
+
+    self
+      .seed_files_total
+      .set(progress.total().try_into().unwrap_or(i64::MAX));
+
+    Ok(TextEncoder::new().encode_to_string(&self.registry.gather())?)
+  }
+
+  /// Times every request, labelled by its route pattern rather than its
+  /// path so that path parameters don't each get their own series.
+  pub(crate) async fn track(
+    AppState(metrics): AppState<Arc<Metrics>>,
+    request: Request<Body>,
+    next: Next,
+  ) -> Response {
+    let start = Instant::now();
+
+    let method = request.method().to_string();
+
+    let route = request
+      .extensions()
+      .get::<MatchedPath>()
+      .map_or("unmatched", MatchedPath::as_str)
+      .to_owned();
+
+    let response = next.run(request).await;
+
+    metrics
+      .http_requests
+      .with_label_values(&[
+        method,
+        route,
+        response.status().as_u16().to_string(),
+      ])
+      .observe(start.elapsed().as_secs_f64());
+
+    response
+  }
+}
+
+impl CommandEventHandler for Metrics {
+  fn handle_command_succeeded_event(&self, event: CommandSucceededEvent) {
+    self.db_command(&event.command_name, "success", event.duration);
+  }
+
+  fn handle_command_failed_event(&self, event: CommandFailedEvent) {
+    self.db_command(&event.command_name, "failure", event.duration);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn metrics_are_rendered() {
+    let metrics = Metrics::new().unwrap();
+
+    let seeding = Seeding::default();
+
+    seeding.start();
+    seeding.progress().start(4);
+    seeding.progress().advance("courses.json".into());
+
+    metrics.rate_limit_rejected(RouteGroup::Search);
+    metrics.rate_limit_rejected(RouteGroup::Search);
+
+    metrics.db_command("find", "success", Duration::from_millis(3));
+
+    let rendered = metrics.render(&seeding).unwrap();
+
+    for line in [
+      "rate_limit_rejections_total{group=\"search\"} 2",
+      "seed_files_completed 1",
+      "seed_files_total 4",
+      "seeding_state{state=\"running\"} 1",
+      "seeding_state{state=\"complete\"} 0",
+      "db_command_duration_seconds_count{command=\"find\",outcome=\"success\"} 1",
+    ] {
+      assert!(rendered.lines().any(|l| l == line), "{line}\n{rendered}");
+    }
+  }
+}
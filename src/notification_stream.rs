@@ -0,0 +1,174 @@
+use super::*;
+
+/// One user's notifications as they happen, ending once their session does.
+pub(crate) struct NotificationStream {
+  /// Notifications waiting to be sent, oldest first.
+  backlog: VecDeque<Notification>,
+  /// The `(created_at, id)` of the newest notification queued while catching
+  /// up. Catching up queues everything up to it in that order, so anything
+  /// at or before it coming through the live feed is a repeat.
+  caught_up_to: Option<(model::DateTime, String)>,
+  db: Arc<Db>,
+  /// The last notification sent, to catch up from if the stream falls behind.
+  last_id: Option<String>,
+  receiver: broadcast::Receiver<Notification>,
+  session: String,
+  session_check: Interval,
+  session_store: MongodbSessionStore,
+  /// When the stream started listening, to catch up from if it falls behind
+  /// before sending anything.
+  started_at: model::DateTime,
+  user_id: String,
+}
+
+impl NotificationStream {
+  /// How often an idle stream checks that its session is still valid.
+  const SESSION_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+  /// Starts streaming notifications to `user_id` for as long as `session`
+  /// lasts, first replaying any created after `last_event_id`.
+  pub(crate) async fn new(
+    db: Arc<Db>,
+    session_store: MongodbSessionStore,
+    session: String,
+    user_id: String,
+    last_event_id: Option<&str>,
+  ) -> Result<Self> {
+    // Listen before catching up, so nothing falls in between.
+    let receiver = db.subscribe_to_notifications();
+
+    let started_at = Utc::now().into();
+
+    let mut session_check = tokio::time::interval_at(
+      Instant::now() + Self::SESSION_CHECK_INTERVAL,
+      Self::SESSION_CHECK_INTERVAL,
+    );
+
+    session_check.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    let mut stream = Self {
+      backlog: VecDeque::new(),
+      caught_up_to: None,
+      db,
+      last_id: last_event_id.map(String::from),
+      receiver,
+      session,
+      session_check,
+      session_store,
+      started_at,
+      user_id,
+    };
+
+    if stream.last_id.is_some() {
+      stream.catch_up().await?;
+    }
+
+    Ok(stream)
+  }
+
+  /// Queues everything created since the last notification sent, or since
+  /// the stream started if none has been.
+  async fn catch_up(&mut self) -> Result {
+    let missed = match &self.last_id {
+      Some(last_id) => {
+        self.db.notifications_after(&self.user_id, last_id).await?
+      }
+      None => {
+        self
+          .db
+          .notifications_since(&self.user_id, self.started_at.clone())
+          .await?
+      }
+    };
+
+    for notification in missed {
+      if self.caught_up(&notification)
+        || self
+          .backlog
+          .iter()
+          .any(|queued| queued.id == notification.id)
+      {
+        continue;
+      }
+
+      self.caught_up_to =
+        Some((notification.created_at.clone(), notification.id.clone()));
+
+      self.backlog.push_back(notification);
+    }
+
+    Ok(())
+  }
+
+  /// Whether `notification` was already queued while catching up.
+  fn caught_up(&self, notification: &Notification) -> bool {
+    self.caught_up_to.as_ref().is_some_and(|(created_at, id)| {
+      (&notification.created_at.0, &notification.id) <= (&created_at.0, id)
+    })
+  }
+
+  async fn session_active(&self) -> bool {
+    matches!(
+      self.session_store.load_session(self.session.clone()).await,
+      Ok(Some(_))
+    )
+  }
+
+  async fn next(&mut self) -> Option<Notification> {
+    loop {
+      if let Some(notification) = self.backlog.pop_front() {
+        if !self.session_active().await {
+          return None;
+        }
+
+        self.last_id = Some(notification.id.clone());
+
+        return Some(notification);
+      }
+
+      tokio::select! {
+        received = self.receiver.recv() => match received {
+          Ok(notification) => {
+            if notification.user_id == self.user_id
+              && !self.caught_up(&notification)
+            {
+              self.backlog.push_back(notification);
+            }
+          }
+          Err(RecvError::Lagged(missed)) => {
+            warn!("Notification stream fell behind by {missed} notifications");
+
+            if let Err(error) = self.catch_up().await {
+              error!("Failed to catch up notification stream: {error}");
+              return None;
+            }
+          }
+          Err(RecvError::Closed) => return None,
+        },
+        _ = self.session_check.tick() => {
+          if !self.session_active().await {
+            return None;
+          }
+        }
+      }
+    }
+  }
+
+  /// Server-sent events for each notification, identified by notification
+  /// id so clients can resume with `Last-Event-ID`.
+  pub(crate) fn events(
+    self,
+  ) -> impl Stream<Item = Result<Event, axum::Error>> + Send {
+    stream::unfold(self, |mut stream| async move {
+      let notification = stream.next().await?;
+
+      Some((
+        Event::default()
+          .event("notification")
+          .id(&notification.id)
+          .json_data(&notification),
+        stream,
+      ))
+    })
+  }
+}
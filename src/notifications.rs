@@ -1,23 +1,85 @@
-use super::*;
+use {super::*, lettre::message::Mailbox};
+
+/// How often an idle notification stream sends a comment, so proxies don't
+/// close it.
+const NOTIFICATION_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub(crate) struct GetNotificationsParams {
+  /// Maximum number of notifications to return, at most 100.
+  pub(crate) limit: Option<i64>,
+  /// Number of notifications to skip.
+  pub(crate) offset: Option<u64>,
+  /// Whether to only return notifications that haven't been seen.
+  pub(crate) unread: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[typeshare]
+pub(crate) struct GetNotificationsPayload {
+  /// The requested page of notifications, newest first.
+  pub(crate) notifications: Vec<Notification>,
+  /// Number of notifications the user has, across all pages.
+  #[typeshare(serialized_as = "number")]
+  pub(crate) total_count: u64,
+  /// Number of notifications the user hasn't seen, across all pages.
+  #[typeshare(serialized_as = "number")]
+  pub(crate) unread_count: u64,
+}
 
 #[utoipa::path(
   get,
   path = "/notifications",
   tag = "notifications",
-  description = "Get the current user's notifications.",
+  description = "Get a page of the current user's notifications, with unread counts.",
   security(("microsoftOAuth" = ["User.Read"])),
+  params(
+    ("limit" = Option<i64>, Query, description = "Maximum number of notifications to return, from 1 to 100, defaulting to 100."),
+    ("offset" = Option<u64>, Query, description = "Number of notifications to skip."),
+    ("unread" = Option<bool>, Query, description = "Whether to only return unseen notifications."),
+  ),
   responses(
-    (status = StatusCode::OK, description = "Notifications for the authenticated user, sorted by recency.", body = [Notification]),
+    (status = StatusCode::OK, description = "Notifications for the authenticated user, newest first.", body = GetNotificationsPayload),
+    (status = StatusCode::BAD_REQUEST, description = "The limit is negative.", body = String),
     (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error.", body = String)
   )
 )]
 pub(crate) async fn get_notifications(
   user: User,
+  Query(params): Query<GetNotificationsParams>,
   AppState(db): AppState<Arc<Db>>,
-) -> Result<impl IntoResponse> {
-  let mut notifications = db.get_notifications(&user.id()).await?;
-  notifications.sort_by(|a, b| b.review.timestamp.cmp(&a.review.timestamp));
-  Ok((StatusCode::OK, Json(notifications)))
+) -> Result<Response> {
+  if params.limit.is_some_and(|limit| limit < 0) {
+    return Ok(
+      (StatusCode::BAD_REQUEST, "The limit can't be negative").into_response(),
+    );
+  }
+
+  let user_id = user.id();
+
+  let notifications = db
+    .notifications_page(
+      &user_id,
+      Some(params.limit.unwrap_or(Db::NOTIFICATION_PAGE_LIMIT)),
+      params.offset,
+      params.unread.unwrap_or(false),
+    )
+    .await?;
+
+  let (total_count, unread_count) = db.notification_counts(&user_id).await?;
+
+  Ok(
+    (
+      StatusCode::OK,
+      Json(GetNotificationsPayload {
+        notifications,
+        total_count,
+        unread_count,
+      }),
+    )
+      .into_response(),
+  )
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -84,3 +146,277 @@ pub(crate) async fn delete_notification(
   db.delete_notification(&user.id(), &body.course_id).await?;
   Ok(StatusCode::OK)
 }
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub(crate) struct UpdateNotificationByIdBody {
+  /// Whether the notification has been marked as seen.
+  pub(crate) seen: bool,
+}
+
+#[utoipa::path(
+  put,
+  path = "/notifications/{id}",
+  tag = "notifications",
+  description = "Mark one of the current user's notifications as seen or unseen.",
+  security(("microsoftOAuth" = ["User.Read"])),
+  params(
+    ("id" = String, Path, description = "Identifier of the notification to update.")
+  ),
+  request_body = UpdateNotificationByIdBody,
+  responses(
+    (status = StatusCode::OK, description = "Notification updated successfully."),
+    (status = StatusCode::NOT_FOUND, description = "The user has no notification with this identifier."),
+    (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error.", body = String)
+  )
+)]
+pub(crate) async fn update_notification_by_id(
+  user: User,
+  Path(id): Path<String>,
+  AppState(db): AppState<Arc<Db>>,
+  body: Json<UpdateNotificationByIdBody>,
+) -> Result<impl IntoResponse> {
+  let result = db
+    .update_notification_by_id(&user.id(), &id, body.seen)
+    .await?;
+
+  Ok(if result.matched_count == 0 {
+    StatusCode::NOT_FOUND
+  } else {
+    StatusCode::OK
+  })
+}
+
+#[utoipa::path(
+  delete,
+  path = "/notifications/{id}",
+  tag = "notifications",
+  description = "Delete one of the current user's notifications.",
+  security(("microsoftOAuth" = ["User.Read"])),
+  params(
+    ("id" = String, Path, description = "Identifier of the notification to delete.")
+  ),
+  responses(
+    (status = StatusCode::OK, description = "Notification deleted successfully."),
+    (status = StatusCode::NOT_FOUND, description = "The user has no notification with this identifier."),
+    (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error.", body = String)
+  )
+)]
+pub(crate) async fn delete_notification_by_id(
+  user: User,
+  Path(id): Path<String>,
+  AppState(db): AppState<Arc<Db>>,
+) -> Result<impl IntoResponse> {
+  let result = db.delete_notification_by_id(&user.id(), &id).await?;
+
+  Ok(if result.deleted_count == 0 {
+    StatusCode::NOT_FOUND
+  } else {
+    StatusCode::OK
+  })
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[typeshare]
+pub(crate) struct BulkNotificationsPayload {
+  /// Number of notifications the action changed.
+  #[typeshare(serialized_as = "number")]
+  pub(crate) count: u64,
+}
+
+#[utoipa::path(
+  post,
+  path = "/notifications/mark-all-read",
+  tag = "notifications",
+  description = "Mark all of the current user's notifications as seen.",
+  security(("microsoftOAuth" = ["User.Read"])),
+  responses(
+    (status = StatusCode::OK, description = "Number of notifications newly marked as seen.", body = BulkNotificationsPayload),
+    (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error.", body = String)
+  )
+)]
+pub(crate) async fn mark_all_notifications_read(
+  user: User,
+  AppState(db): AppState<Arc<Db>>,
+) -> Result<impl IntoResponse> {
+  let result = db.mark_all_notifications_seen(&user.id()).await?;
+
+  Ok((
+    StatusCode::OK,
+    Json(BulkNotificationsPayload {
+      count: result.modified_count,
+    }),
+  ))
+}
+
+#[utoipa::path(
+  post,
+  path = "/notifications/clear-all",
+  tag = "notifications",
+  description = "Delete all of the current user's notifications.",
+  security(("microsoftOAuth" = ["User.Read"])),
+  responses(
+    (status = StatusCode::OK, description = "Number of notifications deleted.", body = BulkNotificationsPayload),
+    (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error.", body = String)
+  )
+)]
+pub(crate) async fn clear_all_notifications(
+  user: User,
+  AppState(db): AppState<Arc<Db>>,
+) -> Result<impl IntoResponse> {
+  let result = db.clear_notifications(&user.id()).await?;
+
+  Ok((
+    StatusCode::OK,
+    Json(BulkNotificationsPayload {
+      count: result.deleted_count,
+    }),
+  ))
+}
+
+#[utoipa::path(
+  get,
+  path = "/notifications/preferences",
+  tag = "notifications",
+  description = "Get how the current user wants to be notified.",
+  security(("microsoftOAuth" = ["User.Read"])),
+  responses(
+    (status = StatusCode::OK, description = "Notification preferences for the authenticated user, defaulting to in-app only.", body = NotificationPreferences),
+    (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error.", body = String)
+  )
+)]
+pub(crate) async fn get_notification_preferences(
+  user: User,
+  AppState(db): AppState<Arc<Db>>,
+) -> Result<impl IntoResponse> {
+  let email = user.mail().to_string();
+
+  let user_id = user.id();
+
+  let preferences = db
+    .notification_preferences(&user_id)
+    .await?
+    .unwrap_or_else(|| NotificationPreferences {
+      user_id,
+      email: Some(email),
+      ..Default::default()
+    });
+
+  Ok((StatusCode::OK, Json(preferences)))
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub(crate) struct UpdateNotificationPreferencesBody {
+  /// Address to email notifications to, which must be the account's own
+  /// address, defaulting to it.
+  pub(crate) email: Option<String>,
+  /// How often to email notifications.
+  pub(crate) email_frequency: EmailFrequency,
+}
+
+#[utoipa::path(
+  put,
+  path = "/notifications/preferences",
+  tag = "notifications",
+  description = "Choose whether notifications are emailed immediately, in daily or weekly digests, or not at all.",
+  security(("microsoftOAuth" = ["User.Read"])),
+  request_body = UpdateNotificationPreferencesBody,
+  responses(
+    (status = StatusCode::OK, description = "Notification preferences updated successfully."),
+    (status = StatusCode::BAD_REQUEST, description = "The email address is invalid or isn't the account's own.", body = String),
+    (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error.", body = String)
+  )
+)]
+pub(crate) async fn update_notification_preferences(
+  user: User,
+  AppState(db): AppState<Arc<Db>>,
+  body: Json<UpdateNotificationPreferencesBody>,
+) -> Result<Response> {
+  let UpdateNotificationPreferencesBody {
+    email,
+    email_frequency,
+  } = body.0;
+
+  let email = match email {
+    Some(email) => {
+      let Ok(mailbox) = email.parse::<Mailbox>() else {
+        return Ok(
+          (
+            StatusCode::BAD_REQUEST,
+            format!("Invalid email address `{email}`"),
+          )
+            .into_response(),
+        );
+      };
+
+      let address = mailbox.email.to_string();
+
+      if !address.eq_ignore_ascii_case(user.mail()) {
+        return Ok(
+          (
+            StatusCode::BAD_REQUEST,
+            "Notifications can only be emailed to the account's own address",
+          )
+            .into_response(),
+        );
+      }
+
+      address
+    }
+    None => user.mail().to_string(),
+  };
+
+  db.set_notification_preferences(NotificationPreferences {
+    user_id: user.id(),
+    email: Some(email),
+    email_frequency,
+    last_digest_at: None,
+  })
+  .await?;
+
+  Ok(StatusCode::OK.into_response())
+}
+
+#[utoipa::path(
+  get,
+  path = "/notifications/stream",
+  tag = "notifications",
+  description = "Stream the current user's notifications as server-sent events while they're signed in.",
+  security(("microsoftOAuth" = ["User.Read"])),
+  params(
+    ("Last-Event-ID" = Option<String>, Header, description = "Identifier of the last notification received, to replay any created since.")
+  ),
+  responses(
+    (status = StatusCode::OK, description = "A `notification` event for each new notification, with the notification's identifier as the event id, and periodic heartbeat comments.", body = Notification, content_type = "text/event-stream"),
+    (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error.", body = String)
+  )
+)]
+pub(crate) async fn stream_notifications(
+  user: User,
+  TypedHeader(cookies): TypedHeader<Cookie>,
+  headers: HeaderMap,
+  AppState(db): AppState<Arc<Db>>,
+  AppState(session_store): AppState<MongodbSessionStore>,
+  AppState(supervisor): AppState<Supervisor>,
+) -> Result<impl IntoResponse> {
+  let stream = NotificationStream::new(
+    db,
+    session_store,
+    cookies.get(COOKIE_NAME).unwrap_or_default().to_owned(),
+    user.id(),
+    headers
+      .get("last-event-id")
+      .and_then(|value| value.to_str().ok()),
+  )
+  .await?;
+
+  Ok(
+    // End the stream on shutdown, so it doesn't hold up draining.
+    Sse::new(
+      stream
+        .events()
+        .take_until(supervisor.shutdown().cancelled_owned()),
+    )
+    .keep_alive(KeepAlive::new().interval(NOTIFICATION_HEARTBEAT_INTERVAL)),
+  )
+}
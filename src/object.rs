@@ -1,22 +1,24 @@
 use super::*;
 
 #[async_trait]
-pub(crate) trait Object {
-  async fn get(&self, bucket: &str, key: &str) -> Result<Option<Vec<u8>>>;
-  async fn put(
-    &self,
-    bucket: &str,
-    key: &str,
-    value: Vec<u8>,
-  ) -> Result<PutObjectOutput>;
+pub(crate) trait Object: Send + Sync {
+  async fn get(&self, key: &str) -> Result<Option<Vec<u8>>>;
+  async fn put(&self, key: &str, value: Vec<u8>) -> Result;
+}
+
+/// Objects in an S3 bucket, or one in an S3-compatible store like MinIO.
+pub(crate) struct S3Store {
+  pub(crate) bucket: String,
+  pub(crate) client: S3Client,
 }
 
 #[async_trait]
-impl Object for S3Client {
-  async fn get(&self, bucket: &str, key: &str) -> Result<Option<Vec<u8>>> {
+impl Object for S3Store {
+  async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
     let response = self
+      .client
       .get_object(GetObjectRequest {
-        bucket: bucket.into(),
+        bucket: self.bucket.clone(),
         key: key.into(),
         ..Default::default()
       })
@@ -31,21 +33,108 @@ impl Object for S3Client {
     })
   }
 
-  async fn put(
-    &self,
-    bucket: &str,
-    key: &str,
-    value: Vec<u8>,
-  ) -> Result<PutObjectOutput> {
-    Ok(
-      self
-        .put_object(PutObjectRequest {
-          bucket: bucket.into(),
-          key: key.into(),
-          body: Some(value.into()),
-          ..Default::default()
-        })
-        .await?,
-    )
+  async fn put(&self, key: &str, value: Vec<u8>) -> Result {
+    self
+      .client
+      .put_object(PutObjectRequest {
+        bucket: self.bucket.clone(),
+        key: key.into(),
+        body: Some(value.into()),
+        ..Default::default()
+      })
+      .await?;
+
+    Ok(())
+  }
+}
+
+/// Objects as files under a directory, keys being paths relative to it.
+pub(crate) struct LocalStore {
+  pub(crate) dir: PathBuf,
+}
+
+#[async_trait]
+impl Object for LocalStore {
+  async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+    match tokio::fs::read(self.dir.join(key)).await {
+      Ok(value) => Ok(Some(value)),
+      Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+      Err(error) => Err(error.into()),
+    }
+  }
+
+  async fn put(&self, key: &str, value: Vec<u8>) -> Result {
+    let path = self.dir.join(key);
+
+    if let Some(parent) = path.parent() {
+      tokio::fs::create_dir_all(parent).await?;
+    }
+
+    tokio::fs::write(path, value).await?;
+
+    Ok(())
+  }
+}
+
+/// Objects kept in memory, gone once the server stops.
+#[derive(Default)]
+pub(crate) struct MemoryStore {
+  objects: Mutex<HashMap<String, Vec<u8>>>,
+}
+
+#[async_trait]
+impl Object for MemoryStore {
+  async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+    Ok(self.objects.lock().unwrap().get(key).cloned())
+  }
+
+  async fn put(&self, key: &str, value: Vec<u8>) -> Result {
+    self.objects.lock().unwrap().insert(key.into(), value);
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, tempfile::tempdir};
+
+  async fn round_trip(store: &dyn Object) {
+    assert_eq!(store.get("source-hash/courses.json").await.unwrap(), None);
+
+    store
+      .put("source-hash/courses.json", b"a".to_vec())
+      .await
+      .unwrap();
+
+    store
+      .put("source-hash/courses.json", b"b".to_vec())
+      .await
+      .unwrap();
+
+    assert_eq!(
+      store.get("source-hash/courses.json").await.unwrap(),
+      Some(b"b".to_vec())
+    );
+  }
+
+  #[tokio::test]
+  async fn local_store_round_trips() {
+    let directory = tempdir().unwrap();
+
+    round_trip(&LocalStore {
+      dir: directory.path().join("storage"),
+    })
+    .await;
+
+    assert_eq!(
+      fs::read(directory.path().join("storage/source-hash/courses.json"))
+        .unwrap(),
+      b"b"
+    );
+  }
+
+  #[tokio::test]
+  async fn memory_store_round_trips() {
+    round_trip(&MemoryStore::default()).await;
   }
 }
@@ -0,0 +1,332 @@
+use {
+  super::*,
+  governor::{
+    Quota, clock::Clock, middleware::StateInformationMiddleware,
+    state::keyed::DefaultKeyedStateStore,
+  },
+};
+
+/// Routes that share a rate limit policy.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum RouteGroup {
+  /// Signing in and out.
+  Auth,
+  /// Likes and dislikes on reviews.
+  Interactions,
+  /// Everything not in another group.
+  Reads,
+  /// Writing, editing and deleting reviews.
+  ReviewWrites,
+  /// Full text search.
+  Search,
+}
+
+impl Display for RouteGroup {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "{}",
+      match self {
+        Self::Auth => "auth",
+        Self::Interactions => "interactions",
+        Self::Reads => "reads",
+        Self::ReviewWrites => "review_writes",
+        Self::Search => "search",
+      }
+    )
+  }
+}
+
+/// How many requests a client can make over a period, e.g. `60/1m`.
+///
+/// Requests are let through in bursts of up to `requests`, and capacity is
+/// replenished evenly over `period`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct RateLimitPolicy {
+  pub(crate) requests: NonZeroU32,
+  pub(crate) period: Duration,
+}
+
+impl RateLimitPolicy {
+  pub(crate) const fn new(requests: NonZeroU32, period: Duration) -> Self {
+    Self { requests, period }
+  }
+
+  fn quota(self) -> Result<Quota> {
+    // Rate limiters keep time in nanoseconds that have to fit in a `u64`.
+    if u64::try_from(self.period.as_nanos()).is_err() {
+      return Err(anyhow!("Rate limit period {self} is too long").into());
+    }
+
+    Ok(
+      Quota::with_period(self.period / self.requests.get())
+        .ok_or_else(|| anyhow!("Rate limit period {self} is too short"))?
+        .allow_burst(self.requests),
+    )
+  }
+}
+
+impl Display for RateLimitPolicy {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(f, "{}/{:?}", self.requests, self.period)
+  }
+}
+
+impl FromStr for RateLimitPolicy {
+  type Err = anyhow::Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let invalid = || anyhow!("Invalid rate limit `{s}`, expected e.g. `60/1m`");
+
+    let (requests, period) = s.trim().split_once('/').ok_or_else(invalid)?;
+
+    let policy = Self::new(
+      requests.parse().map_err(|_| invalid())?,
+      parse_duration(period).map_err(|_| invalid())?,
+    );
+
+    if policy.quota().is_err() {
+      return Err(invalid());
+    }
+
+    Ok(policy)
+  }
+}
+
+/// A rate limit policy for each route group, and the proxies trusted to say
+/// who clients are.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct RateLimits {
+  pub(crate) auth: RateLimitPolicy,
+  pub(crate) interactions: RateLimitPolicy,
+  pub(crate) reads: RateLimitPolicy,
+  pub(crate) review_writes: RateLimitPolicy,
+  pub(crate) search: RateLimitPolicy,
+  /// Reverse proxies whose `X-Forwarded-For` entries are believed.
+  pub(crate) trusted_proxies: Vec<IpAddr>,
+}
+
+impl Default for RateLimits {
+  fn default() -> Self {
+    let per_minute = |requests| {
+      RateLimitPolicy::new(
+        NonZeroU32::new(requests).unwrap(),
+        Duration::from_secs(60),
+      )
+    };
+
+    Self {
+      auth: per_minute(20),
+      interactions: per_minute(60),
+      reads: per_minute(600),
+      review_writes: per_minute(10),
+      search: per_minute(120),
+      trusted_proxies: Vec::new(),
+    }
+  }
+}
+
+impl RateLimits {
+  fn policy(&self, group: RouteGroup) -> RateLimitPolicy {
+    match group {
+      RouteGroup::Auth => self.auth,
+      RouteGroup::Interactions => self.interactions,
+      RouteGroup::Reads => self.reads,
+      RouteGroup::ReviewWrites => self.review_writes,
+      RouteGroup::Search => self.search,
+    }
+  }
+}
+
+type KeyedLimiter = governor::RateLimiter<
+  String,
+  DefaultKeyedStateStore<String>,
+  governor::clock::DefaultClock,
+  StateInformationMiddleware,
+>;
+
+/// Enforces one group's policy, per signed in user or, failing that, per
+/// client IP.
+#[derive(Clone)]
+pub(crate) struct RateLimiter {
+  group: RouteGroup,
+  limiter: Arc<KeyedLimiter>,
+  metrics: Arc<Metrics>,
+  policy: RateLimitPolicy,
+  session_store: MongodbSessionStore,
+  trusted_proxies: Arc<[IpAddr]>,
+}
+
+impl RateLimiter {
+  /// How often clients whose limits have replenished are forgotten.
+  pub(crate) const CLEANUP_INTERVAL: Duration = Duration::from_secs(60);
+
+  pub(crate) fn new(
+    limits: &RateLimits,
+    group: RouteGroup,
+    metrics: Arc<Metrics>,
+    session_store: MongodbSessionStore,
+  ) -> Result<Self> {
+    let policy = limits.policy(group);
+
+    Ok(Self {
+      group,
+      limiter: Arc::new(
+        governor::RateLimiter::keyed(policy.quota()?)
+          .with_middleware::<StateInformationMiddleware>(),
+      ),
+      metrics,
+      policy,
+      session_store,
+      trusted_proxies: limits.trusted_proxies.clone().into(),
+    })
+  }
+
+  /// Forgets clients whose limits have fully replenished.
+  pub(crate) fn retain_recent(&self) {
+    self.limiter.retain_recent();
+  }
+
+  /// The signed in user, or the client's IP.
+  ///
+  /// When the peer is a trusted reverse proxy, the client is the last
+  /// `X-Forwarded-For` entry not added by another trusted proxy. Anyone else
+  /// could say whatever they like there, so the header is otherwise ignored.
+  async fn key(&self, headers: &HeaderMap, peer: Option<IpAddr>) -> String {
+    if let Some(cookie) = headers
+      .typed_get::<Cookie>()
+      .and_then(|cookies| cookies.get(COOKIE_NAME).map(String::from))
+      && let Ok(Some(session)) = self.session_store.load_session(cookie).await
+      && let Some(user) = session.get::<User>("user")
+    {
+      return format!("user:{}", user.id());
+    }
+
+    let trusted = |ip: &IpAddr| self.trusted_proxies.contains(ip);
+
+    let client = match peer {
+      Some(peer) if trusted(&peer) => headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .rev()
+        .map_while(|ip| ip.trim().parse::<IpAddr>().ok())
+        .find(|ip| !trusted(ip))
+        .or(Some(peer)),
+      peer => peer,
+    };
+
+    match client {
+      Some(ip) => format!("ip:{ip}"),
+      None => "unknown".into(),
+    }
+  }
+
+  fn headers(&self, remaining: u32, reset: Duration) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+
+    let seconds = |duration: Duration| {
+      duration.as_secs() + u64::from(duration.subsec_nanos() > 0)
+    };
+
+    for (name, value) in [
+      (
+        "ratelimit-policy",
+        format!("{};w={}", self.policy.requests, seconds(self.policy.period)),
+      ),
+      ("ratelimit-limit", self.policy.requests.to_string()),
+      ("ratelimit-remaining", remaining.to_string()),
+      ("ratelimit-reset", seconds(reset).to_string()),
+    ] {
+      headers.insert(name, value.parse().unwrap());
+    }
+
+    headers
+  }
+
+  pub(crate) async fn middleware(
+    AppState(limiter): AppState<RateLimiter>,
+    request: Request<Body>,
+    next: Next,
+  ) -> Response {
+    let peer = request
+      .extensions()
+      .get::<ConnectInfo<SocketAddr>>()
+      .map(|ConnectInfo(address)| address.ip());
+
+    let key = limiter.key(request.headers(), peer).await;
+
+    match limiter.limiter.check_key(&key) {
+      Ok(snapshot) => {
+        let remaining = snapshot.remaining_burst_capacity();
+
+        let reset = snapshot.quota().replenish_interval()
+          * (limiter.policy.requests.get() - remaining);
+
+        let mut response = next.run(request).await;
+
+        response
+          .headers_mut()
+          .extend(limiter.headers(remaining, reset));
+
+        response
+      }
+      Err(not_until) => {
+        limiter.metrics.rate_limit_rejected(limiter.group);
+
+        let wait = not_until.wait_time_from(limiter.limiter.clock().now());
+
+        let mut headers = limiter.headers(0, wait);
+
+        headers.insert(header::RETRY_AFTER, headers["ratelimit-reset"].clone());
+
+        (
+          StatusCode::TOO_MANY_REQUESTS,
+          headers,
+          "Too many requests, please try again later.",
+        )
+          .into_response()
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn policies_parse() {
+    assert_eq!(
+      "60/1m".parse::<RateLimitPolicy>().unwrap(),
+      RateLimitPolicy::new(
+        NonZeroU32::new(60).unwrap(),
+        Duration::from_secs(60)
+      )
+    );
+
+    assert_eq!(
+      " 5/500ms ".parse::<RateLimitPolicy>().unwrap(),
+      RateLimitPolicy::new(
+        NonZeroU32::new(5).unwrap(),
+        Duration::from_millis(500)
+      )
+    );
+
+    for invalid in [
+      "60",
+      "0/1m",
+      "60/m",
+      "60/1w",
+      "60/0s",
+      "x/1s",
+      "1000000000/1ms",
+      "1/18446744073709551615s",
+      "1/18446744073709551615m",
+      "1/1000000d",
+    ] {
+      assert!(invalid.parse::<RateLimitPolicy>().is_err(), "{invalid}");
+    }
+  }
+}
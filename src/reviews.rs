@@ -146,6 +146,7 @@ pub(crate) struct AddOrUpdateReviewBody {
 ))]
 pub(crate) async fn add_review(
   AppState(db): AppState<Arc<Db>>,
+  AppState(dispatcher): AppState<Arc<Dispatcher>>,
   user: User,
   body: Json<AddOrUpdateReviewBody>,
 ) -> Result<impl IntoResponse> {
@@ -180,7 +181,10 @@ pub(crate) async fn add_review(
 
   info!("Adding notifications for course {}...", &course_id);
 
-  db.add_notifications(review).await?;
+  // The review is in, so failing to notify subscribers shouldn't fail it.
+  if let Err(error) = dispatcher.dispatch(&review).await {
+    error!("Failed to notify subscribers of course {course_id}: {error}");
+  }
 
   Ok(StatusCode::OK)
 }
@@ -0,0 +1,166 @@
+use {super::*, std::path::Path};
+
+/// The hash of every seed file in a source, to tell which changed since they
+/// were last seeded into a database.
+#[derive(Debug)]
+pub(crate) struct SeedHashes {
+  hashes: BTreeMap<PathBuf, Vec<u8>>,
+  /// Where the hashes are kept in the store, so that each deployment's
+  /// databases are tracked separately.
+  prefix: String,
+}
+
+impl SeedHashes {
+  /// Hashes `source`, a seed file or a directory of them, for seeding the
+  /// `db_name` database of `environment`. Like seeding, only the files
+  /// directly inside a directory are looked at.
+  pub(crate) fn new(
+    source: &PathBuf,
+    environment: Environment,
+    db_name: &str,
+  ) -> Result<Self> {
+    let paths = if source.is_dir() {
+      fs::read_dir(source)?
+        .map(|entry| Ok(entry?.path()))
+        .collect::<Result<Vec<_>, io::Error>>()?
+        .into_iter()
+        .filter(|path| path.is_file())
+        .collect()
+    } else {
+      vec![source.clone()]
+    };
+
+    Ok(Self {
+      hashes: paths
+        .into_iter()
+        .map(|path| -> Result<(PathBuf, Vec<u8>)> {
+          let hash = path.hash()?;
+          Ok((path, hash))
+        })
+        .collect::<Result<_>>()?,
+      prefix: format!("source-hash/{environment}/{db_name}"),
+    })
+  }
+
+  fn key(&self, path: &Path) -> String {
+    format!(
+      "{}/{}",
+      self.prefix,
+      path.file_name().unwrap_or_default().to_string_lossy()
+    )
+  }
+
+  pub(crate) fn len(&self) -> usize {
+    self.hashes.len()
+  }
+
+  /// Seed files last seeded as they are now.
+  pub(crate) async fn up_to_date(
+    &self,
+    store: &dyn Object,
+  ) -> Result<HashSet<PathBuf>> {
+    let mut up_to_date = HashSet::new();
+
+    for (path, hash) in &self.hashes {
+      if store.get(&self.key(path)).await?.as_ref() == Some(hash) {
+        up_to_date.insert(path.clone());
+      }
+    }
+
+    Ok(up_to_date)
+  }
+
+  /// Records `seeded` as seeded as they are now.
+  pub(crate) async fn record(
+    &self,
+    store: &dyn Object,
+    seeded: &[PathBuf],
+  ) -> Result {
+    for path in seeded {
+      if let Some(hash) = self.hashes.get(path) {
+        store.put(&self.key(path), hash.clone()).await?;
+      }
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, tempfile::tempdir};
+
+  #[tokio::test]
+  async fn only_changed_files_are_out_of_date() {
+    let directory = tempdir().unwrap();
+
+    let source = directory.path().to_path_buf();
+
+    let (courses, exams) =
+      (source.join("courses.json"), source.join("exams.json"));
+
+    fs::write(&courses, "[]").unwrap();
+    fs::write(&exams, "[]").unwrap();
+    fs::create_dir(source.join("nested")).unwrap();
+
+    let store = MemoryStore::default();
+
+    let hashes =
+      SeedHashes::new(&source, Environment::Development, "a").unwrap();
+
+    assert_eq!(hashes.len(), 2);
+    assert!(hashes.up_to_date(&store).await.unwrap().is_empty());
+
+    hashes
+      .record(&store, &[courses.clone(), exams.clone()])
+      .await
+      .unwrap();
+
+    assert_eq!(
+      SeedHashes::new(&source, Environment::Development, "a")
+        .unwrap()
+        .up_to_date(&store)
+        .await
+        .unwrap(),
+      HashSet::from([courses.clone(), exams.clone()])
+    );
+
+    fs::write(&exams, "[{}]").unwrap();
+
+    assert_eq!(
+      SeedHashes::new(&source, Environment::Development, "a")
+        .unwrap()
+        .up_to_date(&store)
+        .await
+        .unwrap(),
+      HashSet::from([courses.clone()])
+    );
+
+    assert!(
+      SeedHashes::new(&source, Environment::Development, "b")
+        .unwrap()
+        .up_to_date(&store)
+        .await
+        .unwrap()
+        .is_empty()
+    );
+
+    assert!(
+      SeedHashes::new(&source, Environment::Production, "a")
+        .unwrap()
+        .up_to_date(&store)
+        .await
+        .unwrap()
+        .is_empty()
+    );
+
+    assert_eq!(
+      SeedHashes::new(&courses, Environment::Development, "a")
+        .unwrap()
+        .up_to_date(&store)
+        .await
+        .unwrap(),
+      HashSet::from([courses])
+    );
+  }
+}
@@ -2,14 +2,12 @@ use super::*;
 
 #[derive(Parser)]
 pub(crate) struct Server {
+  #[clap(long, env = "CONFIG", help = "TOML file to read settings from")]
+  config: Option<PathBuf>,
+  #[clap(flatten)]
+  settings: Settings,
   #[clap(long, default_value = "courses.json")]
   source: PathBuf,
-  #[clap(long, help = "Directory to serve assets from")]
-  asset_dir: Option<PathBuf>,
-  #[clap(long, default_value = "8000", help = "Port to listen on")]
-  port: u16,
-  #[clap(long, default_value = "admin", help = "Database name")]
-  db_name: String,
   #[clap(long, default_value = "false", help = "Seed latest courses only")]
   latest_courses: bool,
   #[clap(long, default_value = "false", help = "Enable multithreaded seeding")]
@@ -24,109 +22,228 @@ pub(crate) struct Server {
 
 #[derive(Debug)]
 struct AppConfig<'a> {
+  config: &'a Config,
   db: Arc<Db>,
+  dispatcher: Arc<Dispatcher>,
   assets: Option<Assets<'a>>,
+  metrics: Arc<Metrics>,
+  seeding: Seeding,
   session_store: MongodbSessionStore,
+  supervisor: Supervisor,
   rate_limit: bool,
 }
 
 impl Server {
-  pub(crate) async fn run(self) -> Result {
-    let addr = SocketAddr::from(([0, 0, 0, 0], self.port));
+  /// Settings from the command line and environment, falling back to the
+  /// config file.
+  pub(crate) fn config(&self) -> Result<Config> {
+    Config::load(self.settings.clone(), self.config.as_deref())
+  }
 
-    info!("Listening on port: {}", addr.port());
+  pub(crate) async fn run(self, config: Config) -> Result {
+    let addr = SocketAddr::from(([0, 0, 0, 0], config.port));
 
-    let db = Arc::new(Db::connect(&self.db_name).await?);
+    info!("Listening on port: {}", addr.port());
 
-    if self.initialize {
-      let source_hash = self.source.hash()?;
+    let metrics = Arc::new(Metrics::new()?);
 
-      let client = match env::var("ENV") {
-        Ok(env) if env == "production" => Some(S3Client::new(Region::UsEast1)),
-        _ => None,
-      };
+    let db = Arc::new(
+      Db::connect_monitored(
+        &config.mongodb_url,
+        &config.db_name,
+        metrics.clone(),
+      )
+      .await?,
+    );
 
-      let prev_hash = match client {
-        Some(ref client) => client.get("mcgill.courses", "source-hash").await?,
-        None => None,
-      };
+    let seeding = Seeding::default();
 
-      if Some(&source_hash) != prev_hash.as_ref() {
-        let clone = db.clone();
+    let supervisor = Supervisor::default();
 
-        if let Some(client) = client {
-          client
-            .put("mcgill.courses", "source-hash", source_hash)
-            .await?;
-        }
+    supervisor.handle_signals();
 
-        tokio::spawn(async move {
-          if let Err(error) = clone
-            .initialize(InitializeOptions {
-              latest_courses: self.latest_courses,
-              multithreaded: self.multithreaded,
-              skip_courses: self.skip_courses,
-              skip_reviews: self.skip_reviews,
-              source: self.source,
-            })
-            .await
+    if self.initialize {
+      let store = config.storage.store();
+
+      let hashes =
+        SeedHashes::new(&self.source, config.environment, &config.db_name)?;
+
+      let up_to_date = hashes.up_to_date(store.as_ref()).await?;
+
+      if up_to_date.len() == hashes.len() {
+        info!("Every seed file is up to date, skipping seeding");
+      } else {
+        let (db, seeding) = (db.clone(), seeding.clone());
+
+        seeding.start();
+
+        supervisor.spawn("seeding", move |shutdown| async move {
+          let progress = seeding.progress();
+
+          let initialize = db.initialize(InitializeOptions {
+            latest_courses: self.latest_courses,
+            multithreaded: self.multithreaded,
+            progress: progress.clone(),
+            skip_courses: self.skip_courses,
+            skip_reviews: self.skip_reviews,
+            source: self.source,
+            up_to_date,
+          });
+
+          tokio::pin!(initialize);
+
+          // Let the seed file underway finish, rather than dropping it halfway.
+          let result = tokio::select! {
+            result = &mut initialize => result,
+            () = shutdown.cancelled() => {
+              progress.cancel();
+              initialize.await
+            }
+          };
+
+          // Record whichever files made it, even if seeding didn't, so that
+          // they aren't seeded again.
+          if let Err(error) =
+            hashes.record(store.as_ref(), &progress.seeded()).await
           {
-            error!("error: {error}");
+            error!("Failed to record seed file hashes: {error}");
+          }
+
+          match result {
+            Ok(()) => {
+              seeding.complete();
+              Ok(())
+            }
+            Err(db::Error::SeedingCancelled) => {
+              seeding.cancel();
+              Ok(())
+            }
+            Err(error) => {
+              seeding.fail(error.to_string());
+              Err(error.into())
+            }
           }
         });
       }
     }
 
-    let assets = self.asset_dir.as_ref().map(|asset_dir| Assets {
+    let assets = config.asset_dir.as_ref().map(|asset_dir| Assets {
       dir: ServeDir::new(asset_dir.clone()),
       index: ServeFile::new(asset_dir.join("index.html")),
       route: "/assets",
     });
 
-    let session_store = MongodbSessionStore::new(
-      &env::var("MONGODB_URL").unwrap_or_else(|_| {
-        "mongodb://localhost:27017/?directConnection=true&replicaSet=rs0".into()
-      }),
-      &db.name(),
-      "store",
-    )
-    .await?;
+    let session_store =
+      MongodbSessionStore::new(&config.mongodb_url, &db.name(), "store")
+        .await?;
+
+    let dispatcher = Arc::new(Dispatcher::from_config(db.clone(), &config)?);
+
+    supervisor.spawn("deliveries", {
+      let dispatcher = dispatcher.clone();
+      |shutdown| async move {
+        dispatcher.run_deliveries(shutdown).await;
+        Ok(())
+      }
+    });
+
+    supervisor.spawn_critical("digests", {
+      let dispatcher = dispatcher.clone();
+      |shutdown| async move {
+        dispatcher.run_digests(shutdown).await;
+        Ok(())
+      }
+    });
+
+    supervisor.spawn_critical("webhooks", {
+      let sender = Arc::new(WebhookSender::new(db.clone())?);
+      |shutdown| async move {
+        sender.run(shutdown).await;
+        Ok(())
+      }
+    });
 
     let app = Self::app(AppConfig {
+      config: &config,
       db,
+      dispatcher,
       assets,
+      metrics,
+      seeding,
       session_store,
+      supervisor: supervisor.clone(),
       rate_limit: true,
     })
     .await?;
 
-    axum::serve(
+    let shutdown = supervisor.shutdown();
+
+    let serve = axum::serve(
       TcpListener::bind(addr).await?,
       app.into_make_service_with_connect_info::<SocketAddr>(),
     )
-    .await?;
+    .with_graceful_shutdown(shutdown.clone().cancelled_owned());
+
+    let served = tokio::select! {
+      result = serve.into_future() => result,
+      () = async {
+        shutdown.cancelled().await;
+        tokio::time::sleep(Supervisor::DRAIN_TIMEOUT).await;
+      } => {
+        warn!("Timed out draining in-flight requests");
+        Ok(())
+      }
+    };
+
+    supervisor.stop().await?;
+
+    info!("Server stopped");
 
-    Ok(())
+    Ok(served?)
   }
 
   async fn app(config: AppConfig<'_>) -> Result<Router> {
-    let mut router = Router::new()
-      .route("/api/auth/authorized", get(auth::login_authorized))
-      .route("/api/auth/login", get(auth::microsoft_auth))
-      .route("/api/auth/logout", get(auth::logout))
+    let reads = Router::new()
+      .route(
+        "/api/admin/webhooks",
+        get(webhooks::get_webhooks).post(webhooks::add_webhook),
+      )
+      .route("/api/admin/webhooks/{id}", delete(webhooks::delete_webhook))
+      .route(
+        "/api/admin/webhooks/{id}/deliveries",
+        get(webhooks::get_webhook_deliveries),
+      )
+      .route("/api/buildings", get(buildings::get_buildings))
+      .route(
+        "/api/buildings/{code}/courses",
+        get(buildings::get_building_courses),
+      )
       .route("/api/courses", post(courses::get_courses))
       .route("/api/courses/{id}", get(courses::get_course_by_id))
+      .route(
+        "/api/courses/{id}/averages",
+        get(courses::get_course_averages),
+      )
+      .route(
+        "/api/courses/{id}/eligibility",
+        get(courses::get_course_eligibility),
+      )
+      .route("/api/courses/{id}/exams", get(exams::get_course_exams))
+      .route(
+        "/api/courses/{id}/instructors/compare",
+        get(instructors::compare_instructors),
+      )
+      .route(
+        "/api/courses/{id}/history",
+        get(courses::get_course_history),
+      )
+      .route("/api/exams", get(exams::get_exams))
       .route("/api/instructors/{name}", get(instructors::get_instructor))
       .route(
         "/api/interactions/{course_id}/referrer/{referrer}",
         get(interactions::get_user_interactions_for_course),
       )
-      .route(
-        "/api/interactions",
-        get(interactions::get_interaction_kind)
-          .post(interactions::add_interaction)
-          .delete(interactions::delete_interaction),
-      )
+      .route("/api/interactions", get(interactions::get_interaction_kind))
       .route(
         "/api/notifications",
         get(notifications::get_notifications)
@@ -134,21 +251,113 @@ impl Server {
           .delete(notifications::delete_notification),
       )
       .route(
-        "/api/reviews",
-        get(reviews::get_reviews)
-          .delete(reviews::delete_review)
-          .post(reviews::add_review)
-          .put(reviews::update_review),
+        "/api/notifications/clear-all",
+        post(notifications::clear_all_notifications),
+      )
+      .route(
+        "/api/notifications/mark-all-read",
+        post(notifications::mark_all_notifications_read),
+      )
+      .route(
+        "/api/notifications/preferences",
+        get(notifications::get_notification_preferences)
+          .put(notifications::update_notification_preferences),
+      )
+      .route(
+        "/api/notifications/stream",
+        get(notifications::stream_notifications),
       )
+      .route(
+        "/api/notifications/{id}",
+        put(notifications::update_notification_by_id)
+          .delete(notifications::delete_notification_by_id),
+      )
+      .route("/api/reviews", get(reviews::get_reviews))
       .route("/api/reviews/{id}", get(reviews::get_review))
-      .route("/api/search", get(search::search))
       .route(
         "/api/subscriptions",
         get(subscriptions::get_subscription)
           .post(subscriptions::add_subscription)
           .delete(subscriptions::delete_subscription),
       )
-      .route("/api/user", get(user::get_user))
+      .route("/api/user", get(user::get_user));
+
+    let auth = Router::new()
+      .route("/api/auth/authorized", get(auth::login_authorized))
+      .route("/api/auth/login", get(auth::microsoft_auth))
+      .route("/api/auth/logout", get(auth::logout));
+
+    let interactions = Router::new().route(
+      "/api/interactions",
+      post(interactions::add_interaction)
+        .delete(interactions::delete_interaction),
+    );
+
+    let review_writes = Router::new().route(
+      "/api/reviews",
+      delete(reviews::delete_review)
+        .post(reviews::add_review)
+        .put(reviews::update_review),
+    );
+
+    let search = Router::new().route("/api/search", get(search::search));
+
+    let mut router = Router::new();
+
+    let mut limiters = Vec::new();
+
+    for (group, routes) in [
+      (RouteGroup::Auth, auth),
+      (RouteGroup::Interactions, interactions),
+      (RouteGroup::Reads, reads),
+      (RouteGroup::ReviewWrites, review_writes),
+      (RouteGroup::Search, search),
+    ] {
+      router = router.merge(if config.rate_limit {
+        let limiter = RateLimiter::new(
+          &config.config.rate_limits,
+          group,
+          config.metrics.clone(),
+          config.session_store.clone(),
+        )?;
+
+        limiters.push(limiter.clone());
+
+        routes.route_layer(middleware::from_fn_with_state(
+          limiter,
+          RateLimiter::middleware,
+        ))
+      } else {
+        routes
+      });
+    }
+
+    if !limiters.is_empty() {
+      config
+        .supervisor
+        .spawn("rate-limit-cleanup", |shutdown| async move {
+          let mut interval = tokio::time::interval_at(
+            Instant::now() + RateLimiter::CLEANUP_INTERVAL,
+            RateLimiter::CLEANUP_INTERVAL,
+          );
+
+          loop {
+            tokio::select! {
+              _ = interval.tick() => {}
+              () = shutdown.cancelled() => return Ok(()),
+            }
+
+            for limiter in &limiters {
+              limiter.retain_recent();
+            }
+          }
+        });
+    }
+
+    router = router
+      .route("/healthz", get(health::healthz))
+      .route("/metrics", get(health::metrics))
+      .route("/readyz", get(health::readyz))
       .merge(Scalar::with_url("/api/docs", Documentation::openapi()));
 
     // Serve microsoft identity association file
@@ -173,7 +382,22 @@ impl Server {
     }
 
     let router = router
-      .with_state(State::new(config.db, config.session_store).await?)
+      .layer(middleware::from_fn_with_state(
+        config.metrics.clone(),
+        Metrics::track,
+      ))
+      .with_state(
+        State::new(
+          config.config,
+          config.db,
+          config.dispatcher,
+          config.metrics,
+          config.seeding,
+          config.session_store,
+          config.supervisor,
+        )
+        .await?,
+      )
       .layer(
         TraceLayer::new_for_http()
           .make_span_with(|request: &Request<Body>| {
@@ -217,32 +441,7 @@ impl Server {
           ),
       );
 
-    let governor_config = GovernorConfigBuilder::default()
-      .per_millisecond(10)
-      .burst_size(100)
-      .finish()
-      .ok_or(anyhow!("Failed to create governor configuration"))?;
-
-    let governor_limiter = governor_config.limiter().clone();
-
-    let interval = Duration::from_secs(60);
-
-    thread::spawn(move || {
-      loop {
-        thread::sleep(interval);
-        governor_limiter.retain_recent();
-      }
-    });
-
-    Ok(if config.rate_limit {
-      router.layer(
-        ServiceBuilder::new()
-          .layer(GovernorLayer::new(governor_config))
-          .layer(CorsLayer::very_permissive()),
-      )
-    } else {
-      router.layer(CorsLayer::very_permissive())
-    })
+    Ok(router.layer(CorsLayer::very_permissive()))
   }
 }
 
@@ -251,15 +450,27 @@ mod tests {
   use {
     super::*,
     crate::{
-      instructors::GetInstructorPayload,
+      buildings::{BuildingCourse, GetBuildingCoursesPayload},
+      config::{MicrosoftSettings, Secret},
+      delivery::EmailChannel,
+      health::ReadinessPayload,
+      instructors::{CompareInstructorsPayload, GetInstructorPayload},
       interactions::GetUserInteractionForCoursePayload,
+      notifications::{BulkNotificationsPayload, GetNotificationsPayload},
       subscriptions::SubscriptionResponse,
     },
     axum::body::Body,
-    courses::{GetCourseByIdPayload, GetCoursesPayload},
+    courses::{
+      Equivalence, EquivalentCourse, GetCourseAveragesPayload,
+      GetCourseByIdPayload, GetCourseEligibilityPayload,
+      GetCourseHistoryPayload, GetCoursesPayload,
+    },
+    futures::StreamExt,
     http::{Method, Request},
     interactions::GetInteractionKindPayload,
-    model::{Notification, Subscription},
+    model::{
+      Notification, Operator, ReqNode, Subscription, TeachingAssignment,
+    },
     pretty_assertions::assert_eq,
     reviews::GetReviewsPayload,
     serde::de::DeserializeOwned,
@@ -308,20 +519,25 @@ mod tests {
         test_database_number,
       );
 
-      let db = Arc::new(Db::connect(&db_name).await.unwrap());
+      let config = config();
 
-      let session_store = MongodbSessionStore::new(
-        "mongodb://localhost:27017/?directConnection=true&replicaSet=rs0",
-        &db.name(),
-        "store",
-      )
-      .await
-      .unwrap();
+      let db =
+        Arc::new(Db::connect(&config.mongodb_url, &db_name).await.unwrap());
+
+      let session_store =
+        MongodbSessionStore::new(&config.mongodb_url, &db.name(), "store")
+          .await
+          .unwrap();
 
       let app = Server::app(AppConfig {
+        config: &config,
         db: db.clone(),
+        dispatcher: Arc::new(Dispatcher::new(db.clone(), Vec::new())),
         assets: None,
+        metrics: Arc::new(Metrics::new().unwrap()),
+        seeding: Seeding::default(),
         session_store: session_store.clone(),
+        supervisor: Supervisor::default(),
         rate_limit: false,
       })
       .await
@@ -335,6 +551,20 @@ mod tests {
     }
   }
 
+  fn config() -> Config {
+    Settings {
+      admin_emails: Some(vec!["admin@mail.mcgill.ca".into()]),
+      microsoft: MicrosoftSettings {
+        client_id: Some("client".into()),
+        client_secret: Some(Secret("secret".into())),
+        redirect_uri: Some("http://localhost:8000/api/auth/authorized".into()),
+      },
+      ..Default::default()
+    }
+    .try_into()
+    .unwrap()
+  }
+
   fn seed() -> PathBuf {
     PathBuf::from("crates/db/test-seeds/mini.json")
   }
@@ -480,6 +710,94 @@ mod tests {
     assert_eq!(response.status(), StatusCode::BAD_REQUEST);
   }
 
+  #[tokio::test]
+  async fn can_list_buildings_and_their_courses() {
+    let TestContext { db, mut app, .. } = TestContext::new().await;
+
+    db.initialize(InitializeOptions {
+      source: seed(),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    let response = app
+      .call(
+        Request::builder()
+          .uri("/api/buildings")
+          .body(Body::empty())
+          .unwrap(),
+      )
+      .await
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let buildings = response.convert::<Vec<Building>>().await;
+
+    assert!(buildings.iter().any(|building| {
+      building.code == "BURN"
+        && building.name == "Burnside Hall"
+        && building.campus.as_deref() == Some("Downtown")
+    }));
+
+    let response = app
+      .call(
+        Request::builder()
+          .uri("/api/buildings/engtr/courses?term=Summer%202023")
+          .body(Body::empty())
+          .unwrap(),
+      )
+      .await
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let payload = response.convert::<GetBuildingCoursesPayload>().await;
+
+    assert_eq!(payload.building.name, "Trottier Building");
+
+    assert_eq!(
+      payload.courses,
+      vec![BuildingCourse {
+        course_id: "COMP202".into(),
+        title: "Foundations of Programming".into(),
+        term: "Summer 2023".into(),
+        rooms: vec!["0100".into()],
+      }]
+    );
+
+    let response = app
+      .call(
+        Request::builder()
+          .uri("/api/buildings/ENGTR/courses?term=Fall%202022")
+          .body(Body::empty())
+          .unwrap(),
+      )
+      .await
+      .unwrap();
+
+    assert!(
+      response
+        .convert::<GetBuildingCoursesPayload>()
+        .await
+        .courses
+        .is_empty()
+    );
+
+    let response = app
+      .call(
+        Request::builder()
+          .uri("/api/buildings/NOPE/courses")
+          .body(Body::empty())
+          .unwrap(),
+      )
+      .await
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+  }
+
   #[tokio::test]
   async fn course_by_id_works() {
     let TestContext { db, app, .. } = TestContext::new().await;
@@ -509,6 +827,108 @@ mod tests {
     );
   }
 
+  #[tokio::test]
+  async fn course_by_id_includes_review_histograms() {
+    let TestContext {
+      db,
+      mut app,
+      session_store,
+    } = TestContext::new().await;
+
+    db.initialize(InitializeOptions {
+      source: seed(),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    for (user, instructor, rating) in [
+      ("a", "Adrian Roshan Vetta", 5),
+      ("b", "Jeremy Macdonald", 2),
+    ] {
+      let cookie =
+        mock_login(session_store.clone(), user, "test@mail.mcgill.ca").await;
+
+      let review = json!({
+        "content": "test",
+        "course_id": "MATH240",
+        "instructors": [instructor],
+        "rating": rating,
+        "difficulty": 3
+      })
+      .to_string();
+
+      let response = app
+        .call(
+          Request::builder()
+            .method(http::Method::POST)
+            .header("Cookie", cookie)
+            .header("Content-Type", "application/json")
+            .uri("/api/reviews")
+            .body(Body::from(review))
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+
+      assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    let response = app
+      .call(
+        Request::builder()
+          .uri("/api/courses/MATH240")
+          .body(Body::empty())
+          .unwrap(),
+      )
+      .await
+      .unwrap();
+
+    let payload = response.convert::<GetCourseByIdPayload>().await;
+
+    assert!(payload.reviews.is_empty());
+    assert_eq!(payload.filtered_stats, None);
+    assert_eq!(payload.course.rating_distribution, vec![0, 1, 0, 0, 1]);
+    assert_eq!(payload.course.difficulty_distribution, vec![0, 0, 2, 0, 0]);
+
+    let response = app
+      .call(
+        Request::builder()
+          .uri("/api/courses/MATH240?instructor=Macdonald,%20Jeremy")
+          .body(Body::empty())
+          .unwrap(),
+      )
+      .await
+      .unwrap();
+
+    let stats = response
+      .convert::<GetCourseByIdPayload>()
+      .await
+      .filtered_stats
+      .unwrap();
+
+    assert_eq!(stats.review_count, 1);
+    assert_eq!(stats.rating_distribution, vec![0, 1, 0, 0, 0]);
+
+    let response = app
+      .call(
+        Request::builder()
+          .uri("/api/courses/MATH240?term=Fall%202022")
+          .body(Body::empty())
+          .unwrap(),
+      )
+      .await
+      .unwrap();
+
+    let stats = response
+      .convert::<GetCourseByIdPayload>()
+      .await
+      .filtered_stats
+      .unwrap();
+
+    assert_eq!(stats.review_count, 0);
+  }
+
   #[tokio::test]
   async fn can_get_course_with_reviews() {
     let TestContext {
@@ -575,7 +995,7 @@ mod tests {
   }
 
   #[tokio::test]
-  async fn course_by_id_invalid_course_code() {
+  async fn course_by_id_links_equivalent_courses() {
     let TestContext { db, app, .. } = TestContext::new().await;
 
     db.initialize(InitializeOptions {
@@ -588,21 +1008,38 @@ mod tests {
     let response = app
       .oneshot(
         Request::builder()
-          .uri("/api/courses/COMP1337")
+          .uri("/api/courses/COMP202")
           .body(Body::empty())
           .unwrap(),
       )
       .await
       .unwrap();
 
-    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(response.status(), StatusCode::OK);
 
-    assert_eq!(response.convert::<Option<Course>>().await, None);
+    assert_eq!(
+      response
+        .convert::<GetCourseByIdPayload>()
+        .await
+        .equivalent_courses,
+      vec![
+        EquivalentCourse {
+          id: "COMP208".into(),
+          title: None,
+          equivalence: Equivalence::MutuallyExclusive,
+        },
+        EquivalentCourse {
+          id: "COMP250".into(),
+          title: None,
+          equivalence: Equivalence::MutuallyExclusive,
+        },
+      ]
+    );
   }
 
   #[tokio::test]
-  async fn unauthenticated_cant_add_review() {
-    let TestContext { db, app, .. } = TestContext::new().await;
+  async fn course_eligibility_shows_exclusions() {
+    let TestContext { db, mut app, .. } = TestContext::new().await;
 
     db.initialize(InitializeOptions {
       source: seed(),
@@ -612,19 +1049,360 @@ mod tests {
     .unwrap();
 
     let response = app
-      .oneshot(
+      .call(
         Request::builder()
-          .method(http::Method::POST)
-          .uri("/api/reviews")
-          .body(Body::from(
-            json!({"content": "test", "course_id": "MATH240"}).to_string(),
-          ))
+          .uri("/api/courses/MATH240/eligibility?taken=COMP202,MATH235")
+          .body(Body::empty())
           .unwrap(),
       )
       .await
       .unwrap();
 
-    assert_eq!(response.status(), StatusCode::TEMPORARY_REDIRECT);
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let payload = response.convert::<GetCourseEligibilityPayload>().await;
+
+    assert_eq!(payload.eligible, Some(false));
+    assert_eq!(payload.prerequisites_met, Some(true));
+    assert_eq!(payload.exclusions, vec!["MATH235"]);
+    assert_eq!(
+      payload.restricted_to.programs,
+      vec![
+        "Computer Science",
+        "Computer Engineering",
+        "Software Engineering"
+      ]
+    );
+
+    let response = app
+      .call(
+        Request::builder()
+          .uri("/api/courses/MATH240/eligibility?taken=COMP202")
+          .body(Body::empty())
+          .unwrap(),
+      )
+      .await
+      .unwrap();
+
+    let payload = response.convert::<GetCourseEligibilityPayload>().await;
+
+    assert_eq!(payload.eligible, Some(true));
+    assert!(payload.exclusions.is_empty());
+
+    let response = app
+      .call(
+        Request::builder()
+          .uri("/api/courses/COMP1337/eligibility")
+          .body(Body::empty())
+          .unwrap(),
+      )
+      .await
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+  }
+
+  #[tokio::test]
+  async fn course_eligibility_is_unknown_without_a_requirement_tree() {
+    let TestContext { db, mut app, .. } = TestContext::new().await;
+
+    let tempdir = tempfile::tempdir().unwrap();
+
+    let source = tempdir.path().join("courses.json");
+
+    // Listed prerequisites and exclusions are stored as published, with a
+    // space, and COMP251's list mixes in MATH235 or MATH240.
+    let course = |id: &str, logical_prerequisites| Course {
+      id: id.into(),
+      subject: "COMP".into(),
+      code: id.trim_start_matches("COMP").into(),
+      prerequisites: vec![
+        "COMP 250".into(),
+        "MATH 235".into(),
+        "MATH 240".into(),
+      ],
+      logical_prerequisites,
+      excluded_courses: vec!["COMP 252".into()],
+      ..Default::default()
+    };
+
+    fs::write(
+      &source,
+      serde_json::to_string(&[
+        course("COMP251", None),
+        course(
+          "COMP252",
+          Some(ReqNode::Group {
+            operator: Operator::And,
+            groups: vec![
+              ReqNode::Course("COMP 250".into()),
+              ReqNode::Group {
+                operator: Operator::Or,
+                groups: vec![
+                  ReqNode::Course("MATH 235".into()),
+                  ReqNode::Course("MATH 240".into()),
+                ],
+              },
+            ],
+          }),
+        ),
+      ])
+      .unwrap(),
+    )
+    .unwrap();
+
+    db.initialize(InitializeOptions {
+      source,
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    for (id, taken, met, eligible) in [
+      ("COMP251", "COMP250,MATH240", None, None),
+      ("COMP251", "COMP 250,MATH 240,COMP 252", None, Some(false)),
+      ("COMP252", "comp 250,MATH240", Some(true), Some(true)),
+      ("COMP252", "COMP250", Some(false), Some(false)),
+    ] {
+      let response = app
+        .call(
+          Request::builder()
+            .uri(format!(
+              "/api/courses/{id}/eligibility?taken={}",
+              taken.replace(' ', "%20")
+            ))
+            .body(Body::empty())
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+
+      let payload = response.convert::<GetCourseEligibilityPayload>().await;
+
+      assert_eq!(payload.prerequisites_met, met, "{id} {taken}");
+      assert_eq!(payload.eligible, eligible, "{id} {taken}");
+    }
+  }
+
+  #[tokio::test]
+  async fn course_averages_are_served_with_trends() {
+    let TestContext { db, mut app, .. } = TestContext::new().await;
+
+    let tempdir = tempfile::TempDir::new().unwrap();
+
+    for seed in ["mini.json", "averages.json"] {
+      fs::copy(
+        PathBuf::from("crates/db/test-seeds").join(seed),
+        tempdir.path().join(seed),
+      )
+      .unwrap();
+    }
+
+    db.initialize(InitializeOptions {
+      source: tempdir.path().into(),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    let response = app
+      .call(
+        Request::builder()
+          .uri("/api/courses/COMP202?with_averages=true")
+          .body(Body::empty())
+          .unwrap(),
+      )
+      .await
+      .unwrap();
+
+    assert_eq!(
+      response
+        .convert::<GetCourseByIdPayload>()
+        .await
+        .averages
+        .iter()
+        .map(|average| average.term.as_str())
+        .collect::<Vec<_>>(),
+      vec!["Fall 2021", "Winter 2022", "Fall 2022"]
+    );
+
+    let response = app
+      .call(
+        Request::builder()
+          .uri("/api/courses/COMP202/averages")
+          .body(Body::empty())
+          .unwrap(),
+      )
+      .await
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let payload = response.convert::<GetCourseAveragesPayload>().await;
+
+    assert_eq!(
+      payload
+        .averages
+        .iter()
+        .map(|average| average.gpa)
+        .collect::<Vec<_>>(),
+      vec![Some(2.7), Some(3.0), Some(3.3)]
+    );
+
+    let trend = payload.trend.unwrap();
+
+    assert_eq!(trend.term_count, 3);
+    assert_eq!(trend.latest, 3.3);
+    assert!(trend.slope_per_year > 0.0);
+
+    let response = app
+      .call(
+        Request::builder()
+          .uri("/api/courses/COMP252/averages")
+          .body(Body::empty())
+          .unwrap(),
+      )
+      .await
+      .unwrap();
+
+    let payload = response.convert::<GetCourseAveragesPayload>().await;
+
+    assert!(payload.averages.is_empty());
+    assert_eq!(payload.trend, None);
+
+    let response = app
+      .call(
+        Request::builder()
+          .uri("/api/courses/COMP999/averages")
+          .body(Body::empty())
+          .unwrap(),
+      )
+      .await
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+  }
+
+  #[tokio::test]
+  async fn course_history_diffs_catalogue_years() {
+    let TestContext { db, mut app, .. } = TestContext::new().await;
+
+    let tempdir = tempfile::TempDir::new().unwrap();
+
+    for (seed, name) in [
+      ("before_update.json", "courses-2022-2023.json"),
+      ("update.json", "courses-2023-2024.json"),
+    ] {
+      fs::copy(
+        PathBuf::from("crates/db/test-seeds").join(seed),
+        tempdir.path().join(name),
+      )
+      .unwrap();
+    }
+
+    db.initialize(InitializeOptions {
+      source: tempdir.path().into(),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    let response = app
+      .call(
+        Request::builder()
+          .uri("/api/courses/COMP251/history")
+          .body(Body::empty())
+          .unwrap(),
+      )
+      .await
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let payload = response.convert::<GetCourseHistoryPayload>().await;
+
+    assert_eq!(
+      payload
+        .history
+        .iter()
+        .map(|entry| entry.catalogue_year.as_str())
+        .collect::<Vec<_>>(),
+      vec!["2022-2023", "2023-2024"]
+    );
+
+    assert!(payload.history[0].changes.is_empty());
+
+    assert!(
+      payload.history[1]
+        .changes
+        .iter()
+        .any(|change| change.field == "prerequisites")
+    );
+
+    let response = app
+      .call(
+        Request::builder()
+          .uri("/api/courses/COMP1337/history")
+          .body(Body::empty())
+          .unwrap(),
+      )
+      .await
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+  }
+
+  #[tokio::test]
+  async fn course_by_id_invalid_course_code() {
+    let TestContext { db, app, .. } = TestContext::new().await;
+
+    db.initialize(InitializeOptions {
+      source: seed(),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    let response = app
+      .oneshot(
+        Request::builder()
+          .uri("/api/courses/COMP1337")
+          .body(Body::empty())
+          .unwrap(),
+      )
+      .await
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    assert_eq!(response.convert::<Option<Course>>().await, None);
+  }
+
+  #[tokio::test]
+  async fn unauthenticated_cant_add_review() {
+    let TestContext { db, app, .. } = TestContext::new().await;
+
+    db.initialize(InitializeOptions {
+      source: seed(),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    let response = app
+      .oneshot(
+        Request::builder()
+          .method(http::Method::POST)
+          .uri("/api/reviews")
+          .body(Body::from(
+            json!({"content": "test", "course_id": "MATH240"}).to_string(),
+          ))
+          .unwrap(),
+      )
+      .await
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::TEMPORARY_REDIRECT);
   }
 
   #[tokio::test]
@@ -1268,58 +2046,59 @@ mod tests {
 
     assert_eq!(
       payload.instructor,
-      Some(Instructor {
+      Some(InstructorProfile {
+        id: "adrian-roshan-vetta".into(),
         name: "Adrian Roshan Vetta".to_string(),
         name_ngrams: Some(
           "Adr Adri Adria Adrian Ros Rosh Rosha Roshan Vet Vett Vetta".into()
         ),
-        term: "Fall 2022".into(),
+        aliases: vec!["Adrian Roshan Vetta".into()],
+        assignments: vec![TeachingAssignment {
+          course_id: "MATH240".into(),
+          term: "Fall 2022".into(),
+        }],
       })
     );
 
-    assert_eq!(payload.reviews.len(), 1)
-  }
-
-  #[tokio::test]
-  async fn get_empty_user_interactions_for_course() {
-    let TestContext { db, mut app, .. } = TestContext::new().await;
-
-    db.initialize(InitializeOptions {
-      source: seed(),
-      ..Default::default()
-    })
-    .await
-    .unwrap();
+    assert_eq!(payload.reviews.len(), 1);
+    assert_eq!(payload.stats.overall.review_count, 1);
+    assert_eq!(payload.stats.overall.avg_rating, 5.0);
+    assert_eq!(payload.stats.overall.avg_difficulty, 5.0);
+    assert_eq!(
+      payload.stats.overall.rating_distribution,
+      vec![0, 0, 0, 0, 1]
+    );
+    assert_eq!(payload.stats.courses.len(), 1);
+    assert_eq!(payload.stats.terms[0].term, "Fall 2022");
+    assert_eq!(payload.stats.terms[0].stats.review_count, 1);
 
     let response = app
       .call(
         Request::builder()
           .method(http::Method::GET)
-          .header("Content-Type", "application/json")
-          .uri("/api/interactions/COMP202/referrer/test")
+          .uri("/api/instructors/Roshan%20Vetta,%20Adrian")
           .body(Body::empty())
           .unwrap(),
       )
       .await
       .unwrap();
 
-    assert_eq!(response.status(), StatusCode::OK);
-
-    let payload = response
-      .convert::<GetUserInteractionForCoursePayload>()
-      .await;
-
-    assert_eq!(payload.course_id, "COMP202");
-    assert_eq!(payload.interactions.len(), 0);
+    assert_eq!(
+      response
+        .convert::<GetInstructorPayload>()
+        .await
+        .instructor
+        .map(|instructor| instructor.id),
+      Some("adrian-roshan-vetta".into())
+    );
   }
 
   #[tokio::test]
-  async fn get_user_interactions_for_course() {
+  async fn can_compare_course_instructors() {
     let TestContext {
       db,
       mut app,
       session_store,
-      ..
     } = TestContext::new().await;
 
     db.initialize(InitializeOptions {
@@ -1329,13 +2108,156 @@ mod tests {
     .await
     .unwrap();
 
-    let cookie = mock_login(session_store, "test", "test@mail.mcgill.ca").await;
+    for (user, instructor, rating) in [
+      ("a", "Adrian Roshan Vetta", 5),
+      ("b", "Jérôme Fortier", 2),
+      ("c", "Jérôme Fortier", 4),
+    ] {
+      let cookie =
+        mock_login(session_store.clone(), user, "test@mail.mcgill.ca").await;
 
-    let review = json!({
-      "content": "test",
-      "course_id": "MATH240",
-      "instructors": ["Adrian Roshan Vetta"],
-      "rating": 5,
+      let review = json!({
+        "content": "test",
+        "course_id": "MATH240",
+        "instructors": [instructor],
+        "rating": rating,
+        "difficulty": 3
+      })
+      .to_string();
+
+      let response = app
+        .call(
+          Request::builder()
+            .method(http::Method::POST)
+            .header("Cookie", cookie)
+            .header("Content-Type", "application/json")
+            .uri("/api/reviews")
+            .body(Body::from(review))
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+
+      assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    let response = app
+      .call(
+        Request::builder()
+          .method(http::Method::GET)
+          .uri("/api/courses/MATH240/instructors/compare")
+          .body(Body::empty())
+          .unwrap(),
+      )
+      .await
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let payload = response.convert::<CompareInstructorsPayload>().await;
+
+    assert_eq!(payload.course_id, "MATH240");
+
+    assert_eq!(
+      payload
+        .instructors
+        .iter()
+        .map(|comparison| (
+          comparison.instructor.name.as_str(),
+          comparison.stats.review_count,
+          comparison.stats.avg_rating,
+        ))
+        .collect::<Vec<_>>(),
+      vec![
+        ("Adrian Roshan Vetta", 1, 5.0),
+        ("Jeremy Macdonald", 0, 0.0),
+        ("Jérôme Fortier", 2, 3.0),
+      ]
+    );
+
+    let fortier = &payload.instructors[2];
+
+    assert_eq!(fortier.stats.rating_distribution, vec![0, 1, 0, 1, 0]);
+
+    assert_eq!(
+      fortier
+        .terms
+        .iter()
+        .map(|term| (term.term.as_str(), term.stats.review_count))
+        .collect::<Vec<_>>(),
+      vec![("Winter 2023", 2), ("Fall 2022", 0)]
+    );
+
+    let response = app
+      .call(
+        Request::builder()
+          .method(http::Method::GET)
+          .uri("/api/courses/COMP999/instructors/compare")
+          .body(Body::empty())
+          .unwrap(),
+      )
+      .await
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+  }
+
+  #[tokio::test]
+  async fn get_empty_user_interactions_for_course() {
+    let TestContext { db, mut app, .. } = TestContext::new().await;
+
+    db.initialize(InitializeOptions {
+      source: seed(),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    let response = app
+      .call(
+        Request::builder()
+          .method(http::Method::GET)
+          .header("Content-Type", "application/json")
+          .uri("/api/interactions/COMP202/referrer/test")
+          .body(Body::empty())
+          .unwrap(),
+      )
+      .await
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let payload = response
+      .convert::<GetUserInteractionForCoursePayload>()
+      .await;
+
+    assert_eq!(payload.course_id, "COMP202");
+    assert_eq!(payload.interactions.len(), 0);
+  }
+
+  #[tokio::test]
+  async fn get_user_interactions_for_course() {
+    let TestContext {
+      db,
+      mut app,
+      session_store,
+      ..
+    } = TestContext::new().await;
+
+    db.initialize(InitializeOptions {
+      source: seed(),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    let cookie = mock_login(session_store, "test", "test@mail.mcgill.ca").await;
+
+    let review = json!({
+      "content": "test",
+      "course_id": "MATH240",
+      "instructors": ["Adrian Roshan Vetta"],
+      "rating": 5,
       "difficulty": 5
     })
     .to_string();
@@ -1670,10 +2592,88 @@ mod tests {
       SubscriptionResponse::Single(Some(Subscription {
         course_id,
         user_id,
+        ..
       })) if course_id == "MATH240" && user_id == "subscriber"
     );
   }
 
+  #[tokio::test]
+  async fn can_add_and_remove_typed_subscriptions() {
+    let TestContext {
+      db,
+      mut app,
+      session_store,
+      ..
+    } = TestContext::new().await;
+
+    let cookie =
+      mock_login(session_store, "subscriber", "subscriber@mail.mcgill.ca")
+        .await;
+
+    let mut subscribe =
+      async |method: http::Method, body: serde_json::Value| {
+        app
+          .call(
+            Request::builder()
+              .method(method)
+              .header("Cookie", cookie.clone())
+              .header("Content-Type", "application/json")
+              .uri("/api/subscriptions")
+              .body(Body::from(body.to_string()))
+              .unwrap(),
+          )
+          .await
+          .unwrap()
+          .status()
+      };
+
+    let instructor = json!({
+      "kind": "instructor",
+      "instructor": "Adrian Roshan Vetta",
+    });
+
+    assert_eq!(
+      subscribe(http::Method::POST, instructor.clone()).await,
+      StatusCode::OK
+    );
+
+    assert_eq!(
+      subscribe(
+        http::Method::POST,
+        json!({ "kind": "offerings", "course_id": "MATH240", "term": "Fall 2025" }),
+      )
+      .await,
+      StatusCode::OK
+    );
+
+    assert_eq!(
+      subscribe(http::Method::POST, json!({ "kind": "courseChanges" })).await,
+      StatusCode::INTERNAL_SERVER_ERROR
+    );
+
+    assert_eq!(
+      db.get_subscriptions("subscriber")
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|subscription| subscription.kind)
+        .collect::<Vec<_>>(),
+      vec![SubscriptionKind::Instructor, SubscriptionKind::Offerings]
+    );
+
+    assert_eq!(
+      db.get_subscription("subscriber", "MATH240").await.unwrap(),
+      None
+    );
+
+    assert_eq!(
+      subscribe(http::Method::DELETE, instructor).await,
+      StatusCode::OK
+    );
+
+    assert_eq!(db.get_subscriptions("subscriber").await.unwrap().len(), 1);
+  }
+
   #[tokio::test]
   async fn get_subscription_returns_none_when_missing() {
     let TestContext {
@@ -1793,7 +2793,14 @@ mod tests {
       .unwrap();
 
     assert_eq!(response.status(), StatusCode::OK);
-    assert_eq!(response.convert::<Vec<Notification>>().await.len(), 1);
+    assert_eq!(
+      response
+        .convert::<GetNotificationsPayload>()
+        .await
+        .notifications
+        .len(),
+      1
+    );
   }
 
   #[tokio::test]
@@ -1872,7 +2879,14 @@ mod tests {
       .unwrap();
 
     assert_eq!(response.status(), StatusCode::OK);
-    assert_eq!(response.convert::<Vec<Notification>>().await.len(), 1);
+    assert_eq!(
+      response
+        .convert::<GetNotificationsPayload>()
+        .await
+        .notifications
+        .len(),
+      1
+    );
 
     let response = app
       .call(
@@ -1903,7 +2917,14 @@ mod tests {
       .unwrap();
 
     assert_eq!(response.status(), StatusCode::OK);
-    assert_eq!(response.convert::<Vec<Notification>>().await.len(), 0);
+    assert_eq!(
+      response
+        .convert::<GetNotificationsPayload>()
+        .await
+        .notifications
+        .len(),
+      0
+    );
   }
 
   #[tokio::test]
@@ -1982,7 +3003,14 @@ mod tests {
       .unwrap();
 
     assert_eq!(response.status(), StatusCode::OK);
-    assert_eq!(response.convert::<Vec<Notification>>().await.len(), 1);
+    assert_eq!(
+      response
+        .convert::<GetNotificationsPayload>()
+        .await
+        .notifications
+        .len(),
+      1
+    );
 
     let response = app
       .call(
@@ -2013,6 +3041,1235 @@ mod tests {
       .unwrap();
 
     assert_eq!(response.status(), StatusCode::OK);
-    assert_eq!(response.convert::<Vec<Notification>>().await.len(), 0);
+    assert_eq!(
+      response
+        .convert::<GetNotificationsPayload>()
+        .await
+        .notifications
+        .len(),
+      0
+    );
+  }
+
+  #[tokio::test]
+  async fn notifications_are_paged_and_managed_by_id() {
+    let TestContext {
+      db,
+      mut app,
+      session_store,
+      ..
+    } = TestContext::new().await;
+
+    let (a, b) = (
+      mock_login(session_store.clone(), "a", "a@mail.mcgill.ca").await,
+      mock_login(session_store, "b", "b@mail.mcgill.ca").await,
+    );
+
+    db.insert_notifications(
+      ["COMP202", "COMP250", "MATH240"]
+        .into_iter()
+        .enumerate()
+        .map(|(i, course_id)| Notification {
+          created_at: model::DateTime::from_millis(i as i64 * 1000),
+          ..Notification::new(
+            "a",
+            Review {
+              course_id: course_id.into(),
+              user_id: "c".into(),
+              ..Default::default()
+            },
+          )
+        })
+        .collect(),
+    )
+    .await
+    .unwrap();
+
+    let mut call =
+      async |method: http::Method, cookie: &str, uri: &str, body| {
+        app
+          .call(
+            Request::builder()
+              .method(method)
+              .header("Cookie", cookie)
+              .header("Content-Type", "application/json")
+              .uri(uri)
+              .body(body)
+              .unwrap(),
+          )
+          .await
+          .unwrap()
+      };
+
+    let page = call(
+      http::Method::GET,
+      &a,
+      "/api/notifications?limit=2",
+      Body::empty(),
+    )
+    .await
+    .convert::<GetNotificationsPayload>()
+    .await;
+
+    assert_eq!(page.total_count, 3);
+    assert_eq!(page.unread_count, 3);
+
+    assert_eq!(
+      page
+        .notifications
+        .iter()
+        .map(|notification| notification.course_id.as_str())
+        .collect::<Vec<_>>(),
+      vec!["MATH240", "COMP250"]
+    );
+
+    assert_eq!(
+      call(
+        http::Method::GET,
+        &a,
+        "/api/notifications?limit=-1",
+        Body::empty()
+      )
+      .await
+      .status(),
+      StatusCode::BAD_REQUEST
+    );
+
+    let newest = page.notifications[0].id.clone();
+
+    let page = call(http::Method::GET, &b, "/api/notifications", Body::empty())
+      .await
+      .convert::<GetNotificationsPayload>()
+      .await;
+
+    assert_eq!(page.total_count, 0);
+
+    let uri = format!("/api/notifications/{newest}");
+
+    let seen = || Body::from(json!({ "seen": true }).to_string());
+
+    assert_eq!(
+      call(http::Method::PUT, &b, &uri, seen()).await.status(),
+      StatusCode::NOT_FOUND
+    );
+
+    assert_eq!(
+      call(http::Method::PUT, &a, &uri, seen()).await.status(),
+      StatusCode::OK
+    );
+
+    assert_eq!(
+      call(http::Method::DELETE, &b, &uri, Body::empty())
+        .await
+        .status(),
+      StatusCode::NOT_FOUND
+    );
+
+    let response = call(
+      http::Method::POST,
+      &a,
+      "/api/notifications/mark-all-read",
+      Body::empty(),
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+      response.convert::<BulkNotificationsPayload>().await.count,
+      2
+    );
+
+    assert_eq!(
+      call(http::Method::DELETE, &a, &uri, Body::empty())
+        .await
+        .status(),
+      StatusCode::OK
+    );
+
+    let response = call(
+      http::Method::POST,
+      &a,
+      "/api/notifications/clear-all",
+      Body::empty(),
+    )
+    .await;
+
+    assert_eq!(
+      response.convert::<BulkNotificationsPayload>().await.count,
+      2
+    );
+
+    let page = call(http::Method::GET, &a, "/api/notifications", Body::empty())
+      .await
+      .convert::<GetNotificationsPayload>()
+      .await;
+
+    assert_eq!((page.total_count, page.unread_count), (0, 0));
+  }
+
+  /// A bare-bones SMTP server that accepts every message and keeps it.
+  struct SmtpStandIn {
+    messages: Arc<std::sync::Mutex<Vec<String>>>,
+    port: u16,
+  }
+
+  impl SmtpStandIn {
+    async fn start() -> Self {
+      let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+
+      let port = listener.local_addr().unwrap().port();
+
+      let messages = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+      let clone = messages.clone();
+
+      tokio::spawn(async move {
+        loop {
+          let (stream, _) = listener.accept().await.unwrap();
+          tokio::spawn(Self::session(stream, clone.clone()));
+        }
+      });
+
+      Self { messages, port }
+    }
+
+    async fn session(
+      stream: tokio::net::TcpStream,
+      messages: Arc<std::sync::Mutex<Vec<String>>>,
+    ) {
+      use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+      let (reader, mut writer) = stream.into_split();
+
+      let mut lines = BufReader::new(reader).lines();
+
+      writer.write_all(b"220 localhost ESMTP\r\n").await.unwrap();
+
+      while let Some(line) = lines.next_line().await.unwrap() {
+        let command = line
+          .split_whitespace()
+          .next()
+          .unwrap_or_default()
+          .to_uppercase();
+
+        let reply: &[u8] = match command.as_str() {
+          "DATA" => {
+            writer.write_all(b"354 End data with .\r\n").await.unwrap();
+
+            let mut message = String::new();
+
+            while let Some(line) = lines.next_line().await.unwrap() {
+              if line == "." {
+                break;
+              }
+
+              message.push_str(&line);
+              message.push('\n');
+            }
+
+            messages.lock().unwrap().push(message);
+
+            b"250 OK\r\n"
+          }
+          "QUIT" => {
+            writer.write_all(b"221 Bye\r\n").await.unwrap();
+            return;
+          }
+          _ => b"250 OK\r\n",
+        };
+
+        writer.write_all(reply).await.unwrap();
+      }
+    }
+
+    fn channel(&self, db: Arc<Db>) -> EmailChannel {
+      EmailChannel::new(
+        db,
+        lettre::AsyncSmtpTransport::<lettre::Tokio1Executor>::builder_dangerous(
+          "127.0.0.1",
+        )
+        .port(self.port)
+        .build(),
+        "mcgill.courses <noreply@mcgill.courses>".parse().unwrap(),
+      )
+    }
+
+    fn messages(&self) -> Vec<String> {
+      self.messages.lock().unwrap().clone()
+    }
+  }
+
+  #[tokio::test]
+  async fn notifications_are_emailed_immediately_or_in_digests() {
+    let TestContext { db, .. } = TestContext::new().await;
+
+    let smtp = SmtpStandIn::start().await;
+
+    let dispatcher =
+      Dispatcher::new(db.clone(), vec![Box::new(smtp.channel(db.clone()))]);
+
+    for (user_id, email_frequency) in [
+      ("a", Some(EmailFrequency::Immediate)),
+      ("b", Some(EmailFrequency::Daily)),
+      ("c", None),
+    ] {
+      db.add_subscription(Subscription {
+        course_id: "MATH240".into(),
+        user_id: user_id.into(),
+        ..Default::default()
+      })
+      .await
+      .unwrap();
+
+      if let Some(email_frequency) = email_frequency {
+        db.set_notification_preferences(NotificationPreferences {
+          user_id: user_id.into(),
+          email: Some(format!("{user_id}@mail.mcgill.ca")),
+          email_frequency,
+          last_digest_at: None,
+        })
+        .await
+        .unwrap();
+      }
+    }
+
+    dispatcher
+      .dispatch(&Review {
+        content: "Great course".into(),
+        course_id: "MATH240".into(),
+        rating: 5,
+        difficulty: 3,
+        user_id: "d".into(),
+        timestamp: Utc::now().into(),
+        ..Default::default()
+      })
+      .await
+      .unwrap();
+
+    for user_id in ["a", "b", "c"] {
+      assert_eq!(db.get_notifications(user_id).await.unwrap().len(), 1);
+    }
+
+    // Emails are left to the background delivery task.
+    assert!(smtp.messages().is_empty());
+
+    assert_eq!(dispatcher.deliver_pending().await.unwrap(), 1);
+    assert_eq!(dispatcher.deliver_pending().await.unwrap(), 0);
+
+    let messages = smtp.messages();
+
+    assert_eq!(messages.len(), 1);
+    assert!(messages[0].contains("To: a@mail.mcgill.ca"));
+    assert!(messages[0].contains("Subject: New review of MATH240"));
+
+    assert!(db.get_notifications("a").await.unwrap()[0].emailed);
+    assert!(!db.get_notifications("b").await.unwrap()[0].emailed);
+
+    let now = Utc::now();
+
+    assert_eq!(dispatcher.send_digests(now).await.unwrap(), 1);
+
+    let messages = smtp.messages();
+
+    assert_eq!(messages.len(), 2);
+    assert!(messages[1].contains("To: b@mail.mcgill.ca"));
+    assert!(db.get_notifications("b").await.unwrap()[0].emailed);
+
+    assert_eq!(dispatcher.send_digests(now).await.unwrap(), 0);
+
+    assert_eq!(
+      dispatcher
+        .send_digests(now + chrono::Duration::days(1))
+        .await
+        .unwrap(),
+      0
+    );
+
+    assert_eq!(smtp.messages().len(), 2);
+  }
+
+  #[tokio::test]
+  async fn can_update_notification_preferences() {
+    let TestContext {
+      mut app,
+      session_store,
+      ..
+    } = TestContext::new().await;
+
+    let cookie = mock_login(session_store, "a", "a@mail.mcgill.ca").await;
+
+    let response = app
+      .call(
+        Request::builder()
+          .method(http::Method::GET)
+          .header("Cookie", cookie.clone())
+          .uri("/api/notifications/preferences")
+          .body(Body::empty())
+          .unwrap(),
+      )
+      .await
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    assert_eq!(
+      response.convert::<NotificationPreferences>().await,
+      NotificationPreferences {
+        user_id: "a".into(),
+        email: Some("a@mail.mcgill.ca".into()),
+        email_frequency: EmailFrequency::Never,
+        last_digest_at: None,
+      }
+    );
+
+    let response = app
+      .call(
+        Request::builder()
+          .method(http::Method::PUT)
+          .header("Cookie", cookie.clone())
+          .header("Content-Type", "application/json")
+          .uri("/api/notifications/preferences")
+          .body(Body::from(
+            json!({ "email_frequency": "weekly" }).to_string(),
+          ))
+          .unwrap(),
+      )
+      .await
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = app
+      .call(
+        Request::builder()
+          .method(http::Method::GET)
+          .header("Cookie", cookie.clone())
+          .uri("/api/notifications/preferences")
+          .body(Body::empty())
+          .unwrap(),
+      )
+      .await
+      .unwrap();
+
+    assert_eq!(
+      response
+        .convert::<NotificationPreferences>()
+        .await
+        .email_frequency,
+      EmailFrequency::Weekly
+    );
+
+    for email in ["not an address", "b@mail.mcgill.ca"] {
+      let response = app
+        .call(
+          Request::builder()
+            .method(http::Method::PUT)
+            .header("Cookie", cookie.clone())
+            .header("Content-Type", "application/json")
+            .uri("/api/notifications/preferences")
+            .body(Body::from(
+              json!({ "email": email, "email_frequency": "daily" }).to_string(),
+            ))
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+
+      assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    let response = app
+      .call(
+        Request::builder()
+          .method(http::Method::PUT)
+          .header("Cookie", cookie.clone())
+          .header("Content-Type", "application/json")
+          .uri("/api/notifications/preferences")
+          .body(Body::from(
+            json!({
+              "email": "A <A@mail.mcgill.ca>",
+              "email_frequency": "daily",
+            })
+            .to_string(),
+          ))
+          .unwrap(),
+      )
+      .await
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = app
+      .call(
+        Request::builder()
+          .method(http::Method::GET)
+          .header("Cookie", cookie)
+          .uri("/api/notifications/preferences")
+          .body(Body::empty())
+          .unwrap(),
+      )
+      .await
+      .unwrap();
+
+    assert_eq!(
+      response.convert::<NotificationPreferences>().await,
+      NotificationPreferences {
+        user_id: "a".into(),
+        email: Some("A@mail.mcgill.ca".into()),
+        email_frequency: EmailFrequency::Daily,
+        last_digest_at: None,
+      }
+    );
+  }
+
+  #[tokio::test]
+  async fn exams_route_filters_by_term_and_subject() {
+    let TestContext { db, app, .. } = TestContext::new().await;
+
+    db.initialize(InitializeOptions {
+      source: PathBuf::from("crates/db/test-seeds/exams.json"),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    let response = app
+      .oneshot(
+        Request::builder()
+          .uri("/api/exams?term=Fall%202099&subject=COMP")
+          .body(Body::empty())
+          .unwrap(),
+      )
+      .await
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let exams = response.convert::<Vec<FinalExam>>().await;
+
+    assert_eq!(exams.len(), 2);
+    assert!(exams.iter().all(|exam| exam.course_id == "COMP202"));
+  }
+
+  #[tokio::test]
+  async fn exams_route_rejects_invalid_subjects() {
+    let TestContext { db, mut app, .. } = TestContext::new().await;
+
+    db.initialize(InitializeOptions {
+      source: PathBuf::from("crates/db/test-seeds/exams.json"),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    for subject in [".*", "CO", "COMPS", "C0MP", "COMP%5D"] {
+      let response = app
+        .call(
+          Request::builder()
+            .uri(format!("/api/exams?subject={subject}"))
+            .body(Body::empty())
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+
+      assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{subject}");
+    }
+  }
+
+  #[tokio::test]
+  async fn course_exams_route_works() {
+    let TestContext { db, app, .. } = TestContext::new().await;
+
+    db.initialize(InitializeOptions {
+      source: PathBuf::from("crates/db/test-seeds/exams.json"),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    let response = app
+      .oneshot(
+        Request::builder()
+          .uri("/api/courses/MATH240/exams")
+          .body(Body::empty())
+          .unwrap(),
+      )
+      .await
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    assert_eq!(
+      response.convert::<Vec<FinalExam>>().await,
+      db.find_exams_by_course_id("MATH240").await.unwrap()
+    );
+  }
+
+  #[tokio::test]
+  async fn course_by_id_with_next_exam() {
+    let TestContext { db, mut app, .. } = TestContext::new().await;
+
+    db.initialize(InitializeOptions {
+      source: seed(),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    db.initialize(InitializeOptions {
+      source: PathBuf::from("crates/db/test-seeds/exams.json"),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    let response = app
+      .call(
+        Request::builder()
+          .uri("/api/courses/COMP202")
+          .body(Body::empty())
+          .unwrap(),
+      )
+      .await
+      .unwrap();
+
+    assert_eq!(
+      response.convert::<GetCourseByIdPayload>().await.next_exam,
+      None
+    );
+
+    let response = app
+      .call(
+        Request::builder()
+          .uri("/api/courses/COMP202?with_next_exam=true")
+          .body(Body::empty())
+          .unwrap(),
+      )
+      .await
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    assert_eq!(
+      response
+        .convert::<GetCourseByIdPayload>()
+        .await
+        .next_exam
+        .map(|exam| exam.start_time),
+      Some("2099-12-10T14:00:00".into())
+    );
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn notifications_are_streamed_until_the_session_ends() {
+    let TestContext {
+      app,
+      db,
+      session_store,
+    } = TestContext::new().await;
+
+    let cookie =
+      mock_login(session_store.clone(), "a", "a@mail.mcgill.ca").await;
+
+    let notification = |user_id: &str, course_id: &str| {
+      Notification::about_course(SubscriptionKind::Reviews, user_id, course_id)
+    };
+
+    let missed =
+      vec![notification("a", "COMP202"), notification("a", "COMP250")];
+
+    db.insert_notifications(missed.clone()).await.unwrap();
+
+    let response = app
+      .oneshot(
+        Request::builder()
+          .method(http::Method::GET)
+          .header("Cookie", cookie.clone())
+          .header("Last-Event-ID", missed[0].id.clone())
+          .uri("/api/notifications/stream")
+          .body(Body::empty())
+          .unwrap(),
+      )
+      .await
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    assert_eq!(
+      response.headers().get(header::CONTENT_TYPE).unwrap(),
+      "text/event-stream"
+    );
+
+    let mut body = response.into_body().into_data_stream();
+
+    let mut buffer = String::new();
+
+    let mut next_event = async || -> Option<(String, Notification)> {
+      loop {
+        if let Some(end) = buffer.find("\n\n") {
+          let event = buffer[..end].to_string();
+
+          buffer.drain(..end + 2);
+
+          if event.starts_with(':') {
+            continue;
+          }
+
+          let field = |name: &str| {
+            event
+              .lines()
+              .find_map(|line| line.strip_prefix(&format!("{name}: ")))
+              .unwrap()
+              .to_string()
+          };
+
+          assert_eq!(field("event"), "notification");
+
+          return Some((
+            field("id"),
+            serde_json::from_str(&field("data")).unwrap(),
+          ));
+        }
+
+        let chunk = tokio::time::timeout(Duration::from_secs(10), body.next())
+          .await
+          .unwrap()?
+          .unwrap();
+
+        buffer.push_str(std::str::from_utf8(&chunk).unwrap());
+      }
+    };
+
+    let (id, replayed) = next_event().await.unwrap();
+
+    assert_eq!(id, missed[1].id);
+    assert_eq!(replayed, missed[1]);
+
+    let live = notification("a", "MATH240");
+
+    db.insert_notifications(vec![notification("b", "COMP202"), live.clone()])
+      .await
+      .unwrap();
+
+    let (id, received) = next_event().await.unwrap();
+
+    assert_eq!(id, live.id);
+    assert_eq!(received, live);
+
+    let session = session_store
+      .load_session(
+        cookie.trim_start_matches(&format!("{COOKIE_NAME}=")).into(),
+      )
+      .await
+      .unwrap()
+      .unwrap();
+
+    session_store.destroy_session(session).await.unwrap();
+
+    db.insert_notifications(vec![notification("a", "COMP251")])
+      .await
+      .unwrap();
+
+    assert!(next_event().await.is_none());
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn webhooks_are_signed_retried_and_logged() {
+    let TestContext {
+      db,
+      mut app,
+      session_store,
+    } = TestContext::new().await;
+
+    db.initialize(InitializeOptions {
+      source: seed(),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    let mut server = mockito::Server::new_async().await;
+
+    let admin =
+      mock_login(session_store.clone(), "admin", "admin@mail.mcgill.ca").await;
+
+    let user =
+      mock_login(session_store.clone(), "a", "test@mail.mcgill.ca").await;
+
+    let mut call = async |method: http::Method,
+                          cookie: &str,
+                          uri: &str,
+                          body: Option<serde_json::Value>| {
+      app
+        .call(
+          Request::builder()
+            .method(method)
+            .header("Cookie", cookie)
+            .header("Content-Type", "application/json")
+            .uri(uri)
+            .body(
+              body
+                .map_or_else(Body::empty, |body| Body::from(body.to_string())),
+            )
+            .unwrap(),
+        )
+        .await
+        .unwrap()
+    };
+
+    let webhook = json!({
+      "url": format!("{}/hook", server.url()),
+      "events": ["review.created"],
+      "secret": "shh",
+    });
+
+    assert_eq!(
+      call(
+        http::Method::POST,
+        &user,
+        "/api/admin/webhooks",
+        Some(webhook.clone())
+      )
+      .await
+      .status(),
+      StatusCode::FORBIDDEN
+    );
+
+    let response = call(
+      http::Method::POST,
+      &admin,
+      "/api/admin/webhooks",
+      Some(webhook),
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let webhook = response.convert::<Webhook>().await;
+
+    assert_eq!(webhook.secret, "shh");
+
+    assert_eq!(
+      call(http::Method::GET, &admin, "/api/admin/webhooks", None)
+        .await
+        .convert::<Vec<Webhook>>()
+        .await,
+      vec![webhook.clone().without_secret()]
+    );
+
+    let response = call(
+      http::Method::POST,
+      &user,
+      "/api/reviews",
+      Some(json!({
+        "content": "test",
+        "course_id": "MATH240",
+        "instructors": ["Adrian Roshan Vetta"],
+        "rating": 5,
+        "difficulty": 3
+      })),
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let deliveries_uri =
+      format!("/api/admin/webhooks/{}/deliveries", webhook.id);
+
+    let deliveries = call(http::Method::GET, &admin, &deliveries_uri, None)
+      .await
+      .convert::<Vec<WebhookDelivery>>()
+      .await;
+
+    assert_eq!(deliveries.len(), 1);
+
+    let delivery = deliveries[0].clone();
+
+    assert_eq!(delivery.event, WebhookEventKind::ReviewCreated);
+    assert_eq!(delivery.status, WebhookDeliveryStatus::Pending);
+
+    let sender = WebhookSender::new(db.clone()).unwrap();
+
+    let failure = server
+      .mock("POST", "/hook")
+      .with_status(500)
+      .expect(1)
+      .create_async()
+      .await;
+
+    let now = Utc::now();
+
+    assert_eq!(sender.send_due(now).await.unwrap(), 0);
+
+    failure.assert_async().await;
+    failure.remove_async().await;
+
+    let delivery = call(http::Method::GET, &admin, &deliveries_uri, None)
+      .await
+      .convert::<Vec<WebhookDelivery>>()
+      .await
+      .remove(0);
+
+    assert_eq!(delivery.status, WebhookDeliveryStatus::Pending);
+    assert_eq!(delivery.attempts, 1);
+    assert_eq!(delivery.response_status, Some(500));
+
+    let success = server
+      .mock("POST", "/hook")
+      .match_header("content-type", "application/json")
+      .match_header("x-webhook-event", "review.created")
+      .match_header("x-webhook-id", delivery.id.as_str())
+      .match_header(
+        "x-webhook-signature",
+        format!(
+          "sha256={}",
+          WebhookSender::sign(b"shh", delivery.payload.as_bytes())
+        )
+        .as_str(),
+      )
+      .match_body(delivery.payload.as_str())
+      .with_status(204)
+      .expect(1)
+      .create_async()
+      .await;
+
+    assert_eq!(sender.send_due(now).await.unwrap(), 0);
+
+    assert_eq!(
+      sender
+        .send_due(now + WebhookSender::backoff(1))
+        .await
+        .unwrap(),
+      1
+    );
+
+    success.assert_async().await;
+
+    let delivery = call(http::Method::GET, &admin, &deliveries_uri, None)
+      .await
+      .convert::<Vec<WebhookDelivery>>()
+      .await
+      .remove(0);
+
+    assert_eq!(delivery.status, WebhookDeliveryStatus::Succeeded);
+    assert_eq!(delivery.attempts, 2);
+    assert_eq!(delivery.response_status, Some(204));
+    assert_eq!(delivery.error, None);
+
+    let uri = format!("/api/admin/webhooks/{}", webhook.id);
+
+    assert_eq!(
+      call(http::Method::DELETE, &admin, &uri, None)
+        .await
+        .status(),
+      StatusCode::OK
+    );
+
+    assert_eq!(
+      call(http::Method::DELETE, &admin, &uri, None)
+        .await
+        .status(),
+      StatusCode::NOT_FOUND
+    );
+
+    assert_eq!(
+      call(http::Method::GET, &admin, &deliveries_uri, None)
+        .await
+        .status(),
+      StatusCode::NOT_FOUND
+    );
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn rate_limits_apply_per_route_group_and_client() {
+    let TestContext {
+      db, session_store, ..
+    } = TestContext::new().await;
+
+    let app = Server::app(AppConfig {
+      config: &Config {
+        rate_limits: RateLimits {
+          reads: "2/1m".parse().unwrap(),
+          review_writes: "1/1m".parse().unwrap(),
+          trusted_proxies: vec![IpAddr::from([10, 0, 0, 1])],
+          ..Default::default()
+        },
+        ..config()
+      },
+      db: db.clone(),
+      dispatcher: Arc::new(Dispatcher::new(db.clone(), Vec::new())),
+      assets: None,
+      metrics: Arc::new(Metrics::new().unwrap()),
+      seeding: Seeding::default(),
+      session_store: session_store.clone(),
+      supervisor: Supervisor::default(),
+      rate_limit: true,
+    })
+    .await
+    .unwrap();
+
+    let cookie =
+      mock_login(session_store.clone(), "a", "a@mail.mcgill.ca").await;
+
+    let call = async |method: Method,
+                      uri: &str,
+                      forwarded_for: Option<&str>,
+                      cookie: Option<&str>| {
+      // Requests come through the trusted proxy.
+      let mut request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .extension(ConnectInfo(SocketAddr::from(([10, 0, 0, 1], 443))));
+
+      if let Some(forwarded_for) = forwarded_for {
+        request = request.header("X-Forwarded-For", forwarded_for);
+      }
+
+      if let Some(cookie) = cookie {
+        request = request.header("Cookie", cookie);
+      }
+
+      app
+        .clone()
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+    };
+
+    let header = |response: &Response, name: &str| {
+      response.headers()[name].to_str().unwrap().to_string()
+    };
+
+    let response =
+      call(Method::GET, "/api/buildings", Some("1.1.1.1"), None).await;
+
+    assert_ne!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(header(&response, "ratelimit-limit"), "2");
+    assert_eq!(header(&response, "ratelimit-remaining"), "1");
+    assert_eq!(header(&response, "ratelimit-policy"), "2;w=60");
+
+    // The proxy appends the peer it saw, so only the last entry is trusted.
+    let response = call(
+      Method::GET,
+      "/api/buildings",
+      Some("9.9.9.9, 1.1.1.1"),
+      None,
+    )
+    .await;
+
+    assert_ne!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(header(&response, "ratelimit-remaining"), "0");
+
+    let response =
+      call(Method::GET, "/api/buildings", Some("1.1.1.1"), None).await;
+
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(header(&response, "ratelimit-remaining"), "0");
+
+    let retry_after = header(&response, "retry-after").parse::<u64>().unwrap();
+
+    assert!((1..=30).contains(&retry_after));
+    assert_eq!(
+      header(&response, "ratelimit-reset"),
+      retry_after.to_string()
+    );
+
+    assert_ne!(
+      call(Method::GET, "/api/buildings", Some("2.2.2.2"), None)
+        .await
+        .status(),
+      StatusCode::TOO_MANY_REQUESTS
+    );
+
+    // Anyone else is limited by their own IP, whatever they forward.
+    let direct = async |forwarded_for: &str| {
+      app
+        .clone()
+        .oneshot(
+          Request::builder()
+            .uri("/api/buildings")
+            .header("X-Forwarded-For", forwarded_for)
+            .extension(ConnectInfo(SocketAddr::from(([5, 5, 5, 5], 443))))
+            .body(Body::empty())
+            .unwrap(),
+        )
+        .await
+        .unwrap()
+        .status()
+    };
+
+    for forwarded_for in ["6.6.6.6", "7.7.7.7"] {
+      assert_ne!(direct(forwarded_for).await, StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    assert_eq!(direct("8.8.8.8").await, StatusCode::TOO_MANY_REQUESTS);
+
+    // Signed in users are limited by account, wherever they connect from.
+    for forwarded_for in ["1.1.1.1", "3.3.3.3"] {
+      assert_ne!(
+        call(
+          Method::GET,
+          "/api/buildings",
+          Some(forwarded_for),
+          Some(&cookie)
+        )
+        .await
+        .status(),
+        StatusCode::TOO_MANY_REQUESTS
+      );
+    }
+
+    assert_eq!(
+      call(
+        Method::GET,
+        "/api/buildings",
+        Some("4.4.4.4"),
+        Some(&cookie)
+      )
+      .await
+      .status(),
+      StatusCode::TOO_MANY_REQUESTS
+    );
+
+    // Review writes are limited separately from reads of the same route.
+    let response = call(
+      Method::DELETE,
+      "/api/reviews",
+      Some("1.1.1.1"),
+      Some(&cookie),
+    )
+    .await;
+
+    assert_ne!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(header(&response, "ratelimit-limit"), "1");
+
+    assert_eq!(
+      call(Method::POST, "/api/reviews", Some("1.1.1.1"), Some(&cookie))
+        .await
+        .status(),
+      StatusCode::TOO_MANY_REQUESTS
+    );
+
+    assert_eq!(
+      header(
+        &call(Method::GET, "/api/search?query=comp", Some("1.1.1.1"), None)
+          .await,
+        "ratelimit-limit"
+      ),
+      RateLimits::default().search.requests.to_string()
+    );
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn health_readiness_and_metrics_are_reported() {
+    let TestContext {
+      db, session_store, ..
+    } = TestContext::new().await;
+
+    let (seeding, supervisor) = (Seeding::default(), Supervisor::default());
+
+    let app = Server::app(AppConfig {
+      config: &config(),
+      db: db.clone(),
+      dispatcher: Arc::new(Dispatcher::new(db.clone(), Vec::new())),
+      assets: None,
+      metrics: Arc::new(Metrics::new().unwrap()),
+      seeding: seeding.clone(),
+      session_store,
+      supervisor: supervisor.clone(),
+      rate_limit: true,
+    })
+    .await
+    .unwrap();
+
+    let get = async |uri: &str| {
+      app
+        .clone()
+        .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+    };
+
+    let response = get("/healthz").await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    seeding.start();
+    seeding.progress().start(3);
+    seeding.progress().advance("courses.json".into());
+
+    let response = get("/readyz").await;
+
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+    let readiness = response.convert::<ReadinessPayload>().await;
+
+    assert!(!readiness.ready);
+    assert!(readiness.mongo.ok);
+    assert!(readiness.session_store.ok);
+    assert_eq!(
+      readiness.seeding.detail.as_deref(),
+      Some("Seeding is running, 1 of 3 seed files done")
+    );
+
+    seeding.fail("Bad seed".into());
+
+    let readiness = get("/readyz").await.convert::<ReadinessPayload>().await;
+
+    assert_eq!(
+      readiness.seeding.detail.as_deref(),
+      Some("Seeding failed: Bad seed")
+    );
+
+    seeding.complete();
+
+    let response = get("/readyz").await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let readiness = response.convert::<ReadinessPayload>().await;
+
+    assert!(readiness.ready);
+    assert_eq!(
+      readiness.tasks.get("rate-limit-cleanup"),
+      Some(&TaskStatus::Running)
+    );
+
+    get("/api/courses/COMP202").await;
+
+    let response = get("/metrics").await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let metrics = String::from_utf8(
+      axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap()
+        .to_vec(),
+    )
+    .unwrap();
+
+    for needle in [
+      "http_request_duration_seconds_count{method=\"GET\",route=\"/api/courses/{id}\"",
+      "http_request_duration_seconds_count{method=\"GET\",route=\"/readyz\",status=\"200\"} 1",
+      "seeding_state{state=\"complete\"} 1",
+    ] {
+      assert!(metrics.contains(needle), "{needle}\n{metrics}");
+    }
+
+    supervisor.stop().await.unwrap();
+
+    let response = get("/readyz").await;
+
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+    let readiness = response.convert::<ReadinessPayload>().await;
+
+    assert!(readiness.shutting_down);
+    assert_eq!(
+      readiness.tasks.get("rate-limit-cleanup"),
+      Some(&TaskStatus::Cancelled)
+    );
   }
 }
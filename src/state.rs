@@ -2,11 +2,23 @@ use super::*;
 
 #[derive(Debug, Clone)]
 pub(crate) struct State {
+  pub(crate) admins: Admins,
   pub(crate) client_secret: String,
   pub(crate) db: Arc<Db>,
+  pub(crate) dispatcher: Arc<Dispatcher>,
+  pub(crate) metrics: Arc<Metrics>,
   pub(crate) oauth_client: BasicClient,
   pub(crate) request_client: reqwest::Client,
+  pub(crate) seeding: Seeding,
+  pub(crate) session_lifetime: Duration,
   pub(crate) session_store: MongodbSessionStore,
+  pub(crate) supervisor: Supervisor,
+}
+
+impl FromRef<State> for Admins {
+  fn from_ref(state: &State) -> Self {
+    state.admins.clone()
+  }
 }
 
 impl FromRef<State> for Arc<Db> {
@@ -15,6 +27,18 @@ impl FromRef<State> for Arc<Db> {
   }
 }
 
+impl FromRef<State> for Arc<Dispatcher> {
+  fn from_ref(state: &State) -> Self {
+    state.dispatcher.clone()
+  }
+}
+
+impl FromRef<State> for Arc<Metrics> {
+  fn from_ref(state: &State) -> Self {
+    state.metrics.clone()
+  }
+}
+
 impl FromRef<State> for BasicClient {
   fn from_ref(state: &State) -> Self {
     state.oauth_client.clone()
@@ -27,52 +51,65 @@ impl FromRef<State> for reqwest::Client {
   }
 }
 
+impl FromRef<State> for Seeding {
+  fn from_ref(state: &State) -> Self {
+    state.seeding.clone()
+  }
+}
+
 impl FromRef<State> for MongodbSessionStore {
   fn from_ref(state: &State) -> Self {
     state.session_store.clone()
   }
 }
 
+impl FromRef<State> for Supervisor {
+  fn from_ref(state: &State) -> Self {
+    state.supervisor.clone()
+  }
+}
+
 impl State {
   pub(crate) async fn new(
+    config: &Config,
     db: Arc<Db>,
+    dispatcher: Arc<Dispatcher>,
+    metrics: Arc<Metrics>,
+    seeding: Seeding,
     session_store: MongodbSessionStore,
+    supervisor: Supervisor,
   ) -> Result<Self> {
-    let client_secret = env::var("MS_CLIENT_SECRET")
-      .expect("Missing the MS_CLIENT_SECRET environment variable.");
+    let MicrosoftConfig {
+      client_id,
+      client_secret,
+      redirect_url,
+    } = config.microsoft.clone();
 
     Ok(Self {
-      client_secret: client_secret.clone(),
+      admins: config.admins.clone(),
+      client_secret: client_secret.0.clone(),
       db: db.clone(),
+      dispatcher,
+      metrics,
       oauth_client: BasicClient::new(
-        ClientId::new(
-          env::var("MS_CLIENT_ID")
-            .expect("Missing the MS_CLIENT_ID environment variable"),
-        ),
-        Some(ClientSecret::new(client_secret)),
+        ClientId::new(client_id),
+        Some(ClientSecret::new(client_secret.0)),
         AuthUrl::new(
           "https://login.microsoftonline.com/common/oauth2/v2.0/authorize"
             .to_string(),
-        )
-        .expect("Invalid authorization URL"),
-        Some(
-          TokenUrl::new(
-            "https://login.microsoftonline.com/common/oauth2/v2.0/token"
-              .to_string(),
-          )
-          .expect("Invalid token endpoint URL"),
-        ),
+        )?,
+        Some(TokenUrl::new(
+          "https://login.microsoftonline.com/common/oauth2/v2.0/token"
+            .to_string(),
+        )?),
       )
       .set_auth_type(AuthType::RequestBody)
-      .set_redirect_uri(
-        RedirectUrl::new(
-          env::var("MS_REDIRECT_URI")
-            .expect("Missing the MS_REDIRECT_URI environment variable."),
-        )
-        .expect("Invalid redirect URL"),
-      ),
+      .set_redirect_uri(redirect_url),
       request_client: reqwest::Client::new(),
+      seeding,
+      session_lifetime: config.session_lifetime,
       session_store,
+      supervisor,
     })
   }
 }
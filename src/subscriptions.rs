@@ -47,8 +47,37 @@ pub(crate) async fn get_subscription(
 
 #[derive(Debug, Deserialize, ToSchema)]
 pub(crate) struct AddOrDeleteSubscriptionBody {
-  /// Course ID to subscribe to or unsubscribe from.
+  /// What to subscribe to, defaulting to new reviews of a course.
+  #[serde(default)]
+  kind: SubscriptionKind,
+  /// Course ID to subscribe to or unsubscribe from, for course review,
+  /// offering and course change subscriptions.
+  #[serde(default)]
   course_id: String,
+  /// Instructor to follow, for instructor subscriptions.
+  instructor: Option<String>,
+  /// Only notify about this term, for offering subscriptions.
+  term: Option<String>,
+}
+
+impl AddOrDeleteSubscriptionBody {
+  fn subscription(&self, user_id: String) -> Result<Subscription> {
+    let subscription = Subscription {
+      kind: self.kind,
+      course_id: self.course_id.clone(),
+      instructor: self.instructor.clone(),
+      term: self.term.clone(),
+      user_id,
+    };
+
+    if !subscription.is_complete() {
+      return Err(
+        anyhow!("Incomplete {} subscription", subscription.kind).into(),
+      );
+    }
+
+    Ok(subscription)
+  }
 }
 
 #[utoipa::path(
@@ -72,16 +101,12 @@ pub(crate) async fn add_subscription(
   let user_id = user.id();
 
   info!(
-    "Adding subscription for user {} to course {}",
-    &user_id, body.course_id
+    "Adding {} subscription for user {} to course {}",
+    body.kind, &user_id, body.course_id
   );
 
   Ok(Json(
-    db.add_subscription(Subscription {
-      user_id,
-      course_id: body.course_id.clone(),
-    })
-    .await?,
+    db.add_subscription(body.subscription(user_id)?).await?,
   ))
 }
 
@@ -106,17 +131,15 @@ pub(crate) async fn delete_subscription(
   let user_id = user.id();
 
   info!(
-    "Removing subscription for user {} to course {}",
-    &user_id, body.course_id
+    "Removing {} subscription for user {} to course {}",
+    body.kind, &user_id, body.course_id
   );
 
-  db.purge_notifications(&user_id, &body.course_id).await?;
+  let subscription = body.subscription(user_id.clone())?;
 
-  Ok(Json(
-    db.delete_subscription(Subscription {
-      user_id,
-      course_id: body.course_id.clone(),
-    })
-    .await?,
-  ))
+  if subscription.kind == SubscriptionKind::Reviews {
+    db.purge_notifications(&user_id, &body.course_id).await?;
+  }
+
+  Ok(Json(db.delete_subscription(subscription).await?))
 }
@@ -0,0 +1,260 @@
+use super::*;
+
+/// How a supervised task is doing.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase", tag = "state", content = "error")]
+pub(crate) enum TaskStatus {
+  /// Stopped because the server is shutting down.
+  Cancelled,
+  /// Stopped with an error, or by panicking.
+  Failed(String),
+  /// Returned on its own.
+  Finished,
+  /// Still going.
+  Running,
+}
+
+/// Owns the server's background tasks, keeping track of how each is doing
+/// and shutting the server down when a critical one fails.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Supervisor {
+  /// The first critical failure, reported once everything has stopped.
+  failure: Arc<Mutex<Option<String>>>,
+  handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
+  shutdown: CancellationToken,
+  tasks: Arc<Mutex<BTreeMap<&'static str, TaskStatus>>>,
+}
+
+impl Supervisor {
+  /// How long in-flight requests and tasks get to finish once the server
+  /// starts shutting down.
+  pub(crate) const DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
+
+  fn launch<F, Fut>(&self, name: &'static str, critical: bool, task: F)
+  where
+    F: FnOnce(CancellationToken) -> Fut,
+    Fut: Future<Output = Result> + Send + 'static,
+  {
+    self.set(name, TaskStatus::Running);
+
+    let future = task(self.shutdown.clone());
+
+    let supervisor = self.clone();
+
+    let handle = tokio::spawn(async move {
+      let status = match AssertUnwindSafe(future).catch_unwind().await {
+        Ok(Ok(())) if supervisor.shutting_down() => TaskStatus::Cancelled,
+        Ok(Ok(())) => TaskStatus::Finished,
+        Ok(Err(error)) => TaskStatus::Failed(error.to_string()),
+        Err(panic) => TaskStatus::Failed(Self::panic_message(panic.as_ref())),
+      };
+
+      if let TaskStatus::Failed(error) = &status {
+        error!("Background task `{name}` failed: {error}");
+
+        if critical {
+          supervisor
+            .failure
+            .lock()
+            .unwrap()
+            .get_or_insert_with(|| format!("`{name}` failed: {error}"));
+
+          supervisor.shutdown.cancel();
+        }
+      }
+
+      supervisor.set(name, status);
+    });
+
+    self.handles.lock().unwrap().push(handle);
+  }
+
+  fn panic_message(panic: &(dyn Any + Send)) -> String {
+    panic
+      .downcast_ref::<&str>()
+      .map(|message| message.to_string())
+      .or_else(|| panic.downcast_ref::<String>().cloned())
+      .unwrap_or_else(|| "task panicked".into())
+  }
+
+  fn set(&self, name: &'static str, status: TaskStatus) {
+    self.tasks.lock().unwrap().insert(name, status);
+  }
+
+  /// Starts shutting down on SIGINT or SIGTERM.
+  pub(crate) fn handle_signals(&self) {
+    let shutdown = self.shutdown.clone();
+
+    tokio::spawn(async move {
+      tokio::select! {
+        signal = Self::signal() => match signal {
+          Ok(signal) => info!("Received {signal}, shutting down..."),
+          Err(error) => {
+            error!("Failed to listen for shutdown signals: {error}");
+            return;
+          }
+        },
+        () = shutdown.cancelled() => return,
+      }
+
+      shutdown.cancel();
+    });
+  }
+
+  #[cfg(unix)]
+  async fn signal() -> io::Result<&'static str> {
+    let mut terminate = tokio::signal::unix::signal(
+      tokio::signal::unix::SignalKind::terminate(),
+    )?;
+
+    tokio::select! {
+      result = tokio::signal::ctrl_c() => result.map(|()| "SIGINT"),
+      _ = terminate.recv() => Ok("SIGTERM"),
+    }
+  }
+
+  #[cfg(not(unix))]
+  async fn signal() -> io::Result<&'static str> {
+    tokio::signal::ctrl_c().await.map(|()| "Ctrl-C")
+  }
+
+  /// Cancelled once the server starts shutting down, which tasks should
+  /// watch to stop at the next sensible point.
+  pub(crate) fn shutdown(&self) -> CancellationToken {
+    self.shutdown.clone()
+  }
+
+  pub(crate) fn shutting_down(&self) -> bool {
+    self.shutdown.is_cancelled()
+  }
+
+  /// Runs a task whose failure is logged and reported, but otherwise left
+  /// alone.
+  pub(crate) fn spawn<F, Fut>(&self, name: &'static str, task: F)
+  where
+    F: FnOnce(CancellationToken) -> Fut,
+    Fut: Future<Output = Result> + Send + 'static,
+  {
+    self.launch(name, false, task);
+  }
+
+  /// Runs a task the server can't do without, shutting the server down with
+  /// its error should it fail.
+  pub(crate) fn spawn_critical<F, Fut>(&self, name: &'static str, task: F)
+  where
+    F: FnOnce(CancellationToken) -> Fut,
+    Fut: Future<Output = Result> + Send + 'static,
+  {
+    self.launch(name, true, task);
+  }
+
+  pub(crate) fn statuses(&self) -> BTreeMap<String, TaskStatus> {
+    self
+      .tasks
+      .lock()
+      .unwrap()
+      .iter()
+      .map(|(name, status)| (name.to_string(), status.clone()))
+      .collect()
+  }
+
+  /// Shuts down every task, waiting up to [`Self::DRAIN_TIMEOUT`] for them
+  /// to stop, then returns the first critical failure, if any.
+  pub(crate) async fn stop(&self) -> Result {
+    self.shutdown.cancel();
+
+    let handles = mem::take(&mut *self.handles.lock().unwrap());
+
+    let aborts = handles
+      .iter()
+      .map(JoinHandle::abort_handle)
+      .collect::<Vec<_>>();
+
+    if tokio::time::timeout(Self::DRAIN_TIMEOUT, join_all(handles))
+      .await
+      .is_err()
+    {
+      warn!("Timed out waiting for background tasks, aborting them");
+
+      for abort in aborts {
+        abort.abort();
+      }
+    }
+
+    match self.failure.lock().unwrap().take() {
+      Some(failure) => Err(anyhow!("Background task {failure}").into()),
+      None => Ok(()),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn tasks_are_cancelled_on_shutdown() {
+    let supervisor = Supervisor::default();
+
+    supervisor.spawn("finite", |_| async { Ok(()) });
+
+    supervisor.spawn("forever", |shutdown| async move {
+      shutdown.cancelled().await;
+      Ok(())
+    });
+
+    tokio::task::yield_now().await;
+
+    assert_eq!(
+      supervisor.statuses().get("forever"),
+      Some(&TaskStatus::Running)
+    );
+
+    supervisor.stop().await.unwrap();
+
+    assert_eq!(
+      supervisor.statuses(),
+      BTreeMap::from([
+        ("finite".into(), TaskStatus::Finished),
+        ("forever".into(), TaskStatus::Cancelled),
+      ])
+    );
+  }
+
+  #[tokio::test]
+  async fn critical_failures_shut_down() {
+    let supervisor = Supervisor::default();
+
+    supervisor.spawn("flaky", |_| async { Err(anyhow!("flaked").into()) });
+
+    supervisor.spawn("forever", |shutdown| async move {
+      shutdown.cancelled().await;
+      Ok(())
+    });
+
+    supervisor.spawn_critical("seeding", |_| async {
+      panic!("bad seed file");
+    });
+
+    tokio::time::timeout(
+      Duration::from_secs(5),
+      supervisor.shutdown().cancelled(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+      supervisor.stop().await.unwrap_err().to_string(),
+      "Background task `seeding` failed: bad seed file"
+    );
+
+    assert_eq!(
+      supervisor.statuses(),
+      BTreeMap::from([
+        ("flaky".into(), TaskStatus::Failed("flaked".into())),
+        ("forever".into(), TaskStatus::Cancelled),
+        ("seeding".into(), TaskStatus::Failed("bad seed file".into())),
+      ])
+    );
+  }
+}
@@ -0,0 +1,192 @@
+use super::*;
+
+/// Posts queued webhook deliveries, retrying failures with exponential
+/// backoff.
+#[derive(Debug)]
+pub(crate) struct WebhookSender {
+  client: reqwest::Client,
+  db: Arc<Db>,
+}
+
+impl WebhookSender {
+  /// How often to check for deliveries that are due.
+  pub(crate) const POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+  /// How many times to try a delivery before giving up on it.
+  pub(crate) const MAX_ATTEMPTS: u32 = 8;
+
+  /// How long to wait before the first retry, doubling after each one.
+  const INITIAL_BACKOFF: Duration = Duration::from_secs(30);
+
+  const MAX_BACKOFF: Duration = Duration::from_secs(6 * 60 * 60);
+
+  const TIMEOUT: Duration = Duration::from_secs(10);
+
+  pub(crate) fn new(db: Arc<Db>) -> Result<Self> {
+    Ok(Self {
+      client: reqwest::Client::builder().timeout(Self::TIMEOUT).build()?,
+      db,
+    })
+  }
+
+  /// How long to wait after a delivery's `attempts`th failed attempt.
+  pub(crate) fn backoff(attempts: u32) -> Duration {
+    Self::INITIAL_BACKOFF
+      .saturating_mul(2u32.saturating_pow(attempts.saturating_sub(1)))
+      .min(Self::MAX_BACKOFF)
+  }
+
+  /// Hex encoded HMAC-SHA256 of `payload` keyed with `secret`, per RFC 2104.
+  pub(crate) fn sign(secret: &[u8], payload: &[u8]) -> String {
+    const BLOCK_SIZE: usize = 64;
+
+    let mut key = [0; BLOCK_SIZE];
+
+    if secret.len() > BLOCK_SIZE {
+      key[..32].copy_from_slice(&Sha256::digest(secret));
+    } else {
+      key[..secret.len()].copy_from_slice(secret);
+    }
+
+    let inner = Sha256::new()
+      .chain_update(key.map(|byte| byte ^ 0x36))
+      .chain_update(payload)
+      .finalize();
+
+    Sha256::new()
+      .chain_update(key.map(|byte| byte ^ 0x5c))
+      .chain_update(inner)
+      .finalize()
+      .iter()
+      .map(|byte| format!("{byte:02x}"))
+      .collect()
+  }
+
+  /// Posts `delivery` to `webhook`, returning the response status.
+  async fn post(
+    &self,
+    webhook: &Webhook,
+    delivery: &WebhookDelivery,
+  ) -> Result<StatusCode> {
+    let response = self
+      .client
+      .post(&webhook.url)
+      .header("Content-Type", "application/json")
+      .header("X-Webhook-Id", &delivery.id)
+      .header("X-Webhook-Event", delivery.event.to_string())
+      .header(
+        "X-Webhook-Signature",
+        format!(
+          "sha256={}",
+          Self::sign(webhook.secret.as_bytes(), delivery.payload.as_bytes())
+        ),
+      )
+      .body(delivery.payload.clone())
+      .send()
+      .await?;
+
+    Ok(StatusCode::from_u16(response.status().as_u16())?)
+  }
+
+  /// Tries every delivery due at `now` once, returning how many succeeded.
+  pub(crate) async fn send_due(&self, now: DateTime<Utc>) -> Result<usize> {
+    let mut webhooks = HashMap::new();
+
+    let mut sent = 0;
+
+    for mut delivery in self.db.due_webhook_deliveries(now.into()).await? {
+      if !webhooks.contains_key(&delivery.webhook_id) {
+        webhooks.insert(
+          delivery.webhook_id.clone(),
+          self.db.webhook(&delivery.webhook_id).await?,
+        );
+      }
+
+      delivery.attempts += 1;
+
+      let Some(webhook) = &webhooks[&delivery.webhook_id] else {
+        delivery.status = WebhookDeliveryStatus::Failed;
+        delivery.error = Some("Webhook was deleted".into());
+        self.db.update_webhook_delivery(delivery).await?;
+        continue;
+      };
+
+      let result = self.post(webhook, &delivery).await;
+
+      delivery.response_status = None;
+      delivery.error = None;
+
+      match result {
+        Ok(status) if status.is_success() => {
+          delivery.status = WebhookDeliveryStatus::Succeeded;
+          delivery.response_status = Some(status.as_u16());
+          delivery.delivered_at = Some(now.into());
+          sent += 1;
+        }
+        Ok(status) => {
+          delivery.response_status = Some(status.as_u16());
+          delivery.error = Some(format!("Webhook responded with {status}"));
+        }
+        Err(error) => delivery.error = Some(error.to_string()),
+      }
+
+      if delivery.status == WebhookDeliveryStatus::Pending {
+        if delivery.attempts >= Self::MAX_ATTEMPTS {
+          delivery.status = WebhookDeliveryStatus::Failed;
+        } else {
+          delivery.next_attempt_at =
+            (now + Self::backoff(delivery.attempts)).into();
+        }
+      }
+
+      self.db.update_webhook_delivery(delivery).await?;
+    }
+
+    Ok(sent)
+  }
+
+  /// Sends due deliveries every [`Self::POLL_INTERVAL`], until `shutdown`.
+  pub(crate) async fn run(self: Arc<Self>, shutdown: CancellationToken) {
+    let mut interval = tokio::time::interval(Self::POLL_INTERVAL);
+
+    loop {
+      tokio::select! {
+        _ = interval.tick() => {}
+        () = shutdown.cancelled() => return,
+      }
+
+      if let Err(error) = self.send_due(Utc::now()).await {
+        error!("Failed to send webhook deliveries: {error}");
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn signatures_match_rfc_4231() {
+    assert_eq!(
+      WebhookSender::sign(b"Jefe", b"what do ya want for nothing?"),
+      "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+    );
+
+    assert_eq!(
+      WebhookSender::sign(
+        &[0xaa; 131],
+        b"Test Using Larger Than Block-Size Key - Hash Key First"
+      ),
+      "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+    );
+  }
+
+  #[test]
+  fn backoff_doubles_up_to_a_limit() {
+    assert_eq!(WebhookSender::backoff(1), Duration::from_secs(30));
+    assert_eq!(WebhookSender::backoff(2), Duration::from_secs(60));
+    assert_eq!(WebhookSender::backoff(4), Duration::from_secs(240));
+    assert_eq!(WebhookSender::backoff(20), WebhookSender::MAX_BACKOFF);
+  }
+}
@@ -0,0 +1,149 @@
+use super::*;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub(crate) struct AddWebhookBody {
+  /// HTTP(S) URL to post events to.
+  pub(crate) url: String,
+  /// Events to send, or every event if empty.
+  #[serde(default)]
+  pub(crate) events: Vec<WebhookEventKind>,
+  /// Key to sign payloads with, generated if not given.
+  pub(crate) secret: Option<String>,
+}
+
+#[utoipa::path(
+  get,
+  path = "/admin/webhooks",
+  tag = "admin",
+  description = "List every webhook.",
+  security(("microsoftOAuth" = ["User.Read"])),
+  responses(
+    (status = StatusCode::OK, description = "Every webhook, oldest first, without their signing secrets.", body = [Webhook]),
+    (status = StatusCode::FORBIDDEN, description = "The user isn't an admin."),
+    (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error.", body = String)
+  )
+)]
+pub(crate) async fn get_webhooks(
+  _: Admin,
+  AppState(db): AppState<Arc<Db>>,
+) -> Result<impl IntoResponse> {
+  Ok((
+    StatusCode::OK,
+    Json(
+      db.webhooks()
+        .await?
+        .into_iter()
+        .map(Webhook::without_secret)
+        .collect::<Vec<_>>(),
+    ),
+  ))
+}
+
+#[utoipa::path(
+  post,
+  path = "/admin/webhooks",
+  tag = "admin",
+  description = "Register a webhook to be posted signed events as reviews and courses change.",
+  security(("microsoftOAuth" = ["User.Read"])),
+  request_body = AddWebhookBody,
+  responses(
+    (status = StatusCode::CREATED, description = "The new webhook, including its signing secret.", body = Webhook),
+    (status = StatusCode::FORBIDDEN, description = "The user isn't an admin."),
+    (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error.", body = String)
+  )
+)]
+pub(crate) async fn add_webhook(
+  _: Admin,
+  AppState(db): AppState<Arc<Db>>,
+  body: Json<AddWebhookBody>,
+) -> Result<impl IntoResponse> {
+  let AddWebhookBody {
+    url,
+    events,
+    secret,
+  } = body.0;
+
+  if !matches!(Url::parse(&url)?.scheme(), "http" | "https") {
+    return Err(anyhow!("Webhook URLs must be HTTP or HTTPS").into());
+  }
+
+  let secret = secret
+    .filter(|secret| !secret.is_empty())
+    .unwrap_or_else(|| Alphanumeric.sample_string(&mut rand::rng(), 32));
+
+  let webhook = Webhook::new(&url, &secret, events);
+
+  db.add_webhook(webhook.clone()).await?;
+
+  Ok((StatusCode::CREATED, Json(webhook)))
+}
+
+#[utoipa::path(
+  delete,
+  path = "/admin/webhooks/{id}",
+  tag = "admin",
+  description = "Delete a webhook and its delivery log.",
+  security(("microsoftOAuth" = ["User.Read"])),
+  params(
+    ("id" = String, Path, description = "Identifier of the webhook to delete.")
+  ),
+  responses(
+    (status = StatusCode::OK, description = "Webhook deleted successfully."),
+    (status = StatusCode::FORBIDDEN, description = "The user isn't an admin."),
+    (status = StatusCode::NOT_FOUND, description = "There's no webhook with this identifier."),
+    (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error.", body = String)
+  )
+)]
+pub(crate) async fn delete_webhook(
+  _: Admin,
+  Path(id): Path<String>,
+  AppState(db): AppState<Arc<Db>>,
+) -> Result<impl IntoResponse> {
+  Ok(if db.delete_webhook(&id).await?.deleted_count == 0 {
+    StatusCode::NOT_FOUND
+  } else {
+    StatusCode::OK
+  })
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub(crate) struct GetWebhookDeliveriesParams {
+  /// Maximum number of deliveries to return.
+  pub(crate) limit: Option<i64>,
+}
+
+#[utoipa::path(
+  get,
+  path = "/admin/webhooks/{id}/deliveries",
+  tag = "admin",
+  description = "Get a webhook's delivery log.",
+  security(("microsoftOAuth" = ["User.Read"])),
+  params(
+    ("id" = String, Path, description = "Identifier of the webhook."),
+    ("limit" = Option<i64>, Query, description = "Maximum number of deliveries to return."),
+  ),
+  responses(
+    (status = StatusCode::OK, description = "The webhook's deliveries, newest first.", body = [WebhookDelivery]),
+    (status = StatusCode::FORBIDDEN, description = "The user isn't an admin."),
+    (status = StatusCode::NOT_FOUND, description = "There's no webhook with this identifier."),
+    (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal server error.", body = String)
+  )
+)]
+pub(crate) async fn get_webhook_deliveries(
+  _: Admin,
+  Path(id): Path<String>,
+  Query(params): Query<GetWebhookDeliveriesParams>,
+  AppState(db): AppState<Arc<Db>>,
+) -> Result<Response> {
+  if db.webhook(&id).await?.is_none() {
+    return Ok(StatusCode::NOT_FOUND.into_response());
+  }
+
+  Ok(
+    (
+      StatusCode::OK,
+      Json(db.webhook_deliveries(&id, params.limit).await?),
+    )
+      .into_response(),
+  )
+}
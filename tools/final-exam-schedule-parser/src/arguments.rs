@@ -0,0 +1,79 @@
+use super::*;
+
+#[derive(Parser, Debug)]
+#[command(
+  author,
+  version,
+  about = "Extract exam schedule data from a PDF file."
+)]
+pub(crate) struct Arguments {
+  /// Path to the source PDF file.
+  source: PathBuf,
+  /// Term to namespace exams for (e.g. 'Fall 2025', 'Winter 2026')
+  #[clap(short, long)]
+  term: String,
+  /// Path to output JSON file.
+  #[clap(short, long, default_value = "client/src/assets/final-exams.json")]
+  output: PathBuf,
+  /// Path to write a JSON validation report of unparseable rows to.
+  #[clap(short, long)]
+  report: Option<PathBuf>,
+  /// Fail without writing any output if a row could not be parsed.
+  #[clap(long)]
+  strict: bool,
+  /// URL of the source PDF file.
+  #[clap(short, long)]
+  url: String,
+}
+
+impl Arguments {
+  pub(crate) fn run(self) -> Result {
+    let spans = layout::extract_pdf_spans(&self.source)?;
+
+    let table = match Table::from_spans(&spans) {
+      Ok(table) => table,
+      Err(error) => {
+        eprintln!("{error}, reading rows from text lines instead");
+        Table::from_lines(&layout::extract_pdf_lines(&self.source)?)?
+      }
+    };
+
+    let (parsed_exams, report) = exam::parse_rows(table.rows())?;
+
+    report.print(&self.source);
+
+    if let Some(path) = &self.report {
+      fs::write(path, serde_json::to_string_pretty(&report)?)?;
+    }
+
+    if self.strict && !report.errors.is_empty() {
+      bail!(
+        "{} of {} rows could not be parsed",
+        report.errors.len(),
+        report.rows
+      );
+    }
+
+    let mut terms: Vec<Term> = if self.output.exists() {
+      serde_json::from_str(&fs::read_to_string(&self.output)?)?
+    } else {
+      Vec::new()
+    };
+
+    if let Some(existing_term) = terms.iter_mut().find(|t| t.term == self.term)
+    {
+      existing_term.url = self.url;
+      existing_term.exams = parsed_exams;
+    } else {
+      terms.push(Term {
+        exams: parsed_exams,
+        term: self.term.clone(),
+        url: self.url.clone(),
+      });
+    }
+
+    fs::write(self.output, serde_json::to_string_pretty(&terms)?)?;
+
+    Ok(())
+  }
+}
@@ -0,0 +1,384 @@
+use super::*;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ExamDetails {
+  pub(crate) format: String,
+  #[serde(rename = "type")]
+  pub(crate) exam_type: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub(crate) location: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct CourseExam {
+  pub(crate) id: String,
+  pub(crate) section: String,
+  pub(crate) exam: ExamDetails,
+  pub(crate) start_time: String,
+  pub(crate) end_time: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct Term {
+  pub(crate) term: String,
+  pub(crate) url: String,
+  pub(crate) exams: Vec<CourseExam>,
+}
+
+/// Parses every row into an exam, collecting rows that fail validation into
+/// a report instead of aborting on the first one.
+pub(crate) fn parse_rows(rows: &[Row]) -> Result<(Vec<CourseExam>, Report)> {
+  let section_pattern = Regex::new(r"^[0-9]{3}[A-Z0-9]*$")?;
+
+  let mut exams = Vec::new();
+
+  let mut report = Report {
+    rows: rows.len(),
+    ..Default::default()
+  };
+
+  for row in rows {
+    match parse_row(row, &section_pattern) {
+      Ok(exam) => exams.push(exam),
+      Err(error) => report.errors.push(RowError {
+        page: row.page,
+        text: row.text(),
+        reason: error.to_string(),
+      }),
+    }
+  }
+
+  report.parsed = exams.len();
+
+  Ok((exams, report))
+}
+
+fn parse_row(row: &Row, section_pattern: &Regex) -> Result<CourseExam> {
+  if let Some(reason) = &row.unparsed {
+    bail!("{reason}");
+  }
+
+  let id = row
+    .get(ColumnKind::Course)
+    .ok_or_else(|| anyhow!("Missing course"))?
+    .split_whitespace()
+    .collect::<String>();
+
+  let section = row
+    .get(ColumnKind::Section)
+    .ok_or_else(|| anyhow!("Missing section for {id}"))?;
+
+  if !section_pattern.is_match(section) {
+    bail!("Invalid section \"{section}\" for {id}");
+  }
+
+  let exam = parse_exam_details(
+    row
+      .get(ColumnKind::Type)
+      .ok_or_else(|| anyhow!("Missing exam details for {id}"))?,
+    row.get(ColumnKind::Format),
+    row.get(ColumnKind::Location),
+  )?;
+
+  let date = row.get(ColumnKind::Date);
+
+  let start_time = parse_time_cell(
+    row
+      .get(ColumnKind::Start)
+      .ok_or_else(|| anyhow!("Missing start time for {id}"))?,
+    date,
+  )
+  .ok_or_else(|| {
+    anyhow!(
+      "Unrecognized start time \"{}\" for {id}",
+      row.get(ColumnKind::Start).unwrap_or_default()
+    )
+  })?;
+
+  let end_time = parse_time_cell(
+    row
+      .get(ColumnKind::End)
+      .ok_or_else(|| anyhow!("Missing end time for {id}"))?,
+    date,
+  )
+  .ok_or_else(|| {
+    anyhow!(
+      "Unrecognized end time \"{}\" for {id}",
+      row.get(ColumnKind::End).unwrap_or_default()
+    )
+  })?;
+
+  Ok(CourseExam {
+    id,
+    section: section.to_string(),
+    exam,
+    start_time,
+    end_time,
+  })
+}
+
+/// Parses an exam type cell.
+///
+/// Older schedules pack the format, type and location into one cell as
+/// `FORMAT - TYPE - LOCATION`, while newer ones give each its own column.
+fn parse_exam_details(
+  line: &str,
+  format: Option<&str>,
+  location: Option<&str>,
+) -> Result<ExamDetails> {
+  let parts = line
+    .split(" - ")
+    .map(str::trim)
+    .filter(|part| !part.is_empty())
+    .collect::<Vec<_>>();
+
+  let (format, rest) = match format {
+    Some(format) => (format, &parts[..]),
+    None if parts.len() >= 2 => (parts[0], &parts[1..]),
+    None => bail!("Invalid exam line \"{line}\""),
+  };
+
+  let exam_type = rest
+    .first()
+    .ok_or_else(|| anyhow!("Invalid exam line \"{line}\""))?;
+
+  let location = location.map(str::to_string).or_else(|| {
+    let joined = rest[1..].join(" - ");
+    (!joined.is_empty()).then_some(joined)
+  });
+
+  Ok(ExamDetails {
+    format: format.to_string(),
+    exam_type: exam_type.to_string(),
+    location,
+  })
+}
+
+fn parse_time_cell(value: &str, date: Option<&str>) -> Option<String> {
+  parse_datetime(value).or_else(|| parse_date_and_time(date?, value))
+}
+
+fn parse_datetime(value: &str) -> Option<String> {
+  NaiveDateTime::parse_from_str(value.trim(), "%d-%b-%Y at %I:%M %p")
+    .ok()
+    .map(|dt| dt.format("%Y-%m-%dT%H:%M:%S").to_string())
+}
+
+fn parse_date_and_time(date: &str, time: &str) -> Option<String> {
+  let date = ["%d-%b-%Y", "%Y-%m-%d", "%B %d, %Y", "%A, %B %d, %Y"]
+    .iter()
+    .find_map(|format| NaiveDate::parse_from_str(date.trim(), format).ok())?;
+
+  let time = ["%I:%M %p", "%I:%M%p", "%H:%M"]
+    .iter()
+    .find_map(|format| NaiveTime::parse_from_str(time.trim(), format).ok())?;
+
+  Some(date.and_time(time).format("%Y-%m-%dT%H:%M:%S").to_string())
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    include_dir::{Dir, include_dir},
+    pretty_assertions::assert_eq,
+  };
+
+  static SAMPLE_DIR: Dir<'_> =
+    include_dir!("tools/final-exam-schedule-parser/test-samples");
+
+  fn parse_sample(name: &str) -> (Vec<CourseExam>, Report) {
+    let spans = serde_json::from_str::<Vec<Span>>(
+      SAMPLE_DIR.get_file(name).unwrap().contents_utf8().unwrap(),
+    )
+    .unwrap();
+
+    parse_rows(Table::from_spans(&spans).unwrap().rows()).unwrap()
+  }
+
+  fn exam(
+    id: &str,
+    (format, exam_type, location): (&str, &str, Option<&str>),
+    start_time: &str,
+    end_time: &str,
+  ) -> CourseExam {
+    CourseExam {
+      id: id.into(),
+      section: "001".into(),
+      exam: ExamDetails {
+        format: format.into(),
+        exam_type: exam_type.into(),
+        location: location.map(String::from),
+      },
+      start_time: start_time.into(),
+      end_time: end_time.into(),
+    }
+  }
+
+  #[test]
+  fn december_2025_combined_exam_column() {
+    let (exams, report) = parse_sample("december_2025.json");
+
+    let in_person = ("IN-PERSON", "FORMAL EXAM", Some("D.T. CAMPUS"));
+
+    assert_eq!(
+      exams,
+      vec![
+        exam(
+          "ACCT352",
+          in_person,
+          "2025-12-09T18:30:00",
+          "2025-12-09T21:30:00"
+        ),
+        exam(
+          "COMP251",
+          in_person,
+          "2025-12-12T09:00:00",
+          "2025-12-12T12:00:00"
+        ),
+        exam(
+          "COMP250",
+          ("ONLINE", "TAKE HOME EXAM", None),
+          "2025-12-10T14:00:00",
+          "2025-12-10T17:00:00"
+        ),
+        exam(
+          "MATH240",
+          in_person,
+          "2025-12-15T14:00:00",
+          "2025-12-15T17:00:00"
+        ),
+      ]
+    );
+
+    assert_eq!(
+      report,
+      Report {
+        rows: 5,
+        parsed: 4,
+        errors: vec![RowError {
+          page: 2,
+          text: "MATH 314 | 001 | Advanced Calculus | IN-PERSON - FORMAL EXAM - D.T. CAMPUS | TBA | TBA".into(),
+          reason: "Unrecognized start time \"TBA\" for MATH314".into(),
+        }],
+      }
+    );
+  }
+
+  #[test]
+  fn split_exam_columns() {
+    let (exams, report) = parse_sample("split_columns.json");
+
+    assert_eq!(
+      exams,
+      vec![
+        exam(
+          "BIOL112",
+          ("IN-PERSON", "FORMAL EXAM", Some("FIELD HOUSE")),
+          "2025-04-28T09:00:00",
+          "2025-04-28T12:00:00"
+        ),
+        exam(
+          "CHEM110",
+          ("IN-PERSON", "FORMAL EXAM", Some("BRONF 151 BRONF 179")),
+          "2025-04-29T14:00:00",
+          "2025-04-29T17:00:00"
+        ),
+      ]
+    );
+
+    assert_eq!(
+      report,
+      Report {
+        rows: 3,
+        parsed: 2,
+        errors: vec![RowError {
+          page: 1,
+          text: "ECON 208 | 002 | Microeconomic Analysis | ONLINE | TAKE HOME | 30-Apr-2025 | 9:00 AM".into(),
+          reason: "Missing end time for ECON208".into(),
+        }],
+      }
+    );
+  }
+
+  #[test]
+  fn december_2025_text_lines() {
+    let pages = serde_json::from_str::<BTreeMap<u32, Vec<String>>>(
+      SAMPLE_DIR
+        .get_file("december_2025_lines.json")
+        .unwrap()
+        .contents_utf8()
+        .unwrap(),
+    )
+    .unwrap();
+
+    let (exams, report) =
+      parse_rows(Table::from_lines(&pages).unwrap().rows()).unwrap();
+
+    let published = serde_json::from_str::<Vec<Term>>(include_str!(
+      "../../../client/src/assets/final-exams.json"
+    ))
+    .unwrap()
+    .into_iter()
+    .find(|term| term.term == "Fall 2025")
+    .unwrap()
+    .exams;
+
+    assert_eq!(exams, published[..8]);
+
+    assert_eq!(
+      report,
+      Report {
+        rows: 8,
+        parsed: 8,
+        errors: Vec::new(),
+      }
+    );
+  }
+
+  #[test]
+  fn exam_details() {
+    assert_eq!(
+      parse_exam_details("IN-PERSON - FORMAL EXAM - D.T. CAMPUS", None, None)
+        .unwrap(),
+      ExamDetails {
+        format: "IN-PERSON".into(),
+        exam_type: "FORMAL EXAM".into(),
+        location: Some("D.T. CAMPUS".into()),
+      }
+    );
+
+    assert_eq!(
+      parse_exam_details("FORMAL EXAM", Some("IN-PERSON"), Some("BURN 1B45"))
+        .unwrap(),
+      ExamDetails {
+        format: "IN-PERSON".into(),
+        exam_type: "FORMAL EXAM".into(),
+        location: Some("BURN 1B45".into()),
+      }
+    );
+
+    assert!(parse_exam_details("FORMAL EXAM", None, None).is_err());
+  }
+
+  #[test]
+  fn time_cells() {
+    assert_eq!(
+      parse_time_cell("09-Dec-2025 at 06:30 PM", None),
+      Some("2025-12-09T18:30:00".into())
+    );
+
+    assert_eq!(
+      parse_time_cell("6:30 PM", Some("09-Dec-2025")),
+      Some("2025-12-09T18:30:00".into())
+    );
+
+    assert_eq!(
+      parse_time_cell("18:30", Some("December 9, 2025")),
+      Some("2025-12-09T18:30:00".into())
+    );
+
+    assert_eq!(parse_time_cell("6:30 PM", None), None);
+    assert_eq!(parse_time_cell("TBA", Some("09-Dec-2025")), None);
+  }
+}
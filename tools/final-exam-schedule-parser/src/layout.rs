@@ -0,0 +1,495 @@
+use super::*;
+
+static IGNORE: &[&[u8]] = &[
+  b"Length",
+  b"BBox",
+  b"FormType",
+  b"Matrix",
+  b"Type",
+  b"XObject",
+  b"Subtype",
+  b"Filter",
+  b"ColorSpace",
+  b"Width",
+  b"Height",
+  b"BitsPerComponent",
+  b"Length1",
+  b"Length2",
+  b"Length3",
+  b"PTEX.FileName",
+  b"PTEX.PageNumber",
+  b"PTEX.InfoDict",
+  b"FontDescriptor",
+  b"ExtGState",
+  b"MediaBox",
+  b"Annot",
+];
+
+/// A run of text drawn at a single position on a page.
+///
+/// Coordinates are in PDF user space, so `y` grows towards the top of the
+/// page.
+#[derive(Debug, Clone, Deserialize, PartialEq, Serialize)]
+pub(crate) struct Span {
+  pub(crate) page: u32,
+  pub(crate) x: f32,
+  pub(crate) y: f32,
+  pub(crate) text: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Matrix([f32; 6]);
+
+impl Matrix {
+  const IDENTITY: Matrix = Matrix([1.0, 0.0, 0.0, 1.0, 0.0, 0.0]);
+
+  fn translate(x: f32, y: f32) -> Self {
+    Matrix([1.0, 0.0, 0.0, 1.0, x, y])
+  }
+
+  fn multiply(self, other: Matrix) -> Self {
+    let [a1, b1, c1, d1, e1, f1] = self.0;
+    let [a2, b2, c2, d2, e2, f2] = other.0;
+
+    Matrix([
+      a1 * a2 + b1 * c2,
+      a1 * b2 + b1 * d2,
+      c1 * a2 + d1 * c2,
+      c1 * b2 + d1 * d2,
+      e1 * a2 + f1 * c2 + e2,
+      e1 * b2 + f1 * d2 + f2,
+    ])
+  }
+
+  fn from_operands(operands: &[Object]) -> Option<Self> {
+    let values = operands
+      .iter()
+      .map(|operand| operand.as_float().ok())
+      .collect::<Option<Vec<f32>>>()?;
+
+    Some(Matrix(values.try_into().ok()?))
+  }
+}
+
+fn filter_func(
+  object_id: (u32, u16),
+  object: &mut Object,
+) -> Option<((u32, u16), Object)> {
+  if IGNORE.contains(&object.type_name().unwrap_or_default()) {
+    return None;
+  }
+
+  if let Ok(dictionary) = object.as_dict_mut() {
+    dictionary.remove(b"Producer");
+    dictionary.remove(b"ModDate");
+    dictionary.remove(b"Creator");
+    dictionary.remove(b"ProcSet");
+    dictionary.remove(b"Procset");
+    dictionary.remove(b"XObject");
+    dictionary.remove(b"MediaBox");
+    dictionary.remove(b"Annots");
+
+    if dictionary.is_empty() {
+      return None;
+    }
+  }
+
+  Some((object_id, object.to_owned()))
+}
+
+fn decode_operands(
+  encoding: &Encoding,
+  operands: &[Object],
+  text: &mut String,
+) -> Result {
+  for operand in operands {
+    match operand {
+      Object::String(bytes, _) => {
+        text.push_str(&Document::decode_text(encoding, bytes)?);
+      }
+      Object::Array(array) => decode_operands(encoding, array, text)?,
+      // Large negative kerning adjustments are how most producers encode
+      // inter-word spacing inside a `TJ` array.
+      Object::Integer(_) | Object::Real(_)
+        if operand.as_float().unwrap_or_default() < -100.0 =>
+      {
+        text.push(' ');
+      }
+      _ => {}
+    }
+  }
+
+  Ok(())
+}
+
+fn page_spans(
+  doc: &Document,
+  page_number: u32,
+  page_id: (u32, u16),
+) -> Result<Vec<Span>> {
+  let fonts = doc.get_page_fonts(page_id)?;
+
+  let encodings = fonts
+    .into_iter()
+    .filter_map(|(name, font)| {
+      font.get_font_encoding(doc).ok().map(|it| (name, it))
+    })
+    .collect::<BTreeMap<Vec<u8>, Encoding>>();
+
+  let content = doc.get_and_decode_page_content(page_id)?;
+
+  let (mut ctm, mut stack) = (Matrix::IDENTITY, Vec::new());
+
+  let (mut text_matrix, mut line_matrix) = (Matrix::IDENTITY, Matrix::IDENTITY);
+
+  let (mut encoding, mut leading) = (None, 0.0);
+
+  let mut spans = Vec::new();
+
+  for operation in &content.operations {
+    let operands = &operation.operands;
+
+    let number = |index: usize| -> f32 {
+      operands
+        .get(index)
+        .and_then(|operand| operand.as_float().ok())
+        .unwrap_or_default()
+    };
+
+    let mut shown = None;
+
+    match operation.operator.as_str() {
+      "q" => stack.push(ctm),
+      "Q" => ctm = stack.pop().unwrap_or(Matrix::IDENTITY),
+      "cm" => {
+        if let Some(matrix) = Matrix::from_operands(operands) {
+          ctm = matrix.multiply(ctm);
+        }
+      }
+      "BT" => {
+        text_matrix = Matrix::IDENTITY;
+        line_matrix = Matrix::IDENTITY;
+      }
+      "Tf" => {
+        encoding = operands
+          .first()
+          .and_then(|operand| operand.as_name().ok())
+          .and_then(|name| encodings.get(name));
+      }
+      "TL" => leading = number(0),
+      "Td" | "TD" => {
+        if operation.operator == "TD" {
+          leading = -number(1);
+        }
+
+        line_matrix =
+          Matrix::translate(number(0), number(1)).multiply(line_matrix);
+        text_matrix = line_matrix;
+      }
+      "Tm" => {
+        if let Some(matrix) = Matrix::from_operands(operands) {
+          line_matrix = matrix;
+          text_matrix = matrix;
+        }
+      }
+      "T*" => {
+        line_matrix = Matrix::translate(0.0, -leading).multiply(line_matrix);
+        text_matrix = line_matrix;
+      }
+      "Tj" | "TJ" => shown = Some(&operands[..]),
+      "'" => {
+        line_matrix = Matrix::translate(0.0, -leading).multiply(line_matrix);
+        text_matrix = line_matrix;
+        shown = Some(&operands[..]);
+      }
+      "\"" => {
+        line_matrix = Matrix::translate(0.0, -leading).multiply(line_matrix);
+        text_matrix = line_matrix;
+        shown = operands.get(2..);
+      }
+      _ => {}
+    }
+
+    let (Some(operands), Some(encoding)) = (shown, encoding) else {
+      continue;
+    };
+
+    let mut text = String::new();
+
+    decode_operands(encoding, operands, &mut text)?;
+
+    if text.trim().is_empty() {
+      continue;
+    }
+
+    let Matrix([.., x, y]) = text_matrix.multiply(ctm);
+
+    spans.push(Span {
+      page: page_number,
+      x,
+      y,
+      text: text.trim().to_string(),
+    });
+  }
+
+  Ok(spans)
+}
+
+/// Extracts every positioned text span in `doc`, ordered by page.
+///
+/// Pages that fail to decode are skipped and reported in the returned error
+/// list rather than aborting the whole extraction.
+pub(crate) fn extract_spans(doc: &Document) -> (Vec<Span>, Vec<String>) {
+  let results = doc
+    .get_pages()
+    .into_par_iter()
+    .map(|(page_number, page_id)| {
+      page_spans(doc, page_number, page_id).map_err(|err| {
+        anyhow!("Failed to extract text from page {page_number}: {err}")
+      })
+    })
+    .collect::<Vec<Result<Vec<Span>>>>();
+
+  let (mut spans, mut errors) = (Vec::new(), Vec::new());
+
+  for result in results {
+    match result {
+      Ok(page) => spans.extend(page),
+      Err(err) => errors.push(err.to_string()),
+    }
+  }
+
+  (spans, errors)
+}
+
+fn print_errors(source: &Path, errors: &[String]) {
+  if !errors.is_empty() {
+    eprintln!("{} produced {} errors:", source.display(), errors.len());
+
+    for error in errors.iter().take(10) {
+      eprintln!("{error}");
+    }
+  }
+}
+
+pub(crate) fn extract_pdf_spans(source: &Path) -> Result<Vec<Span>> {
+  let doc = Document::load_filtered(source, filter_func)?;
+
+  let (spans, errors) = extract_spans(&doc);
+
+  print_errors(source, &errors);
+
+  Ok(spans)
+}
+
+/// Extracts each page's text lines in content order, without positions, for
+/// [`Table::from_lines`].
+pub(crate) fn extract_pdf_lines(
+  source: &Path,
+) -> Result<BTreeMap<u32, Vec<String>>> {
+  let doc = Document::load_filtered(source, filter_func)?;
+
+  let results = doc
+    .get_pages()
+    .into_par_iter()
+    .map(|(page_number, _)| {
+      doc
+        .extract_text(&[page_number])
+        .map(|text| {
+          (
+            page_number,
+            text
+              .split('\n')
+              .map(|line| line.trim_end().to_string())
+              .collect::<Vec<_>>(),
+          )
+        })
+        .map_err(|err| {
+          anyhow!("Failed to extract text from page {page_number}: {err}")
+        })
+    })
+    .collect::<Vec<Result<(u32, Vec<String>)>>>();
+
+  let (mut pages, mut errors) = (BTreeMap::new(), Vec::new());
+
+  for result in results {
+    match result {
+      Ok((page_number, lines)) => {
+        pages.insert(page_number, lines);
+      }
+      Err(err) => errors.push(err.to_string()),
+    }
+  }
+
+  print_errors(source, &errors);
+
+  Ok(pages)
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    lopdf::{
+      Stream,
+      content::{Content, Operation},
+      dictionary,
+    },
+  };
+
+  fn document(operations: Vec<Operation>) -> Document {
+    let mut doc = Document::with_version("1.5");
+
+    let pages_id = doc.new_object_id();
+
+    let font_id = doc.add_object(dictionary! {
+      "Type" => "Font",
+      "Subtype" => "Type1",
+      "BaseFont" => "Helvetica",
+      "Encoding" => "WinAnsiEncoding",
+    });
+
+    let resources_id = doc.add_object(dictionary! {
+      "Font" => dictionary! { "F1" => font_id },
+    });
+
+    let content_id = doc.add_object(Stream::new(
+      dictionary! {},
+      Content { operations }.encode().unwrap(),
+    ));
+
+    let page_id = doc.add_object(dictionary! {
+      "Type" => "Page",
+      "Parent" => pages_id,
+      "Contents" => content_id,
+      "Resources" => resources_id,
+    });
+
+    doc.objects.insert(
+      pages_id,
+      Object::Dictionary(dictionary! {
+        "Type" => "Pages",
+        "Kids" => vec![page_id.into()],
+        "Count" => 1,
+        "MediaBox" => vec![0.into(), 0.into(), 612.into(), 792.into()],
+      }),
+    );
+
+    let catalog_id = doc.add_object(dictionary! {
+      "Type" => "Catalog",
+      "Pages" => pages_id,
+    });
+
+    doc.trailer.set("Root", catalog_id);
+
+    doc
+  }
+
+  fn text(value: &str) -> Object {
+    Object::string_literal(value)
+  }
+
+  #[test]
+  fn spans_are_positioned() {
+    let doc = document(vec![
+      Operation::new("BT", vec![]),
+      Operation::new("Tf", vec!["F1".into(), 10.into()]),
+      Operation::new("Td", vec![50.into(), 700.into()]),
+      Operation::new("Tj", vec![text("COMP 251")]),
+      Operation::new("Td", vec![100.into(), 0.into()]),
+      Operation::new("Tj", vec![text("001")]),
+      Operation::new("ET", vec![]),
+      Operation::new("BT", vec![]),
+      Operation::new("Tf", vec!["F1".into(), 10.into()]),
+      Operation::new(
+        "Tm",
+        vec![
+          1.into(),
+          0.into(),
+          0.into(),
+          1.into(),
+          50.into(),
+          680.into(),
+        ],
+      ),
+      Operation::new("TL", vec![12.into()]),
+      Operation::new("Tj", vec![text("MATH 240")]),
+      Operation::new("T*", vec![]),
+      Operation::new(
+        "TJ",
+        vec![Object::Array(vec![
+          text("Discrete"),
+          (-250).into(),
+          text("Structures"),
+        ])],
+      ),
+      Operation::new("ET", vec![]),
+    ]);
+
+    let (spans, errors) = extract_spans(&doc);
+
+    assert!(errors.is_empty());
+
+    assert_eq!(
+      spans,
+      vec![
+        Span {
+          page: 1,
+          x: 50.0,
+          y: 700.0,
+          text: "COMP 251".into(),
+        },
+        Span {
+          page: 1,
+          x: 150.0,
+          y: 700.0,
+          text: "001".into(),
+        },
+        Span {
+          page: 1,
+          x: 50.0,
+          y: 680.0,
+          text: "MATH 240".into(),
+        },
+        Span {
+          page: 1,
+          x: 50.0,
+          y: 668.0,
+          text: "Discrete Structures".into(),
+        },
+      ]
+    );
+  }
+
+  #[test]
+  fn spans_respect_transformation_matrix() {
+    let doc = document(vec![
+      Operation::new("q", vec![]),
+      Operation::new(
+        "cm",
+        vec![1.into(), 0.into(), 0.into(), 1.into(), 20.into(), 30.into()],
+      ),
+      Operation::new("BT", vec![]),
+      Operation::new("Tf", vec!["F1".into(), 10.into()]),
+      Operation::new("Td", vec![10.into(), 10.into()]),
+      Operation::new("Tj", vec![text("inside")]),
+      Operation::new("ET", vec![]),
+      Operation::new("Q", vec![]),
+      Operation::new("BT", vec![]),
+      Operation::new("Tf", vec!["F1".into(), 10.into()]),
+      Operation::new("Td", vec![10.into(), 10.into()]),
+      Operation::new("Tj", vec![text("outside")]),
+      Operation::new("ET", vec![]),
+    ]);
+
+    let (spans, _) = extract_spans(&doc);
+
+    assert_eq!(
+      spans
+        .iter()
+        .map(|span| (span.x, span.y, span.text.as_str()))
+        .collect::<Vec<_>>(),
+      vec![(30.0, 40.0, "inside"), (10.0, 10.0, "outside")]
+    );
+  }
+}
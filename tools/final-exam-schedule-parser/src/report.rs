@@ -0,0 +1,36 @@
+use super::*;
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Serialize)]
+pub(crate) struct RowError {
+  /// Page the row was found on.
+  pub(crate) page: u32,
+  /// The row's cells joined in column order.
+  pub(crate) text: String,
+  /// Why the row could not be parsed.
+  pub(crate) reason: String,
+}
+
+#[derive(Debug, Default, Deserialize, PartialEq, Serialize)]
+pub(crate) struct Report {
+  /// Number of table rows found in the document.
+  pub(crate) rows: usize,
+  /// Number of rows successfully parsed into exams.
+  pub(crate) parsed: usize,
+  /// Rows that could not be parsed, in document order.
+  pub(crate) errors: Vec<RowError>,
+}
+
+impl Report {
+  pub(crate) fn print(&self, source: &Path) {
+    eprintln!(
+      "{}: parsed {} of {} rows",
+      source.display(),
+      self.parsed,
+      self.rows
+    );
+
+    for error in &self.errors {
+      eprintln!("page {}: {} (\"{}\")", error.page, error.reason, error.text);
+    }
+  }
+}
@@ -0,0 +1,510 @@
+use super::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub(crate) enum ColumnKind {
+  Course,
+  Section,
+  Title,
+  Format,
+  Type,
+  Date,
+  Start,
+  End,
+  Location,
+}
+
+impl ColumnKind {
+  fn from_header(text: &str) -> Option<Self> {
+    match text.trim().to_uppercase().as_str() {
+      "COURSE" | "COURSE NUMBER" | "COURSE ID" => Some(Self::Course),
+      "SECTION" | "SEC" => Some(Self::Section),
+      "TITLE" | "COURSE TITLE" => Some(Self::Title),
+      "FORMAT" | "DELIVERY" => Some(Self::Format),
+      "TYPE" | "EXAM TYPE" | "EXAM" => Some(Self::Type),
+      "DATE" | "EXAM DATE" => Some(Self::Date),
+      "START" | "START TIME" | "FROM" => Some(Self::Start),
+      "END" | "END TIME" | "TO" => Some(Self::End),
+      "LOCATION" | "BUILDING" | "ROOM" | "CAMPUS" => Some(Self::Location),
+      _ => None,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Column {
+  kind: ColumnKind,
+  x: f32,
+}
+
+/// A reconstructed table row, with the text of each column it has a value in.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Row {
+  pub(crate) page: u32,
+  pub(crate) cells: BTreeMap<ColumnKind, String>,
+  /// Why the line couldn't be read as a row, if it couldn't.
+  pub(crate) unparsed: Option<String>,
+}
+
+impl Row {
+  pub(crate) fn get(&self, kind: ColumnKind) -> Option<&str> {
+    self
+      .cells
+      .get(&kind)
+      .map(String::as_str)
+      .filter(|cell| !cell.is_empty())
+  }
+
+  pub(crate) fn text(&self) -> String {
+    self
+      .cells
+      .values()
+      .map(String::as_str)
+      .collect::<Vec<_>>()
+      .join(" | ")
+  }
+
+  fn append(&mut self, cells: BTreeMap<ColumnKind, String>) {
+    for (kind, text) in cells {
+      let cell = self.cells.entry(kind).or_default();
+
+      if !cell.is_empty() {
+        cell.push(' ');
+      }
+
+      cell.push_str(&text);
+    }
+  }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Table {
+  rows: Vec<Row>,
+}
+
+impl Table {
+  /// Vertical distance within which spans are considered part of one line.
+  const LINE_TOLERANCE: f32 = 2.0;
+
+  /// How far left of its header a cell may start and still belong to it.
+  const COLUMN_TOLERANCE: f32 = 4.0;
+
+  /// Lines further than this below the previous one never continue a row,
+  /// which keeps page footers from being glued onto the last row.
+  const MAX_CONTINUATION_GAP: f32 = 14.0;
+
+  /// Reconstructs table rows from positioned spans.
+  ///
+  /// Columns are located from each page's header line, and every span below it
+  /// is assigned to the rightmost column whose header starts at or before it.
+  /// A row begins at each line with a course code in the course column, and
+  /// subsequent lines without one (e.g. wrapped titles) are appended to it.
+  /// Lines that do neither are kept as unparsed rows, so they're reported,
+  /// except for single cells without a course, like page footers.
+  pub(crate) fn from_spans(spans: &[Span]) -> Result<Self> {
+    let course_pattern = Regex::new(r"^[A-Z0-9]{3,5}\s+[0-9]{3}[A-Z0-9]*$")?;
+
+    let mut pages = BTreeMap::<u32, Vec<&Span>>::new();
+
+    for span in spans {
+      pages.entry(span.page).or_default().push(span);
+    }
+
+    let (mut rows, mut columns) = (Vec::<Row>::new(), None::<Vec<Column>>);
+
+    for (page, spans) in pages {
+      let mut current = None::<(Row, f32)>;
+
+      for line in Self::lines(spans) {
+        if let Some(header) = Self::header(&line) {
+          rows.extend(current.take().map(|(row, _)| row));
+          columns = Some(header);
+          continue;
+        }
+
+        let Some(columns) = &columns else {
+          continue;
+        };
+
+        let y = line[0].y;
+
+        let cells = Self::cells(columns, &line);
+
+        let starts_row = cells
+          .get(&ColumnKind::Course)
+          .is_some_and(|course| course_pattern.is_match(course));
+
+        if starts_row {
+          rows.extend(current.take().map(|(row, _)| row));
+          current = Some((
+            Row {
+              page,
+              cells,
+              unparsed: None,
+            },
+            y,
+          ));
+          continue;
+        }
+
+        match current.as_mut() {
+          Some((row, last_y))
+            if !cells.contains_key(&ColumnKind::Course)
+              && *last_y - y <= Self::MAX_CONTINUATION_GAP =>
+          {
+            row.append(cells);
+            *last_y = y;
+          }
+          _ => {
+            rows.extend(current.take().map(|(row, _)| row));
+
+            let unparsed = match cells.get(&ColumnKind::Course) {
+              Some(course) => Some(format!("Unrecognized course \"{course}\"")),
+              None if cells.len() > 1 => {
+                Some("Line neither starts nor continues a row".into())
+              }
+              None => None,
+            };
+
+            if unparsed.is_some() {
+              rows.push(Row {
+                page,
+                cells,
+                unparsed,
+              });
+            }
+          }
+        }
+      }
+
+      rows.extend(current.map(|(row, _)| row));
+    }
+
+    if columns.is_none() {
+      bail!("Failed to find a table header");
+    }
+
+    Ok(Self { rows })
+  }
+
+  /// Reads rows from each page's text lines in content order, for schedules
+  /// whose header can't be found.
+  ///
+  /// This is how schedules were read before their layout was used: a row is a
+  /// course code line, a section line, one or more title lines, then a
+  /// `FORMAT - TYPE - LOCATION` line followed by the start and end times.
+  pub(crate) fn from_lines(pages: &BTreeMap<u32, Vec<String>>) -> Result<Self> {
+    let course_pattern = Regex::new(r"^[A-Z0-9]{3,5}\s+[0-9]{3}[A-Z0-9]*$")?;
+
+    let exam_formats = ["IN-PERSON", "ONLINE"];
+
+    let lines = pages
+      .iter()
+      .flat_map(|(page, lines)| {
+        lines.iter().map(move |line| (*page, line.trim()))
+      })
+      .collect::<Vec<_>>();
+
+    let (mut rows, mut index) = (Vec::new(), 0);
+
+    while index < lines.len() {
+      let (page, course) = lines[index];
+
+      if !course_pattern.is_match(course) {
+        index += 1;
+        continue;
+      }
+
+      let mut cells =
+        BTreeMap::from([(ColumnKind::Course, course.to_string())]);
+
+      let Some(details) = (index + 2..lines.len()).find(|&i| {
+        exam_formats
+          .contains(&lines[i].1.split(" - ").next().unwrap_or_default())
+      }) else {
+        rows.push(Row {
+          page,
+          cells,
+          unparsed: Some("Missing exam details".into()),
+        });
+
+        break;
+      };
+
+      let title = lines[index + 2..details]
+        .iter()
+        .map(|(_, line)| *line)
+        .collect::<Vec<_>>()
+        .join(" ");
+
+      cells.insert(ColumnKind::Section, lines[index + 1].1.to_string());
+      cells.insert(ColumnKind::Title, title);
+      cells.insert(ColumnKind::Type, lines[details].1.to_string());
+
+      for (kind, offset) in [(ColumnKind::Start, 1), (ColumnKind::End, 2)] {
+        if let Some((_, line)) = lines.get(details + offset) {
+          cells.insert(kind, line.to_string());
+        }
+      }
+
+      rows.push(Row {
+        page,
+        cells,
+        unparsed: None,
+      });
+
+      index = details + 3;
+    }
+
+    Ok(Self { rows })
+  }
+
+  pub(crate) fn rows(&self) -> &[Row] {
+    &self.rows
+  }
+
+  fn lines(mut spans: Vec<&Span>) -> Vec<Vec<&Span>> {
+    spans.sort_by(|a, b| b.y.total_cmp(&a.y).then(a.x.total_cmp(&b.x)));
+
+    let mut lines = Vec::<Vec<&Span>>::new();
+
+    for span in spans {
+      match lines.last_mut() {
+        Some(line) if (line[0].y - span.y).abs() <= Self::LINE_TOLERANCE => {
+          line.push(span);
+        }
+        _ => lines.push(vec![span]),
+      }
+    }
+
+    for line in &mut lines {
+      line.sort_by(|a, b| a.x.total_cmp(&b.x));
+    }
+
+    lines
+  }
+
+  fn header(line: &[&Span]) -> Option<Vec<Column>> {
+    let columns = line
+      .iter()
+      .map(|span| {
+        ColumnKind::from_header(&span.text)
+          .map(|kind| Column { kind, x: span.x })
+      })
+      .collect::<Option<Vec<Column>>>()?;
+
+    (columns.len() >= 3
+      && columns
+        .iter()
+        .any(|column| column.kind == ColumnKind::Course))
+    .then_some(columns)
+  }
+
+  fn cells(columns: &[Column], line: &[&Span]) -> BTreeMap<ColumnKind, String> {
+    let mut cells = BTreeMap::<ColumnKind, String>::new();
+
+    for span in line {
+      let column = columns
+        .iter()
+        .filter(|column| column.x - Self::COLUMN_TOLERANCE <= span.x)
+        .max_by(|a, b| a.x.total_cmp(&b.x))
+        .unwrap_or(&columns[0]);
+
+      let cell = cells.entry(column.kind).or_default();
+
+      if !cell.is_empty() {
+        cell.push(' ');
+      }
+
+      cell.push_str(span.text.trim());
+    }
+
+    cells
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, pretty_assertions::assert_eq};
+
+  fn span(page: u32, x: f32, y: f32, text: &str) -> Span {
+    Span {
+      page,
+      x,
+      y,
+      text: text.into(),
+    }
+  }
+
+  fn row(page: u32, cells: &[(ColumnKind, &str)]) -> Row {
+    Row {
+      page,
+      cells: cells
+        .iter()
+        .map(|(kind, text)| (*kind, text.to_string()))
+        .collect(),
+      unparsed: None,
+    }
+  }
+
+  #[test]
+  fn rows_are_reconstructed_from_columns() {
+    let spans = vec![
+      span(1, 200.0, 770.0, "FINAL EXAMINATION SCHEDULE"),
+      span(1, 30.0, 740.0, "COURSE"),
+      span(1, 90.0, 740.0, "SECTION"),
+      span(1, 150.0, 740.0, "TITLE"),
+      span(1, 30.0, 720.5, "COMP 251"),
+      span(1, 150.0, 720.0, "Algorithms and Data"),
+      span(1, 90.0, 719.5, "001"),
+      span(1, 150.0, 710.0, "Structures"),
+      span(1, 30.0, 690.0, "COMP 250"),
+      span(1, 90.0, 690.0, "001"),
+      span(1, 150.0, 690.0, "Intro"),
+      span(1, 300.0, 40.0, "Page 1"),
+    ];
+
+    assert_eq!(
+      Table::from_spans(&spans).unwrap().rows(),
+      vec![
+        row(
+          1,
+          &[
+            (ColumnKind::Course, "COMP 251"),
+            (ColumnKind::Section, "001"),
+            (ColumnKind::Title, "Algorithms and Data Structures"),
+          ]
+        ),
+        row(
+          1,
+          &[
+            (ColumnKind::Course, "COMP 250"),
+            (ColumnKind::Section, "001"),
+            (ColumnKind::Title, "Intro"),
+          ]
+        ),
+      ]
+    );
+  }
+
+  #[test]
+  fn header_carries_over_pages() {
+    let spans = vec![
+      span(1, 30.0, 740.0, "COURSE"),
+      span(1, 90.0, 740.0, "SECTION"),
+      span(1, 150.0, 740.0, "TITLE"),
+      span(2, 30.0, 740.0, "COMP 251"),
+      span(2, 90.0, 740.0, "001"),
+    ];
+
+    assert_eq!(
+      Table::from_spans(&spans).unwrap().rows(),
+      vec![row(
+        2,
+        &[
+          (ColumnKind::Course, "COMP 251"),
+          (ColumnKind::Section, "001")
+        ]
+      )]
+    );
+  }
+
+  #[test]
+  fn stray_lines_are_kept_as_unparsed_rows() {
+    let spans = vec![
+      span(1, 30.0, 740.0, "COURSE"),
+      span(1, 90.0, 740.0, "SECTION"),
+      span(1, 150.0, 740.0, "TITLE"),
+      span(1, 30.0, 720.0, "COMP25l"),
+      span(1, 90.0, 720.0, "001"),
+      span(1, 150.0, 720.0, "Algorithms"),
+      span(1, 30.0, 700.0, "COMP 250"),
+      span(1, 90.0, 700.0, "001"),
+      span(1, 90.0, 600.0, "002"),
+      span(1, 150.0, 600.0, "Intro"),
+      span(1, 300.0, 40.0, "Page 1"),
+    ];
+
+    assert_eq!(
+      Table::from_spans(&spans).unwrap().rows(),
+      vec![
+        Row {
+          unparsed: Some("Unrecognized course \"COMP25l\"".into()),
+          ..row(
+            1,
+            &[
+              (ColumnKind::Course, "COMP25l"),
+              (ColumnKind::Section, "001"),
+              (ColumnKind::Title, "Algorithms"),
+            ]
+          )
+        },
+        row(
+          1,
+          &[
+            (ColumnKind::Course, "COMP 250"),
+            (ColumnKind::Section, "001")
+          ]
+        ),
+        Row {
+          unparsed: Some("Line neither starts nor continues a row".into()),
+          ..row(
+            1,
+            &[(ColumnKind::Section, "002"), (ColumnKind::Title, "Intro")]
+          )
+        },
+      ]
+    );
+  }
+
+  #[test]
+  fn rows_are_read_from_lines() {
+    let pages = BTreeMap::from([(
+      1,
+      [
+        "FINAL EXAMINATION SCHEDULE",
+        "COMP 251",
+        "001",
+        "Algorithms and Data",
+        "Structures",
+        "IN-PERSON - FORMAL EXAM - D.T. CAMPUS",
+        "12-Dec-2025 at 09:00 AM",
+        "12-Dec-2025 at 12:00 PM",
+        "MATH 240",
+        "001",
+      ]
+      .map(String::from)
+      .to_vec(),
+    )]);
+
+    assert_eq!(
+      Table::from_lines(&pages).unwrap().rows(),
+      vec![
+        row(
+          1,
+          &[
+            (ColumnKind::Course, "COMP 251"),
+            (ColumnKind::Section, "001"),
+            (ColumnKind::Title, "Algorithms and Data Structures"),
+            (ColumnKind::Type, "IN-PERSON - FORMAL EXAM - D.T. CAMPUS"),
+            (ColumnKind::Start, "12-Dec-2025 at 09:00 AM"),
+            (ColumnKind::End, "12-Dec-2025 at 12:00 PM"),
+          ]
+        ),
+        Row {
+          unparsed: Some("Missing exam details".into()),
+          ..row(1, &[(ColumnKind::Course, "MATH 240")])
+        },
+      ]
+    );
+  }
+
+  #[test]
+  fn missing_header_is_an_error() {
+    assert_eq!(
+      Table::from_spans(&[span(1, 30.0, 740.0, "COMP 251")])
+        .unwrap_err()
+        .to_string(),
+      "Failed to find a table header"
+    );
+  }
+}
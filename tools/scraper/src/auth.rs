@@ -13,7 +13,7 @@ impl Drop for Driver {
   }
 }
 
-pub(crate) fn authenticate() -> Result<String> {
+pub(crate) async fn authenticate() -> Result<String> {
   let email =
     env::var("VSB_EMAIL").expect("VSB_EMAIL must be specified for scraping");
 
@@ -31,15 +31,11 @@ pub(crate) fn authenticate() -> Result<String> {
       .spawn()?,
   );
 
-  thread::sleep(Duration::from_secs(2));
+  sleep(Duration::from_secs(2)).await;
 
   info!("Retrieving cookie for VSB authentication...");
 
-  let rt = tokio::runtime::Builder::new_current_thread()
-    .enable_all()
-    .build()?;
-
-  rt.block_on(get_vsb_cookie(email, password, otp_secret))
+  get_vsb_cookie(email, password, otp_secret).await
 }
 
 fn format_cookie(cookies: Vec<Cookie>) -> String {
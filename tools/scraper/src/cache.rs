@@ -0,0 +1,300 @@
+use super::*;
+
+#[derive(Debug, Deserialize, Serialize)]
+struct Entry {
+  url: String,
+  etag: Option<String>,
+  last_modified: Option<String>,
+  body: String,
+}
+
+/// An on-disk cache of response bodies keyed by URL.
+///
+/// Cached responses are revalidated with `If-None-Match` and
+/// `If-Modified-Since`, so unchanged pages cost a `304` instead of a full
+/// download.
+#[derive(Debug)]
+pub(crate) struct Cache {
+  dir: PathBuf,
+}
+
+impl Cache {
+  pub(crate) fn new(dir: PathBuf) -> Result<Self> {
+    fs::create_dir_all(&dir)?;
+    Ok(Self { dir })
+  }
+
+  /// Fetches `url`, revalidating any cached response for it.
+  ///
+  /// Returns `None` if the page doesn't exist.
+  pub(crate) async fn get(
+    &self,
+    client: &Client,
+    url: &str,
+    config: &RetryConfig,
+  ) -> Result<Option<String>> {
+    self.fetch(client, url, config, self.entry(url)).await
+  }
+
+  /// Fetches `url` unconditionally, replacing any cached response for it.
+  pub(crate) async fn refresh(
+    &self,
+    client: &Client,
+    url: &str,
+    config: &RetryConfig,
+  ) -> Result<Option<String>> {
+    self.fetch(client, url, config, None).await
+  }
+
+  async fn fetch(
+    &self,
+    client: &Client,
+    url: &str,
+    config: &RetryConfig,
+    cached: Option<Entry>,
+  ) -> Result<Option<String>> {
+    let mut request = client.get(url);
+
+    if let Some(entry) = &cached {
+      if let Some(etag) = &entry.etag {
+        request = request.header(IF_NONE_MATCH, etag);
+      }
+
+      if let Some(last_modified) = &entry.last_modified {
+        request = request.header(IF_MODIFIED_SINCE, last_modified);
+      }
+    }
+
+    let response = request.retry_with_config(config).await?;
+
+    match response.status() {
+      StatusCode::NOT_FOUND => {
+        let path = self.path(url);
+
+        if path.exists() {
+          fs::remove_file(path)?;
+        }
+
+        Ok(None)
+      }
+      StatusCode::NOT_MODIFIED => cached
+        .map(|entry| Some(entry.body))
+        .ok_or_else(|| anyhow!("Received 304 for uncached page {url}")),
+      _ => {
+        let header = |name| {
+          response
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(String::from)
+        };
+
+        let (etag, last_modified) = (header(ETAG), header(LAST_MODIFIED));
+
+        let entry = Entry {
+          url: url.to_string(),
+          etag,
+          last_modified,
+          body: response.text().await?,
+        };
+
+        fs::write(self.path(url), serde_json::to_string(&entry)?)?;
+
+        Ok(Some(entry.body))
+      }
+    }
+  }
+
+  fn entry(&self, url: &str) -> Option<Entry> {
+    serde_json::from_str::<Entry>(&fs::read_to_string(self.path(url)).ok()?)
+      .ok()
+      .filter(|entry| entry.url == url)
+  }
+
+  fn path(&self, url: &str) -> PathBuf {
+    self.dir.join(format!("{:x}.json", Sha256::digest(url)))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, mockito::Server, tempfile::TempDir};
+
+  #[tokio::test]
+  async fn responses_are_cached() {
+    let (mut server, tempdir) =
+      (Server::new_async().await, TempDir::new().unwrap());
+
+    let cache = Cache::new(tempdir.path().into()).unwrap();
+
+    let url = format!("{}/courses/comp-251/index.html", server.url());
+
+    let (client, config) = (
+      Client::new(),
+      RetryConfig {
+        max_retries: 0,
+        ..Default::default()
+      },
+    );
+
+    let first = server
+      .mock("GET", "/courses/comp-251/index.html")
+      .match_header("if-none-match", mockito::Matcher::Missing)
+      .with_status(200)
+      .with_header("etag", "\"v1\"")
+      .with_body("original")
+      .expect(1)
+      .create_async()
+      .await;
+
+    assert_eq!(
+      cache.get(&client, &url, &config).await.unwrap(),
+      Some("original".into())
+    );
+
+    first.assert_async().await;
+
+    let revalidated = server
+      .mock("GET", "/courses/comp-251/index.html")
+      .match_header("if-none-match", "\"v1\"")
+      .with_status(304)
+      .expect(1)
+      .create_async()
+      .await;
+
+    assert_eq!(
+      cache.get(&client, &url, &config).await.unwrap(),
+      Some("original".into())
+    );
+
+    revalidated.assert_async().await;
+  }
+
+  #[tokio::test]
+  async fn last_modified_is_revalidated() {
+    let (mut server, tempdir) =
+      (Server::new_async().await, TempDir::new().unwrap());
+
+    let cache = Cache::new(tempdir.path().into()).unwrap();
+
+    let url = format!("{}/courses", server.url());
+
+    let (client, config) = (
+      Client::new(),
+      RetryConfig {
+        max_retries: 0,
+        ..Default::default()
+      },
+    );
+
+    server
+      .mock("GET", "/courses")
+      .with_status(200)
+      .with_header("last-modified", "Wed, 21 Oct 2025 07:28:00 GMT")
+      .with_body("original")
+      .create_async()
+      .await;
+
+    cache.get(&client, &url, &config).await.unwrap();
+
+    let changed = server
+      .mock("GET", "/courses")
+      .match_header("if-modified-since", "Wed, 21 Oct 2025 07:28:00 GMT")
+      .with_status(200)
+      .with_body("changed")
+      .expect(1)
+      .create_async()
+      .await;
+
+    assert_eq!(
+      cache.get(&client, &url, &config).await.unwrap(),
+      Some("changed".into())
+    );
+
+    changed.assert_async().await;
+  }
+
+  #[tokio::test]
+  async fn refresh_skips_revalidation() {
+    let (mut server, tempdir) =
+      (Server::new_async().await, TempDir::new().unwrap());
+
+    let cache = Cache::new(tempdir.path().into()).unwrap();
+
+    let url = format!("{}/courses", server.url());
+
+    let (client, config) = (
+      Client::new(),
+      RetryConfig {
+        max_retries: 0,
+        ..Default::default()
+      },
+    );
+
+    server
+      .mock("GET", "/courses")
+      .with_status(200)
+      .with_header("etag", "\"v1\"")
+      .with_body("original")
+      .create_async()
+      .await;
+
+    cache.get(&client, &url, &config).await.unwrap();
+
+    let refreshed = server
+      .mock("GET", "/courses")
+      .match_header("if-none-match", mockito::Matcher::Missing)
+      .with_status(200)
+      .with_body("refreshed")
+      .expect(1)
+      .create_async()
+      .await;
+
+    assert_eq!(
+      cache.refresh(&client, &url, &config).await.unwrap(),
+      Some("refreshed".into())
+    );
+
+    refreshed.assert_async().await;
+  }
+
+  #[tokio::test]
+  async fn missing_pages_are_evicted() {
+    let (mut server, tempdir) =
+      (Server::new_async().await, TempDir::new().unwrap());
+
+    let cache = Cache::new(tempdir.path().into()).unwrap();
+
+    let url = format!("{}/courses/comp-999/index.html", server.url());
+
+    let (client, config) = (
+      Client::new(),
+      RetryConfig {
+        max_retries: 0,
+        ..Default::default()
+      },
+    );
+
+    server
+      .mock("GET", "/courses/comp-999/index.html")
+      .with_status(200)
+      .with_header("etag", "\"v1\"")
+      .with_body("withdrawn")
+      .create_async()
+      .await;
+
+    cache.get(&client, &url, &config).await.unwrap();
+
+    assert!(cache.path(&url).exists());
+
+    server
+      .mock("GET", "/courses/comp-999/index.html")
+      .with_status(404)
+      .create_async()
+      .await;
+
+    assert_eq!(cache.get(&client, &url, &config).await.unwrap(), None);
+
+    assert!(!cache.path(&url).exists());
+  }
+}
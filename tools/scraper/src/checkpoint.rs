@@ -0,0 +1,123 @@
+use super::*;
+
+/// Progress through a single term, saved after every batch so an
+/// interrupted run can pick up where it stopped.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub(crate) struct Checkpoint {
+  /// Course page URLs that have already been scraped.
+  pub(crate) completed: HashSet<String>,
+  /// Courses scraped so far.
+  pub(crate) courses: Vec<Course>,
+  #[serde(skip)]
+  path: PathBuf,
+}
+
+impl Checkpoint {
+  /// Starts a new checkpoint for the output file at `output`.
+  pub(crate) fn new(output: &Path) -> Self {
+    Self {
+      path: output.with_extension("checkpoint.json"),
+      ..Default::default()
+    }
+  }
+
+  /// Loads the checkpoint for the output file at `output`, or starts a new
+  /// one if there isn't any.
+  pub(crate) fn load(output: &Path) -> Result<Self> {
+    let checkpoint = Self::new(output);
+
+    if !checkpoint.path.exists() {
+      return Ok(checkpoint);
+    }
+
+    let loaded = serde_json::from_str::<Checkpoint>(&fs::read_to_string(
+      &checkpoint.path,
+    )?)?;
+
+    info!(
+      "Resuming from {} with {} pages already scraped...",
+      checkpoint.path.display(),
+      loaded.completed.len()
+    );
+
+    Ok(Self {
+      path: checkpoint.path,
+      ..loaded
+    })
+  }
+
+  pub(crate) fn record(&mut self, urls: &[String], courses: Vec<Course>) {
+    self.completed.extend(urls.iter().cloned());
+    self.courses.extend(courses);
+  }
+
+  /// Writes the checkpoint, replacing the previous one atomically so a
+  /// crash mid-write never leaves a truncated file behind.
+  pub(crate) fn save(&self) -> Result {
+    let temporary = self.path.with_extension("tmp");
+
+    fs::write(&temporary, serde_json::to_string(self)?)?;
+    fs::rename(temporary, &self.path)?;
+
+    Ok(())
+  }
+
+  pub(crate) fn remove(self) -> Result {
+    if self.path.exists() {
+      fs::remove_file(self.path)?;
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, tempfile::TempDir};
+
+  #[test]
+  fn round_trip() {
+    let tempdir = TempDir::new().unwrap();
+
+    let output = tempdir.path().join("courses-2025-2026.json");
+
+    let mut checkpoint = Checkpoint::load(&output).unwrap();
+
+    assert!(checkpoint.completed.is_empty());
+
+    checkpoint.record(
+      &["/courses/comp-251/index.html".into()],
+      vec![Course {
+        id: "COMP251".into(),
+        ..Default::default()
+      }],
+    );
+
+    checkpoint.save().unwrap();
+
+    assert!(
+      tempdir
+        .path()
+        .join("courses-2025-2026.checkpoint.json")
+        .exists()
+    );
+
+    let checkpoint = Checkpoint::load(&output).unwrap();
+
+    assert!(
+      checkpoint
+        .completed
+        .contains("/courses/comp-251/index.html")
+    );
+    assert_eq!(checkpoint.courses[0].id, "COMP251");
+
+    checkpoint.remove().unwrap();
+
+    assert!(
+      !tempdir
+        .path()
+        .join("courses-2025-2026.checkpoint.json")
+        .exists()
+    );
+  }
+}
@@ -74,12 +74,11 @@ pub fn extract_course_page(text: &str) -> Result<CoursePage> {
       Some((captures[1].to_string(), captures[2].to_string()))
     });
 
-  // TODO: Extract instructors when they're available.
-  //
-  // We currently rely on vsb to populate instructors in `Loader`.
-  //
-  // We'll also need to update the test samples in `/test-samples/`.
-  let instructors = Vec::new();
+  let instructors = element
+    .select_optional(".detail-instructors .value")?
+    .map(|elem| extract_instructors(&elem.text().collect::<String>(), &terms))
+    .transpose()?
+    .unwrap_or_default();
 
   Ok(CoursePage {
     title,
@@ -95,6 +94,63 @@ pub fn extract_course_page(text: &str) -> Result<CoursePage> {
   })
 }
 
+/// Parses a catalogue instructor listing such as
+/// `Elsaadawy, Mona; Errington, Jacob (Fall) Alberini, Giulia (Winter)`.
+///
+/// Seasons are qualified with their year from the terms the course is
+/// offered in when possible, and left bare otherwise. Instructors listed
+/// without a season are taken to teach every offered term.
+fn extract_instructors(
+  text: &str,
+  terms: &[String],
+) -> Result<Vec<Instructor>> {
+  let pattern = Regex::new(r"([^()]*)\((Fall|Winter|Summer)\)")?;
+
+  let names = |names: &str| {
+    names
+      .split(';')
+      .map(InstructorProfile::normalize_name)
+      .filter(|name| !name.is_empty())
+      .collect::<Vec<_>>()
+  };
+
+  let (mut instructors, mut end) = (Vec::new(), 0);
+
+  for captures in pattern.captures_iter(text) {
+    let season = &captures[2];
+
+    let term = terms
+      .iter()
+      .find(|term| term.starts_with(season))
+      .cloned()
+      .unwrap_or_else(|| season.to_string());
+
+    for name in names(&captures[1]) {
+      instructors.push(Instructor {
+        name,
+        term: term.clone(),
+        ..Default::default()
+      });
+    }
+
+    end = captures.get(0).map_or(end, |m| m.end());
+  }
+
+  for name in names(&text[end..]) {
+    for term in terms {
+      instructors.push(Instructor {
+        name: name.clone(),
+        term: term.clone(),
+        ..Default::default()
+      });
+    }
+  }
+
+  utils::dedup(&mut instructors);
+
+  Ok(instructors)
+}
+
 fn extract_course_requirements(element: &ElementRef) -> Result<Requirements> {
   let notes = element.select_optional("div.detail-note_text ul")?;
 
@@ -119,14 +175,39 @@ fn extract_course_requirements(element: &ElementRef) -> Result<Requirements> {
               requirements.set_prerequisites_text(Some(paragraph.inner_html()));
               requirements
                 .set_prerequisites(utils::get_course_codes(&paragraph)?);
+
+              let (tree, confidence) =
+                requirement_parser::parse_requirement(&paragraph.inner_html())?
+                  .unzip();
+
+              requirements.set_logical_prerequisites(tree);
+              requirements.set_prerequisites_confidence(confidence);
             }
             Requirement::Corequisites => {
               requirements.set_corequisites_text(Some(paragraph.inner_html()));
               requirements
                 .set_corequisites(utils::get_course_codes(&paragraph)?);
+
+              let (tree, confidence) =
+                requirement_parser::parse_requirement(&paragraph.inner_html())?
+                  .unzip();
+
+              requirements.set_logical_corequisites(tree);
+              requirements.set_corequisites_confidence(confidence);
             }
             Requirement::Restrictions => {
-              requirements.set_restrictions(utils::get_text(&paragraph));
+              let text = utils::get_text(&paragraph);
+
+              let Restrictions {
+                excluded_courses,
+                cross_listings,
+                restricted_to,
+              } = Restrictions::parse(&text)?;
+
+              requirements.add_excluded_courses(excluded_courses);
+              requirements.add_cross_listings(cross_listings);
+              requirements.add_restricted_to(restricted_to);
+              requirements.set_restrictions(text);
             }
             Requirement::Unknown => {
               return Err(anyhow!("Unknown requirement type"));
@@ -247,13 +328,82 @@ mod tests {
         requirements: Requirements {
           prerequisites_text: Some("Prerequisites: COMP 250; MATH 235 or MATH 240".into()),
           prerequisites: vec!["COMP250".into(), "MATH235".into(), "MATH240".into()],
+          logical_prerequisites: Some(ReqNode::Group {
+            operator: Operator::And,
+            groups: vec![
+              ReqNode::Course("COMP250".into()),
+              ReqNode::Group {
+                operator: Operator::Or,
+                groups: vec![
+                  ReqNode::Course("MATH235".into()),
+                  ReqNode::Course("MATH240".into()),
+                ],
+              },
+            ],
+          }),
+          prerequisites_confidence: Some(Confidence::High),
           restrictions: Some("Not open to students who have taken or are taking: COMP 252 or COMP 260.".into()),
+          excluded_courses: vec!["COMP252".into(), "COMP260".into()],
           ..Requirements::default()
         }
       }
     );
   }
 
+  #[test]
+  fn extract_catalog_course_page_2025_2026_with_instructors() {
+    let instructor = |name: &str, term: &str| Instructor {
+      name: name.into(),
+      term: term.into(),
+      ..Default::default()
+    };
+
+    let page = extract_course_page(&get_content(
+      "catalog_course_page_2025_2026_with_instructors.html",
+    ))
+    .unwrap();
+
+    assert_eq!(page.terms, vec!["Fall 2025", "Winter 2026"]);
+
+    assert_eq!(
+      page.instructors,
+      vec![
+        instructor("Mona Elsaadawy", "Fall 2025"),
+        instructor("Jacob Errington", "Fall 2025"),
+        instructor("Giulia Alberini", "Winter 2026"),
+        instructor("David Becerra", "Winter 2026"),
+      ]
+    );
+  }
+
+  #[test]
+  fn extract_instructors_without_seasons() {
+    let terms = vec!["Fall 2025".to_string(), "Winter 2026".to_string()];
+
+    assert_eq!(
+      extract_instructors("Vybihal, Joseph P", &terms)
+        .unwrap()
+        .into_iter()
+        .map(|instructor| (instructor.name, instructor.term))
+        .collect::<Vec<_>>(),
+      vec![
+        ("Joseph P Vybihal".to_string(), "Fall 2025".to_string()),
+        ("Joseph P Vybihal".to_string(), "Winter 2026".to_string()),
+      ]
+    );
+
+    assert_eq!(
+      extract_instructors("Vybihal, Joseph P (Summer)", &[])
+        .unwrap()
+        .into_iter()
+        .map(|instructor| (instructor.name, instructor.term))
+        .collect::<Vec<_>>(),
+      vec![("Joseph P Vybihal".to_string(), "Summer".to_string())]
+    );
+
+    assert!(extract_instructors("TBA", &[]).unwrap().is_empty());
+  }
+
   #[test]
   fn extract_catalog_course_page_2025_2026_with_terms() {
     assert_eq!(
@@ -5,21 +5,60 @@ pub(crate) struct Loader {
   #[clap(
     long,
     default_value = "20",
-    help = "Number of pages to scrape per concurrent batch"
+    help = "Number of pages to scrape between progress checkpoints"
   )]
   batch_size: usize,
   #[clap(
     long,
-    default_value = "0",
-    help = "Time delay between course requests in milliseconds"
+    default_value = "5",
+    help = "Number of requests allowed to each host in a burst before throttling"
   )]
-  course_delay: u64,
+  burst: u32,
+  #[clap(
+    long,
+    help = "Directory to cache catalogue responses in, revalidated on later runs"
+  )]
+  cache_dir: Option<PathBuf>,
+  #[clap(
+    long,
+    default_value = "8",
+    help = "Maximum number of course pages to scrape at once"
+  )]
+  concurrency: usize,
+  #[clap(
+    long,
+    help = "Read catalogue and VSB pages from a local mirror directory instead of the network"
+  )]
+  from_dir: Option<PathBuf>,
   #[clap(
     long,
     default_values = ["2025-2026",],
     help = "The mcgill terms to scrape"
   )]
   mcgill_terms: Vec<String>,
+  #[clap(
+    long,
+    default_value = "false",
+    help = "Scrape visual schedule builder information without authenticating"
+  )]
+  no_vsb_auth: bool,
+  #[clap(
+    long,
+    help = "Only scrape a subset of the catalogue (e.g. `subjects=COMP,MATH`)"
+  )]
+  only: Option<Only>,
+  #[clap(
+    long,
+    default_value = "false",
+    help = "Ignore progress checkpoints left by previous runs"
+  )]
+  restart: bool,
+  #[clap(
+    long,
+    default_value = "5",
+    help = "Maximum requests per second to each host, or 0 for no limit"
+  )]
+  requests_per_second: f64,
   #[clap(long, default_value = "10", help = "Number of retries")]
   retries: usize,
   #[clap(
@@ -30,8 +69,8 @@ pub(crate) struct Loader {
   scrape_vsb: bool,
   #[clap(long, default_value = "courses.json")]
   source: PathBuf,
-  #[clap(long, help = "A user agent")]
-  user_agent: String,
+  #[clap(long, required_unless_present = "from_dir", help = "A user agent")]
+  user_agent: Option<String>,
   #[clap(
     long,
     default_values = ["202505", "202509", "202601"],
@@ -41,34 +80,93 @@ pub(crate) struct Loader {
 }
 
 impl Loader {
-  const BASE_URL: &str = "https://coursecatalogue.mcgill.ca";
+  pub(crate) const BASE_URL: &str = "https://coursecatalogue.mcgill.ca";
+
+  /// Scrapes from the local mirror if one was given, and from the network
+  /// otherwise.
+  pub(crate) async fn load(&self) -> Result {
+    match &self.from_dir {
+      Some(dir) => {
+        info!("Reading pages from {}...", dir.display());
+        self.run(&Mirror::new(dir.clone())?).await
+      }
+      None => self.run(&self.remote().await?).await,
+    }
+  }
+
+  /// Builds the network source, authenticating with VSB first if its
+  /// schedules will be scraped.
+  async fn remote(&self) -> Result<Remote> {
+    let cookie = if self.scrape_vsb && !self.no_vsb_auth {
+      Some(auth::authenticate().await?)
+    } else {
+      None
+    };
+
+    let client = Client::builder()
+      .user_agent(self.user_agent.as_deref().unwrap_or_default())
+      .pool_max_idle_per_host(self.concurrency)
+      .build()?;
+
+    Ok(Remote::new(
+      client,
+      cookie,
+      RetryConfig {
+        max_retries: self.retries,
+        rate_limiter: Some(Arc::new(RateLimiter::new(
+          self.requests_per_second,
+          self.burst,
+        ))),
+        ..Default::default()
+      },
+      self.cache_dir.clone().map(Cache::new).transpose()?,
+    ))
+  }
 
-  pub(crate) fn run(&self, cookie: &str) -> Result<()> {
+  pub(crate) async fn run(&self, source: &impl Source) -> Result {
     info!("Running extractor...");
 
     for (index, term) in self.mcgill_terms.iter().enumerate() {
       let scrape_vsb = self.scrape_vsb && index == self.mcgill_terms.len() - 1;
 
-      let urls = self.get_course_urls()?;
+      let path = if self.source.is_dir() {
+        self.source.join(format!("courses-{term}.json"))
+      } else {
+        self.source.clone()
+      };
+
+      let mut checkpoint = if self.restart {
+        Checkpoint::new(&path)
+      } else {
+        Checkpoint::load(&path)?
+      };
 
-      let mut courses = Vec::new();
+      let urls = source
+        .course_urls()
+        .await?
+        .into_iter()
+        .filter(|url| {
+          self.only.as_ref().is_none_or(|only| only.matches_url(url))
+            && !checkpoint.completed.contains(url)
+        })
+        .collect::<Vec<String>>();
 
       for chunk in urls.chunks(self.batch_size) {
-        let chunk = chunk
-          .par_iter()
-          .map(|url| {
-            self.parse_course(
-              &format!("{}{}", Self::BASE_URL, url),
-              cookie,
-              scrape_vsb,
-            )
+        let courses = stream::iter(chunk)
+          .map(|url| async move {
+            self
+              .parse_course(source, &Self::absolute_url(url), term, scrape_vsb)
+              .await
           })
-          .collect::<Result<Vec<Option<Course>>, _>>()?;
+          .buffered(self.concurrency.max(1))
+          .try_collect::<Vec<Option<Course>>>()
+          .await?;
 
-        courses.extend(chunk.into_iter().flatten());
+        checkpoint.record(chunk, courses.into_iter().flatten().collect());
+        checkpoint.save()?;
       }
 
-      let mut courses = courses
+      let mut courses = mem::take(&mut checkpoint.courses)
         .into_iter()
         .collect::<HashSet<Course>>()
         .into_iter()
@@ -77,17 +175,11 @@ impl Loader {
 
       courses.sort();
 
-      let source = if self.source.is_dir() {
-        self.source.join(format!("courses-{term}.json"))
-      } else {
-        self.source.clone()
-      };
-
-      if source.exists() {
+      if path.exists() {
         info!("Merging with existing courses...");
 
         let sourced =
-          serde_json::from_str::<Vec<Course>>(&fs::read_to_string(&source)?)?;
+          serde_json::from_str::<Vec<Course>>(&fs::read_to_string(&path)?)?;
 
         let mut merged = courses
           .iter()
@@ -100,15 +192,24 @@ impl Loader {
           })
           .collect::<Vec<Course>>();
 
+        // Courses outside the selection weren't scraped, so keep them as is.
+        if let Some(only) = &self.only {
+          merged
+            .extend(sourced.into_iter().filter(|course| !only.matches(course)));
+          merged.sort();
+        }
+
         let courses = &self.post_process(&mut merged)?;
 
-        fs::write(&source, serde_json::to_string_pretty(&courses)?)?;
+        fs::write(&path, serde_json::to_string_pretty(&courses)?)?;
       } else {
         fs::write(
-          &source,
+          &path,
           serde_json::to_string_pretty(&self.post_process(&mut courses)?)?,
         )?;
       }
+
+      checkpoint.remove()?;
     }
 
     Ok(())
@@ -138,57 +239,46 @@ impl Loader {
       courses[i].leading_to = leading_to;
     }
 
+    let low_confidence = courses
+      .iter()
+      .filter(|course| {
+        course.prerequisites_confidence == Some(Confidence::Low)
+          || course.corequisites_confidence == Some(Confidence::Low)
+      })
+      .map(|course| course.id.as_str())
+      .collect::<Vec<_>>();
+
+    if !low_confidence.is_empty() {
+      warn!(
+        "Parsed requirements with low confidence for {} courses: {}",
+        low_confidence.len(),
+        low_confidence.join(", ")
+      );
+    }
+
     Ok(courses.to_vec())
   }
 
-  fn get_course_urls(&self) -> Result<Vec<String>> {
-    let client = Client::builder().user_agent(&self.user_agent).build()?;
-
-    let page = client
-      .get(format!("{}/courses", Self::BASE_URL))
-      .retry(self.retries)?
-      .text()?;
-
-    course_extractor::extract_course_urls(&page)
+  fn absolute_url(url: &str) -> String {
+    if url.starts_with("http") {
+      url.to_string()
+    } else {
+      format!("{}{}", Self::BASE_URL, url)
+    }
   }
 
-  fn parse_course(
+  async fn parse_course(
     &self,
+    source: &impl Source,
     url: &str,
-    cookie: &str,
+    term: &str,
     scrape_vsb: bool,
   ) -> Result<Option<Course>> {
     info!("{url}");
 
-    let client = Client::builder().user_agent(&self.user_agent).build()?;
-
-    let course_page = {
-      let response = client.get(url).retry(self.retries)?;
-
-      if response.status() == reqwest::StatusCode::NOT_FOUND {
-        info!("Page for {url} not found, skipping...");
-        return Ok(None);
-      }
-
-      let mut course_page =
-        course_extractor::extract_course_page(&response.text()?);
-
-      while course_page.is_err() {
-        warn!("Retrying course page: {url}");
-
-        thread::sleep(Duration::from_millis(500));
-
-        let response = client.get(url).retry(self.retries)?;
-
-        if response.status() == reqwest::StatusCode::NOT_FOUND {
-          info!("Page for {url} not found, skipping...");
-          return Ok(None);
-        };
-
-        course_page = course_extractor::extract_course_page(&response.text()?);
-      }
-
-      course_page?
+    let Some(course_page) = source.course_page(url).await? else {
+      info!("Page for {url} not found, skipping...");
+      return Ok(None);
     };
 
     info!(
@@ -196,21 +286,21 @@ impl Loader {
       &course_page.subject, &course_page.code
     );
 
-    thread::sleep(Duration::from_millis(self.course_delay));
-
     let schedule = if scrape_vsb {
       Some(
-        VsbClient::new(&self.user_agent, cookie, self.retries)?.schedule(
-          &format!("{}-{}", course_page.subject, course_page.code),
-          self.vsb_terms.clone(),
-        )?,
+        source
+          .schedules(
+            &format!("{}-{}", course_page.subject, course_page.code),
+            &self.vsb_terms,
+          )
+          .await?,
       )
     } else {
       None
     };
 
-    // Temporarily using VSB information to get term/instructor info
-    // since course catalogue doesn't have it...
+    // VSB knows exactly which terms have sections and who teaches them, so
+    // it takes precedence over the catalogue when we have it.
     let schedule_info = schedule.clone().map(|schedules| {
       let mut terms = schedules
         .iter()
@@ -240,8 +330,19 @@ impl Loader {
       (terms, instructors)
     });
 
+    let catalogue_instructors = course_page
+      .instructors
+      .iter()
+      .map(|instructor| Instructor {
+        term: utils::resolve_term(&instructor.term, term),
+        ..instructor.clone()
+      })
+      .collect::<Vec<_>>();
+
+    let id = format!("{}{}", course_page.subject, course_page.code);
+
     Ok(Some(Course {
-      id: format!("{}{}", course_page.subject, course_page.code),
+      id: id.clone(),
       id_ngrams: None,
       title: course_page.title.clone(),
       title_ngrams: None,
@@ -256,20 +357,355 @@ impl Loader {
         .map(|s| s.0.clone())
         .unwrap_or(course_page.terms),
       description: course_page.description,
-      instructors: schedule_info
-        .as_ref()
-        .map(|s| s.1.clone())
-        .unwrap_or(course_page.instructors),
+      instructors: utils::reconcile_instructors(
+        schedule_info
+          .as_ref()
+          .map(|s| s.1.clone())
+          .unwrap_or_default(),
+        catalogue_instructors,
+      ),
       prerequisites_text: course_page.requirements.prerequisites_text,
       corequisites_text: course_page.requirements.corequisites_text,
       prerequisites: course_page.requirements.prerequisites,
       corequisites: course_page.requirements.corequisites,
       leading_to: Vec::new(),
       restrictions: course_page.requirements.restrictions,
+      excluded_courses: course_page
+        .requirements
+        .excluded_courses
+        .into_iter()
+        .filter(|excluded| *excluded != id)
+        .collect(),
+      cross_listings: course_page
+        .requirements
+        .cross_listings
+        .into_iter()
+        .filter(|listing| *listing != id)
+        .collect(),
+      restricted_to: course_page.requirements.restricted_to,
       logical_prerequisites: course_page.requirements.logical_prerequisites,
       logical_corequisites: course_page.requirements.logical_corequisites,
+      prerequisites_confidence: course_page
+        .requirements
+        .prerequisites_confidence,
+      corequisites_confidence: course_page.requirements.corequisites_confidence,
       schedule,
       ..Default::default()
     }))
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*, pretty_assertions::assert_eq, std::path::Path, tempfile::TempDir,
+  };
+
+  fn mirror() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test-samples/mirror")
+  }
+
+  fn loader(output: &Path, args: &[&str]) -> Loader {
+    Loader::try_parse_from(
+      [
+        "scraper",
+        "--from-dir",
+        mirror().to_str().unwrap(),
+        "--source",
+        output.to_str().unwrap(),
+        "--vsb-terms",
+        "202509",
+      ]
+      .into_iter()
+      .chain(args.iter().copied()),
+    )
+    .unwrap()
+  }
+
+  async fn run(loader: &Loader) -> Vec<Course> {
+    loader.load().await.unwrap();
+
+    serde_json::from_str(&fs::read_to_string(&loader.source).unwrap()).unwrap()
+  }
+
+  #[test]
+  fn user_agent_is_only_required_online() {
+    assert!(Loader::try_parse_from(["scraper"]).is_err());
+    assert!(
+      Loader::try_parse_from(["scraper", "--user-agent", "test"]).is_ok()
+    );
+    assert!(
+      Loader::try_parse_from(["scraper", "--from-dir", "mirror"]).is_ok()
+    );
+  }
+
+  #[tokio::test]
+  async fn run_from_mirror() {
+    let tempdir = TempDir::new().unwrap();
+
+    let courses = run(&loader(&tempdir.path().join("courses.json"), &[])).await;
+
+    assert_eq!(
+      courses
+        .iter()
+        .map(|course| course.id.as_str())
+        .collect::<Vec<_>>(),
+      vec!["COMP250", "COMP251"]
+    );
+
+    let (comp250, comp251) = (&courses[0], &courses[1]);
+
+    assert_eq!(comp250.title, "Introduction to Computer Science");
+    assert_eq!(
+      comp250.url,
+      "https://coursecatalogue.mcgill.ca/courses/comp-250/index.html"
+    );
+    assert_eq!(comp250.terms, vec!["Fall 2025", "Winter 2026"]);
+    assert_eq!(comp250.leading_to, vec!["COMP251"]);
+    assert_eq!(comp250.schedule, None);
+
+    assert_eq!(comp251.title, "Algorithms and Data Structures");
+    assert_eq!(comp251.prerequisites, vec!["COMP250", "MATH235", "MATH240"]);
+    assert!(comp251.leading_to.is_empty());
+
+    assert_eq!(
+      comp251
+        .instructors
+        .iter()
+        .map(|instructor| (instructor.name.as_str(), instructor.term.as_str()))
+        .collect::<Vec<_>>(),
+      vec![
+        ("Mona Elsaadawy", "Fall 2025"),
+        ("Giulia Alberini", "Winter 2026")
+      ]
+    );
+  }
+
+  #[tokio::test]
+  async fn run_from_mirror_with_vsb() {
+    let tempdir = TempDir::new().unwrap();
+
+    let courses = run(&loader(
+      &tempdir.path().join("courses.json"),
+      &["--scrape-vsb"],
+    ))
+    .await;
+
+    let (comp250, comp251) = (&courses[0], &courses[1]);
+
+    assert_eq!(comp250.schedule, Some(Vec::new()));
+    assert!(comp250.terms.is_empty());
+
+    assert_eq!(comp251.terms, vec!["Fall 2025"]);
+
+    assert_eq!(
+      comp251
+        .instructors
+        .iter()
+        .map(|instructor| (instructor.name.as_str(), instructor.term.as_str()))
+        .collect::<Vec<_>>(),
+      vec![
+        ("Mona Elsaadawy", "Fall 2025"),
+        ("Jacob Errington", "Fall 2025"),
+        ("Giulia Alberini", "Winter 2026")
+      ]
+    );
+
+    let blocks = comp251.schedule.as_ref().unwrap()[0]
+      .blocks
+      .as_ref()
+      .unwrap();
+
+    assert_eq!(blocks[0].display.as_deref(), Some("Lec 001"));
+    assert_eq!(blocks[0].location.as_deref(), Some("LEA 132"));
+  }
+
+  #[tokio::test]
+  async fn run_from_mirror_merges_existing_courses() {
+    let tempdir = TempDir::new().unwrap();
+
+    let output = tempdir.path().join("courses.json");
+
+    let logical_prerequisites = ReqNode::Course("COMP250".into());
+
+    fs::write(
+      &output,
+      serde_json::to_string(&vec![Course {
+        id: "COMP251".into(),
+        title: "Outdated Title".into(),
+        prerequisites_text: Some(
+          "Prerequisites: COMP 250; MATH 235 or MATH 240".into(),
+        ),
+        logical_prerequisites: Some(logical_prerequisites.clone()),
+        ..Default::default()
+      }])
+      .unwrap(),
+    )
+    .unwrap();
+
+    let courses = run(&loader(&output, &[])).await;
+
+    assert_eq!(courses.len(), 2);
+
+    let comp251 = &courses[1];
+
+    assert_eq!(comp251.title, "Algorithms and Data Structures");
+    assert_eq!(comp251.logical_prerequisites, Some(logical_prerequisites));
+    assert_eq!(comp251.prerequisites_confidence, None);
+    assert_eq!(courses[0].leading_to, vec!["COMP251"]);
+  }
+
+  #[tokio::test]
+  async fn run_from_mirror_reparses_changed_requirements() {
+    let tempdir = TempDir::new().unwrap();
+
+    let output = tempdir.path().join("courses.json");
+
+    fs::write(
+      &output,
+      serde_json::to_string(&vec![Course {
+        id: "COMP251".into(),
+        prerequisites_text: Some("Prerequisite: COMP 250".into()),
+        logical_prerequisites: Some(ReqNode::Course("COMP250".into())),
+        ..Default::default()
+      }])
+      .unwrap(),
+    )
+    .unwrap();
+
+    let courses = run(&loader(&output, &[])).await;
+
+    let comp251 = &courses[1];
+
+    assert_eq!(
+      comp251.logical_prerequisites,
+      Some(ReqNode::Group {
+        operator: Operator::And,
+        groups: vec![
+          ReqNode::Course("COMP250".into()),
+          ReqNode::Group {
+            operator: Operator::Or,
+            groups: vec![
+              ReqNode::Course("MATH235".into()),
+              ReqNode::Course("MATH240".into()),
+            ],
+          },
+        ],
+      })
+    );
+
+    assert_eq!(comp251.prerequisites_confidence, Some(Confidence::High));
+  }
+
+  #[tokio::test]
+  async fn run_from_mirror_writes_per_term_files() {
+    let tempdir = TempDir::new().unwrap();
+
+    let loader = loader(
+      tempdir.path(),
+      &["--mcgill-terms", "2024-2025", "--mcgill-terms", "2025-2026"],
+    );
+
+    loader.load().await.unwrap();
+
+    for term in ["2024-2025", "2025-2026"] {
+      assert!(tempdir.path().join(format!("courses-{term}.json")).exists());
+    }
+  }
+
+  fn write_checkpoint(output: &Path) {
+    fs::write(
+      output.with_extension("checkpoint.json"),
+      serde_json::to_string(&serde_json::json!({
+        "completed": ["/courses/comp-250/index.html"],
+        "courses": [Course {
+          id: "COMP250".into(),
+          title: "From Checkpoint".into(),
+          subject: "COMP".into(),
+          ..Default::default()
+        }],
+      }))
+      .unwrap(),
+    )
+    .unwrap();
+  }
+
+  #[tokio::test]
+  async fn run_resumes_from_checkpoint() {
+    let tempdir = TempDir::new().unwrap();
+
+    let output = tempdir.path().join("courses.json");
+
+    write_checkpoint(&output);
+
+    let courses = run(&loader(&output, &[])).await;
+
+    assert_eq!(courses[0].title, "From Checkpoint");
+    assert_eq!(courses[1].title, "Algorithms and Data Structures");
+    assert_eq!(courses[0].leading_to, vec!["COMP251"]);
+
+    assert!(!output.with_extension("checkpoint.json").exists());
+  }
+
+  #[tokio::test]
+  async fn restart_ignores_checkpoint() {
+    let tempdir = TempDir::new().unwrap();
+
+    let output = tempdir.path().join("courses.json");
+
+    write_checkpoint(&output);
+
+    let courses = run(&loader(&output, &["--restart"])).await;
+
+    assert_eq!(courses[0].title, "Introduction to Computer Science");
+    assert!(!output.with_extension("checkpoint.json").exists());
+  }
+
+  #[tokio::test]
+  async fn only_refreshes_selected_subjects() {
+    let tempdir = TempDir::new().unwrap();
+
+    let output = tempdir.path().join("courses.json");
+
+    let course = |id: &str, subject: &str, title: &str| Course {
+      id: id.into(),
+      subject: subject.into(),
+      title: title.into(),
+      ..Default::default()
+    };
+
+    fs::write(
+      &output,
+      serde_json::to_string(&vec![
+        course("COMP250", "COMP", "Outdated Title"),
+        course("MATH240", "MATH", "Discrete Structures"),
+      ])
+      .unwrap(),
+    )
+    .unwrap();
+
+    let courses = run(&loader(&output, &["--only", "subjects=COMP"])).await;
+
+    assert_eq!(
+      courses
+        .iter()
+        .map(|course| (course.id.as_str(), course.title.as_str()))
+        .collect::<Vec<_>>(),
+      vec![
+        ("COMP250", "Introduction to Computer Science"),
+        ("COMP251", "Algorithms and Data Structures"),
+        ("MATH240", "Discrete Structures"),
+      ]
+    );
+
+    let courses = run(&loader(&output, &["--only", "subjects=MATH"])).await;
+
+    assert_eq!(
+      courses
+        .iter()
+        .map(|course| course.id.as_str())
+        .collect::<Vec<_>>(),
+      vec!["COMP250", "COMP251"]
+    );
+  }
+}
@@ -1,27 +1,47 @@
 use {
-  crate::{loader::Loader, select::Select, vsb_client::VsbClient},
+  crate::{
+    cache::Cache,
+    checkpoint::Checkpoint,
+    loader::Loader,
+    only::Only,
+    rate_limiter::RateLimiter,
+    restrictions::Restrictions,
+    retry::{Retry, RetryConfig},
+    select::Select,
+    source::{Mirror, Remote, Source},
+    vsb_client::VsbClient,
+  },
   anyhow::{Error, anyhow, bail},
-  chrono::Utc,
+  chrono::{DateTime, Utc},
   clap::Parser,
+  futures::{StreamExt, TryStreamExt, stream},
   model::{
-    Block, Course, CoursePage, Instructor, Requirement, Requirements, Schedule,
+    Block, Confidence, Course, CoursePage, Instructor, InstructorProfile,
+    Operator, ReqNode, Requirement, Requirements, RestrictedTo, Schedule,
     TimeBlock,
   },
-  rayon::iter::{IntoParallelRefIterator, ParallelIterator},
   regex::Regex,
-  reqwest::StatusCode,
-  reqwest::blocking::{Client, RequestBuilder},
-  retry::Retry,
+  reqwest::{
+    Client, RequestBuilder, Response, StatusCode,
+    header::{
+      COOKIE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED,
+      RETRY_AFTER,
+    },
+  },
   scraper::{ElementRef, Html, Selector},
+  serde::{Deserialize, Serialize},
+  sha2::{Digest, Sha256},
   std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     env, fs,
     hash::Hash,
-    path::PathBuf,
+    mem,
+    path::{Path, PathBuf},
     process,
     process::{Child, Command},
-    thread,
-    time::Duration,
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
   },
   thirtyfour::prelude::*,
   tokio::time::sleep,
@@ -31,17 +51,24 @@ use {
 };
 
 mod auth;
+mod cache;
+mod checkpoint;
 mod course_extractor;
 mod loader;
+mod only;
+mod rate_limiter;
+mod requirement_parser;
+mod restrictions;
 mod retry;
 mod select;
+mod source;
 mod utils;
 mod vsb_client;
 mod vsb_extractor;
 
 type Result<T = (), E = Error> = std::result::Result<T, E>;
 
-fn run() -> Result {
+async fn run() -> Result {
   tracing_subscriber::registry()
     .with(
       tracing_subscriber::EnvFilter::try_from_default_env()
@@ -50,11 +77,12 @@ fn run() -> Result {
     .with(tracing_subscriber::fmt::layer())
     .init();
 
-  Loader::parse().run(&auth::authenticate()?)
+  Loader::parse().load().await
 }
 
-fn main() {
-  if let Err(error) = run() {
+#[tokio::main]
+async fn main() {
+  if let Err(error) = run().await {
     eprintln!("error: {error}");
     process::exit(1);
   }
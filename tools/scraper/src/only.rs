@@ -0,0 +1,85 @@
+use super::*;
+
+/// Restricts scraping to a subset of the catalogue, e.g. `subjects=COMP,MATH`.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Only {
+  Subjects(Vec<String>),
+}
+
+impl FromStr for Only {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self> {
+    let (key, values) = s.split_once('=').ok_or_else(|| {
+      anyhow!("Expected a selector like `subjects=COMP,MATH`")
+    })?;
+
+    let values = values
+      .split(',')
+      .map(|value| value.trim().to_uppercase())
+      .filter(|value| !value.is_empty())
+      .collect::<Vec<_>>();
+
+    if values.is_empty() {
+      bail!("Selector `{key}` needs at least one value");
+    }
+
+    match key.trim() {
+      "subjects" | "subject" => Ok(Self::Subjects(values)),
+      key => bail!("Unknown selector `{key}`"),
+    }
+  }
+}
+
+impl Only {
+  /// Whether the course page at `url` (e.g. `/courses/comp-251/index.html`)
+  /// is selected.
+  pub(crate) fn matches_url(&self, url: &str) -> bool {
+    let subject = url
+      .split('/')
+      .rev()
+      .find_map(|segment| segment.split_once('-'))
+      .map(|(subject, _)| subject.to_uppercase());
+
+    match self {
+      Self::Subjects(subjects) => {
+        subject.is_some_and(|subject| subjects.contains(&subject))
+      }
+    }
+  }
+
+  pub(crate) fn matches(&self, course: &Course) -> bool {
+    match self {
+      Self::Subjects(subjects) => subjects.contains(&course.subject),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parse() {
+    assert_eq!(
+      "subjects=COMP,math".parse::<Only>().unwrap(),
+      Only::Subjects(vec!["COMP".into(), "MATH".into()])
+    );
+
+    assert!("subjects=".parse::<Only>().is_err());
+    assert!("faculties=Science".parse::<Only>().is_err());
+    assert!("COMP".parse::<Only>().is_err());
+  }
+
+  #[test]
+  fn matches_url() {
+    let only = Only::Subjects(vec!["COMP".into()]);
+
+    assert!(only.matches_url("/courses/comp-251/index.html"));
+    assert!(only.matches_url(
+      "https://coursecatalogue.mcgill.ca/courses/comp-251/index.html"
+    ));
+    assert!(!only.matches_url("/courses/math-240/index.html"));
+    assert!(!only.matches_url("/courses"));
+  }
+}
@@ -0,0 +1,121 @@
+use super::*;
+
+#[derive(Debug)]
+struct Bucket {
+  tokens: f64,
+  updated: Instant,
+}
+
+/// A token bucket rate limiter with a separate bucket per host.
+///
+/// Each host's bucket holds up to `burst` tokens and refills at `rate`
+/// tokens per second; every request spends one token, waiting for the bucket
+/// to refill if it's empty.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+  buckets: Mutex<HashMap<String, Bucket>>,
+  burst: f64,
+  rate: f64,
+}
+
+impl RateLimiter {
+  pub(crate) fn new(rate: f64, burst: u32) -> Self {
+    Self {
+      buckets: Mutex::new(HashMap::new()),
+      burst: f64::from(burst.max(1)),
+      rate,
+    }
+  }
+
+  /// Waits until a request to `host` is allowed.
+  pub(crate) async fn acquire(&self, host: &str) {
+    if self.rate <= 0.0 {
+      return;
+    }
+
+    loop {
+      let wait = {
+        let mut buckets = self.buckets.lock().unwrap();
+
+        let now = Instant::now();
+
+        let bucket = buckets.entry(host.to_string()).or_insert(Bucket {
+          tokens: self.burst,
+          updated: now,
+        });
+
+        bucket.tokens = (bucket.tokens
+          + now.duration_since(bucket.updated).as_secs_f64() * self.rate)
+          .min(self.burst);
+
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+          bucket.tokens -= 1.0;
+          return;
+        }
+
+        Duration::from_secs_f64((1.0 - bucket.tokens) / self.rate)
+      };
+
+      sleep(wait).await;
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn burst_is_immediate() {
+    let limiter = RateLimiter::new(1.0, 3);
+
+    let start = Instant::now();
+
+    for _ in 0..3 {
+      limiter.acquire("coursecatalogue.mcgill.ca").await;
+    }
+
+    assert!(start.elapsed() < Duration::from_millis(100));
+  }
+
+  #[tokio::test]
+  async fn requests_past_burst_wait_for_refill() {
+    let limiter = RateLimiter::new(20.0, 1);
+
+    let start = Instant::now();
+
+    for _ in 0..3 {
+      limiter.acquire("coursecatalogue.mcgill.ca").await;
+    }
+
+    // Two refills at 20 tokens per second take at least 100ms.
+    assert!(start.elapsed() >= Duration::from_millis(90));
+  }
+
+  #[tokio::test]
+  async fn hosts_have_separate_buckets() {
+    let limiter = RateLimiter::new(1.0, 1);
+
+    let start = Instant::now();
+
+    limiter.acquire("coursecatalogue.mcgill.ca").await;
+    limiter.acquire("vsb.mcgill.ca").await;
+
+    assert!(start.elapsed() < Duration::from_millis(100));
+  }
+
+  #[tokio::test]
+  async fn zero_rate_is_unlimited() {
+    let limiter = RateLimiter::new(0.0, 1);
+
+    let start = Instant::now();
+
+    for _ in 0..100 {
+      limiter.acquire("coursecatalogue.mcgill.ca").await;
+    }
+
+    assert!(start.elapsed() < Duration::from_millis(100));
+  }
+}
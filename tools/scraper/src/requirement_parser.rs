@@ -0,0 +1,603 @@
+use super::*;
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+  And,
+  Close,
+  Comma,
+  Course(String),
+  OneOf,
+  Open,
+  Or,
+  Separator,
+  Waiver,
+}
+
+/// Parses requirement text like `Prerequisites: COMP 250; MATH 235 or MATH
+/// 240` (or the HTML it's scraped as) into a logical tree, along with how
+/// confident we are that the tree captures the text.
+///
+/// Returns `None` if the text doesn't mention any courses.
+pub(crate) fn parse_requirement(
+  html: &str,
+) -> Result<Option<(ReqNode, Confidence)>> {
+  let text = Html::parse_fragment(html)
+    .root_element()
+    .text()
+    .collect::<String>();
+
+  let text = match text.split_once(':') {
+    Some((label, rest))
+      if label.trim_start().starts_with("Prerequisite")
+        || label.trim_start().starts_with("Corequisite") =>
+    {
+      rest.to_string()
+    }
+    _ => text,
+  };
+
+  let Tokens {
+    tokens,
+    unknown,
+    waiver,
+  } = tokenize(&text)?;
+
+  let courses = tokens
+    .iter()
+    .filter_map(|token| match token {
+      Token::Course(course) => Some(course.clone()),
+      _ => None,
+    })
+    .collect::<HashSet<_>>();
+
+  if courses.is_empty() {
+    return Ok(None);
+  }
+
+  let mut parser = Parser {
+    tokens,
+    position: 0,
+    ambiguous: false,
+    malformed: false,
+  };
+
+  let Some(tree) = parser.parse() else {
+    return Ok(None);
+  };
+
+  let confidence = if unknown || parser.malformed || leaves(&tree) != courses {
+    Confidence::Low
+  } else if waiver || parser.ambiguous {
+    Confidence::Medium
+  } else {
+    Confidence::High
+  };
+
+  Ok(Some((tree, confidence)))
+}
+
+struct Tokens {
+  tokens: Vec<Token>,
+  /// Whether any words we don't understand were skipped.
+  unknown: bool,
+  /// Whether the text allows instructor permission or equivalent courses.
+  waiver: bool,
+}
+
+/// Words that carry no meaning for the tree and can be skipped safely.
+const FILLER: &[&str] = &[
+  "both",
+  "course",
+  "courses",
+  "either",
+  "following",
+  "of",
+  "the",
+];
+
+fn tokenize(text: &str) -> Result<Tokens> {
+  let pattern = Regex::new(
+    r"(?ix)
+    (?P<waiver>
+      (?:permission|consent)\ of\ (?:the\ )?(?:instructor|department)
+      | instructor(?:'s|’s)?\ (?:permission|consent)
+      | (?:an?\ )?equivalent(?:\ courses?)?
+    )
+    | (?P<one_of>(?:one|any)\ of(?:\ the\ following)?(?:\ courses)?)
+    | (?P<course>(?-i:[A-Z]{3}[A-Z0-9])\s?[0-9]{3}(?-i:D1|D2|N1|N2|J1|J2|J3)?\b)
+    | (?P<number>\b[0-9]{3}(?-i:D1|D2|N1|N2|J1|J2|J3)?\b)
+    | (?P<and_or>\band/or\b)
+    | (?P<and>\band\b|&)
+    | (?P<or>\bor\b|/)
+    | (?P<comma>,)
+    | (?P<separator>[;.])
+    | (?P<open>[(\[])
+    | (?P<close>[)\]])
+    | (?P<word>[^\s,;.()\[\]/&:]+)
+    ",
+  )?;
+
+  let (mut tokens, mut unknown, mut subject) =
+    (Vec::new(), false, None::<String>);
+
+  for captures in pattern.captures_iter(text) {
+    let token = if captures.name("waiver").is_some() {
+      Token::Waiver
+    } else if captures.name("one_of").is_some() {
+      Token::OneOf
+    } else if let Some(course) = captures.name("course") {
+      let course = course
+        .as_str()
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>();
+
+      subject = Some(course[..4].to_string());
+
+      Token::Course(course)
+    } else if let Some(number) = captures.name("number") {
+      // A bare number continues the previous subject, as in `COMP 250 or 251`.
+      match &subject {
+        Some(subject) => Token::Course(format!("{subject}{}", number.as_str())),
+        None => {
+          unknown = true;
+          continue;
+        }
+      }
+    } else if captures.name("and_or").is_some() || captures.name("or").is_some()
+    {
+      Token::Or
+    } else if captures.name("and").is_some() {
+      Token::And
+    } else if captures.name("comma").is_some() {
+      Token::Comma
+    } else if captures.name("separator").is_some() {
+      Token::Separator
+    } else if captures.name("open").is_some() {
+      Token::Open
+    } else if captures.name("close").is_some() {
+      Token::Close
+    } else {
+      let word = captures["word"].to_lowercase();
+
+      if !FILLER.contains(&word.as_str()) {
+        unknown = true;
+      }
+
+      continue;
+    };
+
+    tokens.push(token);
+  }
+
+  let waiver = tokens.contains(&Token::Waiver);
+
+  // Drop waivers that trail a course, like `COMP 250 (or equivalent)`, since
+  // the tree has no way to express them.
+  let mut cleaned = Vec::with_capacity(tokens.len());
+
+  for token in tokens {
+    if token == Token::Waiver {
+      if matches!(cleaned.last(), Some(Token::Or | Token::Comma)) {
+        cleaned.pop();
+      }
+
+      continue;
+    }
+
+    if token == Token::Close && cleaned.last() == Some(&Token::Open) {
+      cleaned.pop();
+      continue;
+    }
+
+    push_token(&mut cleaned, token);
+  }
+
+  Ok(Tokens {
+    tokens: cleaned,
+    unknown,
+    waiver,
+  })
+}
+
+fn push_token(tokens: &mut Vec<Token>, token: Token) {
+  // Collapse runs of separators left behind by skipped words.
+  if token == Token::Separator
+    && matches!(tokens.last(), None | Some(Token::Separator))
+  {
+    return;
+  }
+
+  tokens.push(token);
+}
+
+fn leaves(tree: &ReqNode) -> HashSet<String> {
+  match tree {
+    ReqNode::Course(course) => HashSet::from([course.clone()]),
+    ReqNode::Group { groups, .. } => groups.iter().flat_map(leaves).collect(),
+  }
+}
+
+/// A recursive descent parser over requirement tokens.
+///
+/// From loosest to tightest, `;` and `.` join clauses with AND, commas join
+/// list items with whichever of `and` or `or` ends the list, then `and`,
+/// then `or`.
+struct Parser {
+  tokens: Vec<Token>,
+  position: usize,
+  /// Whether `and` and `or` were mixed without parentheses.
+  ambiguous: bool,
+  /// Whether tokens had to be skipped to finish parsing.
+  malformed: bool,
+}
+
+impl Parser {
+  fn parse(&mut self) -> Option<ReqNode> {
+    let mut clauses = Vec::new();
+
+    while self.position < self.tokens.len() {
+      clauses.push(self.sequence());
+
+      if self.position < self.tokens.len() {
+        self.malformed = true;
+        self.position += 1;
+      }
+    }
+
+    group(Operator::And, clauses)
+  }
+
+  fn peek(&self) -> Option<&Token> {
+    self.tokens.get(self.position)
+  }
+
+  fn advance(&mut self) -> Option<Token> {
+    let token = self.tokens.get(self.position).cloned();
+    self.position += 1;
+    token
+  }
+
+  fn sequence(&mut self) -> Option<ReqNode> {
+    let mut clauses = vec![self.list(None)];
+
+    while self.peek() == Some(&Token::Separator) {
+      self.advance();
+
+      if matches!(self.peek(), None | Some(Token::Close)) {
+        break;
+      }
+
+      clauses.push(self.list(None));
+    }
+
+    group(Operator::And, clauses)
+  }
+
+  fn list(&mut self, operator: Option<Operator>) -> Option<ReqNode> {
+    let (mut items, mut connective) = (vec![self.conjunction()], None);
+
+    while self.peek() == Some(&Token::Comma) {
+      self.advance();
+
+      match self.peek() {
+        Some(Token::And) => {
+          self.advance();
+          connective = Some(Operator::And);
+        }
+        Some(Token::Or) => {
+          self.advance();
+          connective = Some(Operator::Or);
+        }
+        _ => {}
+      }
+
+      items.push(self.conjunction());
+    }
+
+    if items.len() == 1 {
+      return items.pop().flatten();
+    }
+
+    let operator = operator.or(connective).unwrap_or_else(|| {
+      // Without an Oxford comma, `A, B or C` puts the connective inside the
+      // last item, so let it decide for the whole list.
+      let leading_courses = items[..items.len() - 1]
+        .iter()
+        .all(|item| matches!(item, Some(ReqNode::Course(_))));
+
+      match items.last() {
+        Some(Some(ReqNode::Group { operator, .. })) if leading_courses => {
+          operator.clone()
+        }
+        _ => Operator::And,
+      }
+    });
+
+    group(operator, items)
+  }
+
+  fn conjunction(&mut self) -> Option<ReqNode> {
+    let (mut items, mut bare_or) = (Vec::new(), false);
+
+    loop {
+      let (item, operands) = self.disjunction();
+
+      items.push(item);
+      bare_or |= operands > 1;
+
+      if self.peek() != Some(&Token::And) {
+        break;
+      }
+
+      self.advance();
+    }
+
+    if items.len() > 1 && bare_or {
+      self.ambiguous = true;
+    }
+
+    group(Operator::And, items)
+  }
+
+  /// Parses operands joined by `or`, also returning how many there were.
+  fn disjunction(&mut self) -> (Option<ReqNode>, usize) {
+    let mut items = vec![self.primary()];
+
+    while self.peek() == Some(&Token::Or) {
+      self.advance();
+      items.push(self.primary());
+    }
+
+    let operands = items.len();
+
+    (group(Operator::Or, items), operands)
+  }
+
+  fn primary(&mut self) -> Option<ReqNode> {
+    match self.peek()? {
+      Token::Course(_) => match self.advance() {
+        Some(Token::Course(course)) => Some(ReqNode::Course(course)),
+        _ => None,
+      },
+      Token::Open => {
+        self.advance();
+
+        let node = self.sequence();
+
+        if self.peek() == Some(&Token::Close) {
+          self.advance();
+        } else {
+          self.malformed = true;
+        }
+
+        node
+      }
+      Token::OneOf => {
+        self.advance();
+        self.list(Some(Operator::Or))
+      }
+      Token::Waiver => {
+        self.advance();
+        None
+      }
+      _ => {
+        self.malformed = true;
+        None
+      }
+    }
+  }
+}
+
+/// Joins `items` with `operator`, dropping empty items, flattening nested
+/// groups with the same operator and collapsing single items.
+fn group(operator: Operator, items: Vec<Option<ReqNode>>) -> Option<ReqNode> {
+  let mut groups = Vec::new();
+
+  for item in items.into_iter().flatten() {
+    let children = match item {
+      ReqNode::Group {
+        operator: ref inner,
+        groups,
+      } if *inner == operator => groups,
+      item => vec![item],
+    };
+
+    for child in children {
+      if !groups.contains(&child) {
+        groups.push(child);
+      }
+    }
+  }
+
+  match groups.len() {
+    0 => None,
+    1 => groups.pop(),
+    _ => Some(ReqNode::Group { operator, groups }),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, pretty_assertions::assert_eq};
+
+  fn course(code: &str) -> ReqNode {
+    ReqNode::Course(code.into())
+  }
+
+  fn and(groups: Vec<ReqNode>) -> ReqNode {
+    ReqNode::Group {
+      operator: Operator::And,
+      groups,
+    }
+  }
+
+  fn or(groups: Vec<ReqNode>) -> ReqNode {
+    ReqNode::Group {
+      operator: Operator::Or,
+      groups,
+    }
+  }
+
+  fn parse(text: &str) -> Option<(ReqNode, Confidence)> {
+    parse_requirement(text).unwrap()
+  }
+
+  #[test]
+  fn single_course() {
+    assert_eq!(
+      parse("Prerequisite: COMP 202"),
+      Some((course("COMP202"), Confidence::High))
+    );
+  }
+
+  #[test]
+  fn semicolons_bind_loosest() {
+    assert_eq!(
+      parse("Prerequisites: COMP 250; MATH 235 or MATH 240"),
+      Some((
+        and(vec![
+          course("COMP250"),
+          or(vec![course("MATH235"), course("MATH240")])
+        ]),
+        Confidence::High
+      ))
+    );
+  }
+
+  #[test]
+  fn html_links() {
+    assert_eq!(
+      parse(
+        r#"Prerequisites: <a href="/courses/comp-250">COMP 250</a> and <a href="/courses/math-240">MATH 240</a>"#
+      ),
+      Some((
+        and(vec![course("COMP250"), course("MATH240")]),
+        Confidence::High
+      ))
+    );
+  }
+
+  #[test]
+  fn comma_lists_take_the_final_connective() {
+    assert_eq!(
+      parse("Prerequisites: MATH 133, MATH 140, or MATH 141"),
+      Some((
+        or(vec![
+          course("MATH133"),
+          course("MATH140"),
+          course("MATH141")
+        ]),
+        Confidence::High
+      ))
+    );
+
+    assert_eq!(
+      parse("Prerequisites: MATH 133, MATH 140 and MATH 141"),
+      Some((
+        and(vec![
+          course("MATH133"),
+          course("MATH140"),
+          course("MATH141")
+        ]),
+        Confidence::High
+      ))
+    );
+  }
+
+  #[test]
+  fn one_of() {
+    assert_eq!(
+      parse("Prerequisites: COMP 250 and one of MATH 235, MATH 240"),
+      Some((
+        and(vec![
+          course("COMP250"),
+          or(vec![course("MATH235"), course("MATH240")])
+        ]),
+        Confidence::High
+      ))
+    );
+  }
+
+  #[test]
+  fn parentheses() {
+    assert_eq!(
+      parse("Prerequisites: (COMP 202 or COMP 208) and (MATH 133 or MATH 140)"),
+      Some((
+        and(vec![
+          or(vec![course("COMP202"), course("COMP208")]),
+          or(vec![course("MATH133"), course("MATH140")])
+        ]),
+        Confidence::High
+      ))
+    );
+  }
+
+  #[test]
+  fn bare_numbers_continue_the_subject() {
+    assert_eq!(
+      parse("Prerequisite: COMP 250 or 251"),
+      Some((
+        or(vec![course("COMP250"), course("COMP251")]),
+        Confidence::High
+      ))
+    );
+  }
+
+  #[test]
+  fn waivers_lower_confidence() {
+    assert_eq!(
+      parse("Prerequisite: COMP 250 or permission of instructor"),
+      Some((course("COMP250"), Confidence::Medium))
+    );
+
+    assert_eq!(
+      parse("Prerequisites: MATH 222 (or equivalent) and MATH 223"),
+      Some((
+        and(vec![course("MATH222"), course("MATH223")]),
+        Confidence::Medium
+      ))
+    );
+
+    assert_eq!(
+      parse("Prerequisite: COMP 206 or instructor's permission."),
+      Some((course("COMP206"), Confidence::Medium))
+    );
+  }
+
+  #[test]
+  fn mixed_operators_are_ambiguous() {
+    assert_eq!(
+      parse("Prerequisites: COMP 250 and MATH 235 or MATH 240"),
+      Some((
+        and(vec![
+          course("COMP250"),
+          or(vec![course("MATH235"), course("MATH240")])
+        ]),
+        Confidence::Medium
+      ))
+    );
+  }
+
+  #[test]
+  fn unknown_words_lower_confidence() {
+    assert_eq!(
+      parse("Prerequisite: COMP 250 with a minimum grade of B"),
+      Some((course("COMP250"), Confidence::Low))
+    );
+
+    assert_eq!(
+      parse("Prerequisites: (COMP 250 or COMP 251"),
+      Some((
+        or(vec![course("COMP250"), course("COMP251")]),
+        Confidence::Low
+      ))
+    );
+  }
+
+  #[test]
+  fn no_courses() {
+    assert_eq!(parse("Prerequisite: Permission of the instructor."), None);
+    assert_eq!(parse("Prerequisite: U3 standing"), None);
+  }
+}
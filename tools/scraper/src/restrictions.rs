@@ -0,0 +1,308 @@
+use super::*;
+
+/// Structured data parsed from a course's restriction notes.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct Restrictions {
+  pub(crate) excluded_courses: Vec<String>,
+  pub(crate) cross_listings: Vec<String>,
+  pub(crate) restricted_to: RestrictedTo,
+}
+
+impl Restrictions {
+  /// Parses restriction text like `Not open to students who have taken or
+  /// are taking COMP 252.` or `Open only to U2 or U3 students in the Faculty
+  /// of Science.`
+  pub(crate) fn parse(text: &str) -> Result<Self> {
+    let mut restrictions = Self::default();
+
+    for clause in clauses(text) {
+      let lowercase = clause.to_lowercase();
+
+      if ["cross-listed", "crosslisted", "same as"]
+        .iter()
+        .any(|marker| lowercase.contains(marker))
+      {
+        restrictions.cross_listings.extend(course_codes(&clause)?);
+        continue;
+      }
+
+      if [
+        "not open to students who",
+        "credit for only one of",
+        "credit will be given for only one of",
+        "cannot receive credit for both",
+        "cannot both be taken for credit",
+        "cannot be taken for credit with",
+        "mutually exclusive",
+        "may not take",
+      ]
+      .iter()
+      .any(|marker| lowercase.contains(marker))
+      {
+        restrictions.excluded_courses.extend(course_codes(&clause)?);
+        continue;
+      }
+
+      if [
+        "open only to",
+        "only open to",
+        "restricted to",
+        "reserved for",
+        "limited to",
+        "for students in",
+      ]
+      .iter()
+      .any(|marker| lowercase.contains(marker))
+      {
+        restrictions.restricted_to.years.extend(years(&clause)?);
+        restrictions
+          .restricted_to
+          .faculties
+          .extend(faculties(&clause)?);
+        restrictions
+          .restricted_to
+          .programs
+          .extend(programs(&clause)?);
+      }
+    }
+
+    utils::dedup(&mut restrictions.excluded_courses);
+    utils::dedup(&mut restrictions.cross_listings);
+    utils::dedup(&mut restrictions.restricted_to.programs);
+    utils::dedup(&mut restrictions.restricted_to.faculties);
+    utils::dedup(&mut restrictions.restricted_to.years);
+
+    Ok(restrictions)
+  }
+}
+
+/// Splits restriction text into clauses on semicolons and sentence ends,
+/// leaving abbreviations like `B.Sc.` intact.
+fn clauses(text: &str) -> Vec<String> {
+  let (mut clauses, mut current) = (Vec::new(), String::new());
+
+  let mut chars = text.chars().peekable();
+
+  while let Some(c) = chars.next() {
+    let boundary = match c {
+      ';' => true,
+      '.' => {
+        let word = current.rsplit(char::is_whitespace).next().unwrap_or("");
+
+        chars.peek().is_none_or(|next| next.is_whitespace())
+          && !word.contains('.')
+          && word.chars().count() > 1
+      }
+      _ => false,
+    };
+
+    if boundary {
+      clauses.push(mem::take(&mut current));
+    } else {
+      current.push(c);
+    }
+  }
+
+  clauses.push(current);
+
+  clauses
+    .into_iter()
+    .map(|clause| clause.trim().to_string())
+    .filter(|clause| !clause.is_empty())
+    .collect()
+}
+
+fn course_codes(text: &str) -> Result<Vec<String>> {
+  let pattern = Regex::new(r"\b[A-Z0-9]{4} [0-9]{3}(D1|D2|N1|N2|J1|J2|J3)?\b")?;
+
+  Ok(
+    pattern
+      .find_iter(text)
+      .map(|m| m.as_str().replace(' ', ""))
+      .collect(),
+  )
+}
+
+fn years(text: &str) -> Result<Vec<u32>> {
+  let (level, ordinal) = (
+    Regex::new(r"\bU([0-4])\b")?,
+    Regex::new(r"(?i)\b(first|second|third|fourth)[- ]year\b")?,
+  );
+
+  let mut years = level
+    .captures_iter(text)
+    .filter_map(|captures| captures[1].parse::<u32>().ok())
+    .collect::<Vec<_>>();
+
+  years.extend(ordinal.captures_iter(text).map(|captures| {
+    match captures[1].to_lowercase().as_str() {
+      "first" => 1,
+      "second" => 2,
+      "third" => 3,
+      _ => 4,
+    }
+  }));
+
+  Ok(years)
+}
+
+fn faculties(text: &str) -> Result<Vec<String>> {
+  let pattern = Regex::new(
+    r"Faculty of (?:the )?([A-Z][A-Za-z&]*(?: (?:and |& )?[A-Z][A-Za-z&]*)*)",
+  )?;
+
+  Ok(
+    pattern
+      .captures_iter(text)
+      .map(|captures| captures[1].to_string())
+      .collect(),
+  )
+}
+
+fn programs(text: &str) -> Result<Vec<String>> {
+  let pattern = Regex::new(
+    r"(?:students in|enrolled in|registered in) (?:the |a |an )?(.+?) (?:program|programme)s?\b",
+  )?;
+
+  Ok(
+    pattern
+      .captures_iter(text)
+      .flat_map(|captures| {
+        captures[1]
+          .split(", ")
+          .flat_map(|part| part.split(" or "))
+          .map(|part| {
+            ["or ", "the ", "any "]
+              .iter()
+              .fold(part.trim(), |part, prefix| part.trim_start_matches(prefix))
+              .to_string()
+          })
+          .filter(|part| !part.is_empty())
+          .collect::<Vec<_>>()
+      })
+      .collect(),
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, pretty_assertions::assert_eq};
+
+  #[test]
+  fn mutually_exclusive_courses() {
+    assert_eq!(
+      Restrictions::parse(
+        "Not open to students who have taken or are taking: COMP 252 or COMP 260."
+      )
+      .unwrap(),
+      Restrictions {
+        excluded_courses: vec!["COMP252".into(), "COMP260".into()],
+        ..Default::default()
+      }
+    );
+
+    assert_eq!(
+      Restrictions::parse(
+        "Students will receive credit for only one of MATH 133 or MATH 134D1."
+      )
+      .unwrap()
+      .excluded_courses,
+      vec!["MATH133".to_string(), "MATH134D1".to_string()]
+    );
+  }
+
+  #[test]
+  fn cross_listings() {
+    assert_eq!(
+      Restrictions::parse(
+        "This course is cross-listed with LING 345. Not open to students who have taken COMP 345."
+      )
+      .unwrap(),
+      Restrictions {
+        excluded_courses: vec!["COMP345".into()],
+        cross_listings: vec!["LING345".into()],
+        ..Default::default()
+      }
+    );
+  }
+
+  #[test]
+  fn faculty_and_year_restrictions() {
+    assert_eq!(
+      Restrictions::parse(
+        "Open only to U2 or U3 students in the Faculty of Science."
+      )
+      .unwrap()
+      .restricted_to,
+      RestrictedTo {
+        faculties: vec!["Science".into()],
+        years: vec![2, 3],
+        ..Default::default()
+      }
+    );
+
+    assert_eq!(
+      Restrictions::parse("Restricted to third-year students.")
+        .unwrap()
+        .restricted_to
+        .years,
+      vec![3]
+    );
+  }
+
+  #[test]
+  fn program_restrictions() {
+    assert_eq!(
+      Restrictions::parse(
+        "Restricted to students in the B.Sc. in Computer Science or Software Engineering programs; not open to students who have taken ECSE 321."
+      )
+      .unwrap(),
+      Restrictions {
+        excluded_courses: vec!["ECSE321".into()],
+        restricted_to: RestrictedTo {
+          programs: vec![
+            "B.Sc. in Computer Science".into(),
+            "Software Engineering".into()
+          ],
+          ..Default::default()
+        },
+        ..Default::default()
+      }
+    );
+  }
+
+  #[test]
+  fn catalogue_examples() {
+    assert_eq!(
+      Restrictions::parse(
+        "COMP 202 and COMP 208 cannot both be taken for credit. COMP 202 is intended as a general introductory course, while COMP 208 is intended for students interested in scientific computation. COMP 202 cannot be taken for credit with or after COMP 250"
+      )
+      .unwrap()
+      .excluded_courses,
+      vec![
+        "COMP202".to_string(),
+        "COMP208".to_string(),
+        "COMP250".to_string()
+      ]
+    );
+
+    assert_eq!(
+      Restrictions::parse(
+        "For students in any Computer Science, Computer Engineering, or Software Engineering programs. Others only with the instructor's permission. Not open to students who have taken or are taking MATH 235."
+      )
+      .unwrap(),
+      Restrictions {
+        excluded_courses: vec!["MATH235".into()],
+        restricted_to: RestrictedTo {
+          programs: vec![
+            "Computer Science".into(),
+            "Computer Engineering".into(),
+            "Software Engineering".into()
+          ],
+          ..Default::default()
+        },
+        ..Default::default()
+      }
+    );
+  }
+}
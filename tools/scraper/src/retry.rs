@@ -5,6 +5,7 @@ pub(crate) struct RetryConfig {
   pub(crate) max_retries: usize,
   pub(crate) base_delay: Duration,
   pub(crate) max_delay: Duration,
+  pub(crate) rate_limiter: Option<Arc<RateLimiter>>,
 }
 
 impl Default for RetryConfig {
@@ -13,43 +14,57 @@ impl Default for RetryConfig {
       max_retries: 3,
       base_delay: Duration::from_millis(100),
       max_delay: Duration::from_secs(30),
+      rate_limiter: None,
     }
   }
 }
 
-pub(crate) trait Retry {
-  fn retry(self, max_retries: usize) -> Result<reqwest::blocking::Response>;
+/// Parses a `Retry-After` header, given either in seconds or as an HTTP date.
+fn retry_after(response: &Response) -> Option<Duration> {
+  let value = response.headers().get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+  if let Ok(seconds) = value.parse::<u64>() {
+    return Some(Duration::from_secs(seconds));
+  }
 
-  fn retry_with_config(
-    self,
-    config: RetryConfig,
-  ) -> Result<reqwest::blocking::Response>;
+  DateTime::parse_from_rfc2822(value)
+    .ok()?
+    .with_timezone(&Utc)
+    .signed_duration_since(Utc::now())
+    .to_std()
+    .ok()
 }
 
-impl Retry for RequestBuilder {
-  fn retry(self, max_retries: usize) -> Result<reqwest::blocking::Response> {
-    self.retry_with_config(RetryConfig {
-      max_retries,
-      ..Default::default()
-    })
-  }
+pub(crate) trait Retry {
+  async fn retry_with_config(self, config: &RetryConfig) -> Result<Response>;
+}
 
-  fn retry_with_config(
-    self,
-    config: RetryConfig,
-  ) -> Result<reqwest::blocking::Response> {
+impl Retry for RequestBuilder {
+  async fn retry_with_config(self, config: &RetryConfig) -> Result<Response> {
     let mut attempts = 0;
 
     while attempts <= config.max_retries {
-      match self
+      let (client, request) = self
         .try_clone()
         .ok_or_else(|| anyhow!("Failed to clone request builder"))?
-        .send()
-      {
+        .build_split();
+
+      let request = request?;
+
+      if let Some(rate_limiter) = &config.rate_limiter {
+        rate_limiter
+          .acquire(request.url().host_str().unwrap_or_default())
+          .await;
+      }
+
+      let mut server_delay = None;
+
+      match client.execute(request).await {
         Ok(response) => {
           // FIXME: Figure out a better way to handle 404s.
           if response.status().is_success()
             || response.status() == StatusCode::NOT_FOUND
+            || response.status() == StatusCode::NOT_MODIFIED
           {
             return Ok(response);
           } else {
@@ -59,6 +74,8 @@ impl Retry for RequestBuilder {
               config.max_retries + 1,
               response.status()
             );
+
+            server_delay = retry_after(&response);
           }
         }
         Err(error) => {
@@ -74,18 +91,25 @@ impl Retry for RequestBuilder {
         break;
       }
 
-      let (exp_delay, jitter) = (
-        config
-          .base_delay
-          .saturating_mul(2_u32.saturating_pow(attempts as u32)),
-        Duration::from_millis(rand::random::<u64>() % 100),
-      );
+      // A server-provided `Retry-After` takes precedence over our own
+      // backoff, since retrying any sooner would just be rejected again,
+      // though it's still capped so a server can't stall us indefinitely.
+      let delay = server_delay.unwrap_or_else(|| {
+        let (exp_delay, jitter) = (
+          config
+            .base_delay
+            .saturating_mul(2_u32.saturating_pow(attempts as u32)),
+          Duration::from_millis(rand::random::<u64>() % 100),
+        );
+
+        exp_delay + jitter
+      });
 
-      let delay = std::cmp::min(exp_delay + jitter, config.max_delay);
+      let delay = delay.min(config.max_delay);
 
       warn!("Retrying in {delay:?}...");
 
-      thread::sleep(delay);
+      sleep(delay).await;
 
       attempts += 1;
     }
@@ -101,17 +125,18 @@ impl Retry for RequestBuilder {
 mod tests {
   use {super::*, mockito::Server, std::time::Instant};
 
-  #[test]
-  fn successful_first_attempt() {
-    let mut server = Server::new();
+  #[tokio::test]
+  async fn successful_first_attempt() {
+    let mut server = Server::new_async().await;
 
     let mock = server
       .mock("GET", "/success")
       .with_status(200)
       .with_body("success")
-      .create();
+      .create_async()
+      .await;
 
-    let client = reqwest::blocking::Client::builder()
+    let client = Client::builder()
       .build()
       .expect("Failed to create test client");
 
@@ -119,34 +144,37 @@ mod tests {
 
     let response = client
       .get(format!("{}/success", server.url()))
-      .retry(3)
+      .retry_with_config(&RetryConfig::default())
+      .await
       .expect("Request should succeed");
 
-    mock.assert();
+    mock.assert_async().await;
 
     assert_eq!(response.status(), 200);
     assert!(start.elapsed() < Duration::from_millis(500));
   }
 
-  #[test]
-  fn success_after_retries() {
-    let mut server = Server::new();
+  #[tokio::test]
+  async fn success_after_retries() {
+    let mut server = Server::new_async().await;
 
     server
       .mock("GET", "/eventually-success")
       .with_status(500)
       .with_body("error")
       .expect(2)
-      .create();
+      .create_async()
+      .await;
 
     server
       .mock("GET", "/eventually-success")
       .with_status(200)
       .with_body("success")
       .expect(1)
-      .create();
+      .create_async()
+      .await;
 
-    let client = reqwest::blocking::Client::builder()
+    let client = Client::builder()
       .build()
       .expect("Failed to create test client");
 
@@ -154,50 +182,56 @@ mod tests {
 
     let response = client
       .get(format!("{}/eventually-success", server.url()))
-      .retry(3)
+      .retry_with_config(&RetryConfig::default())
+      .await
       .expect("Request should eventually succeed");
 
     assert_eq!(response.status(), 200);
     assert!(start.elapsed() > Duration::from_millis(300));
   }
 
-  #[test]
-  fn max_retries_exceeded() {
-    let mut server = Server::new();
+  #[tokio::test]
+  async fn max_retries_exceeded() {
+    let mut server = Server::new_async().await;
 
     let mock = server
       .mock("GET", "/always-fail")
       .with_status(500)
       .with_body("error")
       .expect(4)  // Initial attempt + 3 retries.
-      .create();
+      .create_async()
+      .await;
 
-    let client = reqwest::blocking::Client::builder()
+    let client = Client::builder()
       .build()
       .expect("Failed to create test client");
 
     let start = Instant::now();
 
-    let result = client.get(format!("{}/always-fail", server.url())).retry(3);
+    let result = client
+      .get(format!("{}/always-fail", server.url()))
+      .retry_with_config(&RetryConfig::default())
+      .await;
 
-    mock.assert();
+    mock.assert_async().await;
     assert!(result.is_err());
     assert!(result.unwrap_err().to_string().contains("after 4 attempts"));
     assert!(start.elapsed() > Duration::from_millis(700));
   }
 
-  #[test]
-  fn max_delay_cap() {
-    let mut server = Server::new();
+  #[tokio::test]
+  async fn max_delay_cap() {
+    let mut server = Server::new_async().await;
 
     let mock = server
       .mock("GET", "/long-retry")
       .with_status(500)
       .with_body("error")
       .expect(10)  // Initial attempt + 9 retries.
-      .create();
+      .create_async()
+      .await;
 
-    let client = reqwest::blocking::Client::builder()
+    let client = Client::builder()
       .build()
       .expect("Failed to create test client");
 
@@ -205,79 +239,194 @@ mod tests {
 
     let result = client
       .get(format!("{}/long-retry", server.url()))
-      .retry_with_config(RetryConfig {
+      .retry_with_config(&RetryConfig {
         max_retries: 9,
         base_delay: Duration::from_millis(1),
         max_delay: Duration::from_millis(10),
-      });
+        ..Default::default()
+      })
+      .await;
 
-    mock.assert(); // Verify we made exactly 10 requests.
+    mock.assert_async().await; // Verify we made exactly 10 requests.
 
     assert!(result.is_err());
     assert!(start.elapsed() < Duration::from_secs(1)); // 10ms * 10 = 100ms max delay + latency.
   }
 
-  #[test]
-  fn no_retries() {
-    let mut server = Server::new();
+  #[tokio::test]
+  async fn no_retries() {
+    let mut server = Server::new_async().await;
 
     let mock = server
       .mock("GET", "/no-retry")
       .with_status(500)
       .with_body("error")
       .expect(1)
-      .create();
+      .create_async()
+      .await;
 
-    let client = reqwest::blocking::Client::new();
+    let client = Client::new();
 
     let start = Instant::now();
 
     let result = client
       .get(format!("{}/no-retry", server.url()))
-      .retry_with_config(RetryConfig {
+      .retry_with_config(&RetryConfig {
         max_retries: 0,
         base_delay: Duration::from_millis(50),
         max_delay: Duration::from_millis(100),
-      });
+        ..Default::default()
+      })
+      .await;
 
-    mock.assert();
+    mock.assert_async().await;
 
     assert!(result.is_err());
     assert!(start.elapsed() < Duration::from_millis(200));
   }
 
-  #[test]
-  fn retry_on_4xx() {
-    let mut server = Server::new();
+  #[tokio::test]
+  async fn retry_on_4xx() {
+    let mut server = Server::new_async().await;
 
     server
       .mock("GET", "/retry-4xx")
       .with_status(400)
       .with_body("not found")
       .expect(1)
-      .create();
+      .create_async()
+      .await;
 
     server
       .mock("GET", "/retry-4xx")
       .with_status(200)
       .with_body("finally success")
       .expect(1)
-      .create();
+      .create_async()
+      .await;
 
-    let client = reqwest::blocking::Client::new();
+    let client = Client::new();
 
     let start = Instant::now();
 
     let response = client
       .get(format!("{}/retry-4xx", server.url()))
-      .retry_with_config(RetryConfig {
+      .retry_with_config(&RetryConfig {
         max_retries: 1,
         base_delay: Duration::from_millis(50),
         max_delay: Duration::from_millis(50),
+        ..Default::default()
       })
+      .await
       .expect("Should succeed on the second attempt");
 
     assert_eq!(response.status(), 200);
     assert!(start.elapsed() > Duration::from_millis(40));
   }
+
+  #[tokio::test]
+  async fn honors_retry_after() {
+    let mut server = Server::new_async().await;
+
+    server
+      .mock("GET", "/rate-limited")
+      .with_status(429)
+      .with_header("retry-after", "1")
+      .expect(1)
+      .create_async()
+      .await;
+
+    server
+      .mock("GET", "/rate-limited")
+      .with_status(200)
+      .with_body("success")
+      .expect(1)
+      .create_async()
+      .await;
+
+    let start = Instant::now();
+
+    let response = Client::new()
+      .get(format!("{}/rate-limited", server.url()))
+      .retry_with_config(&RetryConfig {
+        max_retries: 1,
+        base_delay: Duration::from_millis(1),
+        max_delay: Duration::from_secs(5),
+        ..Default::default()
+      })
+      .await
+      .expect("Should succeed after waiting");
+
+    assert_eq!(response.status(), 200);
+    assert!(start.elapsed() >= Duration::from_secs(1));
+  }
+
+  #[tokio::test]
+  async fn retry_after_is_capped_at_max_delay() {
+    let mut server = Server::new_async().await;
+
+    server
+      .mock("GET", "/stalling")
+      .with_status(429)
+      .with_header("retry-after", "3600")
+      .expect(1)
+      .create_async()
+      .await;
+
+    server
+      .mock("GET", "/stalling")
+      .with_status(200)
+      .expect(1)
+      .create_async()
+      .await;
+
+    let start = Instant::now();
+
+    let response = Client::new()
+      .get(format!("{}/stalling", server.url()))
+      .retry_with_config(&RetryConfig {
+        max_retries: 1,
+        base_delay: Duration::from_millis(1),
+        max_delay: Duration::from_millis(50),
+        ..Default::default()
+      })
+      .await
+      .expect("Should succeed after the capped delay");
+
+    assert_eq!(response.status(), 200);
+    assert!(start.elapsed() < Duration::from_secs(5));
+  }
+
+  #[tokio::test]
+  async fn rate_limiter_throttles_attempts() {
+    let mut server = Server::new_async().await;
+
+    let mock = server
+      .mock("GET", "/throttled")
+      .with_status(200)
+      .expect(3)
+      .create_async()
+      .await;
+
+    let config = RetryConfig {
+      rate_limiter: Some(Arc::new(RateLimiter::new(20.0, 1))),
+      ..Default::default()
+    };
+
+    let client = Client::new();
+
+    let start = Instant::now();
+
+    for _ in 0..3 {
+      client
+        .get(format!("{}/throttled", server.url()))
+        .retry_with_config(&config)
+        .await
+        .unwrap();
+    }
+
+    mock.assert_async().await;
+
+    assert!(start.elapsed() >= Duration::from_millis(90));
+  }
 }
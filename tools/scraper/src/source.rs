@@ -0,0 +1,216 @@
+use super::*;
+
+/// Where the loader reads catalogue and VSB pages from.
+pub(crate) trait Source {
+  /// Returns the URLs of every course page listed in the catalogue.
+  async fn course_urls(&self) -> Result<Vec<String>>;
+
+  /// Returns the parsed course page at `url`, or `None` if it doesn't exist.
+  async fn course_page(&self, url: &str) -> Result<Option<CoursePage>>;
+
+  /// Returns the VSB schedules for `code` (e.g. `COMP-251`) in `terms`.
+  async fn schedules(
+    &self,
+    code: &str,
+    terms: &[usize],
+  ) -> Result<Vec<Schedule>>;
+}
+
+/// Scrapes the live course catalogue and schedule builder.
+///
+/// Every request goes through one shared connection pool and is throttled by
+/// the rate limiter in `retry`.
+pub(crate) struct Remote {
+  cache: Option<Cache>,
+  client: Client,
+  retry: RetryConfig,
+  vsb: VsbClient,
+}
+
+impl Remote {
+  pub(crate) fn new(
+    client: Client,
+    cookie: Option<String>,
+    retry: RetryConfig,
+    cache: Option<Cache>,
+  ) -> Self {
+    Self {
+      cache,
+      vsb: VsbClient::new(client.clone(), cookie, retry.clone()),
+      client,
+      retry,
+    }
+  }
+
+  /// Fetches a catalogue page, going through the response cache if there is
+  /// one. Returns `None` if the page doesn't exist.
+  async fn fetch(&self, url: &str, refresh: bool) -> Result<Option<String>> {
+    match &self.cache {
+      Some(cache) if refresh => {
+        cache.refresh(&self.client, url, &self.retry).await
+      }
+      Some(cache) => cache.get(&self.client, url, &self.retry).await,
+      None => {
+        let response =
+          self.client.get(url).retry_with_config(&self.retry).await?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+          return Ok(None);
+        }
+
+        Ok(Some(response.text().await?))
+      }
+    }
+  }
+}
+
+impl Source for Remote {
+  async fn course_urls(&self) -> Result<Vec<String>> {
+    let url = format!("{}/courses", Loader::BASE_URL);
+
+    let page = self
+      .fetch(&url, false)
+      .await?
+      .ok_or_else(|| anyhow!("Course listing {url} not found"))?;
+
+    course_extractor::extract_course_urls(&page)
+  }
+
+  async fn course_page(&self, url: &str) -> Result<Option<CoursePage>> {
+    let Some(text) = self.fetch(url, false).await? else {
+      return Ok(None);
+    };
+
+    let mut course_page = course_extractor::extract_course_page(&text);
+
+    while course_page.is_err() {
+      warn!("Retrying course page: {url}");
+
+      sleep(Duration::from_millis(500)).await;
+
+      // Refresh rather than revalidate, otherwise a cached page that failed
+      // to parse would keep coming back as `304 Not Modified`.
+      let Some(text) = self.fetch(url, true).await? else {
+        return Ok(None);
+      };
+
+      course_page = course_extractor::extract_course_page(&text);
+    }
+
+    course_page.map(Some)
+  }
+
+  async fn schedules(
+    &self,
+    code: &str,
+    terms: &[usize],
+  ) -> Result<Vec<Schedule>> {
+    self.vsb.schedule(code, terms).await
+  }
+}
+
+/// Reads pages from a local mirror of the catalogue and schedule builder.
+///
+/// Catalogue pages live at their URL path relative to the mirror root (with
+/// `index.html` appended to extensionless paths), so the course listing is
+/// `courses/index.html` and `/courses/comp-251/index.html` is
+/// `courses/comp-251/index.html`. VSB responses live at
+/// `vsb/<term>/<code>.xml`, e.g. `vsb/202509/COMP-251.xml`.
+pub(crate) struct Mirror {
+  root: PathBuf,
+}
+
+impl Mirror {
+  pub(crate) fn new(root: PathBuf) -> Result<Self> {
+    if !root.is_dir() {
+      bail!("Mirror directory {} does not exist", root.display());
+    }
+
+    Ok(Self { root })
+  }
+
+  fn path(&self, url: &str) -> PathBuf {
+    let path = self.root.join(
+      url
+        .strip_prefix(Loader::BASE_URL)
+        .unwrap_or(url)
+        .trim_start_matches('/'),
+    );
+
+    if path.extension().is_none() {
+      path.join("index.html")
+    } else {
+      path
+    }
+  }
+}
+
+impl Source for Mirror {
+  async fn course_urls(&self) -> Result<Vec<String>> {
+    course_extractor::extract_course_urls(&fs::read_to_string(
+      self.path("/courses"),
+    )?)
+  }
+
+  async fn course_page(&self, url: &str) -> Result<Option<CoursePage>> {
+    let path = self.path(url);
+
+    if !path.exists() {
+      return Ok(None);
+    }
+
+    course_extractor::extract_course_page(&fs::read_to_string(path)?).map(Some)
+  }
+
+  async fn schedules(
+    &self,
+    code: &str,
+    terms: &[usize],
+  ) -> Result<Vec<Schedule>> {
+    let mut schedules = Vec::new();
+
+    for term in terms {
+      let path = self.root.join(format!("vsb/{term}/{code}.xml"));
+
+      if path.exists() {
+        schedules.extend(vsb_extractor::extract_course_schedules(
+          &fs::read_to_string(path)?,
+        )?);
+      }
+    }
+
+    Ok(schedules)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn mirror_paths() {
+    let mirror = Mirror {
+      root: PathBuf::from("mirror"),
+    };
+
+    assert_eq!(
+      mirror.path("/courses"),
+      PathBuf::from("mirror/courses/index.html")
+    );
+
+    assert_eq!(
+      mirror.path("/courses/comp-251/index.html"),
+      PathBuf::from("mirror/courses/comp-251/index.html")
+    );
+
+    assert_eq!(
+      mirror.path("https://coursecatalogue.mcgill.ca/courses/comp-251/"),
+      PathBuf::from("mirror/courses/comp-251/index.html")
+    );
+  }
+
+  #[test]
+  fn missing_mirror_is_an_error() {
+    assert!(Mirror::new(PathBuf::from("does-not-exist")).is_err());
+  }
+}
@@ -8,12 +8,46 @@ where
   v.retain(|e| set.insert(e.clone()));
 }
 
-pub(crate) fn format_instructor_name(last_first: &str) -> Result<String> {
-  let Some((last_name, first_name)) = last_first.split_once(", ") else {
-    bail!("Improperly formatted instructor name")
+/// Qualifies a bare season (e.g. `Fall`) with its year in a catalogue year
+/// like `2025-2026`, leaving already qualified terms untouched.
+pub(crate) fn resolve_term(term: &str, catalogue_year: &str) -> String {
+  let Some((first, second)) = catalogue_year.split_once('-') else {
+    return term.to_string();
   };
 
-  Ok(format!("{first_name} {last_name}"))
+  match term {
+    "Fall" => format!("Fall {first}"),
+    "Winter" | "Summer" => format!("{term} {second}"),
+    _ => term.to_string(),
+  }
+}
+
+/// Merges catalogue instructors into the ones found in VSB.
+///
+/// VSB is authoritative where both list the same instructor for a term, and
+/// catalogue entries fill in whoever VSB doesn't know about.
+pub(crate) fn reconcile_instructors(
+  vsb: Vec<Instructor>,
+  catalogue: Vec<Instructor>,
+) -> Vec<Instructor> {
+  let key = |instructor: &Instructor| {
+    (
+      InstructorProfile::normalize_name(&instructor.name).to_lowercase(),
+      instructor.term.clone(),
+    )
+  };
+
+  let mut seen = vsb.iter().map(key).collect::<HashSet<_>>();
+
+  let mut instructors = vsb;
+
+  instructors.extend(
+    catalogue
+      .into_iter()
+      .filter(|instructor| seen.insert(key(instructor))),
+  );
+
+  instructors
 }
 
 pub(crate) fn get_course_codes(par: &ElementRef) -> Result<Vec<String>> {
@@ -64,28 +98,41 @@ mod tests {
   }
 
   #[test]
-  fn format_instructor_name() {
-    assert_eq!(
-      super::format_instructor_name("Smith, John").unwrap(),
-      "John Smith"
-    );
+  fn resolve_term() {
+    assert_eq!(super::resolve_term("Fall", "2025-2026"), "Fall 2025");
+    assert_eq!(super::resolve_term("Winter", "2025-2026"), "Winter 2026");
+    assert_eq!(super::resolve_term("Summer", "2025-2026"), "Summer 2026");
+    assert_eq!(super::resolve_term("Fall 2024", "2025-2026"), "Fall 2024");
+    assert_eq!(super::resolve_term("Fall", "2025"), "Fall");
+  }
 
-    assert_eq!(
-      super::format_instructor_name("Johnson, Mary Jane").unwrap(),
-      "Mary Jane Johnson"
-    );
+  #[test]
+  fn reconcile_instructors() {
+    let instructor = |name: &str, term: &str| Instructor {
+      name: name.into(),
+      term: term.into(),
+      ..Default::default()
+    };
 
     assert_eq!(
-      super::format_instructor_name("O'Connor, Patrick").unwrap(),
-      "Patrick O'Connor"
+      super::reconcile_instructors(
+        vec![
+          instructor("Mona Elsaadawy", "Fall 2025"),
+          instructor("Jacob Errington", "Fall 2025"),
+        ],
+        vec![
+          instructor("Mona  elsaadawy", "Fall 2025"),
+          instructor("Mona Elsaadawy", "Winter 2026"),
+          instructor("Giulia Alberini", "Winter 2026"),
+        ],
+      ),
+      vec![
+        instructor("Mona Elsaadawy", "Fall 2025"),
+        instructor("Jacob Errington", "Fall 2025"),
+        instructor("Mona Elsaadawy", "Winter 2026"),
+        instructor("Giulia Alberini", "Winter 2026"),
+      ]
     );
-
-    assert!(super::format_instructor_name("Smith John").is_err());
-    assert!(super::format_instructor_name("Smith,John").is_err());
-    assert!(super::format_instructor_name("Smith").is_err());
-    assert!(super::format_instructor_name("").is_err());
-    assert_eq!(super::format_instructor_name(", John").unwrap(), "John ");
-    assert_eq!(super::format_instructor_name("Smith, ").unwrap(), " Smith");
   }
 
   #[test]
@@ -2,37 +2,32 @@ use super::*;
 
 #[derive(Debug)]
 pub(crate) struct VsbClient {
-  client: reqwest::blocking::Client,
-  retries: usize,
+  client: Client,
+  cookie: Option<String>,
+  retry: RetryConfig,
 }
 
 impl VsbClient {
   pub const BASE_URL: &'static str = "https://vsb.mcgill.ca/api/class-data";
 
+  /// Creates a schedule builder client sharing `client`'s connection pool,
+  /// sending `cookie` with every request if authenticated.
   pub(crate) fn new(
-    user_agent: &str,
-    cookie: &str,
-    retries: usize,
-  ) -> Result<Self> {
-    let client = Client::builder()
-      .user_agent(user_agent)
-      .default_headers(
-        [(
-          reqwest::header::COOKIE,
-          reqwest::header::HeaderValue::from_str(cookie)?,
-        )]
-        .into_iter()
-        .collect(),
-      )
-      .build()?;
-
-    Ok(Self { client, retries })
+    client: Client,
+    cookie: Option<String>,
+    retry: RetryConfig,
+  ) -> Self {
+    Self {
+      client,
+      cookie,
+      retry,
+    }
   }
 
-  pub(crate) fn schedule(
+  pub(crate) async fn schedule(
     &self,
     code: &str,
-    terms: Vec<usize>,
+    terms: &[usize],
   ) -> Result<Vec<Schedule>> {
     info!("Scraping schedules for {code}...");
 
@@ -47,21 +42,19 @@ impl VsbClient {
       )
     };
 
-    let schedules = terms
-      .into_iter()
-      .map(|term| -> Result<Vec<Schedule>> {
-        vsb_extractor::extract_course_schedules(
-          &self
-            .client
-            .get(url(code, term))
-            .retry(self.retries)?
-            .text()?,
-        )
-      })
-      .collect::<Result<Vec<_>>>()?
-      .into_iter()
-      .flatten()
-      .collect();
+    let mut schedules = Vec::new();
+
+    for term in terms {
+      let mut request = self.client.get(url(code, *term));
+
+      if let Some(cookie) = &self.cookie {
+        request = request.header(COOKIE, cookie);
+      }
+
+      schedules.extend(vsb_extractor::extract_course_schedules(
+        &request.retry_with_config(&self.retry).await?.text().await?,
+      )?);
+    }
 
     info!("Found schedules: {schedules:?}");
 
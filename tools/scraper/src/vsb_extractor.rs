@@ -66,13 +66,14 @@ fn extract_course_schedule(
             instructors: block
               .value()
               .attr("teacher")
-              .and_then(|s| {
-                s.split("; ")
-                  .map(utils::format_instructor_name)
-                  .collect::<Result<Vec<String>>>()
-                  .ok()
+              .map(|s| {
+                s.split(';')
+                  .map(InstructorProfile::normalize_name)
+                  .filter(|name| !name.is_empty())
+                  .collect()
               })
               .unwrap_or_default(),
+            ..Default::default()
           })
         })
         .collect::<Result<Vec<_>>>()?,
@@ -123,7 +124,8 @@ mod tests {
             }
           ]),
           crn: Some("2411".into()),
-          instructors: vec!["Mona Elsaadawy".into(), "Jacob Errington".into()]
+          instructors: vec!["Mona Elsaadawy".into(), "Jacob Errington".into()],
+          ..Default::default()
         }]),
         term: Some("Fall 2025".into())
       }]